@@ -0,0 +1,58 @@
+/* C API for cascii-core-view, built with `--features ffi`. */
+#ifndef CASCII_CORE_VIEW_H
+#define CASCII_CORE_VIEW_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    CASCII_OK = 0,
+    CASCII_NULL_POINTER = 1,
+    CASCII_FILE_TOO_SMALL = 2,
+    CASCII_SIZE_MISMATCH = 3,
+    CASCII_INVALID_DIMENSIONS = 4,
+    CASCII_INVALID_FRAME_COUNT = 5,
+    CASCII_FRAME_COUNT_MISMATCH = 6,
+    CASCII_UNSUPPORTED_ENCODING = 7,
+    CASCII_DIMENSIONS_TOO_LARGE = 8,
+} CasciiStatus;
+
+typedef struct CasciiController CasciiController;
+typedef struct CasciiFrame CasciiFrame;
+
+typedef struct {
+    uint8_t *data;
+    size_t len;
+    uint32_t width;
+    uint32_t height;
+} CasciiImage;
+
+CasciiController *cascii_controller_new(uint32_t fps);
+void cascii_controller_free(CasciiController *controller);
+void cascii_controller_set_frame_count(CasciiController *controller, size_t count);
+void cascii_controller_append_frames(CasciiController *controller, size_t count);
+void cascii_controller_play(CasciiController *controller);
+void cascii_controller_pause(CasciiController *controller);
+bool cascii_controller_tick(CasciiController *controller);
+void cascii_controller_seek(CasciiController *controller, double position);
+size_t cascii_controller_current_frame(const CasciiController *controller);
+uint32_t cascii_controller_interval_ms(const CasciiController *controller);
+
+CasciiFrame *cascii_frame_parse(const uint8_t *data, size_t len, CasciiStatus *status);
+void cascii_frame_free(CasciiFrame *frame);
+uint32_t cascii_frame_width(const CasciiFrame *frame);
+uint32_t cascii_frame_height(const CasciiFrame *frame);
+
+CasciiImage cascii_render_rgb(const CasciiFrame *frame, double font_size);
+void cascii_image_free(CasciiImage image);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CASCII_CORE_VIEW_H */
@@ -1,7 +1,10 @@
 //! Animation controller for frame playback.
 
+use crate::FrameSource;
+
 /// Loop mode for animation playback.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoopMode {
     /// Stop at the end of the animation
     Once,
@@ -65,6 +68,8 @@ pub struct AnimationController {
     range_start: f64,
     /// Range end (0.0 - 1.0)
     range_end: f64,
+    /// Per-frame durations for variable timing (empty = fixed FPS)
+    frame_durations: Vec<Option<u32>>,
 }
 
 impl AnimationController {
@@ -78,6 +83,7 @@ impl AnimationController {
             loop_mode: LoopMode::Loop,
             range_start: 0.0,
             range_end: 1.0,
+            frame_durations: Vec::new(),
         }
     }
 
@@ -90,6 +96,26 @@ impl AnimationController {
         }
     }
 
+    /// Grow the frame count by `count` frames loaded after playback started.
+    ///
+    /// Unlike [`set_frame_count`](Self::set_frame_count) this never moves the
+    /// current frame or changes the playback state. The range keeps the
+    /// frames it covered, except that a range running to the end grows to
+    /// take in the new frames.
+    pub fn append_frames(&mut self, count: usize) {
+        let (start, end) = self.range_frames();
+        let had_frames = self.frame_count > 0;
+        self.frame_count += count;
+        if !had_frames || count == 0 {
+            return;
+        }
+        let max_idx = (self.frame_count - 1) as f64;
+        self.range_start = start as f64 / max_idx;
+        if self.range_end < 1.0 {
+            self.range_end = end as f64 / max_idx;
+        }
+    }
+
     /// Get the total number of frames.
     #[inline]
     pub fn frame_count(&self) -> usize {
@@ -115,6 +141,35 @@ impl AnimationController {
         (1000.0 / self.fps as f64).max(1.0) as u32
     }
 
+    /// Switch to variable timing, where each frame stays on screen for its
+    /// own duration in milliseconds.
+    ///
+    /// Frames with a `None` entry, or past the end of the list, keep the FPS
+    /// interval. An empty list switches back to fixed-rate playback. Build
+    /// the list from frame metadata with [`frame_durations`](crate::frame_durations).
+    pub fn set_frame_durations(&mut self, durations: Vec<Option<u32>>) {
+        self.frame_durations = durations;
+    }
+
+    /// Whether any frame has its own duration.
+    #[inline]
+    pub fn has_variable_timing(&self) -> bool {
+        self.frame_durations.iter().any(Option::is_some)
+    }
+
+    /// Milliseconds that frame `index` stays on screen.
+    pub fn frame_interval_ms(&self, index: usize) -> u32 {
+        self.frame_durations.get(index).copied().flatten().unwrap_or_else(|| self.interval_ms()).max(1)
+    }
+
+    /// Milliseconds until the next `tick()` should be called.
+    ///
+    /// Equals `interval_ms()` unless variable timing is enabled.
+    #[inline]
+    pub fn current_interval_ms(&self) -> u32 {
+        self.frame_interval_ms(self.current_frame)
+    }
+
     /// Set the loop mode.
     pub fn set_loop_mode(&mut self, mode: LoopMode) {
         self.loop_mode = mode;
@@ -136,8 +191,25 @@ impl AnimationController {
     pub fn set_range(&mut self, start: f64, end: f64) {
         self.range_start = start.clamp(0.0, 1.0);
         self.range_end = end.clamp(0.0, 1.0).max(self.range_start + 0.01);
+        self.clamp_to_range();
+    }
 
-        // Clamp current frame to range
+    /// Set the playback range by frame index (inclusive), e.g. to loop a
+    /// section from [`ProjectDetails::loop_range_at`](crate::ProjectDetails::loop_range_at).
+    ///
+    /// Unlike [`set_range`](Self::set_range) there is no minimum length, so
+    /// `start == end` plays a single frame.
+    pub fn set_range_frames(&mut self, start: usize, end: usize) {
+        let last = self.frame_count.saturating_sub(1);
+        let start = start.min(last);
+        let max_idx = last.max(1) as f64;
+        self.range_start = start as f64 / max_idx;
+        self.range_end = end.clamp(start, last) as f64 / max_idx;
+        self.clamp_to_range();
+    }
+
+    /// Move the current frame to the range start when it's outside the range.
+    fn clamp_to_range(&mut self) {
         let (start_frame, end_frame) = self.range_frames();
         if self.current_frame < start_frame || self.current_frame > end_frame {
             self.current_frame = start_frame;
@@ -287,6 +359,58 @@ impl AnimationController {
         }
     }
 
+    /// Advance to the next frame of `source`, waiting for it to be ready.
+    ///
+    /// The frame count follows [`FrameSource::len`] first (growing without
+    /// moving the playhead, as with [`append_frames`](Self::append_frames)).
+    /// When the next frame is not [ready](FrameSource::is_ready) the
+    /// playhead holds and `false` is returned, so playback buffers instead
+    /// of showing a missing frame.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{AnimationController, Frame, FrameLoaderState};
+    ///
+    /// let mut loader = FrameLoaderState::new();
+    /// loader.progress.reset(3);
+    /// loader.frames.push(Frame::text_only("a".into()).into());
+    ///
+    /// let mut controller = AnimationController::new(10);
+    /// controller.set_frame_count(loader.frames.len());
+    /// controller.play();
+    /// assert!(!controller.tick_source(&loader));
+    /// assert_eq!((controller.frame_count(), controller.current_frame()), (3, 0));
+    ///
+    /// loader.frames.push(Frame::text_only("b".into()).into());
+    /// assert!(controller.tick_source(&loader));
+    /// assert_eq!(controller.current_frame(), 1);
+    /// ```
+    pub fn tick_source<S: FrameSource + ?Sized>(&mut self, source: &S) -> bool {
+        let len = source.len();
+        if len > self.frame_count {
+            self.append_frames(len - self.frame_count);
+        } else if len < self.frame_count {
+            self.set_frame_count(len);
+        }
+        if self.state != AnimationState::Playing || self.frame_count == 0 {
+            return false;
+        }
+
+        let (start, end) = self.range_frames();
+        let next = if self.current_frame < start || (self.current_frame >= end && self.loop_mode == LoopMode::Loop) {
+            Some(start)
+        } else if self.current_frame < end {
+            Some(self.current_frame + 1)
+        } else {
+            None
+        };
+        match next {
+            Some(index) if !source.is_ready(index) => false,
+            _ => self.tick(),
+        }
+    }
+
     /// Step forward one frame (manual stepping).
     ///
     /// Pauses playback and advances one frame, wrapping if at end.
@@ -387,6 +511,31 @@ mod tests {
         assert_eq!(ctrl.state(), AnimationState::Finished);
     }
 
+    #[test]
+    fn test_append_frames() {
+        let mut ctrl = AnimationController::new(24);
+        ctrl.set_frame_count(48);
+        ctrl.set_range(0.5, 1.0);
+        ctrl.play();
+        assert_eq!(ctrl.current_frame(), 24);
+        ctrl.tick();
+
+        ctrl.append_frames(52);
+        assert_eq!(ctrl.frame_count(), 100);
+        assert_eq!(ctrl.current_frame(), 25);
+        assert_eq!(ctrl.state(), AnimationState::Playing);
+        // The range keeps its start frame and grows with the clip.
+        assert_eq!(ctrl.range_frames(), (24, 99));
+        assert!(ctrl.tick());
+        assert_eq!(ctrl.current_frame(), 26);
+
+        // A range ending before the last frame keeps its frames.
+        ctrl.set_range_frames(10, 30);
+        ctrl.append_frames(100);
+        assert_eq!(ctrl.range_frames(), (10, 30));
+        assert_eq!(ctrl.current_frame(), 26);
+    }
+
     #[test]
     fn test_range() {
         let mut ctrl = AnimationController::new(24);
@@ -448,4 +597,48 @@ mod tests {
         let ctrl2 = AnimationController::new(60);
         assert_eq!(ctrl2.interval_ms(), 16); // 1000/60 ≈ 16.67
     }
+
+    #[test]
+    fn test_variable_timing() {
+        let mut ctrl = AnimationController::new(10);
+        ctrl.set_frame_count(3);
+        assert!(!ctrl.has_variable_timing());
+
+        ctrl.set_frame_durations(vec![Some(250), None]);
+        assert!(ctrl.has_variable_timing());
+        assert_eq!(ctrl.current_interval_ms(), 250);
+        ctrl.play();
+        ctrl.tick();
+        assert_eq!(ctrl.current_interval_ms(), 100);
+        assert_eq!(ctrl.frame_interval_ms(2), 100);
+
+        ctrl.set_frame_durations(Vec::new());
+        assert_eq!(ctrl.frame_interval_ms(0), 100);
+    }
+
+    #[test]
+    fn test_range_frames_loops_section() {
+        let mut ctrl = AnimationController::new(10);
+        ctrl.set_frame_count(7);
+        ctrl.set_range_frames(2, 4);
+        assert_eq!((ctrl.range_frames(), ctrl.current_frame()), ((2, 4), 2));
+
+        ctrl.play();
+        let visited: Vec<usize> = (0..4).map(|_| { ctrl.tick(); ctrl.current_frame() }).collect();
+        assert_eq!(visited, [3, 4, 2, 3]);
+    }
+
+    #[test]
+    fn test_range_frames_single_frame_on_long_clip() {
+        let mut ctrl = AnimationController::new(10);
+        ctrl.set_frame_count(1001);
+        ctrl.set_range_frames(500, 500);
+        assert_eq!((ctrl.range_frames(), ctrl.range_frame_count()), ((500, 500), 1));
+
+        ctrl.play();
+        assert!(ctrl.tick());
+        assert_eq!(ctrl.current_frame(), 500);
+        ctrl.set_range_frames(999, 5000);
+        assert_eq!(ctrl.range_frames(), (999, 1000));
+    }
 }
@@ -0,0 +1,297 @@
+//! Streaming frames out of `.tar` / `.tar.gz` archives.
+//!
+//! [`TarStream`] is a push parser: feed it archive bytes as they arrive
+//! (gzip is detected from the first bytes) and it hands back every entry as
+//! soon as its data is complete. [`TarFrameProvider`] wraps it as a
+//! [`FrameDataProvider`] that serves the entries read so far, and reports
+//! each new text frame from [`feed`](TarFrameProvider::feed), so playback can
+//! start before the archive has finished downloading.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use flate2::write::GzDecoder;
+
+use crate::{FrameDataProvider, FrameFile, LoadResult};
+
+const BLOCK: usize = 512;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A regular file read from an archive.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TarEntry {
+    /// Path inside the archive
+    pub path: String,
+    /// File contents
+    pub data: Vec<u8>,
+}
+
+/// Incremental `.tar` / `.tar.gz` parser.
+///
+/// Handles ustar name prefixes and GNU / pax long names; directories,
+/// links, and other special entries are skipped.
+#[derive(Default)]
+pub struct TarStream {
+    /// `None` until enough bytes arrived to tell gzip from plain tar
+    gzip: Option<Option<GzDecoder<Vec<u8>>>>,
+    /// Undecoded tar bytes not yet consumed
+    buffer: Vec<u8>,
+    /// Path announced by a preceding long-name entry
+    long_name: Option<String>,
+    finished: bool,
+}
+
+impl TarStream {
+    /// Create a parser that has seen no bytes yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the end-of-archive marker was reached.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Feed the next chunk of the archive and return the entries it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> LoadResult<Vec<TarEntry>> {
+        if self.finished {
+            return Ok(Vec::new());
+        }
+        match &mut self.gzip {
+            None => {
+                self.buffer.extend_from_slice(chunk);
+                if self.buffer.len() < GZIP_MAGIC.len() {
+                    return Ok(Vec::new());
+                }
+                if self.buffer[..2] == GZIP_MAGIC {
+                    let mut decoder = GzDecoder::new(Vec::new());
+                    decoder.write_all(&std::mem::take(&mut self.buffer)).map_err(|err| format!("Invalid gzip data: {}", err))?;
+                    self.buffer = std::mem::take(decoder.get_mut());
+                    self.gzip = Some(Some(decoder));
+                } else {
+                    self.gzip = Some(None);
+                }
+            }
+            Some(Some(decoder)) => {
+                decoder.write_all(chunk).map_err(|err| format!("Invalid gzip data: {}", err))?;
+                self.buffer.append(decoder.get_mut());
+            }
+            Some(None) => self.buffer.extend_from_slice(chunk),
+        }
+        self.parse()
+    }
+
+    /// Consume every complete entry in the buffer.
+    fn parse(&mut self) -> LoadResult<Vec<TarEntry>> {
+        let mut entries = Vec::new();
+        let mut pos = 0;
+        while !self.finished && self.buffer.len() - pos >= BLOCK {
+            let header = &self.buffer[pos..pos + BLOCK];
+            if header.iter().all(|&b| b == 0) {
+                self.finished = true;
+                break;
+            }
+            verify_checksum(header)?;
+            let size = parse_octal(&header[124..136]).ok_or("Invalid tar entry size")?;
+            let padded = size.div_ceil(BLOCK) * BLOCK;
+            if self.buffer.len() - pos < BLOCK + padded {
+                break;
+            }
+            let typeflag = header[156];
+            let name = header_path(header);
+            let data = self.buffer[pos + BLOCK..pos + BLOCK + size].to_vec();
+            pos += BLOCK + padded;
+            match typeflag {
+                b'L' => self.long_name = Some(c_string(&data)),
+                b'x' => self.long_name = pax_path(&data).or(self.long_name.take()),
+                b'0' | 0 => entries.push(TarEntry {path: self.long_name.take().unwrap_or(name), data}),
+                _ => self.long_name = None,
+            }
+        }
+        self.buffer.drain(..pos);
+        Ok(entries)
+    }
+}
+
+/// Bytes up to the first NUL, as a string.
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+fn parse_octal(field: &[u8]) -> Option<usize> {
+    let digits = c_string(field);
+    let digits = digits.trim_matches(' ');
+    if digits.is_empty() {
+        return Some(0);
+    }
+    usize::from_str_radix(digits, 8).ok()
+}
+
+fn verify_checksum(header: &[u8]) -> LoadResult<()> {
+    let expected = parse_octal(&header[148..156]).ok_or("Invalid tar header checksum")?;
+    let actual: usize = header.iter().enumerate().map(|(i, &b)| if (148..156).contains(&i) { b' ' as usize } else { b as usize }).sum();
+    if expected == actual { Ok(()) } else { Err("Tar header checksum mismatch".to_string()) }
+}
+
+/// The entry path, joining the ustar prefix when present.
+fn header_path(header: &[u8]) -> String {
+    let name = c_string(&header[..100]);
+    let prefix = if &header[257..262] == b"ustar" { c_string(&header[345..500]) } else { String::new() };
+    if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) }
+}
+
+/// The `path` record of a pax extended header.
+fn pax_path(data: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(data).lines().find_map(|record| {
+        let (_, field) = record.split_once(' ')?;
+        field.strip_prefix("path=").map(str::to_string)
+    })
+}
+
+/// A [`FrameDataProvider`] over a `.tar` / `.tar.gz` archive that is still
+/// arriving.
+///
+/// Text frames are the `.txt` entries, in archive order; `frame.txt`'s color
+/// data is the `frame.cframe` entry. With the `json` feature a
+/// [`MANIFEST_FILE`](crate::MANIFEST_FILE) entry is used as the manifest.
+#[derive(Default)]
+pub struct TarFrameProvider {
+    stream: TarStream,
+    entries: HashMap<String, Vec<u8>>,
+    frame_files: Vec<FrameFile>,
+}
+
+impl TarFrameProvider {
+    /// Create a provider with no archive bytes yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a complete archive held in memory.
+    pub fn from_bytes(bytes: &[u8]) -> LoadResult<Self> {
+        let mut provider = Self::new();
+        provider.feed(bytes)?;
+        Ok(provider)
+    }
+
+    /// Feed the next chunk of the archive and return the text frames it
+    /// completed, in archive order.
+    pub fn feed(&mut self, chunk: &[u8]) -> LoadResult<Vec<FrameFile>> {
+        let start = self.frame_files.len();
+        for entry in self.stream.feed(chunk)? {
+            if entry.path.ends_with(".txt") {
+                let name = entry.path.rsplit('/').next().unwrap_or(&entry.path).to_string();
+                self.frame_files.push(FrameFile::new(entry.path.clone(), name, self.frame_files.len() as u32 + 1));
+            }
+            self.entries.insert(entry.path, entry.data);
+        }
+        Ok(self.frame_files[start..].to_vec())
+    }
+
+    /// Whether the whole archive has been read.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.stream.is_finished()
+    }
+
+    /// Raw bytes of the entry at `path`, if it has been read.
+    pub fn entry(&self, path: &str) -> Option<&[u8]> {
+        self.entries.get(path).map(Vec::as_slice)
+    }
+
+    /// Path of `directory`'s entry called `name`.
+    fn join(directory: &str, name: &str) -> String {
+        let directory = directory.trim_end_matches('/');
+        if directory.is_empty() { name.to_string() } else { format!("{}/{}", directory, name) }
+    }
+}
+
+impl FrameDataProvider for TarFrameProvider {
+    async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+        let prefix = Self::join(directory, "");
+        Ok(self.frame_files.iter().filter(|file| file.path.starts_with(&prefix)).cloned().collect())
+    }
+
+    async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+        let bytes = self.entry(path).ok_or_else(|| format!("{} not found in archive", path))?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        let stem = txt_path.strip_suffix(".txt").unwrap_or(txt_path);
+        Ok(self.entry(&format!("{}.cframe", stem)).map(<[u8]>::to_vec))
+    }
+
+    #[cfg(feature = "json")]
+    async fn read_manifest(&self, directory: &str) -> LoadResult<Option<crate::FrameManifest>> {
+        let Some(bytes) = self.entry(&Self::join(directory, crate::MANIFEST_FILE)) else { return Ok(None); };
+        let json = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+        crate::FrameManifest::from_json_str(json).map(Some).map_err(|err| format!("Invalid manifest: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::test_support::block_on;
+
+    fn tar_entry(path: &str, data: &[u8]) -> Vec<u8> {
+        let mut header = [0u8; BLOCK];
+        header[..path.len()].copy_from_slice(path.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[148..156].fill(b' ');
+        let checksum: usize = header.iter().map(|&b| b as usize).sum();
+        header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+        let mut out = header.to_vec();
+        out.extend_from_slice(data);
+        out.resize(out.len().div_ceil(BLOCK) * BLOCK, 0);
+        out
+    }
+
+    fn archive() -> Vec<u8> {
+        let cframe = crate::encode_cframe(&crate::CFrameData::new(1, 1, vec![b'b'], vec![1, 2, 3])).unwrap();
+        let mut tar = [tar_entry("clip/frame_0002.txt", b"b"), tar_entry("clip/frame_0002.cframe", &cframe), tar_entry("clip/frame_0001.txt", b"a")].concat();
+        tar.extend_from_slice(&[0; BLOCK * 2]);
+        tar
+    }
+
+    #[test]
+    fn test_tar_stream_yields_entries_as_they_complete() {
+        let tar = archive();
+        let mut provider = TarFrameProvider::new();
+        let first = provider.feed(&tar[..BLOCK + 10]).unwrap();
+        assert!(first.is_empty());
+        let next = provider.feed(&tar[BLOCK + 10..BLOCK * 5]).unwrap();
+        assert_eq!(next.iter().map(|file| file.name.as_str()).collect::<Vec<_>>(), ["frame_0002.txt"]);
+        provider.feed(&tar[BLOCK * 5..]).unwrap();
+        assert!(provider.is_complete());
+
+        let files = block_on(provider.get_frame_files("clip")).unwrap();
+        assert_eq!(files.iter().map(|file| file.index).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(block_on(provider.read_frame_text("clip/frame_0001.txt")).unwrap(), "a");
+        assert!(block_on(provider.read_cframe_bytes("clip/frame_0002.txt")).unwrap().is_some());
+        assert!(block_on(provider.read_cframe_bytes("clip/frame_0001.txt")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_tar_gz_and_corrupt_headers() {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&archive()).unwrap();
+        let gz = encoder.finish().unwrap();
+        let mut provider = TarFrameProvider::new();
+        for chunk in gz.chunks(7) {
+            provider.feed(chunk).unwrap();
+        }
+        assert!(provider.is_complete());
+        assert_eq!(block_on(provider.get_frame_files("")).unwrap().len(), 2);
+
+        let mut corrupt = archive();
+        corrupt[0] = b'X';
+        assert!(TarFrameProvider::from_bytes(&corrupt).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Sprite-atlas storage for animations of many small frames.
+//!
+//! A [`FrameAtlas`] packs every frame into three contiguous buffers
+//! (characters, foreground, background) and keeps per-frame offsets, instead
+//! of three heap allocations per [`CFrameData`]. Frames are read back as
+//! borrowed [`AtlasFrame`] views, so the render loop walks one buffer and
+//! only allocates when a full [`CFrameData`] is asked for.
+
+use crate::CFrameData;
+
+/// Where one frame lives inside a [`FrameAtlas`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AtlasEntry {
+    /// Frame width in characters
+    pub width: u32,
+    /// Frame height in characters
+    pub height: u32,
+    /// Index of the frame's first cell in the character buffer
+    pub offset: usize,
+    /// Index of the frame's first cell in the background buffer, if it has one
+    pub bg_offset: Option<usize>,
+}
+
+impl AtlasEntry {
+    /// Number of cells in the frame.
+    #[inline]
+    pub fn cell_count(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+}
+
+/// Frames packed into shared, contiguous buffers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameAtlas {
+    /// Per-frame offsets, in frame order
+    pub entries: Vec<AtlasEntry>,
+    /// ASCII characters of every frame, frame-major then row-major
+    pub chars: Vec<u8>,
+    /// Foreground RGB of every frame (3 bytes per cell)
+    pub rgb: Vec<u8>,
+    /// Background RGB of the frames that have one (3 bytes per cell)
+    pub bg_rgb: Vec<u8>,
+}
+
+impl FrameAtlas {
+    /// Create an empty atlas.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pack `frames` into one atlas, skipping frames whose buffers do not
+    /// match their dimensions.
+    pub fn from_cframes<'a>(frames: impl IntoIterator<Item = &'a CFrameData>) -> Self {
+        let mut atlas = Self::new();
+        for cframe in frames {
+            atlas.push(cframe);
+        }
+        atlas
+    }
+
+    /// Append a frame and return its index, or `None` when its buffers do not
+    /// match its dimensions.
+    pub fn push(&mut self, cframe: &CFrameData) -> Option<usize> {
+        let cells = cframe.pixel_count();
+        if cframe.chars.len() != cells || cframe.rgb.len() != cells * 3 {
+            return None;
+        }
+        let offset = self.chars.len();
+        self.chars.extend_from_slice(&cframe.chars);
+        self.rgb.extend_from_slice(&cframe.rgb);
+        let bg_offset = cframe.bg_rgb.as_deref().filter(|_| cframe.has_background()).map(|bg| {
+            let bg_offset = self.bg_rgb.len() / 3;
+            self.bg_rgb.extend_from_slice(bg);
+            bg_offset
+        });
+        self.entries.push(AtlasEntry {width: cframe.width, height: cframe.height, offset, bg_offset});
+        Some(self.entries.len() - 1)
+    }
+
+    /// Number of frames in the atlas.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when the atlas holds no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Borrow frame `index`.
+    pub fn get(&self, index: usize) -> Option<AtlasFrame<'_>> {
+        let entry = *self.entries.get(index)?;
+        let cells = entry.cell_count();
+        let chars = self.chars.get(entry.offset..entry.offset + cells)?;
+        let rgb = self.rgb.get(entry.offset * 3..(entry.offset + cells) * 3)?;
+        let bg_rgb = match entry.bg_offset {
+            Some(bg_offset) => Some(self.bg_rgb.get(bg_offset * 3..(bg_offset + cells) * 3)?),
+            None => None,
+        };
+        Some(AtlasFrame {width: entry.width, height: entry.height, chars, rgb, bg_rgb})
+    }
+
+    /// Iterate over every frame in order.
+    pub fn iter(&self) -> impl Iterator<Item = AtlasFrame<'_>> {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+
+    /// Copy frame `index` out into a standalone [`CFrameData`].
+    pub fn decode(&self, index: usize) -> Option<CFrameData> {
+        self.get(index).map(|frame| frame.to_cframe())
+    }
+
+    /// Heap bytes used by the cell buffers and offsets.
+    pub fn byte_len(&self) -> usize {
+        self.chars.len() + self.rgb.len() + self.bg_rgb.len() + self.entries.len() * std::mem::size_of::<AtlasEntry>()
+    }
+}
+
+/// A borrowed frame inside a [`FrameAtlas`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AtlasFrame<'a> {
+    /// Frame width in characters
+    pub width: u32,
+    /// Frame height in characters
+    pub height: u32,
+    /// ASCII characters as bytes (width * height)
+    pub chars: &'a [u8],
+    /// Foreground RGB (width * height * 3)
+    pub rgb: &'a [u8],
+    /// Optional background RGB (width * height * 3)
+    pub bg_rgb: Option<&'a [u8]>,
+}
+
+impl AtlasFrame<'_> {
+    #[inline]
+    fn cell(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.height as usize && col < self.width as usize).then(|| row * self.width as usize + col)
+    }
+
+    /// Get the character at the given position.
+    #[inline]
+    pub fn char_at(&self, row: usize, col: usize) -> Option<u8> {
+        self.chars.get(self.cell(row, col)?).copied()
+    }
+
+    /// Get the foreground color at the given position.
+    #[inline]
+    pub fn rgb_at(&self, row: usize, col: usize) -> Option<(u8, u8, u8)> {
+        let idx = self.cell(row, col)? * 3;
+        self.rgb.get(idx..idx + 3).map(|rgb| (rgb[0], rgb[1], rgb[2]))
+    }
+
+    /// Get the background color at the given position, if the frame has one.
+    #[inline]
+    pub fn bg_rgb_at(&self, row: usize, col: usize) -> Option<(u8, u8, u8)> {
+        let idx = self.cell(row, col)? * 3;
+        self.bg_rgb?.get(idx..idx + 3).map(|rgb| (rgb[0], rgb[1], rgb[2]))
+    }
+
+    /// Copy this frame into a standalone [`CFrameData`].
+    pub fn to_cframe(&self) -> CFrameData {
+        CFrameData {width: self.width, height: self.height, chars: self.chars.to_vec(), rgb: self.rgb.to_vec(), bg_rgb: self.bg_rgb.map(<[u8]>::to_vec)}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atlas_round_trip() {
+        let a = CFrameData::new(2, 1, b"AB".to_vec(), vec![1, 2, 3, 4, 5, 6]);
+        let b = CFrameData::with_background(1, 2, b"CD".to_vec(), vec![7; 6], vec![9, 9, 9, 8, 8, 8]);
+        let bad = CFrameData::new(3, 3, b"X".to_vec(), vec![0; 3]);
+        let atlas = FrameAtlas::from_cframes([&a, &bad, &b]);
+
+        assert_eq!(atlas.len(), 2);
+        assert_eq!((atlas.chars.len(), atlas.bg_rgb.len()), (4, 6));
+        assert_eq!(atlas.decode(0).map(|cframe| cframe.checksum()), Some(a.checksum()));
+        assert_eq!(atlas.decode(1).map(|cframe| cframe.checksum()), Some(b.checksum()));
+        let frame = atlas.get(1).unwrap();
+        assert_eq!((frame.char_at(1, 0), frame.rgb_at(0, 0), frame.bg_rgb_at(1, 0)), (Some(b'D'), Some((7, 7, 7)), Some((8, 8, 8))));
+        assert_eq!(atlas.get(0).unwrap().bg_rgb_at(0, 0), None);
+        assert!(atlas.get(2).is_none());
+        assert_eq!(atlas.iter().count(), 2);
+    }
+}
@@ -0,0 +1,305 @@
+//! Single-file `.cascii` bundles.
+//!
+//! A bundle packs a whole project (`details.toml`, every text frame, and
+//! every `.cframe`) into one file, so an animation can be distributed and
+//! loaded without a directory tree. [`load_bundle`] turns one straight into
+//! a [`FrameSequence`]; [`Bundle`] also works as a [`FrameDataProvider`],
+//! so the regular two-phase loaders and the player can read from it.
+//!
+//! ## Layout
+//!
+//! All integers are little-endian `u32`.
+//!
+//! | Field         | Size          | Notes                                   |
+//! |---------------|---------------|-----------------------------------------|
+//! | magic         | 8             | [`BUNDLE_MAGIC`]                        |
+//! | version       | 4             | [`BUNDLE_VERSION`]                      |
+//! | entry count   | 4             |                                         |
+//! | index         | per entry     | name length, UTF-8 name, offset, length |
+//! | data          | rest          | entry contents; offsets start here      |
+//!
+//! Text frames are the `.txt` entries, in index order; `frame.txt`'s color
+//! data is the `frame.cframe` entry.
+
+use std::collections::HashMap;
+
+use crate::{FrameDataProvider, FrameFile, FrameSequence, LoadResult};
+
+/// First bytes of every bundle.
+pub const BUNDLE_MAGIC: [u8; 8] = *b"CASCIIB\0";
+
+/// Current bundle format version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Entry holding the project's `details.toml`.
+pub const BUNDLE_DETAILS: &str = "details.toml";
+
+/// Frame rate of bundles whose details don't set one.
+const DEFAULT_FPS: u32 = 24;
+
+/// An in-memory `.cascii` bundle: named entries in index order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bundle {
+    entries: Vec<(String, Vec<u8>)>,
+    /// Position of each entry in `entries`, by name
+    positions: HashMap<String, usize>,
+}
+
+impl Bundle {
+    /// Create an empty bundle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pack `sequence` as `frame_0001.txt` / `frame_0001.cframe`, ..., with
+    /// `details_toml` as the [`BUNDLE_DETAILS`] entry when given.
+    pub fn from_sequence(sequence: &FrameSequence, details_toml: Option<&str>) -> LoadResult<Self> {
+        let mut bundle = Self::new();
+        if let Some(details) = details_toml {
+            bundle.push(BUNDLE_DETAILS, details.as_bytes().to_vec());
+        }
+        for (i, frame) in sequence.frames.iter().enumerate() {
+            bundle.push(format!("frame_{:04}.txt", i + 1), frame.content.as_bytes().to_vec());
+            if let Some(cframe) = &frame.cframe {
+                bundle.push(format!("frame_{:04}.cframe", i + 1), crate::encode_cframe(cframe).map_err(|err| format!("Frame {}: {}", i + 1, err))?);
+            }
+        }
+        Ok(bundle)
+    }
+
+    /// Parse a bundle written by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> LoadResult<Self> {
+        let mut reader = Reader {bytes, pos: 0};
+        if reader.take(BUNDLE_MAGIC.len())? != BUNDLE_MAGIC {
+            return Err("Not a .cascii bundle".to_string());
+        }
+        let version = reader.u32()?;
+        if version != BUNDLE_VERSION {
+            return Err(format!("Unsupported bundle version {}", version));
+        }
+        let count = reader.u32()? as usize;
+        let mut index = Vec::with_capacity(count.min(bytes.len()));
+        for _ in 0..count {
+            let name_len = reader.u32()? as usize;
+            let name = std::str::from_utf8(reader.take(name_len)?).map_err(|_| "Invalid bundle entry name")?.to_string();
+            index.push((name, reader.u32()? as usize, reader.u32()? as usize));
+        }
+        let data = &bytes[reader.pos..];
+        let mut bundle = Self::new();
+        for (name, offset, len) in index {
+            let contents = offset.checked_add(len).and_then(|end| data.get(offset..end)).ok_or_else(|| format!("Bundle entry {} is out of bounds", name))?;
+            bundle.push(name, contents.to_vec());
+        }
+        Ok(bundle)
+    }
+
+    /// Serialize as a `.cascii` file. Fails when a count, name or offset
+    /// doesn't fit the format's `u32` fields, i.e. past 4 GiB of data.
+    pub fn to_bytes(&self) -> LoadResult<Vec<u8>> {
+        let mut out = BUNDLE_MAGIC.to_vec();
+        out.extend_from_slice(&BUNDLE_VERSION.to_le_bytes());
+        out.extend_from_slice(&to_u32(self.entries.len(), "Bundle entry count")?.to_le_bytes());
+        let mut offset = 0u32;
+        for (name, data) in &self.entries {
+            out.extend_from_slice(&to_u32(name.len(), "Bundle entry name")?.to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&offset.to_le_bytes());
+            out.extend_from_slice(&to_u32(data.len(), name)?.to_le_bytes());
+            offset = next_offset(offset, data.len()).ok_or("Bundle data exceeds 4 GiB")?;
+        }
+        for (_, data) in &self.entries {
+            out.extend_from_slice(data);
+        }
+        Ok(out)
+    }
+
+    /// Add an entry, replacing any entry with the same name.
+    pub fn push(&mut self, name: impl Into<String>, data: Vec<u8>) {
+        let name = name.into();
+        match self.positions.get(&name) {
+            Some(&position) => self.entries[position].1 = data,
+            None => {
+                self.positions.insert(name.clone(), self.entries.len());
+                self.entries.push((name, data));
+            }
+        }
+    }
+
+    /// Contents of the entry called `name`.
+    pub fn entry(&self, name: &str) -> Option<&[u8]> {
+        self.positions.get(name).map(|&position| self.entries[position].1.as_slice())
+    }
+
+    /// Entry names in index order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// The text frames, in index order.
+    pub fn frame_files(&self) -> Vec<FrameFile> {
+        self.names().filter(|name| name.ends_with(".txt")).enumerate().map(|(i, name)| {
+            FrameFile::new(name.to_string(), name.rsplit('/').next().unwrap_or(name).to_string(), i as u32 + 1)
+        }).collect()
+    }
+
+    /// The parsed [`BUNDLE_DETAILS`] entry, if present.
+    #[cfg(feature = "toml")]
+    pub fn details(&self) -> LoadResult<Option<crate::ProjectDetails>> {
+        let Some(bytes) = self.entry(BUNDLE_DETAILS) else { return Ok(None); };
+        let toml = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+        crate::ProjectDetails::from_toml_str(toml).map(Some).map_err(|err| format!("Invalid {}: {}", BUNDLE_DETAILS, err))
+    }
+
+    /// Frame rate for the sequence: the details' `fps` with the `toml`
+    /// feature, otherwise 24.
+    fn fps(&self) -> LoadResult<u32> {
+        #[cfg(feature = "toml")]
+        if let Some(fps) = self.details()?.and_then(|details| details.fps) {
+            return Ok(fps);
+        }
+        Ok(DEFAULT_FPS)
+    }
+
+    /// Every frame with its color data, at the bundle's frame rate.
+    pub fn to_sequence(&self) -> LoadResult<FrameSequence> {
+        let mut frames = Vec::new();
+        for file in self.frame_files() {
+            let content = String::from_utf8_lossy(self.entry(&file.path).unwrap_or_default()).into_owned();
+            let frame = match self.entry(&cframe_name(&file.path)) {
+                Some(bytes) => crate::Frame::with_color(content, crate::parse_cframe(bytes).map_err(|err| format!("{}: {}", file.path, err))?),
+                None => crate::Frame::text_only(content),
+            };
+            frames.push(frame);
+        }
+        if frames.is_empty() {
+            return Err("No frames found in bundle".to_string());
+        }
+        Ok(FrameSequence::new(frames, self.fps()?))
+    }
+}
+
+/// Load a `.cascii` bundle into a ready-to-play sequence.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{load_bundle, Bundle, Frame, FrameSequence};
+///
+/// let sequence = FrameSequence::new(vec![Frame::text_only("a".into()), Frame::text_only("b".into())], 12);
+/// let bytes = Bundle::from_sequence(&sequence, None).unwrap().to_bytes().unwrap();
+///
+/// let loaded = load_bundle(&bytes).unwrap();
+/// assert_eq!(loaded.len(), 2);
+/// assert_eq!(loaded.frames[1].content, "b");
+/// ```
+pub fn load_bundle(bytes: &[u8]) -> LoadResult<FrameSequence> {
+    Bundle::from_bytes(bytes)?.to_sequence()
+}
+
+/// `len` as a bundle `u32` field, or an error naming `what` is too large.
+fn to_u32(len: usize, what: &str) -> LoadResult<u32> {
+    u32::try_from(len).map_err(|_| format!("{} is too large for a .cascii bundle", what))
+}
+
+/// Offset of the entry after one of `len` bytes at `offset`, if it fits.
+fn next_offset(offset: u32, len: usize) -> Option<u32> {
+    u32::try_from(len).ok().and_then(|len| offset.checked_add(len))
+}
+
+/// Name of the color data entry for a text frame entry.
+fn cframe_name(txt_name: &str) -> String {
+    format!("{}.cframe", txt_name.strip_suffix(".txt").unwrap_or(txt_name))
+}
+
+/// Bounds-checked cursor over bundle bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> LoadResult<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.bytes.len()).ok_or("Truncated .cascii bundle")?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> LoadResult<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+impl FrameDataProvider for Bundle {
+    async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+        let directory = directory.trim_end_matches('/');
+        let prefix = if directory.is_empty() { String::new() } else { format!("{}/", directory) };
+        Ok(self.frame_files().into_iter().filter(|file| file.path.starts_with(&prefix)).collect())
+    }
+
+    async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+        let bytes = self.entry(path).ok_or_else(|| format!("{} not found in bundle", path))?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        Ok(self.entry(&cframe_name(txt_path)).map(<[u8]>::to_vec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::test_support::block_on;
+    use crate::{CFrameData, Frame};
+
+    fn sequence() -> FrameSequence {
+        let colored = Frame::with_color("b".into(), CFrameData::new(1, 1, vec![b'b'], vec![1, 2, 3]));
+        FrameSequence::new(vec![Frame::text_only("a".into()), colored], 10)
+    }
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let bundle = Bundle::from_sequence(&sequence(), Some("fps = 30\n")).unwrap();
+        assert_eq!(bundle.names().collect::<Vec<_>>(), ["details.toml", "frame_0001.txt", "frame_0002.txt", "frame_0002.cframe"]);
+        let bytes = bundle.to_bytes().unwrap();
+        assert_eq!(Bundle::from_bytes(&bytes).unwrap(), bundle);
+
+        let loaded = load_bundle(&bytes).unwrap();
+        assert_eq!(loaded.frames.iter().map(|frame| frame.content.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+        assert!(loaded.frames[0].cframe.is_none());
+        assert_eq!(loaded.frames[1].cframe.as_ref().map(|cframe| cframe.rgb.clone()), Some(vec![1, 2, 3]));
+        assert_eq!(loaded.fps, if cfg!(feature = "toml") { 30 } else { DEFAULT_FPS });
+
+        let (frames, files) = block_on(crate::load_text_frames(&bundle, "", &crate::LoadOptions::default(), |_| {})).unwrap();
+        assert_eq!((frames.len(), files[1].index), (2, 2));
+        assert!(block_on(bundle.read_cframe_bytes("frame_0002.txt")).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_bundle_rejects_corrupt_input() {
+        let bytes = Bundle::from_sequence(&sequence(), None).unwrap().to_bytes().unwrap();
+        assert!(Bundle::from_bytes(b"not a bundle").is_err());
+        assert!(Bundle::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let mut newer = bytes.clone();
+        newer[8] = 2;
+        assert!(Bundle::from_bytes(&newer).is_err());
+        assert!(load_bundle(&Bundle::new().to_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_bundle_push_replaces_and_offsets_are_checked() {
+        let mut bundle = Bundle::new();
+        bundle.push("a.txt", b"1".to_vec());
+        bundle.push("b.txt", b"2".to_vec());
+        bundle.push("a.txt", b"3".to_vec());
+        assert_eq!(bundle.names().collect::<Vec<_>>(), ["a.txt", "b.txt"]);
+        assert_eq!((bundle.entry("a.txt"), bundle.entry("c.txt")), (Some(&b"3"[..]), None));
+
+        assert_eq!(next_offset(10, 5), Some(15));
+        assert_eq!(next_offset(u32::MAX - 1, 2), None);
+        assert_eq!(next_offset(0, u32::MAX as usize + 1), None);
+        assert!(to_u32(u32::MAX as usize + 1, "frame.cframe").unwrap_err().contains("frame.cframe"));
+    }
+}
@@ -0,0 +1,24 @@
+//! Monotonic millisecond clock that works on native and in the browser.
+
+/// Milliseconds since an arbitrary, fixed epoch.
+///
+/// Uses `std::time::Instant` natively and `Date.now()` on wasm with the
+/// `web` feature. Returns `0.0` on wasm without it, where no clock exists.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_ms() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "web")))]
+pub(crate) fn now_ms() -> f64 {
+    0.0
+}
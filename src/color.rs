@@ -11,16 +11,192 @@ pub struct FrameColors {
 /// - Named colors: black, white, red, green, blue, yellow, cyan, magenta,
 ///   gray/grey, orange, purple, pink, brown
 /// - Hex: `#RGB` (expanded to `#RRGGBB`), `#RRGGBB`
+/// - CSS functional notation: `rgb(255, 0, 0)`, `rgba(255, 0, 0, 0.5)`,
+///   `rgb(100% 0% 0% / 50%)`, `hsl(120, 100%, 50%)`, `hsla(120deg 100% 50% / 0.5)`,
+///   and `hsv(120, 100%, 100%)`; the alpha is dropped (see [`parse_color_alpha`])
 /// - Case-insensitive, trims whitespace
 pub fn parse_color(s: &str) -> Option<(u8, u8, u8)> {
+    parse_color_alpha(s).map(|(rgb, _)| rgb)
+}
+
+/// Parse a color string like [`parse_color`], also returning its alpha in
+/// 0.0 - 1.0 (1.0 unless given through the functional notations).
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::parse_color_alpha;
+///
+/// assert_eq!(parse_color_alpha("rgba(255,0,0,0.5)"), Some(((255, 0, 0), 0.5)));
+/// assert_eq!(parse_color_alpha("#00ff00"), Some(((0, 255, 0), 1.0)));
+/// ```
+pub fn parse_color_alpha(s: &str) -> Option<((u8, u8, u8), f64)> {
     let s = s.trim();
     if s.starts_with('#') {
-        parse_hex(s)
+        parse_hex(s).map(|rgb| (rgb, 1.0))
+    } else if s.ends_with(')') {
+        parse_functional(s)
     } else {
-        parse_named(s)
+        parse_named(s).map(|rgb| (rgb, 1.0))
     }
 }
 
+/// `name(a, b, c[, alpha])` or `name(a b c[ / alpha])` split into the
+/// lowercased function name, its three components, and the optional alpha.
+fn split_functional(s: &str) -> Option<(String, [&str; 3], Option<&str>)> {
+    let (name, args) = s.strip_suffix(')')?.split_once('(')?;
+    let (args, slash_alpha) = match args.split_once('/') {
+        Some((args, alpha)) => (args, Some(alpha.trim())),
+        None => (args, None),
+    };
+    let parts: Vec<&str> = if args.contains(',') {
+        args.split(',').map(str::trim).collect()
+    } else {
+        args.split_whitespace().collect()
+    };
+    let (components, alpha) = match (parts.as_slice(), slash_alpha) {
+        ([a, b, c], alpha) => ([*a, *b, *c], alpha),
+        ([a, b, c, alpha], None) => ([*a, *b, *c], Some(*alpha)),
+        _ => return None,
+    };
+    Some((name.trim().to_lowercase(), components, alpha))
+}
+
+fn parse_functional(s: &str) -> Option<((u8, u8, u8), f64)> {
+    let (name, [a, b, c], alpha) = split_functional(s)?;
+    let rgb = match name.as_str() {
+        "rgb" | "rgba" => (parse_channel(a)?, parse_channel(b)?, parse_channel(c)?),
+        "hsl" | "hsla" => hsl_to_rgb((parse_hue(a)?, parse_percentage(b)?, parse_percentage(c)?)),
+        "hsv" | "hsva" => hsv_to_rgb((parse_hue(a)?, parse_percentage(b)?, parse_percentage(c)?)),
+        _ => return None,
+    };
+    let alpha = match alpha {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 1.0,
+    };
+    Some((rgb, alpha))
+}
+
+/// A hue in degrees, optionally suffixed `deg` / `turn`.
+fn parse_hue(s: &str) -> Option<f64> {
+    let degrees = match s.strip_suffix("turn") {
+        Some(turns) => turns.trim().parse::<f64>().ok()? * 360.0,
+        None => s.strip_suffix("deg").unwrap_or(s).trim().parse::<f64>().ok()?,
+    };
+    degrees.is_finite().then_some(degrees)
+}
+
+/// An `rgb()` channel: 0 - 255 or a percentage, clamped.
+fn parse_channel(s: &str) -> Option<u8> {
+    let value = match s.strip_suffix('%') {
+        Some(pct) => pct.trim().parse::<f64>().ok()? / 100.0 * 255.0,
+        None => s.parse::<f64>().ok()?,
+    };
+    value.is_finite().then(|| value.round().clamp(0.0, 255.0) as u8)
+}
+
+/// A saturation, lightness or value as a fraction: a percentage, with or
+/// without the `%` (bare numbers are percentages, as in CSS Color 4),
+/// clamped.
+fn parse_percentage(s: &str) -> Option<f64> {
+    let value = s.strip_suffix('%').unwrap_or(s).trim().parse::<f64>().ok()? / 100.0;
+    value.is_finite().then(|| value.clamp(0.0, 1.0))
+}
+
+/// An alpha: 0.0 - 1.0 or a percentage, clamped.
+fn parse_alpha(s: &str) -> Option<f64> {
+    let value = match s.strip_suffix('%') {
+        Some(pct) => pct.trim().parse::<f64>().ok()? / 100.0,
+        None => s.parse::<f64>().ok()?,
+    };
+    value.is_finite().then(|| value.clamp(0.0, 1.0))
+}
+
+/// Convert RGB to HSL: hue in degrees (0.0 - 360.0), saturation and
+/// lightness in 0.0 - 1.0.
+pub fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    (hue(r, g, b, max, delta), saturation.min(1.0), lightness)
+}
+
+/// Convert HSL (hue in degrees, saturation and lightness in 0.0 - 1.0) to
+/// RGB. Hues wrap; saturation and lightness are clamped.
+pub fn hsl_to_rgb((h, s, l): (f64, f64, f64)) -> (u8, u8, u8) {
+    let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    from_chroma(h, chroma, l - chroma / 2.0)
+}
+
+/// Convert RGB to HSV: hue in degrees (0.0 - 360.0), saturation and value
+/// in 0.0 - 1.0.
+pub fn rgb_to_hsv((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, max);
+    }
+    (hue(r, g, b, max, delta), delta / max, max)
+}
+
+/// Convert HSV (hue in degrees, saturation and value in 0.0 - 1.0) to RGB.
+/// Hues wrap; saturation and value are clamped.
+pub fn hsv_to_rgb((h, s, v): (f64, f64, f64)) -> (u8, u8, u8) {
+    let (s, v) = (s.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+    let chroma = v * s;
+    from_chroma(h, chroma, v - chroma)
+}
+
+/// Rotate the hue of `color` by `degrees`, keeping saturation and lightness.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::shift_hue;
+///
+/// assert_eq!(shift_hue((255, 0, 0), 120.0), (0, 255, 0));
+/// ```
+pub fn shift_hue(color: (u8, u8, u8), degrees: f64) -> (u8, u8, u8) {
+    let (h, s, l) = rgb_to_hsl(color);
+    hsl_to_rgb((h + degrees, s, l))
+}
+
+/// Hue in degrees of normalized `r`, `g`, `b` with the given max channel
+/// and chroma (`delta > 0`).
+fn hue(r: f64, g: f64, b: f64, max: f64, delta: f64) -> f64 {
+    let sector = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    sector * 60.0
+}
+
+/// RGB from a hue, chroma and the amount added to every channel.
+fn from_chroma(h: f64, chroma: f64, offset: f64) -> (u8, u8, u8) {
+    let sector = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |c: f64| ((c + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
 fn parse_hex(s: &str) -> Option<(u8, u8, u8)> {
     let hex = s.strip_prefix('#')?;
     match hex.len() {
@@ -69,6 +245,26 @@ impl FrameColors {
         }
     }
 
+    /// Black or white, whichever has the higher WCAG
+    /// [contrast ratio](contrast_ratio) against `background`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::FrameColors;
+    ///
+    /// assert_eq!(FrameColors::auto_foreground((26, 26, 46)), (255, 255, 255));
+    /// assert_eq!(FrameColors::auto_foreground((255, 255, 0)), (0, 0, 0));
+    /// ```
+    pub fn auto_foreground(background: (u8, u8, u8)) -> (u8, u8, u8) {
+        let (black, white) = ((0, 0, 0), (255, 255, 255));
+        if contrast_ratio(black, background) > contrast_ratio(white, background) {
+            black
+        } else {
+            white
+        }
+    }
+
     /// Returns a CSS `rgb(r,g,b)` string for the foreground color.
     pub fn foreground_css(&self) -> String {
         let (r, g, b) = self.foreground;
@@ -82,6 +278,665 @@ impl FrameColors {
     }
 }
 
+/// Brightness / contrast / gamma / saturation adjustment of cell colors.
+///
+/// Applied to foreground and background colors before batching, so viewers
+/// can compensate for dark source material without re-encoding frames.
+/// The default is the identity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ColorAdjust {
+    /// Added to every channel, in -1.0 - 1.0 (0.0 is neutral)
+    pub brightness: f64,
+    /// Scale around mid-gray (1.0 is neutral, 0.0 is flat gray)
+    pub contrast: f64,
+    /// Gamma correction; values above 1.0 lift shadows (1.0 is neutral)
+    pub gamma: f64,
+    /// Saturation scale (1.0 is neutral, 0.0 is grayscale)
+    pub saturation: f64,
+    /// Space the adjustments are computed in; [`ColorSpace::Linear`] keeps
+    /// brightened and desaturated colors from turning muddy
+    pub space: ColorSpace,
+}
+
+impl Default for ColorAdjust {
+    fn default() -> Self {
+        Self {brightness: 0.0, contrast: 1.0, gamma: 1.0, saturation: 1.0, space: ColorSpace::Srgb}
+    }
+}
+
+impl ColorAdjust {
+    /// Returns `true` when the adjustment leaves every color unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Per-channel lookup table for brightness, contrast, and gamma.
+    fn channel_table(&self) -> [u8; 256] {
+        let gamma = if self.gamma > 0.0 { self.gamma } else { 1.0 };
+        let space = self.space;
+        let pivot = space.mid_gray();
+        let mut table = [0u8; 256];
+        for (value, entry) in table.iter_mut().enumerate() {
+            let x = space.decode(value as u8) + self.brightness;
+            let x = ((x - pivot) * self.contrast + pivot).clamp(0.0, 1.0);
+            *entry = space.encode(x.powf(1.0 / gamma));
+        }
+        table
+    }
+
+    fn saturate(&self, (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+        if self.saturation == 1.0 {
+            return (r, g, b);
+        }
+        let space = self.space;
+        let (r, g, b) = (space.decode(r), space.decode(g), space.decode(b));
+        let luma = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let mix = |c: f64| space.encode(luma + (c - luma) * self.saturation);
+        (mix(r), mix(g), mix(b))
+    }
+
+    /// Adjust one color.
+    pub fn apply(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        let table = self.channel_table();
+        self.saturate((table[color.0 as usize], table[color.1 as usize], table[color.2 as usize]))
+    }
+
+    /// Adjust a packed `[r, g, b, ...]` buffer in place.
+    pub fn apply_in_place(&self, rgb: &mut [u8]) {
+        if self.is_identity() {
+            return;
+        }
+        let table = self.channel_table();
+        for pixel in rgb.chunks_exact_mut(3) {
+            let (r, g, b) = self.saturate((table[pixel[0] as usize], table[pixel[1] as usize], table[pixel[2] as usize]));
+            pixel.copy_from_slice(&[r, g, b]);
+        }
+    }
+}
+
+/// WCAG relative luminance of `color`, 0.0 (black) - 1.0 (white).
+pub fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    0.2126 * srgb_to_linear(r) + 0.7152 * srgb_to_linear(g) + 0.0722 * srgb_to_linear(b)
+}
+
+/// WCAG contrast ratio between two colors, 1.0 (identical) - 21.0 (black
+/// on white). Text needs at least 4.5 to meet WCAG AA.
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Linear-light value (0.0 - 1.0) of an 8-bit sRGB channel.
+pub fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+/// 8-bit sRGB channel of a linear-light value, clamped to 0.0 - 1.0.
+pub fn linear_to_srgb(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let c = if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    (c * 255.0).round() as u8
+}
+
+/// Space in which colors are adjusted and mixed.
+///
+/// Channel values are sRGB-encoded, so mixing them directly darkens the
+/// midpoint of a red-to-green blend; decoding to linear light first mixes
+/// the way light does.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::ColorSpace;
+///
+/// assert_eq!(ColorSpace::Srgb.mix(0, 255, 0.5), 128);
+/// assert_eq!(ColorSpace::Linear.mix(0, 255, 0.5), 188);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorSpace {
+    /// Operate on the encoded channel values directly
+    #[default]
+    Srgb,
+    /// Decode to linear light, operate, and re-encode
+    Linear,
+}
+
+impl ColorSpace {
+    /// A channel value in this space, 0.0 - 1.0.
+    #[inline]
+    fn decode(self, c: u8) -> f64 {
+        match self {
+            ColorSpace::Srgb => c as f64 / 255.0,
+            ColorSpace::Linear => srgb_to_linear(c),
+        }
+    }
+
+    /// The channel value of `c` in this space, clamped to 0.0 - 1.0.
+    #[inline]
+    fn encode(self, c: f64) -> u8 {
+        match self {
+            ColorSpace::Srgb => (c.clamp(0.0, 1.0) * 255.0).round() as u8,
+            ColorSpace::Linear => linear_to_srgb(c),
+        }
+    }
+
+    /// Mid-gray in this space, the pivot of contrast adjustments.
+    #[inline]
+    fn mid_gray(self) -> f64 {
+        match self {
+            ColorSpace::Srgb => 0.5,
+            ColorSpace::Linear => srgb_to_linear(128),
+        }
+    }
+
+    /// Mix two channel values, `t` = 0.0 giving `a`.
+    pub fn mix(self, a: u8, b: u8, t: f64) -> u8 {
+        let (a, b) = (self.decode(a), self.decode(b));
+        self.encode(a + (b - a) * t)
+    }
+
+    /// Mix two packed buffers channel by channel, up to the shorter length.
+    pub fn mix_bytes(self, a: &[u8], b: &[u8], t: f64) -> Vec<u8> {
+        a.iter().zip(b).map(|(&a, &b)| self.mix(a, b, t)).collect()
+    }
+}
+
+/// Linearly interpolate between two colors in sRGB space; `t` is clamped
+/// to 0.0 - 1.0, with 0.0 giving `a`.
+///
+/// Use [`ColorSpace::mix`] per channel to blend in linear light instead.
+pub fn lerp_rgb(a: (u8, u8, u8), b: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    lerp_rgb_in(a, b, t, ColorSpace::Srgb)
+}
+
+fn lerp_rgb_in(a: (u8, u8, u8), b: (u8, u8, u8), t: f64, space: ColorSpace) -> (u8, u8, u8) {
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+    (space.mix(a.0, b.0, t), space.mix(a.1, b.1, t), space.mix(a.2, b.2, t))
+}
+
+/// A multi-stop color gradient, e.g. for progress bars or crossfades.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::Gradient;
+///
+/// let heat = Gradient::new(vec![(0.0, (0, 0, 255)), (0.5, (255, 255, 0)), (1.0, (255, 0, 0))]);
+/// assert_eq!(heat.at(0.25), (128, 128, 128));
+/// assert_eq!(heat.at(0.75), (255, 128, 0));
+/// assert_eq!(heat.at(2.0), (255, 0, 0));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "GradientStops"))]
+pub struct Gradient {
+    /// `(position, color)` stops sorted by position, usually in 0.0 - 1.0
+    stops: Vec<(f64, (u8, u8, u8))>,
+    /// Space colors between stops are mixed in
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub space: ColorSpace,
+}
+
+/// Deserialized form of [`Gradient`], passed through [`Gradient::new`] so
+/// the stops are sorted and finite.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GradientStops {
+    stops: Vec<(f64, (u8, u8, u8))>,
+    #[serde(default)]
+    space: ColorSpace,
+}
+
+#[cfg(feature = "serde")]
+impl From<GradientStops> for Gradient {
+    fn from(GradientStops {stops, space}: GradientStops) -> Self {
+        Self {space, ..Self::new(stops)}
+    }
+}
+
+impl Gradient {
+    /// Create a gradient from `(position, color)` stops in any order;
+    /// stops with a non-finite position are dropped.
+    pub fn new(mut stops: Vec<(f64, (u8, u8, u8))>) -> Self {
+        stops.retain(|(position, _)| position.is_finite());
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {stops, space: ColorSpace::Srgb}
+    }
+
+    /// Spread `colors` evenly over 0.0 - 1.0.
+    pub fn evenly(colors: &[(u8, u8, u8)]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f64;
+        Self::new(colors.iter().enumerate().map(|(i, &color)| (i as f64 / last, color)).collect())
+    }
+
+    /// The stops, sorted by position.
+    pub fn stops(&self) -> &[(f64, (u8, u8, u8))] {
+        &self.stops
+    }
+
+    /// Color at `position`. Positions before the first or after the last
+    /// stop take that stop's color; an empty gradient is black.
+    pub fn at(&self, position: f64) -> (u8, u8, u8) {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else { return (0, 0, 0); };
+        if position.is_nan() || position <= first.0 {
+            return first.1;
+        }
+        if position >= last.0 {
+            return last.1;
+        }
+        let next = self.stops.partition_point(|(stop, _)| *stop <= position);
+        let ((p0, c0), (p1, c1)) = (self.stops[next - 1], self.stops[next]);
+        lerp_rgb_in(c0, c1, (position - p0) / (p1 - p0), self.space)
+    }
+}
+
+/// A form of dichromatic color blindness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorBlindness {
+    /// Missing long-wavelength (red) cones
+    Protanopia,
+    /// Missing medium-wavelength (green) cones
+    Deuteranopia,
+    /// Missing short-wavelength (blue) cones
+    Tritanopia,
+}
+
+impl ColorBlindness {
+    /// Machado et al. (2009) full-severity simulation matrix, applied to
+    /// linear RGB.
+    fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            ColorBlindness::Protanopia => [[0.152286, 1.052583, -0.204868], [0.114503, 0.786281, 0.099216], [-0.003882, -0.048116, 1.051998]],
+            ColorBlindness::Deuteranopia => [[0.367322, 0.860646, -0.227968], [0.280085, 0.672501, 0.047413], [-0.011820, 0.042940, 0.968881]],
+            ColorBlindness::Tritanopia => [[1.255528, -0.076749, -0.178779], [-0.078411, 0.930809, 0.147602], [0.004733, 0.691367, 0.303900]],
+        }
+    }
+}
+
+/// Color-vision transform applied to cell colors.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{ColorBlindness, ColorVision};
+///
+/// let simulate = ColorVision::Simulate(ColorBlindness::Deuteranopia);
+/// let (r, g, _) = simulate.apply((255, 0, 0));
+/// // red and green are hard to tell apart, so pure red gains green
+/// assert!(g > 100 && r < 200);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorVision {
+    /// Show colors as a viewer with this deficiency perceives them
+    Simulate(ColorBlindness),
+    /// Shift the information lost to this deficiency into channels the
+    /// viewer can still distinguish (Fidaner et al. daltonization)
+    Daltonize(ColorBlindness),
+}
+
+impl ColorVision {
+    /// Transform one color.
+    pub fn apply(self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        self.apply_with(&srgb_decode_table(), color)
+    }
+
+    /// Transform a packed `[r, g, b, ...]` buffer in place.
+    pub fn apply_in_place(self, rgb: &mut [u8]) {
+        let table = srgb_decode_table();
+        for pixel in rgb.chunks_exact_mut(3) {
+            let (r, g, b) = self.apply_with(&table, (pixel[0], pixel[1], pixel[2]));
+            pixel.copy_from_slice(&[r, g, b]);
+        }
+    }
+
+    fn apply_with(self, table: &[f64; 256], (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+        let (kind, daltonize) = match self {
+            ColorVision::Simulate(kind) => (kind, false),
+            ColorVision::Daltonize(kind) => (kind, true),
+        };
+        let linear = [table[r as usize], table[g as usize], table[b as usize]];
+        let m = kind.matrix();
+        let simulated: [f64; 3] = std::array::from_fn(|i| m[i][0] * linear[0] + m[i][1] * linear[1] + m[i][2] * linear[2]);
+        let out = if daltonize {
+            let error: [f64; 3] = std::array::from_fn(|i| linear[i] - simulated[i]);
+            [linear[0], linear[1] + 0.7 * error[0] + error[1], linear[2] + 0.7 * error[0] + error[2]]
+        } else {
+            simulated
+        };
+        (linear_to_srgb(out[0]), linear_to_srgb(out[1]), linear_to_srgb(out[2]))
+    }
+}
+
+/// Linear-light value of every 8-bit sRGB channel value.
+fn srgb_decode_table() -> [f64; 256] {
+    std::array::from_fn(|value| srgb_to_linear(value as u8))
+}
+
+/// Entries kept by a [`PaletteQuantizer`]'s lookup cache before it is cleared.
+const PALETTE_CACHE_CAPACITY: usize = 65_536;
+
+type NearestCache = std::sync::Arc<std::sync::Mutex<std::collections::HashMap<(u8, u8, u8), usize>>>;
+
+/// Maps arbitrary colors to the nearest entry of a fixed palette.
+///
+/// Lookups are cached and the cache is shared between clones, so one
+/// quantizer can serve the render-time palette remap ([`Palette`]) and
+/// palette-indexed encoding
+/// ([`IndexedCFrameData::quantize`](crate::IndexedCFrameData::quantize))
+/// across frames without repeating the search.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::PaletteQuantizer;
+///
+/// let quantizer = PaletteQuantizer::new(vec![(0, 0, 0), (255, 255, 255)]);
+/// assert_eq!(quantizer.nearest_index((200, 210, 190)), Some(1));
+/// assert_eq!(quantizer.nearest((20, 30, 10)), (0, 0, 0));
+/// assert_eq!(quantizer.cache_len(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaletteQuantizer {
+    colors: Vec<(u8, u8, u8)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    metric: ColorMetric,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cache: NearestCache,
+}
+
+impl PartialEq for PaletteQuantizer {
+    fn eq(&self, other: &Self) -> bool {
+        self.colors == other.colors && self.metric == other.metric
+    }
+}
+
+impl PaletteQuantizer {
+    /// Create a quantizer matching by Euclidean RGB distance.
+    pub fn new(colors: Vec<(u8, u8, u8)>) -> Self {
+        Self::with_metric(colors, ColorMetric::Rgb)
+    }
+
+    /// Create a quantizer matching by `metric`.
+    pub fn with_metric(colors: Vec<(u8, u8, u8)>, metric: ColorMetric) -> Self {
+        Self {colors, metric, cache: Default::default()}
+    }
+
+    /// The palette's colors.
+    pub fn colors(&self) -> &[(u8, u8, u8)] {
+        &self.colors
+    }
+
+    /// The metric used to pick the nearest color.
+    pub fn metric(&self) -> ColorMetric {
+        self.metric
+    }
+
+    /// Number of cached lookups.
+    pub fn cache_len(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).len()
+    }
+
+    /// Index of the palette color nearest to `color`, or `None` for an
+    /// empty palette. Ties go to the earlier entry.
+    pub fn nearest_index(&self, color: (u8, u8, u8)) -> Option<usize> {
+        if self.colors.is_empty() {
+            return None;
+        }
+        let mut cache = self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(&hit) = cache.get(&color) {
+            return Some(hit);
+        }
+        let nearest = match self.metric {
+            ColorMetric::Rgb => (0..self.colors.len()).min_by_key(|&i| distance_squared(self.colors[i], color)),
+            metric => (0..self.colors.len()).min_by(|&a, &b| metric.distance(self.colors[a], color).total_cmp(&metric.distance(self.colors[b], color))),
+        }?;
+        if cache.len() >= PALETTE_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(color, nearest);
+        Some(nearest)
+    }
+
+    /// Nearest palette color. Returns `color` unchanged for an empty palette.
+    pub fn nearest(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        self.nearest_index(color).map_or(color, |index| self.colors[index])
+    }
+
+    /// Remap a packed `[r, g, b, ...]` buffer in place.
+    pub fn remap_in_place(&self, rgb: &mut [u8]) {
+        if self.colors.is_empty() {
+            return;
+        }
+        for pixel in rgb.chunks_exact_mut(3) {
+            let (r, g, b) = self.nearest((pixel[0], pixel[1], pixel[2]));
+            pixel.copy_from_slice(&[r, g, b]);
+        }
+    }
+}
+
+/// A fixed set of colors that cell colors can be remapped to.
+///
+/// Nearest-color lookups go through a [`PaletteQuantizer`] whose cache is
+/// shared between clones, so a palette stored in a render config stays warm
+/// across frames.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Palette {
+    quantizer: PaletteQuantizer,
+}
+
+fn hex_palette(hex: &[u32]) -> Palette {
+    Palette::new(hex.iter().map(|&c| ((c >> 16) as u8, (c >> 8) as u8, c as u8)).collect())
+}
+
+impl Palette {
+    /// Create a palette from a list of colors.
+    pub fn new(colors: Vec<(u8, u8, u8)>) -> Self {
+        Self {quantizer: PaletteQuantizer::new(colors)}
+    }
+
+    /// Parse a palette from color strings (see [`parse_color`]); invalid
+    /// entries are skipped.
+    pub fn from_strings<S: AsRef<str>>(colors: &[S]) -> Self {
+        Self::new(colors.iter().filter_map(|c| parse_color(c.as_ref())).collect())
+    }
+
+    /// The standard 16-color terminal palette (xterm defaults).
+    pub fn ansi16() -> Self {
+        Self::new(ANSI16.to_vec())
+    }
+
+    /// The xterm 256-color palette, in index order (see [`ansi256_to_rgb`]).
+    pub fn ansi256() -> Self {
+        Self::new((0..=255).map(ansi256_to_rgb).collect())
+    }
+
+    /// The Gruvbox (dark) 16-color palette.
+    pub fn gruvbox() -> Self {
+        hex_palette(&[
+            0x282828, 0xcc241d, 0x98971a, 0xd79921, 0x458588, 0xb16286, 0x689d6a, 0xa89984,
+            0x928374, 0xfb4934, 0xb8bb26, 0xfabd2f, 0x83a598, 0xd3869b, 0x8ec07c, 0xebdbb2,
+        ])
+    }
+
+    /// The Solarized 16-color palette.
+    pub fn solarized() -> Self {
+        hex_palette(&[
+            0x002b36, 0x073642, 0x586e75, 0x657b83, 0x839496, 0x93a1a1, 0xeee8d5, 0xfdf6e3,
+            0xb58900, 0xcb4b16, 0xdc322f, 0xd33682, 0x6c71c4, 0x268bd2, 0x2aa198, 0x859900,
+        ])
+    }
+
+    /// The palette's colors.
+    pub fn colors(&self) -> &[(u8, u8, u8)] {
+        self.quantizer.colors()
+    }
+
+    /// The quantizer performing nearest-color lookups.
+    pub fn quantizer(&self) -> &PaletteQuantizer {
+        &self.quantizer
+    }
+
+    /// Returns `true` when the palette has no colors (remapping is a no-op).
+    pub fn is_empty(&self) -> bool {
+        self.quantizer.colors().is_empty()
+    }
+
+    /// Nearest palette color by the quantizer's metric (Euclidean RGB
+    /// distance by default). Returns `color` unchanged for an empty palette.
+    pub fn nearest(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        self.quantizer.nearest(color)
+    }
+
+    /// Remap a packed `[r, g, b, ...]` buffer in place.
+    pub fn remap_in_place(&self, rgb: &mut [u8]) {
+        self.quantizer.remap_in_place(rgb);
+    }
+}
+
+impl From<PaletteQuantizer> for Palette {
+    fn from(quantizer: PaletteQuantizer) -> Self {
+        Self {quantizer}
+    }
+}
+
+/// The 16 system colors of terminal palettes (xterm defaults), indexed
+/// like SGR colors 30-37 followed by the bright 90-97.
+pub const ANSI16: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00), (0x80, 0x00, 0x00), (0x00, 0x80, 0x00), (0x80, 0x80, 0x00),
+    (0x00, 0x00, 0x80), (0x80, 0x00, 0x80), (0x00, 0x80, 0x80), (0xc0, 0xc0, 0xc0),
+    (0x80, 0x80, 0x80), (0xff, 0x00, 0x00), (0x00, 0xff, 0x00), (0xff, 0xff, 0x00),
+    (0x00, 0x00, 0xff), (0xff, 0x00, 0xff), (0x00, 0xff, 0xff), (0xff, 0xff, 0xff),
+];
+
+/// Channel levels of the 6x6x6 color cube (indices 16-231).
+const ANSI_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// RGB of xterm 256-color index `index`: the [`ANSI16`] system colors,
+/// then the 6x6x6 color cube, then a 24-step gray ramp.
+pub fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize],
+        16..=231 => {
+            let cube = index - 16;
+            let level = |i: u8| ANSI_CUBE_LEVELS[i as usize];
+            (level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+/// Nearest xterm 256-color index to `color`.
+///
+/// Only the color cube and gray ramp (16-255) are considered: the system
+/// colors are commonly re-themed by terminals, so they can't be relied on
+/// to look like [`ANSI16`].
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{ansi256_to_rgb, rgb_to_ansi256};
+///
+/// assert_eq!(rgb_to_ansi256((255, 0, 0)), 196);
+/// assert_eq!(ansi256_to_rgb(rgb_to_ansi256((120, 121, 119))), (118, 118, 118));
+/// ```
+pub fn rgb_to_ansi256(color: (u8, u8, u8)) -> u8 {
+    let nearest_level = |c: u8| (0..6u8).min_by_key(|&i| (ANSI_CUBE_LEVELS[i as usize] as i32 - c as i32).abs()).unwrap_or(0);
+    let cube = 16 + 36 * nearest_level(color.0) + 6 * nearest_level(color.1) + nearest_level(color.2);
+    let average = (color.0 as u32 + color.1 as u32 + color.2 as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+    if distance_squared(ansi256_to_rgb(gray), color) < distance_squared(ansi256_to_rgb(cube), color) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// RGB of 16-color index `index` (0-7 normal, 8-15 bright), or `None` past 15.
+pub fn ansi16_to_rgb(index: u8) -> Option<(u8, u8, u8)> {
+    ANSI16.get(index as usize).copied()
+}
+
+/// Nearest 16-color index to `color`.
+pub fn rgb_to_ansi16(color: (u8, u8, u8)) -> u8 {
+    (0..16u8).min_by_key(|&i| distance_squared(ANSI16[i as usize], color)).unwrap_or(0)
+}
+
+/// Squared Euclidean distance between two RGB colors.
+#[inline]
+pub(crate) fn distance_squared(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+/// How the distance between two colors is measured.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorMetric {
+    /// Euclidean distance in RGB space (0 - ~441)
+    #[default]
+    Rgb,
+    /// CIE76 ΔE in L*a*b* space; ~2.3 is a just-noticeable difference
+    DeltaE,
+}
+
+impl ColorMetric {
+    /// Distance between `a` and `b` under this metric.
+    pub fn distance(self, a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+        match self {
+            ColorMetric::Rgb => (distance_squared(a, b) as f64).sqrt(),
+            ColorMetric::DeltaE => {
+                let (la, aa, ba) = to_lab(a);
+                let (lb, ab, bb) = to_lab(b);
+                ((la - lb).powi(2) + (aa - ab).powi(2) + (ba - bb).powi(2)).sqrt()
+            }
+        }
+    }
+}
+
+/// sRGB to CIE L*a*b* (D65 white point).
+fn to_lab((r, g, b): (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b));
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    let f = |t: f64| if t > 0.008856 { t.cbrt() } else { 7.787 * t + 16.0 / 116.0 };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+}
+
+/// Snap each row's colors to the first color of their run while they stay
+/// within `tolerance` of it, so near-identical neighbours batch together.
+pub(crate) fn merge_similar_runs(rgb: &mut [u8], width: usize, tolerance: f64, metric: ColorMetric) {
+    if width == 0 {
+        return;
+    }
+    for row in rgb.chunks_exact_mut(width * 3) {
+        let mut anchor = (row[0], row[1], row[2]);
+        for pixel in row.chunks_exact_mut(3).skip(1) {
+            let color = (pixel[0], pixel[1], pixel[2]);
+            if color != anchor && metric.distance(color, anchor) <= tolerance {
+                pixel.copy_from_slice(&[anchor.0, anchor.1, anchor.2]);
+            } else {
+                anchor = color;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +1003,47 @@ mod tests {
         assert_eq!(parse_color("#1234567"),     None);
     }
 
+    #[test]
+    fn functional_rgb() {
+        assert_eq!(parse_color("rgb(255, 0, 0)"),         Some((255, 0, 0)));
+        assert_eq!(parse_color("RGB(10,20,30)"),          Some((10, 20, 30)));
+        assert_eq!(parse_color("rgb(100% 50% 0%)"),       Some((255, 128, 0)));
+        assert_eq!(parse_color("rgb(300, -5, 0)"),        Some((255, 0, 0)));
+        assert_eq!(parse_color_alpha("rgba(255,0,0,0.5)"), Some(((255, 0, 0), 0.5)));
+        assert_eq!(parse_color_alpha("rgb(0 0 255 / 25%)"), Some(((0, 0, 255), 0.25)));
+        assert_eq!(parse_color_alpha("rgba(1, 2, 3)"),    Some(((1, 2, 3), 1.0)));
+        assert_eq!(parse_color("rgb(1, 2)"),              None);
+        assert_eq!(parse_color("rgb(1, 2, x)"),           None);
+        assert_eq!(parse_color("rgb(1 2 3 / 0.5, 1)"),    None);
+        assert_eq!(parse_color("cmyk(1, 2, 3)"),          None);
+    }
+
+    #[test]
+    fn functional_hsl_hsv() {
+        assert_eq!(parse_color("hsl(0, 100%, 50%)"),             Some((255, 0, 0)));
+        assert_eq!(parse_color("hsl(120deg 100% 25%)"),          Some((0, 128, 0)));
+        assert_eq!(parse_color("hsl(0.5turn, 100%, 50%)"),       Some((0, 255, 255)));
+        assert_eq!(parse_color("hsl(-120, 100%, 50%)"),          Some((0, 0, 255)));
+        assert_eq!(parse_color_alpha("hsla(60, 100%, 50%, 0.5)"), Some(((255, 255, 0), 0.5)));
+        assert_eq!(parse_color("hsv(300, 100%, 100%)"),          Some((255, 0, 255)));
+        assert_eq!(parse_color("hsl(0, 0%, 100%)"),              Some((255, 255, 255)));
+        assert_eq!(parse_color("hsl(x, 0%, 0%)"),                None);
+        // bare numbers are percentages, not fractions
+        assert_eq!(parse_color("hsl(120 100 50)"),               Some((0, 255, 0)));
+        assert_eq!(parse_color("hsv(240, 100, 100)"),            Some((0, 0, 255)));
+    }
+
+    #[test]
+    fn hsl_hsv_round_trip() {
+        for color in [(0, 0, 0), (255, 255, 255), (12, 200, 99), (250, 10, 5), (128, 64, 200)] {
+            assert_eq!(hsl_to_rgb(rgb_to_hsl(color)), color);
+            assert_eq!(hsv_to_rgb(rgb_to_hsv(color)), color);
+        }
+        assert_eq!(rgb_to_hsl((255, 0, 0)), (0.0, 1.0, 0.5));
+        assert_eq!(rgb_to_hsv((0, 0, 255)), (240.0, 1.0, 1.0));
+        assert_eq!(shift_hue((0, 0, 255), 240.0), (0, 255, 0));
+    }
+
     #[test]
     fn frame_colors_from_strings() {
         let colors = FrameColors::from_strings("white", "black");
@@ -169,6 +1065,18 @@ mod tests {
         assert_eq!(colors.background, (0, 0, 0));
     }
 
+    #[test]
+    fn auto_foreground_contrast() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 1e-9);
+        assert_eq!(contrast_ratio((12, 34, 56), (12, 34, 56)), 1.0);
+        assert_eq!(FrameColors::auto_foreground((0, 0, 255)), (255, 255, 255));
+        assert_eq!(FrameColors::auto_foreground((0, 255, 255)), (0, 0, 0));
+        assert_eq!(FrameColors::auto_foreground((246, 246, 246)), (0, 0, 0));
+        for background in [(128, 128, 128), (200, 30, 30), (20, 120, 20)] {
+            assert!(contrast_ratio(FrameColors::auto_foreground(background), background) >= 4.5);
+        }
+    }
+
     #[test]
     fn frame_colors_css() {
         let colors = FrameColors::from_strings("white", "black");
@@ -182,4 +1090,163 @@ mod tests {
         assert_eq!(colors.foreground_css(), "rgb(246,246,246)");
         assert_eq!(colors.background_css(), "rgb(26,26,46)");
     }
+
+    #[test]
+    fn color_adjust_identity() {
+        let adjust = ColorAdjust::default();
+        assert!(adjust.is_identity());
+        assert_eq!(adjust.apply((12, 200, 99)), (12, 200, 99));
+    }
+
+    #[test]
+    fn color_adjust_components() {
+        let brighter = ColorAdjust {brightness: 0.2, ..ColorAdjust::default()};
+        assert_eq!(brighter.apply((0, 100, 250)), (51, 151, 255));
+
+        let flat = ColorAdjust {contrast: 0.0, ..ColorAdjust::default()};
+        assert_eq!(flat.apply((0, 255, 30)), (128, 128, 128));
+
+        let gray = ColorAdjust {saturation: 0.0, ..ColorAdjust::default()};
+        let (r, g, b) = gray.apply((255, 0, 0));
+        assert!(r == g && g == b);
+
+        let lifted = ColorAdjust {gamma: 2.0, ..ColorAdjust::default()};
+        assert!(lifted.apply((64, 64, 64)).0 > 64);
+    }
+
+    #[test]
+    fn lerp_and_gradients() {
+        assert_eq!(lerp_rgb((0, 100, 200), (100, 100, 0), 0.25), (25, 100, 150));
+        assert_eq!(lerp_rgb((0, 0, 0), (255, 255, 255), -1.0), (0, 0, 0));
+        assert_eq!(lerp_rgb((0, 0, 0), (255, 255, 255), f64::NAN), (0, 0, 0));
+
+        let gradient = Gradient::new(vec![(1.0, (255, 0, 0)), (0.0, (0, 0, 0)), (f64::NAN, (1, 2, 3))]);
+        assert_eq!(gradient.stops().len(), 2);
+        assert_eq!(gradient.at(0.5), (128, 0, 0));
+        assert_eq!(gradient.at(-0.5), (0, 0, 0));
+        let linear = Gradient {space: ColorSpace::Linear, ..gradient};
+        assert_eq!(linear.at(0.5), (188, 0, 0));
+
+        // hard stops switch colors without blending
+        let hard = Gradient::new(vec![(0.0, (0, 0, 0)), (0.5, (0, 0, 0)), (0.5, (255, 255, 255)), (1.0, (255, 255, 255))]);
+        assert_eq!((hard.at(0.49), hard.at(0.5)), ((0, 0, 0), (255, 255, 255)));
+        assert_eq!(Gradient::evenly(&[(0, 0, 0), (0, 255, 0), (0, 0, 0)]).at(0.5), (0, 255, 0));
+        assert_eq!(Gradient::evenly(&[(9, 9, 9)]).at(0.3), (9, 9, 9));
+        assert_eq!(Gradient::default().at(0.3), (0, 0, 0));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn gradient_deserializes_through_new() {
+        let gradient: Gradient = serde_json::from_str(r#"{"stops":[[1.0,[255,0,0]],[0.0,[0,0,0]]]}"#).unwrap();
+        assert_eq!(gradient.stops()[0], (0.0, (0, 0, 0)));
+        assert_eq!(gradient.at(0.5), (128, 0, 0));
+        let linear = Gradient {space: ColorSpace::Linear, ..gradient};
+        assert_eq!(serde_json::from_str::<Gradient>(&serde_json::to_string(&linear).unwrap()).unwrap(), linear);
+    }
+
+    #[test]
+    fn color_vision_transforms() {
+        for kind in [ColorBlindness::Protanopia, ColorBlindness::Deuteranopia, ColorBlindness::Tritanopia] {
+            for vision in [ColorVision::Simulate(kind), ColorVision::Daltonize(kind)] {
+                // neutral colors are perceived unchanged
+                assert_eq!(vision.apply((0, 0, 0)), (0, 0, 0));
+                let (r, g, b) = vision.apply((128, 128, 128));
+                assert!(r.abs_diff(128) <= 1 && g.abs_diff(128) <= 1 && b.abs_diff(128) <= 1);
+            }
+        }
+        // red and green collapse towards each other for a protanope...
+        let simulate = ColorVision::Simulate(ColorBlindness::Protanopia);
+        let (red, green) = (simulate.apply((200, 40, 40)), simulate.apply((40, 160, 40)));
+        assert!(distance_squared(red, green) < distance_squared((200, 40, 40), (40, 160, 40)));
+        // ...and daltonizing moves the lost red signal into blue
+        assert!(ColorVision::Daltonize(ColorBlindness::Protanopia).apply((200, 40, 40)).2 > 40);
+
+        let mut rgb = vec![200, 40, 40, 0, 0, 0];
+        simulate.apply_in_place(&mut rgb);
+        assert_eq!((rgb[0], rgb[1], rgb[2]), red);
+    }
+
+    #[test]
+    fn linear_light_adjustments() {
+        for value in [0, 1, 10, 128, 200, 255] {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+        assert!((srgb_to_linear(128) - 0.2158).abs() < 1e-3);
+        assert_eq!(ColorSpace::Linear.mix_bytes(&[255, 0], &[0, 255], 0.5), [188, 188]);
+
+        let linear = ColorAdjust {space: ColorSpace::Linear, ..ColorAdjust::default()};
+        assert!(!linear.is_identity());
+        assert_eq!(linear.apply((12, 200, 99)), (12, 200, 99));
+        // adding light in linear space brightens shadows far more than
+        // adding to their encoded values
+        let brighter = |space| ColorAdjust {brightness: 0.1, space, ..ColorAdjust::default()}.apply((20, 20, 20)).0;
+        assert!(brighter(ColorSpace::Linear) > brighter(ColorSpace::Srgb));
+        // desaturating keeps the linear-light luminance
+        let gray = ColorAdjust {saturation: 0.0, space: ColorSpace::Linear, ..ColorAdjust::default()}.apply((255, 0, 0));
+        assert_eq!(gray, (127, 127, 127));
+    }
+
+    #[test]
+    fn palette_nearest() {
+        let palette = Palette::ansi16();
+        assert_eq!(palette.nearest((250, 10, 5)), (255, 0, 0));
+        assert_eq!(palette.nearest((20, 20, 20)), (0, 0, 0));
+        // cached lookups are shared between clones
+        assert_eq!(palette.clone().quantizer().cache_len(), 2);
+
+        let mut rgb = vec![250, 10, 5, 190, 190, 200];
+        palette.remap_in_place(&mut rgb);
+        assert_eq!(rgb, vec![255, 0, 0, 192, 192, 192]);
+
+        assert_eq!(Palette::default().nearest((1, 2, 3)), (1, 2, 3));
+        assert_eq!(Palette::from_strings(&["#f00", "bogus", "blue"]).colors(), &[(255, 0, 0), (0, 0, 255)]);
+    }
+
+    #[test]
+    fn ansi_index_mapping() {
+        assert_eq!(ansi256_to_rgb(9), (255, 0, 0));
+        assert_eq!(ansi256_to_rgb(16), (0, 0, 0));
+        assert_eq!(ansi256_to_rgb(110), (135, 175, 215));
+        assert_eq!(ansi256_to_rgb(231), (255, 255, 255));
+        assert_eq!(ansi256_to_rgb(232), (8, 8, 8));
+        assert_eq!(ansi256_to_rgb(255), (238, 238, 238));
+        for index in 16..=255 {
+            assert_eq!(rgb_to_ansi256(ansi256_to_rgb(index)), index, "index {index}");
+        }
+        assert_eq!(rgb_to_ansi256((130, 180, 210)), 110);
+
+        assert_eq!(ansi16_to_rgb(1), Some((128, 0, 0)));
+        assert_eq!(ansi16_to_rgb(16), None);
+        assert_eq!(rgb_to_ansi16((250, 10, 5)), 9);
+        assert_eq!(rgb_to_ansi16((20, 20, 20)), 0);
+        assert_eq!(Palette::ansi256().colors().len(), 256);
+    }
+
+    #[test]
+    fn quantizer_metrics_and_ties() {
+        let quantizer = PaletteQuantizer::new(vec![(0, 0, 0), (255, 255, 255), (0, 0, 0)]);
+        assert_eq!(quantizer.nearest_index((10, 10, 10)), Some(0));
+        assert_eq!(PaletteQuantizer::default().nearest_index((1, 2, 3)), None);
+
+        // a dark saturated blue is nearer black in RGB but nearer blue by ΔE
+        let colors = vec![(0, 0, 0), (0, 0, 255)];
+        assert_eq!(PaletteQuantizer::new(colors.clone()).nearest_index((0, 0, 120)), Some(0));
+        assert_eq!(PaletteQuantizer::with_metric(colors, ColorMetric::DeltaE).nearest_index((0, 0, 120)), Some(1));
+    }
+
+    #[test]
+    fn color_metrics() {
+        assert_eq!(ColorMetric::Rgb.distance((0, 0, 0), (3, 4, 0)), 5.0);
+        assert!(ColorMetric::DeltaE.distance((0, 0, 0), (255, 255, 255)) > 99.0);
+        assert!(ColorMetric::DeltaE.distance((120, 60, 30), (121, 60, 30)) < 1.0);
+    }
+
+    #[test]
+    fn merge_similar_runs_snaps_to_anchor() {
+        let mut rgb = vec![100, 100, 100, 102, 100, 100, 104, 100, 100, 200, 0, 0, 0, 0, 0, 1, 1, 1];
+        merge_similar_runs(&mut rgb, 3, 3.0, ColorMetric::Rgb);
+        // 104 is within 3 of 102 but not of the anchor 100
+        assert_eq!(rgb, vec![100, 100, 100, 100, 100, 100, 104, 100, 100, 200, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
 }
@@ -1,5 +1,45 @@
 //! Core data structures for ASCII frames.
 
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::{CompositeMode, Region};
+
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// FNV-1a over `bytes`, continuing from `hash`.
+#[inline]
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Stable 64-bit checksum (FNV-1a) of a fetched file's raw bytes, as stored
+/// in [`ManifestFrame::text_checksum`](crate::ManifestFrame::text_checksum)
+/// and [`ManifestFrame::cframe_checksum`](crate::ManifestFrame::cframe_checksum).
+///
+/// For a text file this equals the [`Frame::checksum`] of a text-only frame
+/// holding its contents.
+pub fn payload_checksum(bytes: &[u8]) -> u64 {
+    fnv1a(FNV_OFFSET, bytes)
+}
+
+/// Number of terminal columns `text` occupies.
+///
+/// With the `unicode-width` feature wide glyphs (CJK) count as two columns
+/// and combining marks as none; otherwise every `char` counts as one.
+#[inline]
+pub fn display_width(text: &str) -> usize {
+    #[cfg(feature = "unicode-width")]
+    {
+        unicode_width::UnicodeWidthStr::width(text)
+    }
+    #[cfg(not(feature = "unicode-width"))]
+    {
+        text.chars().count()
+    }
+}
+
 /// Metadata about a frame file on disk.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -10,12 +50,18 @@ pub struct FrameFile {
     pub name: String,
     /// Frame index for ordering (extracted from filename)
     pub index: u32,
+    /// Expected [`payload_checksum`] of the text file, verified by the loaders
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub text_checksum: Option<u64>,
+    /// Expected [`payload_checksum`] of the `.cframe` file, verified by the loaders
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cframe_checksum: Option<u64>,
 }
 
 impl FrameFile {
     /// Create a new FrameFile
     pub fn new(path: String, name: String, index: u32) -> Self {
-        Self { path, name, index }
+        Self { path, name, index, text_checksum: None, cframe_checksum: None }
     }
 
     /// Extract frame index from a filename stem.
@@ -35,6 +81,32 @@ impl FrameFile {
     }
 }
 
+/// Invariant violated by a [`CFrameData`]'s buffers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CFrameError {
+    /// `width * height` does not fit in memory
+    DimensionsOverflow { width: u32, height: u32 },
+    /// `chars` does not hold one byte per cell
+    CharCountMismatch { expected: usize, actual: usize },
+    /// `rgb` does not hold three bytes per cell
+    RgbLengthMismatch { expected: usize, actual: usize },
+    /// `bg_rgb` is present but does not hold three bytes per cell
+    BackgroundLengthMismatch { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for CFrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CFrameError::DimensionsOverflow { width, height } => write!(f, "Frame dimensions overflow: {}x{}", width, height),
+            CFrameError::CharCountMismatch { expected, actual } => write!(f, "Character count mismatch: expected {}, got {}", expected, actual),
+            CFrameError::RgbLengthMismatch { expected, actual } => write!(f, "Foreground color length mismatch: expected {} bytes, got {}", expected, actual),
+            CFrameError::BackgroundLengthMismatch { expected, actual } => write!(f, "Background color length mismatch: expected {} bytes, got {}", expected, actual),
+        }
+    }
+}
+
+impl std::error::Error for CFrameError {}
+
 /// Color frame data containing character and RGB information.
 ///
 /// This represents the parsed contents of a `.cframe` binary file, optionally
@@ -80,6 +152,43 @@ impl CFrameData {
         Self {width, height, chars, rgb, bg_rgb: Some(bg_rgb)}
     }
 
+    /// Like [`new`](Self::new), but rejects buffers that do not match the
+    /// dimensions instead of deferring the failure to a render-time panic.
+    pub fn try_new(width: u32, height: u32, chars: Vec<u8>, rgb: Vec<u8>) -> Result<Self, CFrameError> {
+        let cframe = Self::new(width, height, chars, rgb);
+        cframe.validate()?;
+        Ok(cframe)
+    }
+
+    /// Like [`with_background`](Self::with_background), but rejects buffers
+    /// that do not match the dimensions.
+    pub fn try_with_background(width: u32, height: u32, chars: Vec<u8>, rgb: Vec<u8>, bg_rgb: Vec<u8>) -> Result<Self, CFrameError> {
+        let cframe = Self::with_background(width, height, chars, rgb, bg_rgb);
+        cframe.validate()?;
+        Ok(cframe)
+    }
+
+    /// Check that `chars`, `rgb`, and `bg_rgb` (when present) hold exactly
+    /// one cell per `width * height`.
+    ///
+    /// Frames that pass are safe to index with any in-bounds `(row, col)`,
+    /// e.g. in [`should_skip`](Self::should_skip) or the renderers.
+    pub fn validate(&self) -> Result<(), CFrameError> {
+        let overflow = CFrameError::DimensionsOverflow {width: self.width, height: self.height};
+        let cells = (self.width as usize).checked_mul(self.height as usize).ok_or(overflow)?;
+        let color_len = cells.checked_mul(3).ok_or(overflow)?;
+        if self.chars.len() != cells {
+            return Err(CFrameError::CharCountMismatch {expected: cells, actual: self.chars.len()});
+        }
+        if self.rgb.len() != color_len {
+            return Err(CFrameError::RgbLengthMismatch {expected: color_len, actual: self.rgb.len()});
+        }
+        match &self.bg_rgb {
+            Some(bg) if bg.len() != color_len => Err(CFrameError::BackgroundLengthMismatch {expected: color_len, actual: bg.len()}),
+            _ => Ok(()),
+        }
+    }
+
     /// Returns `true` when this frame carries per-cell background colors.
     #[inline]
     pub fn has_background(&self) -> bool {
@@ -187,8 +296,154 @@ impl CFrameData {
         self.width as usize * self.height as usize
     }
 
+    /// Stable 64-bit content checksum (FNV-1a over dimensions, characters,
+    /// foreground and background colors).
+    ///
+    /// Equal frames always have equal checksums, independent of platform or
+    /// build, so checksums can be stored in manifests and compared later.
+    pub fn checksum(&self) -> u64 {
+        let mut hash = fnv1a(FNV_OFFSET, &self.width.to_le_bytes());
+        hash = fnv1a(hash, &self.height.to_le_bytes());
+        hash = fnv1a(hash, &self.chars);
+        hash = fnv1a(hash, &self.rgb);
+        match &self.bg_rgb {
+            Some(bg) => fnv1a(fnv1a(hash, &[1]), bg),
+            None => fnv1a(hash, &[0]),
+        }
+    }
+
+    /// Smallest `(x, y, width, height)` cell rectangle holding all content,
+    /// or `None` for a blank frame.
+    ///
+    /// Margin cells are spaces on the same background as the top-left cell,
+    /// so a uniformly filled backdrop is trimmed along with the padding.
+    pub fn content_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let width = self.width as usize;
+        if width == 0 {
+            return None;
+        }
+        let bg = self.bg_rgb.as_deref().filter(|_| self.has_background());
+        let margin_bg = bg.and_then(|bg| bg.get(..3));
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        for (idx, &ch) in self.chars.iter().enumerate().take(self.pixel_count()) {
+            let on_margin_bg = bg.map(|bg| bg.get(idx * 3..idx * 3 + 3) == margin_bg).unwrap_or(true);
+            if ch == b' ' && on_margin_bg {
+                continue;
+            }
+            let (x, y) = (idx % width, idx / width);
+            (min_x, min_y, max_x, max_y) = (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y));
+        }
+        (min_x != usize::MAX).then(|| (min_x as u32, min_y as u32, (max_x - min_x + 1) as u32, (max_y - min_y + 1) as u32))
+    }
+
+    /// Copy the `width` x `height` region whose top-left cell is (`x`, `y`).
+    ///
+    /// The region is clipped to the frame, so an out-of-range request yields
+    /// a smaller (possibly empty) frame rather than panicking.
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> CFrameData {
+        let col = x.min(self.width) as usize;
+        let row = y.min(self.height) as usize;
+        let cols = width.min(self.width - col as u32) as usize;
+        let rows = row..row + height.min(self.height - row as u32) as usize;
+        let stride = self.width as usize;
+        let mut chars = Vec::with_capacity(cols * rows.len());
+        let mut rgb = Vec::with_capacity(cols * rows.len() * 3);
+        for r in rows.clone() {
+            let start = r * stride + col;
+            chars.extend_from_slice(self.chars.get(start..start + cols).unwrap_or_default());
+            rgb.extend_from_slice(self.rgb.get(start * 3..(start + cols) * 3).unwrap_or_default());
+        }
+        let height = rows.len() as u32;
+        let bg_rgb = self.bg_rgb.as_ref().filter(|_| self.has_background()).map(|bg| rows.flat_map(|r| {
+            let start = (r * stride + col) * 3;
+            bg[start..start + cols * 3].iter().copied()
+        }).collect());
+        CFrameData {width: cols as u32, height, chars, rgb, bg_rgb}
+    }
+
+    /// Nearest-neighbor resample to `width` x `height` cells, e.g. to shrink
+    /// a huge frame into a scrubber thumbnail or enlarge a small one for
+    /// block-mode rendering. Each target cell copies the source cell under
+    /// its center.
+    pub fn resample(&self, width: u32, height: u32) -> CFrameData {
+        if self.width == 0 || self.height == 0 {
+            return CFrameData {width: 0, height: 0, chars: Vec::new(), rgb: Vec::new(), bg_rgb: self.bg_rgb.as_ref().map(|_| Vec::new())};
+        }
+        let source = |target: u32, from: u32, to: u32| ((2 * target as u64 + 1) * from as u64 / (2 * to as u64)) as usize;
+        let cols: Vec<usize> = (0..width).map(|col| source(col, self.width, width)).collect();
+        let cells: Vec<usize> = (0..height).flat_map(|row| {
+            let start = source(row, self.height, height) * self.width as usize;
+            cols.iter().map(move |col| start + col)
+        }).collect();
+        let pick = |buffer: &[u8]| cells.iter().flat_map(|&cell| buffer.get(cell * 3..cell * 3 + 3).unwrap_or(&[0; 3]).iter().copied()).collect::<Vec<u8>>();
+        let chars = cells.iter().map(|&cell| self.chars.get(cell).copied().unwrap_or(b' ')).collect();
+        let bg_rgb = self.bg_rgb.as_deref().filter(|_| self.has_background()).map(pick);
+        CFrameData {width, height, chars, rgb: pick(&self.rgb), bg_rgb}
+    }
+
+    /// Composite `overlay` onto this frame with its top-left cell at
+    /// (`x`, `y`), which may be negative or past the edges to clip sprites.
+    ///
+    /// Overlay spaces without a background are transparent. With
+    /// [`CompositeMode::SourceOver`] opaque overlay cells replace the cells
+    /// below; with [`CompositeMode::DestinationOver`] they only fill cells
+    /// that are transparent here; the blend modes take the overlay's glyph
+    /// and mix the colors. Backgrounds are composited only when this frame
+    /// has a background payload to hold them.
+    pub fn composite(&mut self, overlay: &CFrameData, x: i32, y: i32, mode: CompositeMode) {
+        let overlay_bg = overlay.bg_rgb.as_deref().filter(|_| overlay.has_background());
+        let has_background = self.has_background();
+        let (width, overlay_width) = (self.width as usize, overlay.width as usize);
+        let rgb = |buffer: &[u8], idx: usize| (buffer[idx * 3], buffer[idx * 3 + 1], buffer[idx * 3 + 2]);
+        for row in 0..overlay.height as usize {
+            let Some(target_row) = usize::try_from(y as i64 + row as i64).ok().filter(|&r| r < self.height as usize) else { continue };
+            for col in 0..overlay_width {
+                let Some(target_col) = usize::try_from(x as i64 + col as i64).ok().filter(|&c| c < width) else { continue };
+                let (src, dst) = (row * overlay_width + col, target_row * width + target_col);
+                if src >= overlay.chars.len() || src * 3 + 3 > overlay.rgb.len() || dst >= self.chars.len() || dst * 3 + 3 > self.rgb.len() {
+                    continue;
+                }
+                let glyph = overlay.chars[src];
+                let src_bg = overlay_bg.map(|bg| rgb(bg, src));
+                if glyph == b' ' && src_bg.is_none() {
+                    continue;
+                }
+                let put = |buffer: &mut [u8], (r, g, b): (u8, u8, u8)| buffer[dst * 3..dst * 3 + 3].copy_from_slice(&[r, g, b]);
+                let (fg, bg) = match mode {
+                    CompositeMode::SourceOver => (rgb(&overlay.rgb, src), src_bg),
+                    CompositeMode::DestinationOver => {
+                        if self.chars[dst] != b' ' || has_background {
+                            continue;
+                        }
+                        (rgb(&overlay.rgb, src), None)
+                    }
+                    _ => {
+                        let bg = self.bg_rgb.as_deref().filter(|_| has_background).zip(src_bg).map(|(bg, top)| mode.blend(rgb(bg, dst), top));
+                        (mode.blend(rgb(&self.rgb, dst), rgb(&overlay.rgb, src)), bg)
+                    }
+                };
+                if glyph != b' ' || mode == CompositeMode::SourceOver {
+                    self.chars[dst] = glyph;
+                }
+                put(&mut self.rgb, fg);
+                if let (Some(bg), Some(buffer)) = (bg, self.bg_rgb.as_mut().filter(|_| has_background)) {
+                    put(buffer, bg);
+                }
+            }
+        }
+    }
+
     /// Reconstruct the plain text representation of this frame.
+    ///
+    /// Lossless: the result matches [`parse_cframe_text`](crate::parse_cframe_text)
+    /// on the source bytes, one `\n`-terminated line per row.
     pub fn to_text(&self) -> String {
+        self.to_text_with(TextOptions::default())
+    }
+
+    /// Reconstruct the plain text with trimming options, e.g. for copying
+    /// to the clipboard.
+    pub fn to_text_with(&self, options: TextOptions) -> String {
         let width = self.width as usize;
         let height = self.height as usize;
         let mut text = String::with_capacity(self.pixel_count() + height);
@@ -196,16 +451,38 @@ impl CFrameData {
         for row in 0..height {
             let start = row * width;
             let end = start + width;
+            let line_start = text.len();
             for &ch in &self.chars[start..end] {
                 text.push(ch as char);
             }
+            if options.trim_trailing_spaces {
+                let trimmed = text[line_start..].trim_end_matches(' ').len();
+                text.truncate(line_start + trimmed);
+            }
             text.push('\n');
         }
+        if options.trim_trailing_lines {
+            let trimmed = text.trim_end_matches(|ch: char| ch == '\n' || (options.trim_trailing_spaces && ch == ' ')).len();
+            text.truncate(trimmed);
+            if trimmed > 0 {
+                text.push('\n');
+            }
+        }
 
         text
     }
 }
 
+/// Options for [`CFrameData::to_text_with`]. The default keeps every
+/// character.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextOptions {
+    /// Drop spaces at the end of each row
+    pub trim_trailing_spaces: bool,
+    /// Drop empty rows at the end of the frame
+    pub trim_trailing_lines: bool,
+}
+
 /// Packed multi-frame color data for efficient transport / storage.
 ///
 /// The layout is one shared header followed by tightly packed frames, where each pixel is stored as `(char, r, g, b)`.
@@ -307,13 +584,43 @@ impl PackedCFrameBlob {
         }
 
         if let Some(bg) = self.background_frame_bytes(index) {
-            Some(CFrameData::with_background(self.width, self.height, chars, rgb, bg.to_vec()))
+            CFrameData::try_with_background(self.width, self.height, chars, rgb, bg.to_vec()).ok()
         } else {
-            Some(CFrameData::new(self.width, self.height, chars, rgb))
+            CFrameData::try_new(self.width, self.height, chars, rgb).ok()
         }
     }
 }
 
+/// Per-frame metadata from sources that carry timing or annotations, such as
+/// recorded terminal sessions or manifests.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FrameMeta {
+    /// When the frame starts, in milliseconds from the start of the animation
+    pub timestamp_ms: Option<f64>,
+    /// How long the frame stays on screen, in milliseconds
+    pub duration_ms: Option<f64>,
+    /// Human-readable label, e.g. a chapter or scene name
+    pub label: Option<String>,
+    /// Free-form tags
+    pub tags: Vec<String>,
+    /// Named regions of interest, in drawing order
+    pub regions: Vec<Region>,
+}
+
+impl FrameMeta {
+    /// The first region called `name`.
+    pub fn region(&self, name: &str) -> Option<&Region> {
+        self.regions.iter().find(|region| region.name == name)
+    }
+
+    /// The topmost (last-listed) region containing cell (`col`, `row`).
+    pub fn region_at(&self, col: usize, row: usize) -> Option<&Region> {
+        self.regions.iter().rev().find(|region| region.contains(col, row))
+    }
+}
+
 /// A loaded frame containing text content and optional color data.
 #[derive(Clone, Debug)]
 pub struct Frame {
@@ -321,6 +628,8 @@ pub struct Frame {
     pub content: String,
     /// Optional color frame data for colored rendering
     pub cframe: Option<CFrameData>,
+    /// Optional timing and annotations
+    pub meta: Option<FrameMeta>,
 }
 
 impl Frame {
@@ -329,6 +638,7 @@ impl Frame {
         Self {
             content,
             cframe: None,
+            meta: None,
         }
     }
 
@@ -337,7 +647,33 @@ impl Frame {
         Self {
             content,
             cframe: Some(cframe),
+            meta: None,
+        }
+    }
+
+    /// Attach metadata to this frame.
+    pub fn with_meta(mut self, meta: FrameMeta) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
+    /// The topmost region of interest containing cell (`col`, `row`); see
+    /// [`FrameMeta::region_at`].
+    pub fn region_at(&self, col: usize, row: usize) -> Option<&Region> {
+        self.meta.as_ref()?.region_at(col, row)
+    }
+
+    /// How long this frame stays on screen, from its metadata.
+    ///
+    /// Uses `duration_ms` when set, otherwise the gap to `next`'s timestamp.
+    /// Returns `None` when neither is known.
+    pub fn duration_ms(&self, next: Option<&Frame>) -> Option<f64> {
+        let meta = self.meta.as_ref()?;
+        if let Some(duration) = meta.duration_ms {
+            return Some(duration);
         }
+        let next_timestamp = next?.meta.as_ref()?.timestamp_ms?;
+        meta.timestamp_ms.map(|timestamp| next_timestamp - timestamp)
     }
 
     /// Check if this frame has color data available.
@@ -346,13 +682,124 @@ impl Frame {
         self.cframe.is_some()
     }
 
+    /// Stable content checksum of the text and, when present, the color data.
+    pub fn checksum(&self) -> u64 {
+        let hash = fnv1a(FNV_OFFSET, self.content.as_bytes());
+        match &self.cframe {
+            Some(cframe) => fnv1a(hash, &cframe.checksum().to_le_bytes()),
+            None => hash,
+        }
+    }
+
     /// Get the frame dimensions (columns, rows) from the text content.
+    ///
+    /// Columns are measured with [`display_width`].
     pub fn dimensions(&self) -> (usize, usize) {
         let lines: Vec<&str> = self.content.lines().collect();
         let rows = lines.len();
-        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let cols = lines.iter().map(|l| display_width(l)).max().unwrap_or(0);
         (cols, rows)
     }
+
+    /// Smallest `(x, y, width, height)` cell rectangle holding all content,
+    /// or `None` for a blank frame. Uses the color data when present (see
+    /// [`CFrameData::content_bounds`]), otherwise the non-space characters.
+    pub fn content_bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        if let Some(cframe) = &self.cframe {
+            return cframe.content_bounds().map(|(x, y, width, height)| (x as usize, y as usize, width as usize, height as usize));
+        }
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        for (y, line) in self.content.lines().enumerate() {
+            for (x, _) in line.chars().enumerate().filter(|(_, ch)| *ch != ' ') {
+                (min_x, min_y, max_x, max_y) = (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y));
+            }
+        }
+        (min_x != usize::MAX).then(|| (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+    }
+
+    /// Crop away the empty margins around this frame's content. Blank
+    /// frames are returned unchanged.
+    ///
+    /// To crop a whole animation consistently, use
+    /// [`FrameSequence::trim`](crate::FrameSequence::trim).
+    pub fn trim(&self) -> Frame {
+        match self.content_bounds() {
+            Some((x, y, width, height)) => self.crop(x, y, width, height),
+            None => self.clone(),
+        }
+    }
+
+    /// Copy the `width` x `height` region whose top-left cell is (`x`, `y`),
+    /// from both the text and the color data. See [`CFrameData::crop`].
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Frame {
+        let content = self.content.lines().skip(y).take(height).map(|line| {
+            let mut line: String = line.chars().skip(x).take(width).collect();
+            line.push('\n');
+            line
+        }).collect();
+        let clamp = |value: usize| value.min(u32::MAX as usize) as u32;
+        let cframe = self.cframe.as_ref().map(|cframe| cframe.crop(clamp(x), clamp(y), clamp(width), clamp(height)));
+        Frame {content, cframe, meta: self.meta.clone()}
+    }
+}
+
+/// Per-frame display durations in milliseconds for
+/// [`AnimationController::set_frame_durations`](crate::AnimationController::set_frame_durations).
+///
+/// Frames without timing metadata get `None` and play at the controller's
+/// FPS. Returns an empty list when no frame carries timing, which leaves the
+/// controller in fixed-rate mode.
+pub fn frame_durations(frames: &[Frame]) -> Vec<Option<u32>> {
+    let durations: Vec<Option<u32>> = frames.iter().enumerate()
+        .map(|(i, frame)| frame.duration_ms(frames.get(i + 1)).filter(|ms| ms.is_finite()).map(|ms| ms.round().clamp(1.0, u32::MAX as f64) as u32))
+        .collect();
+    if durations.iter().all(Option::is_none) { Vec::new() } else { durations }
+}
+
+/// A cheaply clonable, shared handle to a [`Frame`].
+///
+/// Cloning bumps a reference count instead of copying the text and color
+/// buffers, so caches, loaders, and render paths can hold the same frame
+/// without duplicating hundreds of megabytes for long animations.
+#[derive(Clone, Debug)]
+pub struct SharedFrame(Arc<Frame>);
+
+impl SharedFrame {
+    /// Wrap a frame for sharing.
+    pub fn new(frame: Frame) -> Self {
+        Self(Arc::new(frame))
+    }
+
+    /// Mutable access, copying the frame first if other handles share it.
+    pub fn make_mut(&mut self) -> &mut Frame {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Whether two handles point at the same frame.
+    #[inline]
+    pub fn ptr_eq(&self, other: &SharedFrame) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Unwrap the frame, cloning it if other handles still share it.
+    pub fn into_inner(self) -> Frame {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl Deref for SharedFrame {
+    type Target = Frame;
+
+    #[inline]
+    fn deref(&self) -> &Frame {
+        &self.0
+    }
+}
+
+impl From<Frame> for SharedFrame {
+    fn from(frame: Frame) -> Self {
+        Self::new(frame)
+    }
 }
 
 #[cfg(test)]
@@ -368,6 +815,81 @@ mod tests {
         assert_eq!(FrameFile::extract_index("no_digits", 99), 99);
     }
 
+    #[test]
+    fn test_crop() {
+        let chars = b"ABCDEFGHI".to_vec();
+        let rgb: Vec<u8> = (0..27).collect();
+        let cframe = CFrameData::with_background(3, 3, chars, rgb.clone(), rgb.iter().map(|v| v + 100).collect());
+
+        let cropped = cframe.crop(1, 1, 2, 5);
+        assert_eq!((cropped.width, cropped.height), (2, 2));
+        assert_eq!(cropped.chars, b"EFHI");
+        assert_eq!(cropped.rgb_at(0, 0), Some((12, 13, 14)));
+        assert_eq!(cropped.bg_rgb_at(1, 1), Some((124, 125, 126)));
+        assert_eq!(cframe.crop(5, 0, 2, 2).pixel_count(), 0);
+
+        let frame = Frame::with_color(cframe.to_text(), cframe);
+        let cropped = frame.crop(0, 2, 2, 1);
+        assert_eq!(cropped.content, "GH\n");
+        assert_eq!(cropped.cframe.map(|cframe| cframe.chars), Some(b"GH".to_vec()));
+    }
+
+    #[test]
+    fn test_resample() {
+        let chars = b"ABCD".to_vec();
+        let rgb: Vec<u8> = (0..12).collect();
+        let cframe = CFrameData::with_background(2, 2, chars, rgb.clone(), rgb);
+
+        let up = cframe.resample(4, 2);
+        assert_eq!(up.chars, b"AABBCCDD");
+        assert_eq!(up.rgb_at(1, 3), Some((9, 10, 11)));
+        assert!(up.has_background());
+
+        let wide = CFrameData::new(6, 1, b"abcdef".to_vec(), vec![0; 18]);
+        assert_eq!(wide.resample(3, 1).chars, b"bdf");
+        assert_eq!(wide.resample(0, 0).pixel_count(), 0);
+    }
+
+    #[test]
+    fn test_composite() {
+        let base = CFrameData::new(3, 2, b"abcdef".to_vec(), vec![100; 18]);
+        let sprite = CFrameData::new(2, 2, b"X YZ".to_vec(), vec![200; 12]);
+
+        let mut over = base.clone();
+        over.composite(&sprite, 2, 1, CompositeMode::SourceOver);
+        assert_eq!(over.chars, b"abcdeX");
+        assert_eq!(over.rgb_at(1, 2), Some((200, 200, 200)));
+
+        let mut multiplied = base.clone();
+        multiplied.composite(&sprite, -1, 0, CompositeMode::Multiply);
+        assert_eq!(multiplied.chars, b"abcZef");
+        assert_eq!(multiplied.rgb_at(1, 0), Some((78, 78, 78)));
+        assert_eq!(multiplied.rgb_at(0, 0), Some((100, 100, 100)));
+
+        let mut under = CFrameData::new(2, 1, b"a ".to_vec(), vec![100; 6]);
+        under.composite(&sprite, 0, 0, CompositeMode::DestinationOver);
+        assert_eq!(under.chars, b"a ");
+        under.composite(&sprite, 0, -1, CompositeMode::DestinationOver);
+        assert_eq!((under.chars.as_slice(), under.rgb_at(0, 1)), (&b"aZ"[..], Some((200, 200, 200))));
+
+        let mut backed = CFrameData::with_background(1, 1, b"a".to_vec(), vec![0; 3], vec![10; 3]);
+        backed.composite(&CFrameData::with_background(1, 1, b" ".to_vec(), vec![0; 3], vec![50; 3]), 0, 0, CompositeMode::SourceOver);
+        assert_eq!((backed.chars[0], backed.bg_rgb_at(0, 0)), (b' ', Some((50, 50, 50))));
+    }
+
+    #[test]
+    fn test_shared_frame() {
+        let mut shared = SharedFrame::from(Frame::text_only("A".to_string()));
+        let other = shared.clone();
+        assert!(shared.ptr_eq(&other));
+        assert_eq!(other.dimensions(), (1, 1));
+
+        shared.make_mut().cframe = Some(CFrameData::new(1, 1, vec![b'A'], vec![255; 3]));
+        assert!(!shared.ptr_eq(&other));
+        assert!(shared.has_color() && !other.has_color());
+        assert_eq!(other.into_inner().content, "A");
+    }
+
     #[test]
     fn test_cframe_accessors() {
         let cframe = CFrameData {
@@ -443,12 +965,35 @@ mod tests {
         assert_eq!(frame2.dimensions(), (4, 2));
     }
 
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("abc"), 3);
+        let frame = Frame::text_only("日本\nab".to_string());
+        #[cfg(feature = "unicode-width")]
+        {
+            assert_eq!(display_width("e\u{301}"), 1);
+            assert_eq!(frame.dimensions(), (4, 2));
+        }
+        #[cfg(not(feature = "unicode-width"))]
+        assert_eq!(frame.dimensions(), (2, 2));
+    }
+
     #[test]
     fn test_cframe_to_text() {
         let cframe = CFrameData {width: 2, height: 2, chars: vec![b'A', b'B', b'C', b'D'], rgb: vec![255, 0, 0, 0, 255, 0, 0, 0, 255, 128, 128, 128], bg_rgb: None};
         assert_eq!(cframe.to_text(), "AB\nCD\n");
     }
 
+    #[test]
+    fn test_cframe_to_text_trimmed() {
+        let cframe = CFrameData::new(3, 3, b"A B C    ".to_vec(), vec![0; 27]);
+        assert_eq!(cframe.to_text(), "A B\n C \n   \n");
+        let trim_spaces = TextOptions {trim_trailing_spaces: true, ..TextOptions::default()};
+        assert_eq!(cframe.to_text_with(trim_spaces), "A B\n C\n\n");
+        assert_eq!(cframe.to_text_with(TextOptions {trim_trailing_lines: true, ..trim_spaces}), "A B\n C\n");
+        assert_eq!(CFrameData::new(1, 1, vec![b' '], vec![0; 3]).to_text_with(TextOptions {trim_trailing_spaces: true, trim_trailing_lines: true}), "");
+    }
+
     #[test]
     fn test_packed_blob_decode_frame() {
         let blob = PackedCFrameBlob::new(2, 2, 1, vec![b'A', 255, 0, 0, b'B', 0, 255, 0, b'C', 0, 0, 255, b'D', 255, 255, 255]);
@@ -473,4 +1018,52 @@ mod tests {
         let second = blob.decode_frame(1).unwrap();
         assert_eq!(second.bg_rgb.as_deref(), Some(&[40, 50, 60][..]));
     }
+
+    #[test]
+    fn test_checksum_is_content_based() {
+        let a = CFrameData::new(2, 1, vec![b'A', b'B'], vec![1, 2, 3, 4, 5, 6]);
+        let mut b = a.clone();
+        assert_eq!(a.checksum(), b.checksum());
+        assert_eq!(a.checksum(), 0xcfab_5b10_7447_c82e);
+
+        b.rgb[5] = 7;
+        assert_ne!(a.checksum(), b.checksum());
+        assert_ne!(a.checksum(), CFrameData::with_background(2, 1, a.chars.clone(), a.rgb.clone(), vec![0; 6]).checksum());
+        assert_ne!(Frame::text_only("AB\n".into()).checksum(), Frame::with_color("AB\n".into(), a).checksum());
+    }
+
+    #[test]
+    fn test_content_bounds_and_trim() {
+        let cframe = CFrameData::new(4, 3, b"     AB  C  ".to_vec(), vec![0; 36]);
+        assert_eq!(cframe.content_bounds(), Some((1, 1, 2, 2)));
+        let mut backed = CFrameData::with_background(3, 1, b"   ".to_vec(), vec![0; 9], vec![0; 9]);
+        assert_eq!(backed.content_bounds(), None);
+        backed.bg_rgb.as_mut().unwrap()[6] = 255;
+        assert_eq!(backed.content_bounds(), Some((2, 0, 1, 1)));
+
+        let frame = Frame::text_only("    \n  x \n  yz\n".into());
+        assert_eq!(frame.content_bounds(), Some((2, 1, 2, 2)));
+        assert_eq!(frame.trim().content, "x \nyz\n");
+        assert_eq!(Frame::text_only("  \n".into()).trim().content, "  \n");
+    }
+
+    #[test]
+    fn test_cframe_validation() {
+        assert!(CFrameData::try_new(2, 1, b"AB".to_vec(), vec![0; 6]).is_ok());
+        assert_eq!(CFrameData::try_new(2, 1, b"A".to_vec(), vec![0; 6]).unwrap_err(), CFrameError::CharCountMismatch {expected: 2, actual: 1});
+        assert_eq!(CFrameData::try_new(2, 1, b"AB".to_vec(), vec![0; 5]).unwrap_err(), CFrameError::RgbLengthMismatch {expected: 6, actual: 5});
+        let err = CFrameData::try_with_background(1, 1, b"A".to_vec(), vec![0; 3], vec![0; 6]).unwrap_err();
+        assert_eq!(err.to_string(), "Background color length mismatch: expected 3 bytes, got 6");
+        assert!(CFrameData::new(0, 0, Vec::new(), Vec::new()).validate().is_ok());
+    }
+
+    #[test]
+    fn test_frame_durations_from_meta() {
+        let timed = |timestamp_ms: f64| Frame::text_only("A".into()).with_meta(FrameMeta {timestamp_ms: Some(timestamp_ms), ..FrameMeta::default()});
+        let held = Frame::text_only("B".into()).with_meta(FrameMeta {duration_ms: Some(500.0), label: Some("intro".into()), ..FrameMeta::default()});
+        let frames = vec![timed(0.0), timed(120.0), held, timed(900.0)];
+        assert_eq!(frame_durations(&frames), [Some(120), None, Some(500), None]);
+        assert_eq!(frames[0].crop(0, 0, 1, 1).meta, frames[0].meta);
+        assert!(frame_durations(&[Frame::text_only("A".into())]).is_empty());
+    }
 }
@@ -1,23 +1,118 @@
-use crate::color::FrameColors;
+use std::collections::BTreeMap;
+
+use crate::color::{parse_color, FrameColors};
+use crate::LoopMode;
 
 /// Project metadata from a `details.toml` file.
 ///
 /// All fields are optional for forward/backward compatibility
-/// with different cascii versions.
+/// with different cascii versions. Keys this version doesn't know are kept
+/// in [`extra`](Self::extra) and written back by
+/// [`to_toml_string`](Self::to_toml_string) and
+/// [`to_json_string`](Self::to_json_string).
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProjectDetails {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub version: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub frames: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub luminance: Option<u8>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub font_ratio: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub columns: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub fps: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub output: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub audio: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub background_color: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub color: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub fit_cell_backgrounds: Option<bool>,
+    /// Soundtrack path, relative to the project directory
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub audio_file: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub title: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub author: Option<String>,
+    /// Creation time as an RFC 3339 string, e.g. `"2026-01-31T12:00:00Z"`;
+    /// TOML datetimes are read as strings too
+    #[cfg_attr(feature = "serde", serde(default, deserialize_with = "datetime_string", skip_serializing_if = "Option::is_none"))]
+    pub created: Option<String>,
+    /// Characters used for luminance, darkest first
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub charset: Option<String>,
+    /// Whether playback should loop (see [`loop_mode`](Self::loop_mode))
+    #[cfg_attr(feature = "serde", serde(rename = "loop", skip_serializing_if = "Option::is_none"))]
+    pub looping: Option<bool>,
+    /// Named frame ranges with their own pace, looping or colors, from the
+    /// `[sections]` table
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub sections: BTreeMap<String, DetailsSection>,
+    /// Keys not covered by the fields above, preserved on round-trip;
+    /// `null` values are dropped
+    #[cfg_attr(feature = "serde", serde(flatten, deserialize_with = "skip_nulls"))]
+    pub extra: BTreeMap<String, DetailsValue>,
+}
+
+/// The value of a key [`ProjectDetails`] doesn't know, in a form both the
+/// TOML and JSON formats can write back.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum DetailsValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    /// A string; TOML dates and times are read as strings too
+    String(String),
+    Array(Vec<DetailsValue>),
+    /// A nested table / object
+    Table(BTreeMap<String, DetailsValue>),
+}
+
+/// Key the `toml` crate wraps dates and times in when deserializing them.
+#[cfg(feature = "serde")]
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+#[cfg(feature = "serde")]
+impl DetailsValue {
+    /// Replace the `toml` crate's wrapped dates and times with plain strings.
+    fn unwrap_datetimes(self) -> Self {
+        match self {
+            DetailsValue::Table(mut table) if table.len() == 1 && table.contains_key(TOML_DATETIME_KEY) => {
+                table.remove(TOML_DATETIME_KEY).unwrap_or(DetailsValue::Table(table))
+            }
+            DetailsValue::Table(table) => DetailsValue::Table(table.into_iter().map(|(key, value)| (key, value.unwrap_datetimes())).collect()),
+            DetailsValue::Array(values) => DetailsValue::Array(values.into_iter().map(DetailsValue::unwrap_datetimes).collect()),
+            value => value,
+        }
+    }
+}
+
+/// Read the unknown keys, dropping `null`s, which TOML can't represent.
+#[cfg(feature = "serde")]
+fn skip_nulls<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, DetailsValue>, D::Error> {
+    let values: BTreeMap<String, Option<DetailsValue>> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(values.into_iter().filter_map(|(key, value)| Some((key, value?.unwrap_datetimes()))).collect())
+}
+
+/// Read an optional string, accepting TOML dates and times as strings.
+#[cfg(feature = "serde")]
+fn datetime_string<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value: Option<DetailsValue> = serde::Deserialize::deserialize(deserializer)?;
+    match value.map(DetailsValue::unwrap_datetimes) {
+        None => Ok(None),
+        Some(DetailsValue::String(value)) => Ok(Some(value)),
+        Some(_) => Err(serde::de::Error::custom("expected a string or datetime")),
+    }
 }
 
 impl ProjectDetails {
@@ -27,18 +122,361 @@ impl ProjectDetails {
         toml::from_str(s)
     }
 
+    /// Serialize as a `details.toml` string; unset fields are omitted.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::ProjectDetails;
+    ///
+    /// let details = ProjectDetails {fps: Some(24), color: Some("#f6f6f6".into()), ..ProjectDetails::default()};
+    /// let toml = details.to_toml_string().unwrap();
+    /// assert_eq!(toml, "fps = 24\ncolor = \"#f6f6f6\"\n");
+    /// assert_eq!(ProjectDetails::from_toml_str(&toml).unwrap().fps, Some(24));
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Parse a `details.json`, as written by web exporters. Keys match the
+    /// TOML ones.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Serialize as a `details.json` string; unset fields are omitted.
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parse details in whichever format `s` is in: JSON when it starts
+    /// with `{`, TOML otherwise. TOML input is reported as an error when
+    /// the `toml` feature is disabled.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::ProjectDetails;
+    ///
+    /// let details = ProjectDetails::from_any(r#"{"fps": 12, "title": "Rain"}"#).unwrap();
+    /// assert_eq!((details.fps, details.title.as_deref()), (Some(12), Some("Rain")));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn from_any(s: &str) -> Result<Self, String> {
+        if s.trim_start().starts_with('{') {
+            return Self::from_json_str(s).map_err(|err| format!("Invalid details JSON: {}", err));
+        }
+        #[cfg(feature = "toml")]
+        return Self::from_toml_str(s).map_err(|err| format!("Invalid details TOML: {}", err));
+        #[cfg(not(feature = "toml"))]
+        Err("details TOML requires the `toml` feature".to_string())
+    }
+
+    /// Write the details to `path` as TOML, e.g. back to a project's
+    /// `details.toml` after editing.
+    #[cfg(all(feature = "toml", feature = "std"))]
+    pub fn write_toml_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let toml = self.to_toml_string().map_err(std::io::Error::other)?;
+        std::fs::write(path, toml)
+    }
+
+    /// The preferred loop mode, if the project states one.
+    pub fn loop_mode(&self) -> Option<LoopMode> {
+        self.looping.map(|looping| if looping { LoopMode::Loop } else { LoopMode::Once })
+    }
+
+    /// Check the details for values that would otherwise be silently
+    /// replaced by defaults. Pass the number of loaded frames to also check
+    /// the declared `frames` count.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{DetailsWarning, ProjectDetails};
+    ///
+    /// let details = ProjectDetails {fps: Some(0), frames: Some(10), ..ProjectDetails::default()};
+    /// assert_eq!(details.validate(Some(8)), vec![
+    ///     DetailsWarning::ZeroFps,
+    ///     DetailsWarning::FrameCountMismatch {declared: 10, loaded: 8},
+    /// ]);
+    /// ```
+    pub fn validate(&self, loaded_frames: Option<usize>) -> Vec<DetailsWarning> {
+        let mut warnings = Vec::new();
+        if self.fps == Some(0) {
+            warnings.push(DetailsWarning::ZeroFps);
+        }
+        if let (Some(declared), Some(loaded)) = (self.frames, loaded_frames) {
+            if declared != loaded {
+                warnings.push(DetailsWarning::FrameCountMismatch {declared, loaded});
+            }
+        }
+        for (field, value) in [("color", &self.color), ("background_color", &self.background_color)] {
+            if let Some(value) = value {
+                if parse_color(value).is_none() {
+                    warnings.push(DetailsWarning::InvalidColor {field: field.to_string(), value: value.clone()});
+                }
+            }
+        }
+        if let Some(version) = &self.version {
+            if parse_version(version).is_none() {
+                warnings.push(DetailsWarning::UnknownVersion(version.clone()));
+            }
+        }
+        let frame_count = loaded_frames.or(self.frames);
+        for (name, section) in &self.sections {
+            if section.start > section.end || frame_count.is_some_and(|count| section.end >= count) {
+                warnings.push(DetailsWarning::InvalidSection {name: name.clone(), start: section.start, end: section.end});
+            }
+        }
+        warnings
+    }
+
+    /// The section covering `frame`, with its name. When sections overlap,
+    /// the one starting last wins.
+    pub fn section_at(&self, frame: usize) -> Option<(&str, &DetailsSection)> {
+        self.sections.iter()
+            .filter(|(_, section)| section.contains(frame))
+            .max_by_key(|(_, section)| section.start)
+            .map(|(name, section)| (name.as_str(), section))
+    }
+
+    /// Per-frame durations from the sections' `fps`, for
+    /// [`AnimationController::set_frame_durations`](crate::AnimationController::set_frame_durations).
+    /// Frames outside a paced section are `None` and keep the project rate;
+    /// the list is empty when no section sets `fps`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{AnimationController, DetailsSection, ProjectDetails};
+    ///
+    /// let mut details = ProjectDetails {fps: Some(24), ..ProjectDetails::default()};
+    /// details.sections.insert("slow".into(), DetailsSection {start: 2, end: 3, fps: Some(4), ..DetailsSection::default()});
+    ///
+    /// let mut controller = AnimationController::new(24);
+    /// controller.set_frame_count(5);
+    /// controller.set_frame_durations(details.section_durations(5));
+    /// assert_eq!((controller.frame_interval_ms(1), controller.frame_interval_ms(2)), (41, 250));
+    /// ```
+    pub fn section_durations(&self, frame_count: usize) -> Vec<Option<u32>> {
+        if !self.sections.values().any(|section| section.fps.is_some()) {
+            return Vec::new();
+        }
+        (0..frame_count)
+            .map(|frame| self.section_at(frame).and_then(|(_, section)| section.fps).map(|fps| (1000 / fps.max(1)).max(1)))
+            .collect()
+    }
+
+    /// Frame range `(start, end)` to repeat while `frame` is playing, when it
+    /// falls in a section with `loop = true`. Hand it to
+    /// [`AnimationController::set_range_frames`](crate::AnimationController::set_range_frames).
+    pub fn loop_range_at(&self, frame: usize) -> Option<(usize, usize)> {
+        self.section_at(frame)
+            .filter(|(_, section)| section.looping == Some(true))
+            .map(|(_, section)| (section.start, section.end))
+    }
+
+    /// Like [`frame_colors`](Self::frame_colors), with the color overrides
+    /// of the section covering `frame` applied first.
+    pub fn frame_colors_at(&self, frame: usize) -> FrameColors {
+        let Some((_, section)) = self.section_at(frame) else {
+            return self.frame_colors();
+        };
+        let background = section.background_color.as_deref().or(self.background_color.as_deref()).and_then(parse_color).unwrap_or((0, 0, 0));
+        let foreground = section.color.as_deref().or(self.color.as_deref()).and_then(parse_color).unwrap_or_else(|| FrameColors::auto_foreground(background));
+        FrameColors {foreground, background}
+    }
+
+    /// Compare the project's `version` with `supported`, the newest
+    /// cascii version the viewer understands, e.g. to warn "this project was
+    /// made with a newer cascii" before loading. An unparseable `supported`
+    /// version is reported as [`InvalidSupported`](Compatibility::InvalidSupported)
+    /// rather than blamed on the project.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{Compatibility, ProjectDetails};
+    ///
+    /// let details = ProjectDetails {version: Some("2.1.0".into()), ..ProjectDetails::default()};
+    /// assert_eq!(details.check_compatibility("2.3.0"), Compatibility::Compatible);
+    /// assert_eq!(details.check_compatibility("2.0.4"), Compatibility::Newer("2.1.0".into()));
+    /// assert!(!details.check_compatibility("1.9.0").is_supported());
+    /// ```
+    pub fn check_compatibility(&self, supported: &str) -> Compatibility {
+        let Some(supported_version) = parse_version(supported) else {
+            return Compatibility::InvalidSupported(supported.to_string());
+        };
+        let Some(version) = &self.version else {
+            return Compatibility::Unversioned;
+        };
+        let Some(project) = parse_version(version) else {
+            return Compatibility::Unknown(version.clone());
+        };
+        match breaking_part(project).cmp(&breaking_part(supported_version)) {
+            std::cmp::Ordering::Greater => Compatibility::Incompatible(version.clone()),
+            std::cmp::Ordering::Less => Compatibility::Older(version.clone()),
+            std::cmp::Ordering::Equal if project > supported_version => Compatibility::Newer(version.clone()),
+            std::cmp::Ordering::Equal => Compatibility::Compatible,
+        }
+    }
+
     /// Extract parsed foreground/background colors from the project details.
     ///
     /// Uses the `color` field as foreground and `background_color` as background.
-    /// Falls back to white foreground and black background when fields are
-    /// missing or contain invalid color values.
+    /// The background falls back to black when missing or invalid; a missing
+    /// or invalid foreground is picked by [`FrameColors::auto_foreground`]
+    /// so text stays readable on the background.
     pub fn frame_colors(&self) -> FrameColors {
-        let fg = self.color.as_deref().unwrap_or("white");
-        let bg = self.background_color.as_deref().unwrap_or("black");
-        FrameColors::from_strings(fg, bg)
+        let background = self.background_color.as_deref().and_then(parse_color).unwrap_or((0, 0, 0));
+        let foreground = self.color.as_deref().and_then(parse_color).unwrap_or_else(|| FrameColors::auto_foreground(background));
+        FrameColors {foreground, background}
+    }
+}
+
+/// Overrides for a range of frames, one entry of the `[sections]` table.
+///
+/// ```toml
+/// [sections.intro]
+/// start = 0
+/// end = 23
+/// fps = 8
+/// loop = true
+/// color = "#ffd166"
+/// ```
+///
+/// Unset fields fall back to the project-wide values.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetailsSection {
+    /// First frame of the section
+    pub start: usize,
+    /// Last frame of the section (inclusive)
+    pub end: usize,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub fps: Option<u32>,
+    /// Whether playback repeats the section instead of moving on
+    #[cfg_attr(feature = "serde", serde(rename = "loop", skip_serializing_if = "Option::is_none"))]
+    pub looping: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub color: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub background_color: Option<String>,
+}
+
+impl DetailsSection {
+    /// Returns `true` when `frame` is inside the section.
+    pub fn contains(&self, frame: usize) -> bool {
+        (self.start..=self.end).contains(&frame)
+    }
+}
+
+/// A problem found by [`ProjectDetails::validate`].
+///
+/// Playback still works with these (the affected setting falls back to its
+/// default), but viewers can surface them instead of failing silently.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DetailsWarning {
+    /// `fps = 0`; the player's default rate is used instead
+    ZeroFps,
+    /// `frames` differs from the number of frames actually loaded
+    FrameCountMismatch {declared: usize, loaded: usize},
+    /// A color field can't be parsed by [`parse_color`]
+    InvalidColor {field: String, value: String},
+    /// `version` isn't a `major[.minor[.patch]]` version number
+    UnknownVersion(String),
+    /// A section starts after it ends, or ends past the last frame
+    InvalidSection {name: String, start: usize, end: usize},
+}
+
+impl std::fmt::Display for DetailsWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DetailsWarning::ZeroFps => write!(f, "fps must be greater than 0"),
+            DetailsWarning::FrameCountMismatch {declared, loaded} => write!(f, "details.toml declares {} frames, found {}", declared, loaded),
+            DetailsWarning::InvalidColor {field, value} => write!(f, "{} = {:?} is not a valid color", field, value),
+            DetailsWarning::UnknownVersion(version) => write!(f, "unrecognized version {:?}", version),
+            DetailsWarning::InvalidSection {name, start, end} => write!(f, "section {:?} covers invalid frames {}..={}", name, start, end),
+        }
+    }
+}
+
+/// How a project's `version` relates to the version a viewer supports,
+/// from [`ProjectDetails::check_compatibility`].
+///
+/// Versions follow semver: a different major version (or minor version,
+/// while the major is `0`) is a breaking change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compatibility {
+    /// Same version, or older without breaking changes
+    Compatible,
+    /// The details don't record a version
+    Unversioned,
+    /// Made with an older, breaking release; some settings may be read
+    /// differently
+    Older(String),
+    /// Made with a newer release without breaking changes; newer settings
+    /// are ignored
+    Newer(String),
+    /// Made with a newer, breaking release; loading may fail or look wrong
+    Incompatible(String),
+    /// The project's version can't be parsed
+    Unknown(String),
+    /// The `supported` version passed to
+    /// [`ProjectDetails::check_compatibility`] can't be parsed
+    InvalidSupported(String),
+}
+
+impl Compatibility {
+    /// Returns `false` for [`Incompatible`](Self::Incompatible),
+    /// [`Unknown`](Self::Unknown) and
+    /// [`InvalidSupported`](Self::InvalidSupported) results, which viewers
+    /// should warn about.
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, Compatibility::Incompatible(_) | Compatibility::Unknown(_) | Compatibility::InvalidSupported(_))
+    }
+}
+
+impl std::fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compatibility::Compatible => write!(f, "compatible"),
+            Compatibility::Unversioned => write!(f, "project does not record a cascii version"),
+            Compatibility::Older(version) => write!(f, "this project was made with an older cascii ({})", version),
+            Compatibility::Newer(version) => write!(f, "this project was made with a newer cascii ({}); some settings may be ignored", version),
+            Compatibility::Incompatible(version) => write!(f, "this project was made with a newer cascii ({}) and may not load correctly", version),
+            Compatibility::Unknown(version) => write!(f, "unrecognized cascii version {:?}", version),
+            Compatibility::InvalidSupported(version) => write!(f, "invalid supported cascii version {:?}", version),
+        }
     }
 }
 
+/// Parse `major[.minor[.patch]]`, with an optional leading `v` and ignoring
+/// any `-pre`/`+build` suffix.
+fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let version = version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// The part of a version that changes on breaking releases.
+fn breaking_part((major, minor, _): (u32, u32, u32)) -> (u32, u32) {
+    if major == 0 { (0, minor) } else { (major, 0) }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,6 +523,193 @@ mod tests {
         assert_eq!(colors.background, (0, 0, 255));
     }
 
+    #[test]
+    fn light_background_gets_dark_foreground() {
+        let details = ProjectDetails {
+            background_color: Some("#f6f6f6".into()),
+            ..Default::default()
+        };
+        let colors = details.frame_colors();
+        assert_eq!(colors.foreground, (0, 0, 0));
+        assert_eq!(colors.background, (246, 246, 246));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_round_trip() {
+        let details = ProjectDetails {
+            version: Some("1.2.0".into()),
+            frames: Some(120),
+            font_ratio: Some(0.5),
+            audio: Some(false),
+            background_color: Some("black".into()),
+            ..Default::default()
+        };
+        let parsed = ProjectDetails::from_toml_str(&details.to_toml_string().unwrap()).unwrap();
+        assert_eq!((parsed.version, parsed.frames, parsed.font_ratio, parsed.audio), (details.version, details.frames, details.font_ratio, details.audio));
+        assert_eq!(parsed.background_color.as_deref(), Some("black"));
+        assert!(parsed.color.is_none());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn extended_fields_and_unknown_keys_round_trip() {
+        let source = r#"
+            version = "2.0.0"
+            title = "Waves"
+            author = "someone"
+            created = "2026-01-31T12:00:00Z"
+            audio = true
+            audio_file = "waves.ogg"
+            charset = " .:-=+*#%@"
+            loop = false
+            future_flag = true
+            exported = 2026-02-01T08:00:00Z
+
+            [future_table]
+            depth = 3
+        "#;
+        let details = ProjectDetails::from_toml_str(source).unwrap();
+        assert_eq!((details.title.as_deref(), details.audio_file.as_deref()), (Some("Waves"), Some("waves.ogg")));
+        assert_eq!(details.charset.as_deref(), Some(" .:-=+*#%@"));
+        assert_eq!(details.loop_mode(), Some(LoopMode::Once));
+        assert_eq!(details.extra.len(), 3);
+        assert_eq!(details.extra["exported"], DetailsValue::String("2026-02-01T08:00:00Z".into()));
+
+        let written = details.to_toml_string().unwrap();
+        assert!(written.contains("loop = false") && written.contains("future_flag = true") && written.contains("[future_table]"));
+        let reparsed = ProjectDetails::from_toml_str(&written).unwrap();
+        assert_eq!(reparsed.extra, details.extra);
+        assert_eq!(reparsed.created, details.created);
+
+        let native = ProjectDetails::from_toml_str("created = 2026-01-31T12:00:00Z\nfps = 12").unwrap();
+        assert_eq!((native.created.as_deref(), native.fps), (Some("2026-01-31T12:00:00Z"), Some(12)));
+        assert!(ProjectDetails::from_toml_str("created = 5").is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_round_trip() {
+        let details = ProjectDetails::from_json_str(r#"{"fps": 30, "loop": true, "color": "white", "frames": null}"#).unwrap();
+        assert_eq!((details.fps, details.looping, details.frames), (Some(30), Some(true), None));
+        assert_eq!(details.color.as_deref(), Some("white"));
+
+        let json = details.to_json_string().unwrap();
+        assert_eq!(json, "{\n  \"fps\": 30,\n  \"color\": \"white\",\n  \"loop\": true\n}");
+        let reparsed = ProjectDetails::from_any(&json).unwrap();
+        assert_eq!((reparsed.fps, reparsed.looping), (Some(30), Some(true)));
+
+        let unknown = ProjectDetails::from_json_str(r#"{"fps": 1, "exporter": null, "tags": ["a", 2, 0.5]}"#).unwrap();
+        assert_eq!(unknown.extra.len(), 1);
+        assert_eq!(unknown.extra["tags"], DetailsValue::Array(vec![DetailsValue::String("a".into()), DetailsValue::Integer(2), DetailsValue::Float(0.5)]));
+        assert!(unknown.to_json_string().unwrap().contains("\"tags\""));
+
+        let warning = DetailsWarning::InvalidColor {field: "color".into(), value: "#zzz".into()};
+        let json = serde_json::to_string(&warning).unwrap();
+        assert_eq!(serde_json::from_str::<DetailsWarning>(&json).unwrap(), warning);
+        assert!(ProjectDetails::from_any("{ not json").unwrap_err().starts_with("Invalid details JSON"));
+    }
+
+    #[test]
+    fn validate_reports_each_problem() {
+        let details = ProjectDetails {
+            version: Some("next".into()),
+            fps: Some(0),
+            frames: Some(3),
+            color: Some("#zzz".into()),
+            background_color: Some("#000080".into()),
+            ..Default::default()
+        };
+        assert_eq!(details.validate(Some(3)), vec![
+            DetailsWarning::ZeroFps,
+            DetailsWarning::InvalidColor {field: "color".into(), value: "#zzz".into()},
+            DetailsWarning::UnknownVersion("next".into()),
+        ]);
+        assert_eq!(details.validate(None).len(), 3);
+        assert!(ProjectDetails::default().validate(Some(5)).is_empty());
+    }
+
+    #[test]
+    fn version_parsing() {
+        assert_eq!(parse_version("1.4.2"), Some((1, 4, 2)));
+        assert_eq!(parse_version("v2"), Some((2, 0, 0)));
+        assert_eq!(parse_version("0.7.1-beta.2"), Some((0, 7, 1)));
+        assert_eq!(parse_version("1.2.3.4"), None);
+        assert_eq!(parse_version("1..2"), None);
+    }
+
+    #[test]
+    fn compatibility_follows_semver() {
+        let with = |version: &str| ProjectDetails {version: Some(version.into()), ..Default::default()};
+        assert_eq!(ProjectDetails::default().check_compatibility("1.0.0"), Compatibility::Unversioned);
+        assert_eq!(with("1.2.0").check_compatibility("1.4.1"), Compatibility::Compatible);
+        assert_eq!(with("1.4.2").check_compatibility("1.4.1"), Compatibility::Newer("1.4.2".into()));
+        assert_eq!(with("2.0.0").check_compatibility("1.4.1"), Compatibility::Incompatible("2.0.0".into()));
+        assert_eq!(with("0.8.0").check_compatibility("0.7.1"), Compatibility::Incompatible("0.8.0".into()));
+        assert_eq!(with("0.6.3").check_compatibility("1.0.0"), Compatibility::Older("0.6.3".into()));
+        assert_eq!(with("latest").check_compatibility("1.0.0"), Compatibility::Unknown("latest".into()));
+        assert_eq!(with("1.0.0").check_compatibility("main"), Compatibility::InvalidSupported("main".into()));
+        assert_eq!(with("1.0.0").check_compatibility("main").to_string(), "invalid supported cascii version \"main\"");
+    }
+
+    #[test]
+    fn section_overrides() {
+        let mut details = ProjectDetails {color: Some("white".into()), background_color: Some("black".into()), ..Default::default()};
+        details.sections.insert("verse".into(), DetailsSection {start: 0, end: 9, fps: Some(10), ..Default::default()});
+        details.sections.insert("hook".into(), DetailsSection {start: 4, end: 5, looping: Some(true), background_color: Some("#ffffff".into()), ..Default::default()});
+
+        assert_eq!(details.section_at(3).map(|(name, _)| name), Some("verse"));
+        assert_eq!(details.section_at(4).map(|(name, _)| name), Some("hook"));
+        assert_eq!(details.section_at(10), None);
+        assert_eq!(details.section_durations(11), [Some(100), Some(100), Some(100), Some(100), None, None, Some(100), Some(100), Some(100), Some(100), None]);
+        assert_eq!((details.loop_range_at(5), details.loop_range_at(6)), (Some((4, 5)), None));
+        assert_eq!(details.frame_colors_at(4), FrameColors {foreground: (255, 255, 255), background: (255, 255, 255)});
+        assert_eq!(details.frame_colors_at(10).background, (0, 0, 0));
+    }
+
+    #[test]
+    fn validate_reports_bad_sections() {
+        let mut details = ProjectDetails {frames: Some(10), ..Default::default()};
+        details.sections.insert("ok".into(), DetailsSection {start: 0, end: 9, ..Default::default()});
+        details.sections.insert("reversed".into(), DetailsSection {start: 5, end: 2, ..Default::default()});
+        details.sections.insert("tail".into(), DetailsSection {start: 8, end: 10, ..Default::default()});
+        assert_eq!(details.validate(None), vec![
+            DetailsWarning::InvalidSection {name: "reversed".into(), start: 5, end: 2},
+            DetailsWarning::InvalidSection {name: "tail".into(), start: 8, end: 10},
+        ]);
+        // Loaded frames take precedence over the declared count.
+        assert_eq!(details.validate(Some(12)), vec![
+            DetailsWarning::FrameCountMismatch {declared: 10, loaded: 12},
+            DetailsWarning::InvalidSection {name: "reversed".into(), start: 5, end: 2},
+        ]);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn sections_from_toml() {
+        let source = r#"
+            fps = 24
+
+            [sections.intro]
+            start = 0
+            end = 11
+            fps = 6
+            loop = true
+
+            [sections.outro]
+            start = 40
+            end = 47
+            color = "red"
+        "#;
+        let details = ProjectDetails::from_toml_str(source).unwrap();
+        assert_eq!(details.sections.len(), 2);
+        assert_eq!(details.sections["intro"], DetailsSection {start: 0, end: 11, fps: Some(6), looping: Some(true), ..Default::default()});
+        assert!(details.extra.is_empty());
+
+        let reparsed = ProjectDetails::from_toml_str(&details.to_toml_string().unwrap()).unwrap();
+        assert_eq!(reparsed.sections, details.sections);
+    }
+
     #[test]
     fn invalid_colors_fallback() {
         let details = ProjectDetails {
@@ -0,0 +1,293 @@
+//! C-compatible API for native hosts (Swift, Kotlin/JNI, C++).
+//!
+//! Everything is exposed through opaque handles created and released by this
+//! library. Strings and buffers returned to the caller must be released with
+//! the matching `*_free` function. The declarations are mirrored in
+//! `include/cascii_core_view.h`.
+//!
+//! Build a linkable library with:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! cargo rustc --release --features ffi --crate-type staticlib
+//! ```
+
+use std::ptr;
+use std::slice;
+
+use crate::render::raster::rasterize_cframe;
+use crate::{parse_cframe, AnimationController, CFrameData, ParseError, RenderConfig};
+
+/// Result codes returned by fallible FFI calls.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CasciiStatus {
+    Ok = 0,
+    NullPointer = 1,
+    FileTooSmall = 2,
+    SizeMismatch = 3,
+    InvalidDimensions = 4,
+    InvalidFrameCount = 5,
+    FrameCountMismatch = 6,
+    UnsupportedEncoding = 7,
+    DimensionsTooLarge = 8,
+}
+
+impl From<&ParseError> for CasciiStatus {
+    fn from(err: &ParseError) -> Self {
+        match err {
+            ParseError::FileTooSmall { .. } => CasciiStatus::FileTooSmall,
+            ParseError::SizeMismatch { .. } => CasciiStatus::SizeMismatch,
+            ParseError::InvalidDimensions { .. } => CasciiStatus::InvalidDimensions,
+            ParseError::InvalidFrameCount { .. } => CasciiStatus::InvalidFrameCount,
+            ParseError::FrameCountMismatch { .. } => CasciiStatus::FrameCountMismatch,
+            ParseError::UnsupportedEncoding { .. } => CasciiStatus::UnsupportedEncoding,
+            ParseError::DimensionsTooLarge { .. } => CasciiStatus::DimensionsTooLarge,
+        }
+    }
+}
+
+/// An RGB pixel buffer owned by this library.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CasciiImage {
+    /// `width * height * 3` bytes, row-major
+    pub data: *mut u8,
+    /// Length of `data` in bytes
+    pub len: usize,
+    /// Image width in pixels
+    pub width: u32,
+    /// Image height in pixels
+    pub height: u32,
+}
+
+// ---- Controller ----
+
+/// Create an animation controller. Release with [`cascii_controller_free`].
+#[no_mangle]
+pub extern "C" fn cascii_controller_new(fps: u32) -> *mut AnimationController {
+    Box::into_raw(Box::new(AnimationController::new(fps)))
+}
+
+/// Release a controller.
+///
+/// # Safety
+///
+/// `controller` must come from [`cascii_controller_new`] and not be used afterwards. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_free(controller: *mut AnimationController) {
+    if !controller.is_null() {
+        drop(Box::from_raw(controller));
+    }
+}
+
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_set_frame_count(controller: *mut AnimationController, count: usize) {
+    if let Some(controller) = controller.as_mut() {
+        controller.set_frame_count(count);
+    }
+}
+
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_append_frames(controller: *mut AnimationController, count: usize) {
+    if let Some(controller) = controller.as_mut() {
+        controller.append_frames(count);
+    }
+}
+
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_play(controller: *mut AnimationController) {
+    if let Some(controller) = controller.as_mut() {
+        controller.play();
+    }
+}
+
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_pause(controller: *mut AnimationController) {
+    if let Some(controller) = controller.as_mut() {
+        controller.pause();
+    }
+}
+
+/// Advance one frame; returns `true` when the frame changed.
+///
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_tick(controller: *mut AnimationController) -> bool {
+    controller.as_mut().map(|controller| controller.tick()).unwrap_or(false)
+}
+
+/// Seek to a position in 0.0 - 1.0.
+///
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_seek(controller: *mut AnimationController, position: f64) {
+    if let Some(controller) = controller.as_mut() {
+        controller.seek(position);
+    }
+}
+
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_current_frame(controller: *const AnimationController) -> usize {
+    controller.as_ref().map(|controller| controller.current_frame()).unwrap_or(0)
+}
+
+/// # Safety
+///
+/// `controller` must be a live handle from [`cascii_controller_new`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_controller_interval_ms(controller: *const AnimationController) -> u32 {
+    controller.as_ref().map(|controller| controller.interval_ms()).unwrap_or(0)
+}
+
+// ---- Frames ----
+
+/// Parse a `.cframe` buffer. Returns null on failure and writes the reason to
+/// `status` when it is non-null. Release with [`cascii_frame_free`].
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes. `status` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_frame_parse(data: *const u8, len: usize, status: *mut CasciiStatus) -> *mut CFrameData {
+    let set_status = |value: CasciiStatus| {
+        if !status.is_null() {
+            *status = value;
+        }
+    };
+    if data.is_null() {
+        set_status(CasciiStatus::NullPointer);
+        return ptr::null_mut();
+    }
+    match parse_cframe(slice::from_raw_parts(data, len)) {
+        Ok(frame) => {
+            set_status(CasciiStatus::Ok);
+            Box::into_raw(Box::new(frame))
+        }
+        Err(err) => {
+            set_status(CasciiStatus::from(&err));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Release a frame.
+///
+/// # Safety
+///
+/// `frame` must come from [`cascii_frame_parse`] and not be used afterwards. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_frame_free(frame: *mut CFrameData) {
+    if !frame.is_null() {
+        drop(Box::from_raw(frame));
+    }
+}
+
+/// # Safety
+///
+/// `frame` must be a live handle from [`cascii_frame_parse`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_frame_width(frame: *const CFrameData) -> u32 {
+    frame.as_ref().map(|frame| frame.width).unwrap_or(0)
+}
+
+/// # Safety
+///
+/// `frame` must be a live handle from [`cascii_frame_parse`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_frame_height(frame: *const CFrameData) -> u32 {
+    frame.as_ref().map(|frame| frame.height).unwrap_or(0)
+}
+
+// ---- Rendering ----
+
+/// Rasterize a frame to an RGB buffer at `font_size` pixels.
+///
+/// Returns an image with a null `data` pointer when `frame` is null. Release
+/// with [`cascii_image_free`].
+///
+/// # Safety
+///
+/// `frame` must be a live handle from [`cascii_frame_parse`] or null.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_render_rgb(frame: *const CFrameData, font_size: f64) -> CasciiImage {
+    let Some(frame) = frame.as_ref() else {
+        return CasciiImage {data: ptr::null_mut(), len: 0, width: 0, height: 0};
+    };
+    let image = rasterize_cframe(frame, &RenderConfig::new(font_size));
+    let mut pixels = image.pixels.into_boxed_slice();
+    let len = pixels.len();
+    let data = pixels.as_mut_ptr();
+    std::mem::forget(pixels);
+    CasciiImage {data, len, width: image.width, height: image.height}
+}
+
+/// Release an image returned by [`cascii_render_rgb`].
+///
+/// # Safety
+///
+/// `image` must come from [`cascii_render_rgb`] and be released only once.
+#[no_mangle]
+pub unsafe extern "C" fn cascii_image_free(image: CasciiImage) {
+    if !image.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(image.data, image.len)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode_cframe;
+
+    #[test]
+    fn test_ffi_parse_and_render() {
+        let bytes = encode_cframe(&CFrameData::new(2, 1, vec![b'A', b'B'], vec![255, 0, 0, 0, 255, 0])).unwrap();
+        let mut status = CasciiStatus::NullPointer;
+        unsafe {
+            let frame = cascii_frame_parse(bytes.as_ptr(), bytes.len(), &mut status);
+            assert_eq!(status, CasciiStatus::Ok);
+            assert_eq!((cascii_frame_width(frame), cascii_frame_height(frame)), (2, 1));
+
+            let image = cascii_render_rgb(frame, 10.0);
+            assert_eq!(image.len, (image.width * image.height * 3) as usize);
+            cascii_image_free(image);
+            cascii_frame_free(frame);
+
+            assert!(cascii_frame_parse(bytes.as_ptr(), 4, &mut status).is_null());
+            assert_eq!(status, CasciiStatus::FileTooSmall);
+        }
+    }
+
+    #[test]
+    fn test_ffi_controller() {
+        unsafe {
+            let controller = cascii_controller_new(10);
+            cascii_controller_set_frame_count(controller, 4);
+            cascii_controller_play(controller);
+            assert!(cascii_controller_tick(controller));
+            assert_eq!(cascii_controller_current_frame(controller), 1);
+            assert_eq!(cascii_controller_interval_ms(controller), 100);
+            cascii_controller_free(controller);
+            assert!(!cascii_controller_tick(ptr::null_mut()));
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Frames read from the local filesystem.
+//!
+//! [`FsFrameProvider`] implements [`FrameDataProvider`] with `std::fs`, so
+//! native CLI players and tests can run the loader pipeline without writing
+//! their own provider. Reads are blocking; the futures complete immediately.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::{FrameDataProvider, FrameFile, LoadResult};
+
+/// A [`FrameDataProvider`] over directories on disk.
+///
+/// Relative paths are resolved against [`root`](Self::root). Frames are the
+/// `.txt` files of a directory, ordered by
+/// [`FrameFile::extract_index`]; `frame.txt`'s color data is `frame.cframe`
+/// next to it. With the `json` feature a [`MANIFEST_FILE`](crate::MANIFEST_FILE)
+/// in the directory is used as the manifest.
+#[derive(Clone, Debug, Default)]
+pub struct FsFrameProvider {
+    root: PathBuf,
+}
+
+impl FsFrameProvider {
+    /// Create a provider resolving relative paths against `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {root: root.into()}
+    }
+
+    /// Directory relative paths are resolved against.
+    #[inline]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Read `path`, returning `None` when it does not exist.
+    fn read_optional(&self, path: &str) -> LoadResult<Option<Vec<u8>>> {
+        let full = self.resolve(path);
+        match std::fs::read(&full) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("Failed to read {}: {}", full.display(), err)),
+        }
+    }
+
+    /// Path of `directory`'s file called `name`, as passed back to the provider.
+    fn join(directory: &str, name: &str) -> String {
+        Path::new(directory).join(name).to_string_lossy().into_owned()
+    }
+}
+
+impl FrameDataProvider for FsFrameProvider {
+    async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+        let full = self.resolve(directory);
+        let entries = std::fs::read_dir(&full).map_err(|err| format!("Failed to list {}: {}", full.display(), err))?;
+        let mut frame_files = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| format!("Failed to list {}: {}", full.display(), err))?;
+            let path = entry.path();
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "txt") {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let stem = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+            frame_files.push(FrameFile::new(Self::join(directory, &name), name, FrameFile::extract_index(&stem, 0)));
+        }
+        frame_files.sort_by(|a, b| a.index.cmp(&b.index).then_with(|| a.name.cmp(&b.name)));
+        Ok(frame_files)
+    }
+
+    async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+        let full = self.resolve(path);
+        std::fs::read_to_string(&full).map_err(|err| format!("Failed to read {}: {}", full.display(), err))
+    }
+
+    async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        let stem = txt_path.strip_suffix(".txt").unwrap_or(txt_path);
+        self.read_optional(&format!("{}.cframe", stem))
+    }
+
+    #[cfg(feature = "json")]
+    async fn read_manifest(&self, directory: &str) -> LoadResult<Option<crate::FrameManifest>> {
+        let Some(bytes) = self.read_optional(&Self::join(directory, crate::MANIFEST_FILE))? else { return Ok(None); };
+        let json = std::str::from_utf8(&bytes).map_err(|err| err.to_string())?;
+        let mut manifest = crate::FrameManifest::from_json_str(json).map_err(|err| format!("Invalid manifest: {}", err))?;
+        for frame in &mut manifest.frames {
+            frame.path = Self::join(directory, &frame.path);
+        }
+        Ok(Some(manifest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::test_support::block_on;
+
+    /// A fresh, empty directory under the system temp dir.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cascii-fs-{}-{}", name, std::process::id()));
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        std::fs::create_dir_all(dir.join("clip")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_fs_provider_lists_and_reads_frames() {
+        let dir = temp_dir("frames");
+        let cframe = crate::encode_cframe(&crate::CFrameData::new(1, 1, vec![b'b'], vec![1, 2, 3])).unwrap();
+        std::fs::write(dir.join("clip/frame_0010.txt"), "c").unwrap();
+        std::fs::write(dir.join("clip/frame_0002.txt"), "b").unwrap();
+        std::fs::write(dir.join("clip/frame_0002.cframe"), cframe).unwrap();
+        std::fs::write(dir.join("clip/frame_0001.txt"), "a").unwrap();
+        std::fs::write(dir.join("clip/notes.md"), "skip").unwrap();
+
+        let provider = FsFrameProvider::new(&dir);
+        let (frames, files) = block_on(crate::load_text_frames(&provider, "clip", &crate::LoadOptions::default(), |_| {})).unwrap();
+        assert_eq!(files.iter().map(|file| file.index).collect::<Vec<_>>(), [1, 2, 10]);
+        assert_eq!(frames.iter().map(|frame| frame.content.as_str()).collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert!(block_on(provider.read_cframe_bytes(&files[1].path)).unwrap().is_some());
+        assert!(block_on(provider.read_cframe_bytes(&files[0].path)).unwrap().is_none());
+        assert!(block_on(provider.get_frame_files("missing")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,342 @@
+//! Frames served over HTTP.
+//!
+//! [`HttpFrameProvider`] implements [`FrameDataProvider`] against a static
+//! file server laid out like a frame directory: `{base}/{dir}/frames.json`
+//! lists the frames, and `frame.txt`'s color data is `frame.cframe` next to
+//! it. With the `http` feature requests go through the browser's `fetch`;
+//! with `http-native` they go through `reqwest` (which needs a Tokio
+//! runtime).
+//!
+//! Responses carrying an `ETag` or `Last-Modified` header are remembered, up
+//! to a byte limit, and later requests for the same URL are sent as
+//! conditional requests, so reloading a project only transfers the frames
+//! that changed.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::{FrameDataProvider, FrameFile, FrameManifest, LoadResult, MANIFEST_FILE};
+
+/// Default [`HttpFrameProvider::with_cache_limit`]: 32 MiB of bodies.
+pub const DEFAULT_HTTP_CACHE_BYTES: usize = 32 << 20;
+
+/// A remembered response body and its validators.
+#[derive(Clone, Debug)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Arc<[u8]>,
+    /// Clock value of the last lookup, for LRU eviction
+    used: u64,
+}
+
+impl CachedResponse {
+    /// `If-None-Match` / `If-Modified-Since` headers revalidating this response.
+    fn conditional_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since", last_modified.clone()));
+        }
+        headers
+    }
+}
+
+/// Remembered responses by URL, evicting the least recently used ones once
+/// their bodies exceed `limit` bytes.
+#[derive(Debug)]
+struct ResponseCache {
+    entries: HashMap<String, CachedResponse>,
+    limit: usize,
+    bytes: usize,
+    clock: u64,
+}
+
+impl ResponseCache {
+    fn new(limit: usize) -> Self {
+        Self {entries: HashMap::new(), limit, bytes: 0, clock: 0}
+    }
+
+    /// Look up `url`, marking it as recently used.
+    fn get(&mut self, url: &str) -> Option<&CachedResponse> {
+        self.clock += 1;
+        let cached = self.entries.get_mut(url)?;
+        cached.used = self.clock;
+        Some(cached)
+    }
+
+    /// Remember a response, evicting old ones to stay within the limit.
+    /// Bodies larger than the whole limit aren't kept.
+    fn insert(&mut self, url: String, etag: Option<String>, last_modified: Option<String>, body: Vec<u8>) {
+        self.remove(&url);
+        if body.len() > self.limit {
+            return;
+        }
+        while self.bytes + body.len() > self.limit {
+            let Some(oldest) = self.entries.iter().min_by_key(|(_, cached)| cached.used).map(|(url, _)| url.clone()) else { break; };
+            self.remove(&oldest);
+        }
+        self.clock += 1;
+        self.bytes += body.len();
+        self.entries.insert(url, CachedResponse {etag, last_modified, body: body.into(), used: self.clock});
+    }
+
+    fn remove(&mut self, url: &str) {
+        if let Some(cached) = self.entries.remove(url) {
+            self.bytes -= cached.body.len();
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+}
+
+/// What the backend hands back for one GET.
+struct HttpResponse {
+    status: u16,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Vec<u8>,
+}
+
+/// A [`FrameDataProvider`] reading frames from a base URL.
+///
+/// Servers can't list directories, so the frame list comes from the
+/// directory's [`MANIFEST_FILE`]; its paths are relative to the directory.
+pub struct HttpFrameProvider {
+    base_url: String,
+    cache: Mutex<ResponseCache>,
+    #[cfg(feature = "http-native")]
+    client: reqwest::Client,
+}
+
+impl HttpFrameProvider {
+    /// Create a provider resolving every path against `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {base_url: base_url.into(), cache: Mutex::new(ResponseCache::new(DEFAULT_HTTP_CACHE_BYTES)), #[cfg(feature = "http-native")] client: reqwest::Client::new()}
+    }
+
+    /// Create a provider that sends its requests through `client`, e.g. one
+    /// configured with timeouts or default headers.
+    #[cfg(feature = "http-native")]
+    pub fn with_client(base_url: impl Into<String>, client: reqwest::Client) -> Self {
+        Self {base_url: base_url.into(), cache: Mutex::new(ResponseCache::new(DEFAULT_HTTP_CACHE_BYTES)), client}
+    }
+
+    /// Remember at most `bytes` of response bodies (default
+    /// [`DEFAULT_HTTP_CACHE_BYTES`]), evicting the least recently used
+    /// responses first; 0 disables conditional requests.
+    pub fn with_cache_limit(self, bytes: usize) -> Self {
+        Self {cache: Mutex::new(ResponseCache::new(bytes)), ..self}
+    }
+
+    /// The URL paths are resolved against.
+    #[inline]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Forget every remembered response, so the next requests are
+    /// unconditional.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap_or_else(|err| err.into_inner()).clear();
+    }
+
+    /// Absolute URL of `path`. Paths that are already absolute URLs are
+    /// returned unchanged.
+    pub fn url(&self, path: &str) -> String {
+        if path.contains("://") {
+            return path.to_string();
+        }
+        let base = self.base_url.trim_end_matches('/');
+        let path = path.trim_start_matches('/');
+        if base.is_empty() { path.to_string() } else { format!("{}/{}", base, path) }
+    }
+
+    /// The remembered response for `url`. Requests keep this snapshot until
+    /// the reply arrives, so a `304` is answered even when concurrent reads
+    /// evicted the entry in the meantime.
+    fn cached(&self, url: &str) -> Option<CachedResponse> {
+        self.cache.lock().unwrap_or_else(|err| err.into_inner()).get(url).cloned()
+    }
+
+    /// Turn a response into its body: `cached`'s body on `304`, `None` on
+    /// `404`, and an error for other failures.
+    fn accept(&self, url: &str, cached: Option<CachedResponse>, response: HttpResponse) -> LoadResult<Option<Vec<u8>>> {
+        match response.status {
+            304 => cached.map(|cached| Some(cached.body.to_vec())).ok_or_else(|| format!("GET {} returned 304 for an uncached response", url)),
+            404 => Ok(None),
+            200..=299 => {
+                if response.etag.is_some() || response.last_modified.is_some() {
+                    self.cache.lock().unwrap_or_else(|err| err.into_inner()).insert(url.to_string(), response.etag, response.last_modified, response.body.clone());
+                }
+                Ok(Some(response.body))
+            }
+            status => Err(format!("GET {} failed with HTTP {}", url, status)),
+        }
+    }
+
+    /// GET `path`, returning `None` when the server has no such file.
+    async fn get(&self, path: &str) -> LoadResult<Option<Vec<u8>>> {
+        let url = self.url(path);
+        let cached = self.cached(&url);
+        let headers = cached.as_ref().map(CachedResponse::conditional_headers).unwrap_or_default();
+        let response = self.send("GET", &url, &headers).await?;
+        self.accept(&url, cached, response)
+    }
+
+    /// Path of the color data for a text frame path.
+    fn cframe_path(txt_path: &str) -> String {
+        format!("{}.cframe", txt_path.strip_suffix(".txt").unwrap_or(txt_path))
+    }
+
+    #[cfg(feature = "http-native")]
+    async fn send(&self, method: &str, url: &str, headers: &[(&'static str, String)]) -> LoadResult<HttpResponse> {
+        let mut request = self.client.request(method.parse().map_err(|_| format!("Invalid HTTP method {}", method))?, url);
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let response = request.send().await.map_err(|err| format!("{} {} failed: {}", method, url, err))?;
+        let header = |name: &str| response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string);
+        let (status, etag, last_modified) = (response.status().as_u16(), header("etag"), header("last-modified"));
+        let body = response.bytes().await.map_err(|err| format!("{} {} failed: {}", method, url, err))?.to_vec();
+        Ok(HttpResponse {status, etag, last_modified, body})
+    }
+
+    #[cfg(not(feature = "http-native"))]
+    async fn send(&self, method: &str, url: &str, headers: &[(&'static str, String)]) -> LoadResult<HttpResponse> {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+
+        let init = web_sys::RequestInit::new();
+        init.set_method(method);
+        let request = web_sys::Request::new_with_str_and_init(url, &init).map_err(|_| format!("Invalid request URL {}", url))?;
+        for (name, value) in headers {
+            request.headers().set(name, value).map_err(|_| "Failed to set request header")?;
+        }
+        let window = web_sys::window().ok_or("No window available")?;
+        let response: web_sys::Response = JsFuture::from(window.fetch_with_request(&request)).await.map_err(|_| format!("{} {} failed", method, url))?.dyn_into().map_err(|_| "Failed to cast to Response")?;
+        let header = |name: &str| response.headers().get(name).ok().flatten();
+        let (status, etag, last_modified) = (response.status(), header("ETag"), header("Last-Modified"));
+        let buffer = JsFuture::from(response.array_buffer().map_err(|_| "Failed to read response body")?).await.map_err(|_| format!("{} {} failed", method, url))?;
+        Ok(HttpResponse {status, etag, last_modified, body: js_sys::Uint8Array::new(&buffer).to_vec()})
+    }
+
+    /// Path of `directory`'s file called `name`.
+    fn join(directory: &str, name: &str) -> String {
+        let directory = directory.trim_end_matches('/');
+        if directory.is_empty() || name.contains("://") { name.to_string() } else { format!("{}/{}", directory, name) }
+    }
+}
+
+impl FrameDataProvider for HttpFrameProvider {
+    async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+        let manifest = self.read_manifest(directory).await?;
+        manifest.map(|manifest| manifest.frame_files()).ok_or_else(|| format!("No {} at {}", MANIFEST_FILE, self.url(&Self::join(directory, MANIFEST_FILE))))
+    }
+
+    async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+        let bytes = self.get(path).await?.ok_or_else(|| format!("{} not found", self.url(path)))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        self.get(&Self::cframe_path(txt_path)).await
+    }
+
+    /// The `ETag` of the `.cframe` file: the one remembered from its last
+    /// response, otherwise from a `HEAD` request.
+    async fn cframe_etag(&self, txt_path: &str) -> LoadResult<Option<String>> {
+        let url = self.url(&Self::cframe_path(txt_path));
+        if let Some(etag) = self.cache.lock().unwrap_or_else(|err| err.into_inner()).get(&url).and_then(|cached| cached.etag.clone()) {
+            return Ok(Some(etag));
+        }
+        let response = self.send("HEAD", &url, &[]).await?;
+        match response.status {
+            200..=299 => Ok(response.etag),
+            404 => Ok(None),
+            status => Err(format!("HEAD {} failed with HTTP {}", url, status)),
+        }
+    }
+
+    async fn read_manifest(&self, directory: &str) -> LoadResult<Option<FrameManifest>> {
+        let Some(bytes) = self.get(&Self::join(directory, MANIFEST_FILE)).await? else { return Ok(None); };
+        let json = std::str::from_utf8(&bytes).map_err(|err| err.to_string())?;
+        let mut manifest = FrameManifest::from_json_str(json).map_err(|err| format!("Invalid manifest: {}", err))?;
+        for frame in &mut manifest.frames {
+            frame.path = Self::join(directory, &frame.path);
+        }
+        Ok(Some(manifest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(etag: Option<&str>, body: &[u8]) -> HttpResponse {
+        HttpResponse {status: 200, etag: etag.map(str::to_string), last_modified: None, body: body.to_vec()}
+    }
+
+    fn headers(provider: &HttpFrameProvider, url: &str) -> Vec<(&'static str, String)> {
+        provider.cached(url).map(|cached| cached.conditional_headers()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_http_urls() {
+        let provider = HttpFrameProvider::new("https://cdn.example/anims/");
+        assert_eq!(provider.url("/clip/frame_0001.txt"), "https://cdn.example/anims/clip/frame_0001.txt");
+        assert_eq!(provider.url("https://other.example/a.txt"), "https://other.example/a.txt");
+        assert_eq!(HttpFrameProvider::join("clip/", "a.txt"), "clip/a.txt");
+        assert_eq!(HttpFrameProvider::join("", "a.txt"), "a.txt");
+    }
+
+    #[test]
+    fn test_http_conditional_requests() {
+        let provider = HttpFrameProvider::new("");
+        assert!(headers(&provider, "a.txt").is_empty());
+        assert_eq!(provider.accept("a.txt", None, ok(Some("\"v1\""), b"abc")).unwrap().as_deref(), Some(&b"abc"[..]));
+        assert_eq!(headers(&provider, "a.txt"), [("If-None-Match", "\"v1\"".to_string())]);
+
+        let not_modified = HttpResponse {status: 304, ..ok(None, b"")};
+        assert_eq!(provider.accept("a.txt", provider.cached("a.txt"), not_modified).unwrap().as_deref(), Some(&b"abc"[..]));
+        assert_eq!(provider.accept("missing.cframe", None, HttpResponse {status: 404, ..ok(None, b"")}).unwrap(), None);
+        assert!(provider.accept("b.txt", None, HttpResponse {status: 500, ..ok(None, b"")}).is_err());
+
+        provider.accept("b.txt", None, ok(None, b"uncached")).unwrap();
+        assert!(headers(&provider, "b.txt").is_empty());
+        provider.clear_cache();
+        assert!(provider.accept("a.txt", provider.cached("a.txt"), HttpResponse {status: 304, ..ok(None, b"")}).is_err());
+    }
+
+    #[test]
+    fn test_http_not_modified_after_eviction() {
+        let provider = HttpFrameProvider::new("").with_cache_limit(4);
+        provider.accept("a.txt", None, ok(Some("a"), b"aaaa")).unwrap();
+        let cached = provider.cached("a.txt");
+        // A concurrent read evicts a.txt while its conditional request is in flight.
+        provider.accept("b.txt", None, ok(Some("b"), b"bbbb")).unwrap();
+        assert!(provider.cached("a.txt").is_none());
+        assert_eq!(provider.accept("a.txt", cached, HttpResponse {status: 304, ..ok(None, b"")}).unwrap().as_deref(), Some(&b"aaaa"[..]));
+    }
+
+    #[test]
+    fn test_http_cache_limit() {
+        let provider = HttpFrameProvider::new("").with_cache_limit(8);
+        provider.accept("a.txt", None, ok(Some("a"), b"aaaa")).unwrap();
+        provider.accept("b.txt", None, ok(Some("b"), b"bbbb")).unwrap();
+        assert!(!headers(&provider, "a.txt").is_empty());
+        // b.txt is now the least recently used and makes room for c.txt.
+        provider.accept("c.txt", None, ok(Some("c"), b"cc")).unwrap();
+        assert!(headers(&provider, "b.txt").is_empty());
+        assert!(!headers(&provider, "a.txt").is_empty() && !headers(&provider, "c.txt").is_empty());
+
+        provider.accept("big.txt", None, ok(Some("big"), b"too large")).unwrap();
+        assert!(headers(&provider, "big.txt").is_empty());
+        assert_eq!(provider.cache.lock().unwrap().bytes, 6);
+    }
+}
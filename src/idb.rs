@@ -0,0 +1,246 @@
+//! Persistent color-data caching in IndexedDB.
+//!
+//! [`IdbCacheProvider`] wraps any [`FrameDataProvider`] and keeps the
+//! `.cframe` bytes it reads in an IndexedDB object store, keyed by frame path
+//! and stored with the inner provider's
+//! [`cframe_etag`](FrameDataProvider::cframe_etag). Repeat visits serve
+//! color data from the browser instead of the network, and keep working while
+//! offline.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use js_sys::{Object, Reflect, Uint8Array};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+use crate::{FrameDataProvider, FrameFile, FrameManifest, LoadResult};
+
+/// Object store holding one record per text frame path.
+const STORE: &str = "cframes";
+
+/// A stored `.cframe` and the validator it was fetched with.
+struct CachedCFrame {
+    etag: Option<String>,
+    bytes: Vec<u8>,
+}
+
+/// A [`FrameDataProvider`] that caches another provider's color data in
+/// IndexedDB.
+///
+/// Stored copies are served without a network round trip. When the frame
+/// list carries a [`cframe_checksum`](FrameFile::cframe_checksum) the copy
+/// is checked against it and fetched again on a mismatch; otherwise it is
+/// served as is and queued for [`revalidate`](Self::revalidate), which
+/// compares `ETag`s with the inner provider afterwards. Text frames and
+/// manifests always come from the inner provider. IndexedDB failures never
+/// fail a load; the wrapper then reads through to the inner provider.
+pub struct IdbCacheProvider<P> {
+    inner: P,
+    db_name: String,
+    db: RefCell<Option<IdbDatabase>>,
+    /// Expected `.cframe` checksums from the frame list, by text frame path
+    checksums: RefCell<HashMap<String, u64>>,
+    /// Paths served from IndexedDB without validation
+    unvalidated: RefCell<Vec<String>>,
+}
+
+impl<P> IdbCacheProvider<P> {
+    /// Cache `inner`'s color data in the IndexedDB database `db_name`.
+    pub fn new(inner: P, db_name: impl Into<String>) -> Self {
+        Self {inner, db_name: db_name.into(), db: RefCell::new(None), checksums: RefCell::default(), unvalidated: RefCell::default()}
+    }
+
+    /// The wrapped provider.
+    #[inline]
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Unwrap the inner provider.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+
+    /// Delete every stored frame.
+    pub async fn clear(&self) -> LoadResult<()> {
+        let db = self.database().await?;
+        let request = object_store(&db, IdbTransactionMode::Readwrite)?.clear().map_err(|_| "Failed to clear IndexedDB store")?;
+        request_result(&request).await.map(|_| ())
+    }
+
+    /// The open database, opening (and on first use creating) it if needed.
+    async fn database(&self) -> LoadResult<IdbDatabase> {
+        if let Some(db) = self.db.borrow().clone() {
+            return Ok(db);
+        }
+        let factory = web_sys::window().ok_or("No window available")?.indexed_db().map_err(|_| "IndexedDB unavailable")?.ok_or("IndexedDB unavailable")?;
+        let open = factory.open_with_u32(&self.db_name, 1).map_err(|_| "Failed to open IndexedDB")?;
+        let upgrade = open.clone();
+        let on_upgrade = Closure::once_into_js(move || {
+            if let Ok(db) = upgrade.result().and_then(|db| db.dyn_into::<IdbDatabase>()) {
+                let _ = db.create_object_store(STORE);
+            }
+        });
+        open.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+        let db: IdbDatabase = request_result(&open).await?.dyn_into().map_err(|_| "Failed to cast to IdbDatabase")?;
+        *self.db.borrow_mut() = Some(db.clone());
+        Ok(db)
+    }
+
+    async fn lookup(&self, path: &str) -> LoadResult<Option<CachedCFrame>> {
+        let db = self.database().await?;
+        let request = object_store(&db, IdbTransactionMode::Readonly)?.get(&JsValue::from_str(path)).map_err(|_| "Failed to read IndexedDB store")?;
+        let record = request_result(&request).await?;
+        if record.is_undefined() {
+            return Ok(None);
+        }
+        let etag = Reflect::get(&record, &"etag".into()).ok().and_then(|etag| etag.as_string());
+        let bytes = Reflect::get(&record, &"bytes".into()).map_err(|_| "Invalid IndexedDB record")?;
+        Ok(Some(CachedCFrame {etag, bytes: Uint8Array::new(&bytes).to_vec()}))
+    }
+
+    async fn store(&self, path: &str, etag: Option<&str>, bytes: &[u8]) -> LoadResult<()> {
+        let db = self.database().await?;
+        let record = Object::new();
+        Reflect::set(&record, &"etag".into(), &etag.map(JsValue::from_str).unwrap_or(JsValue::NULL)).map_err(|_| "Invalid IndexedDB record")?;
+        Reflect::set(&record, &"bytes".into(), &Uint8Array::from(bytes)).map_err(|_| "Invalid IndexedDB record")?;
+        let request = object_store(&db, IdbTransactionMode::Readwrite)?.put_with_key(&record, &JsValue::from_str(path)).map_err(|_| "Failed to write IndexedDB store")?;
+        request_result(&request).await.map(|_| ())
+    }
+}
+
+fn object_store(db: &IdbDatabase, mode: IdbTransactionMode) -> LoadResult<IdbObjectStore> {
+    db.transaction_with_str_and_mode(STORE, mode).and_then(|transaction| transaction.object_store(STORE)).map_err(|_| "Failed to open IndexedDB store".to_string())
+}
+
+/// Wait for `request` and return its result.
+async fn request_result(request: &IdbRequest) -> LoadResult<JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        let done = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            let _ = resolve.call1(&JsValue::NULL, &done.result().unwrap_or(JsValue::UNDEFINED));
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await.map_err(|_| "IndexedDB request failed".to_string())
+}
+
+/// Whether stored `bytes` match the frame list's `expected` checksum, or
+/// `None` when there is none to check against.
+fn matches_checksum(bytes: &[u8], expected: Option<u64>) -> Option<bool> {
+    expected.map(|expected| crate::payload_checksum(bytes) == expected)
+}
+
+/// Whether a copy stored with the `stored` validator can be served when the
+/// inner provider currently reports `current`.
+fn is_fresh(stored: Option<&str>, current: &LoadResult<Option<String>>) -> bool {
+    match current {
+        Ok(Some(current)) => stored == Some(current.as_str()),
+        Ok(None) | Err(_) => true,
+    }
+}
+
+impl<P: FrameDataProvider> IdbCacheProvider<P> {
+    /// Check the copies served without a checksum against the inner
+    /// provider's current `ETag`s, fetching and storing the ones that
+    /// changed. Run it once loading is done; returns the text frame paths
+    /// whose color data was refreshed, for the host to reload.
+    pub async fn revalidate(&self) -> Vec<String> {
+        let paths = std::mem::take(&mut *self.unvalidated.borrow_mut());
+        let mut refreshed = Vec::new();
+        for path in paths {
+            let Ok(Some(cached)) = self.lookup(&path).await else { continue; };
+            if is_fresh(cached.etag.as_deref(), &self.inner.cframe_etag(&path).await) {
+                continue;
+            }
+            if let Ok(Some(_)) = self.fetch_and_store(&path).await {
+                refreshed.push(path);
+            }
+        }
+        refreshed
+    }
+
+    /// Read `txt_path`'s color data from the inner provider and store it.
+    async fn fetch_and_store(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        let bytes = self.inner.read_cframe_bytes(txt_path).await?;
+        if let Some(bytes) = &bytes {
+            let etag = self.inner.cframe_etag(txt_path).await.ok().flatten();
+            let _ = self.store(txt_path, etag.as_deref(), bytes).await;
+        }
+        Ok(bytes)
+    }
+
+    fn remember_checksums<'a>(&self, files: impl IntoIterator<Item = (&'a str, Option<u64>)>) {
+        let mut checksums = self.checksums.borrow_mut();
+        for (path, checksum) in files {
+            if let Some(checksum) = checksum {
+                checksums.insert(path.to_string(), checksum);
+            }
+        }
+    }
+}
+
+impl<P: FrameDataProvider> FrameDataProvider for IdbCacheProvider<P> {
+    async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+        let files = self.inner.get_frame_files(directory).await?;
+        self.remember_checksums(files.iter().map(|file| (file.path.as_str(), file.cframe_checksum)));
+        Ok(files)
+    }
+
+    async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+        self.inner.read_frame_text(path).await
+    }
+
+    async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        if let Ok(Some(cached)) = self.lookup(txt_path).await {
+            let expected = self.checksums.borrow().get(txt_path).copied();
+            match matches_checksum(&cached.bytes, expected) {
+                Some(true) => return Ok(Some(cached.bytes)),
+                Some(false) => {}
+                None => {
+                    self.unvalidated.borrow_mut().push(txt_path.to_string());
+                    return Ok(Some(cached.bytes));
+                }
+            }
+        }
+        self.fetch_and_store(txt_path).await
+    }
+
+    async fn read_manifest(&self, directory: &str) -> LoadResult<Option<FrameManifest>> {
+        let manifest = self.inner.read_manifest(directory).await?;
+        if let Some(manifest) = &manifest {
+            self.remember_checksums(manifest.frames.iter().map(|frame| (frame.path.as_str(), frame.cframe_checksum)));
+        }
+        Ok(manifest)
+    }
+
+    async fn cframe_etag(&self, txt_path: &str) -> LoadResult<Option<String>> {
+        self.inner.cframe_etag(txt_path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idb_cache_freshness() {
+        assert!(is_fresh(Some("\"v1\""), &Ok(Some("\"v1\"".into()))));
+        assert!(!is_fresh(Some("\"v1\""), &Ok(Some("\"v2\"".into()))));
+        assert!(!is_fresh(None, &Ok(Some("\"v2\"".into()))));
+        assert!(is_fresh(Some("\"v1\""), &Ok(None)));
+        assert!(is_fresh(Some("\"v1\""), &Err("offline".into())));
+    }
+
+    #[test]
+    fn test_idb_cache_checksums() {
+        let checksum = crate::payload_checksum(b"cframe");
+        assert_eq!(matches_checksum(b"cframe", Some(checksum)), Some(true));
+        assert_eq!(matches_checksum(b"stale", Some(checksum)), Some(false));
+        assert_eq!(matches_checksum(b"cframe", None), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! Palette-compressed in-memory color frames.
+//!
+//! Most frames use far fewer than 256 distinct colors, so storing one
+//! palette per frame plus a 1-byte index per cell cuts the color payload to
+//! roughly a third of the 3-bytes-per-cell [`CFrameData`] layout. Cells are
+//! read straight from the indices; a full [`CFrameData`] is only rebuilt on
+//! [`decode`](IndexedCFrameData::decode). The player keeps loaded colour in
+//! this form with
+//! [`set_indexed_colors`](crate::FramePlayer::set_indexed_colors).
+
+use std::collections::HashMap;
+
+use crate::{CFrameData, PaletteQuantizer};
+
+/// A [`CFrameData`] stored as a per-frame palette and 1-byte color indices.
+///
+/// Foreground and background colors share one palette of up to 256 colors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedCFrameData {
+    /// Frame width in characters
+    pub width: u32,
+    /// Frame height in characters
+    pub height: u32,
+    /// ASCII characters as bytes (width * height)
+    pub chars: Vec<u8>,
+    /// Distinct colors referenced by the indices
+    pub palette: Vec<(u8, u8, u8)>,
+    /// Foreground palette index per cell
+    pub indices: Vec<u8>,
+    /// Optional background palette index per cell
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bg_indices: Option<Vec<u8>>,
+}
+
+impl IndexedCFrameData {
+    /// Compress `cframe`. Returns `None` when it uses more than 256 distinct
+    /// colors or its buffers do not match its dimensions.
+    pub fn from_cframe(cframe: &CFrameData) -> Option<Self> {
+        let cells = cframe.pixel_count();
+        if cframe.chars.len() != cells || cframe.rgb.len() != cells * 3 {
+            return None;
+        }
+        let mut palette = Vec::new();
+        let mut lookup: HashMap<(u8, u8, u8), u8> = HashMap::new();
+        let mut index = |rgb: &[u8]| -> Option<u8> {
+            let color = (rgb[0], rgb[1], rgb[2]);
+            if let Some(&index) = lookup.get(&color) {
+                return Some(index);
+            }
+            let index = u8::try_from(palette.len()).ok()?;
+            palette.push(color);
+            lookup.insert(color, index);
+            Some(index)
+        };
+        let indices = cframe.rgb.chunks_exact(3).map(&mut index).collect::<Option<Vec<u8>>>()?;
+        let bg_indices = match cframe.bg_rgb.as_deref().filter(|_| cframe.has_background()) {
+            Some(bg) => Some(bg.chunks_exact(3).map(&mut index).collect::<Option<Vec<u8>>>()?),
+            None => None,
+        };
+        Some(Self {width: cframe.width, height: cframe.height, chars: cframe.chars.clone(), palette, indices, bg_indices})
+    }
+
+    /// Encode `cframe` against a fixed palette, mapping every cell to its
+    /// nearest entry. Unlike [`from_cframe`](Self::from_cframe) this is lossy
+    /// but never runs out of indices, and frames quantized with the same
+    /// quantizer share one palette. Returns `None` when the palette is empty
+    /// or has more than 256 colors, or the buffers do not match the
+    /// dimensions.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{CFrameData, IndexedCFrameData, PaletteQuantizer};
+    ///
+    /// let quantizer = PaletteQuantizer::new(vec![(0, 0, 0), (255, 0, 0)]);
+    /// let cframe = CFrameData::new(2, 1, b"ab".to_vec(), vec![240, 20, 10, 5, 5, 5]);
+    /// let indexed = IndexedCFrameData::quantize(&cframe, &quantizer).unwrap();
+    /// assert_eq!(indexed.indices, [1, 0]);
+    /// assert_eq!(indexed.rgb_at(0, 0), Some((255, 0, 0)));
+    /// ```
+    pub fn quantize(cframe: &CFrameData, quantizer: &PaletteQuantizer) -> Option<Self> {
+        let cells = cframe.pixel_count();
+        if quantizer.colors().len() > 256 || cframe.chars.len() != cells || cframe.rgb.len() != cells * 3 {
+            return None;
+        }
+        let index = |rgb: &[u8]| quantizer.nearest_index((rgb[0], rgb[1], rgb[2])).map(|index| index as u8);
+        let indices = cframe.rgb.chunks_exact(3).map(index).collect::<Option<Vec<u8>>>()?;
+        let bg_indices = match cframe.bg_rgb.as_deref().filter(|_| cframe.has_background()) {
+            Some(bg) => Some(bg.chunks_exact(3).map(index).collect::<Option<Vec<u8>>>()?),
+            None => None,
+        };
+        Some(Self {width: cframe.width, height: cframe.height, chars: cframe.chars.clone(), palette: quantizer.colors().to_vec(), indices, bg_indices})
+    }
+
+    /// Rebuild the full [`CFrameData`].
+    pub fn decode(&self) -> CFrameData {
+        let expand = |indices: &[u8]| indices.iter().flat_map(|&index| {
+            let (r, g, b) = self.color(index);
+            [r, g, b]
+        }).collect::<Vec<u8>>();
+        CFrameData {width: self.width, height: self.height, chars: self.chars.clone(), rgb: expand(&self.indices), bg_rgb: self.bg_indices.as_deref().map(expand)}
+    }
+
+    #[inline]
+    fn color(&self, index: u8) -> (u8, u8, u8) {
+        self.palette.get(index as usize).copied().unwrap_or((0, 0, 0))
+    }
+
+    #[inline]
+    fn cell(&self, row: usize, col: usize) -> Option<usize> {
+        (row < self.height as usize && col < self.width as usize).then(|| row * self.width as usize + col)
+    }
+
+    /// Get the character at the given position.
+    #[inline]
+    pub fn char_at(&self, row: usize, col: usize) -> Option<u8> {
+        self.chars.get(self.cell(row, col)?).copied()
+    }
+
+    /// Get the foreground color at the given position.
+    #[inline]
+    pub fn rgb_at(&self, row: usize, col: usize) -> Option<(u8, u8, u8)> {
+        self.indices.get(self.cell(row, col)?).map(|&index| self.color(index))
+    }
+
+    /// Get the background color at the given position, if the frame has one.
+    #[inline]
+    pub fn bg_rgb_at(&self, row: usize, col: usize) -> Option<(u8, u8, u8)> {
+        self.bg_indices.as_ref()?.get(self.cell(row, col)?).map(|&index| self.color(index))
+    }
+
+    /// Heap bytes used by the cell data, for comparing against a
+    /// [`CFrameData`]'s `chars`, `rgb`, and `bg_rgb`.
+    pub fn byte_len(&self) -> usize {
+        self.chars.len() + self.palette.len() * 3 + self.indices.len() + self.bg_indices.as_ref().map_or(0, Vec::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexed_round_trip() {
+        let rgb = [[255, 0, 0], [0, 255, 0], [255, 0, 0], [0, 0, 255]].concat();
+        let cframe = CFrameData::with_background(2, 2, b"ABCD".to_vec(), rgb, vec![0; 12]);
+        let indexed = IndexedCFrameData::from_cframe(&cframe).unwrap();
+
+        assert_eq!(indexed.palette, [(255, 0, 0), (0, 255, 0), (0, 0, 255), (0, 0, 0)]);
+        assert_eq!(indexed.indices, [0, 1, 0, 2]);
+        assert_eq!((indexed.rgb_at(1, 1), indexed.bg_rgb_at(0, 1), indexed.char_at(1, 0)), (Some((0, 0, 255)), Some((0, 0, 0)), Some(b'C')));
+        assert_eq!(indexed.rgb_at(2, 0), None);
+        assert_eq!(indexed.decode().checksum(), cframe.checksum());
+        assert!(indexed.byte_len() < cframe.chars.len() + cframe.rgb.len() * 2);
+    }
+
+    #[test]
+    fn test_indexed_rejects_too_many_colors() {
+        let rgb: Vec<u8> = (0..300u32).flat_map(|i| [(i % 256) as u8, (i / 256) as u8, 0]).collect();
+        let cframe = CFrameData::new(300, 1, vec![b'x'; 300], rgb);
+        assert!(IndexedCFrameData::from_cframe(&cframe).is_none());
+
+        let quantizer = PaletteQuantizer::new(vec![(0, 0, 0), (255, 0, 0), (0, 255, 0)]);
+        let indexed = IndexedCFrameData::quantize(&cframe, &quantizer).unwrap();
+        assert_eq!((indexed.palette.len(), indexed.indices[200], indexed.indices[300 - 1]), (3, 1, 0));
+        assert!(IndexedCFrameData::quantize(&cframe, &PaletteQuantizer::default()).is_none());
+    }
+}
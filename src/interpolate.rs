@@ -0,0 +1,98 @@
+//! Temporal color interpolation between adjacent frames.
+//!
+//! When the display refreshes faster than the animation's source FPS, the
+//! ticks in between two source frames can show a blend of their colors
+//! instead of repeating the earlier frame, which smooths slow gradients and
+//! fades without authoring more frames.
+
+use crate::{CFrameData, ColorSpace, LoopMode};
+
+/// Two adjacent source frames and how far playback is between them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameBlend {
+    /// Index of the earlier frame
+    pub from: usize,
+    /// Index of the later frame (wraps to 0 when looping)
+    pub to: usize,
+    /// Blend factor in 0.0 - 1.0; 0.0 shows `from` unchanged
+    pub t: f64,
+}
+
+/// Locate `time_ms` within an animation of `frame_count` frames played at
+/// `source_fps`. Returns `None` for an empty animation or a zero FPS.
+///
+/// With [`LoopMode::Loop`] time wraps and the last frame blends into the
+/// first; with [`LoopMode::Once`] playback holds on the last frame.
+pub fn frame_blend_at(time_ms: f64, source_fps: u32, frame_count: usize, loop_mode: LoopMode) -> Option<FrameBlend> {
+    if frame_count == 0 || source_fps == 0 {
+        return None;
+    }
+    let position = (time_ms.max(0.0) * source_fps as f64 / 1000.0).max(0.0);
+    let whole = position.floor();
+    let t = position - whole;
+    let whole = whole as usize;
+    match loop_mode {
+        LoopMode::Loop => {
+            let from = whole % frame_count;
+            Some(FrameBlend {from, to: (from + 1) % frame_count, t})
+        }
+        LoopMode::Once if whole + 1 >= frame_count => Some(FrameBlend {from: frame_count - 1, to: frame_count - 1, t: 0.0}),
+        LoopMode::Once => Some(FrameBlend {from: whole, to: whole + 1, t}),
+    }
+}
+
+/// Blend the colors of two same-sized frames, taking characters from the
+/// nearer one.
+///
+/// Backgrounds are blended when both frames have them and kept from the
+/// nearer frame otherwise. Returns `None` when the dimensions differ.
+pub fn blend_cframes(from: &CFrameData, to: &CFrameData, t: f64) -> Option<CFrameData> {
+    blend_cframes_in(from, to, t, ColorSpace::Srgb)
+}
+
+/// Like [`blend_cframes`], mixing colors in `space`.
+pub fn blend_cframes_in(from: &CFrameData, to: &CFrameData, t: f64, space: ColorSpace) -> Option<CFrameData> {
+    if from.width != to.width || from.height != to.height || from.rgb.len() != to.rgb.len() {
+        return None;
+    }
+    let t = t.clamp(0.0, 1.0);
+    let nearer = if t < 0.5 { from } else { to };
+    let bg_rgb = match (&from.bg_rgb, &to.bg_rgb) {
+        (Some(a), Some(b)) if a.len() == b.len() => Some(space.mix_bytes(a, b, t)),
+        _ => nearer.bg_rgb.clone(),
+    };
+    Some(CFrameData {width: from.width, height: from.height, chars: nearer.chars.clone(), rgb: space.mix_bytes(&from.rgb, &to.rgb, t), bg_rgb})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_blend_at() {
+        assert_eq!(frame_blend_at(250.0, 2, 4, LoopMode::Loop), Some(FrameBlend {from: 0, to: 1, t: 0.5}));
+        assert_eq!(frame_blend_at(1750.0, 2, 4, LoopMode::Loop), Some(FrameBlend {from: 3, to: 0, t: 0.5}));
+        assert_eq!(frame_blend_at(2250.0, 2, 4, LoopMode::Loop).map(|blend| blend.from), Some(0));
+        assert_eq!(frame_blend_at(1750.0, 2, 4, LoopMode::Once), Some(FrameBlend {from: 3, to: 3, t: 0.0}));
+        assert_eq!(frame_blend_at(1250.0, 2, 4, LoopMode::Once), Some(FrameBlend {from: 2, to: 3, t: 0.5}));
+        assert_eq!(frame_blend_at(0.0, 0, 4, LoopMode::Loop), None);
+        assert_eq!(frame_blend_at(0.0, 2, 0, LoopMode::Loop), None);
+    }
+
+    #[test]
+    fn test_blend_cframes() {
+        let a = CFrameData::with_background(1, 1, vec![b'a'], vec![0, 100, 200], vec![0, 0, 0]);
+        let b = CFrameData::with_background(1, 1, vec![b'b'], vec![100, 100, 0], vec![50, 50, 50]);
+
+        let quarter = blend_cframes(&a, &b, 0.25).unwrap();
+        assert_eq!((quarter.chars[0], quarter.rgb.as_slice()), (b'a', &[25, 100, 150][..]));
+        assert_eq!(quarter.bg_rgb, Some(vec![13, 13, 13]));
+        assert_eq!(blend_cframes(&a, &b, 0.75).unwrap().chars, vec![b'b']);
+
+        let linear = blend_cframes_in(&a, &b, 0.25, ColorSpace::Linear).unwrap();
+        assert_eq!(linear.rgb, [50, 100, 176]);
+
+        let wide = CFrameData::new(2, 1, vec![b'x'; 2], vec![0; 6]);
+        assert!(blend_cframes(&a, &wide, 0.5).is_none());
+    }
+}
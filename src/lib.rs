@@ -9,11 +9,31 @@
 //! - Controlling animation playback (speed, loop, stepping)
 //! - Rendering frames to canvas (with optional web support)
 //! - High-level playback through [`FramePlayer`]
+//! - Declarative HUD / OSD overlays through [`OverlayScene`]
+//! - Sequence editing (concatenation, retiming) through [`FrameSequence`]
+//! - Project validation through [`lint_project`]
+//! - Deterministic record / replay of playback through [`InteractionLog`]
+//! - Timeline metadata (chapters, bookmarks, scene cuts) through [`Timeline`]
+//! - Temporal color interpolation between frames through [`blend_cframes`]
+//! - Contiguous sprite-atlas storage for many small frames through [`FrameAtlas`]
+//! - Hit-testable regions of interest on frames through [`Region`]
+//! - Random-access playback over vectors, loaders and packed containers through [`FrameSource`]
 //!
 //! ## Features
 //!
+//! - `std` (default) - [`FsFrameProvider`] for reading frame directories from disk
 //! - `serde` - Enable serialization/deserialization for data structures
+//! - `json` - JSON sidecars for [`Timeline`] metadata and [`FrameManifest`]s
 //! - `web` - Enable web/WASM canvas rendering support
+//! - `webgl` - WebGL2 renderer for large frames (implies `web`)
+//! - `ffi` - Expose a C ABI for native hosts
+//! - `python` - Python bindings via pyo3
+//! - `png` - PNG export of rasterized frames
+//! - `rayon` - Row-parallel batching of large frames
+//! - `unicode-width` - Display-width measurement of text frames
+//! - `tar` - Streaming frames out of `.tar` / `.tar.gz` archives
+//! - `http` / `http-native` - [`HttpFrameProvider`] over `fetch` or `reqwest`
+//! - `indexeddb` - [`IdbCacheProvider`] caching color data in IndexedDB
 //!
 //! ## Examples
 //!
@@ -27,6 +47,7 @@
 //! let frame = Frame {
 //!     content: text_content,
 //!     cframe: Some(cframe_data),
+//!     meta: None,
 //! };
 //!
 //! // Calculate optimal font size
@@ -57,28 +78,80 @@
 //! ```
 
 mod animation;
+#[cfg(feature = "tar")]
+mod archive;
+mod atlas;
+mod bundle;
+mod clock;
 mod color;
 mod data;
 mod details;
+#[cfg(feature = "std")]
+mod fs;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(feature = "http", feature = "http-native"))]
+mod http;
+#[cfg(feature = "indexeddb")]
+mod idb;
+mod indexed;
+mod interpolate;
+mod lint;
 mod loader;
+mod manifest;
+mod overlay;
 mod parser;
 pub mod player;
+mod prefetch;
+#[cfg(feature = "python")]
+mod python;
 pub mod render;
+mod region;
+mod replay;
+mod sequence;
 mod sizing;
+mod source;
+mod stats;
+mod timeline;
 
 pub use animation::{AnimationController, AnimationState, LoopMode};
-pub use color::{parse_color, FrameColors};
-pub use data::{CFrameData, Frame, FrameFile, PackedCFrameBlob};
-pub use details::ProjectDetails;
-pub use loader::{load_color_frames, load_text_frames, FrameDataProvider, FrameLoaderState, LoadResult, LoadingPhase, LoadingProgress};
-pub use parser::{encode_cframe, parse_cframe, parse_cframe_text, parse_packed_cframes, split_cframe_extension, ParseError, CFRAME_EXT_FLAG_HAS_BG};
+#[cfg(feature = "tar")]
+pub use archive::{TarEntry, TarFrameProvider, TarStream};
+pub use atlas::{AtlasEntry, AtlasFrame, FrameAtlas};
+pub use bundle::{load_bundle, Bundle, BUNDLE_DETAILS, BUNDLE_MAGIC, BUNDLE_VERSION};
+pub use color::{ansi16_to_rgb, ansi256_to_rgb, contrast_ratio, hsl_to_rgb, hsv_to_rgb, lerp_rgb, linear_to_srgb, parse_color, parse_color_alpha, relative_luminance, rgb_to_ansi16, rgb_to_ansi256, rgb_to_hsl, rgb_to_hsv, shift_hue, srgb_to_linear, ColorAdjust, ColorBlindness, ColorMetric, ColorSpace, ColorVision, FrameColors, Gradient, Palette, PaletteQuantizer, ANSI16};
+pub use data::{display_width, frame_durations, payload_checksum, CFrameData, CFrameError, Frame, FrameFile, FrameMeta, PackedCFrameBlob, SharedFrame, TextOptions};
+pub use details::{Compatibility, DetailsSection, DetailsValue, DetailsWarning, ProjectDetails};
+#[cfg(feature = "std")]
+pub use fs::FsFrameProvider;
+#[cfg(any(feature = "http", feature = "http-native"))]
+pub use http::{HttpFrameProvider, DEFAULT_HTTP_CACHE_BYTES};
+#[cfg(feature = "indexeddb")]
+pub use idb::IdbCacheProvider;
+pub use indexed::IndexedCFrameData;
+pub use interpolate::{blend_cframes, blend_cframes_in, frame_blend_at, FrameBlend};
+pub use lint::{lint_project, LintCode, LintIssue, LintOptions, LintProject, LintReport, Severity};
+pub use loader::{load_color_bytes_prioritized, load_color_frames, load_color_frames_prioritized, load_poster_frame, load_text_frames, load_text_frames_concurrent, load_text_frames_partial, load_text_frames_streaming, is_transient_error, BoxFuture, CancellationToken, DynFrameDataProvider, FrameDataProvider, FrameFailure, FrameLoaderState, LoadOptions, LoadResult, LoadSchedule, LoaderMetrics, LoadingEvent, LoadingPhase, LoadingProgress, PlayableThreshold, RetryPolicy, TextLoadReport, LOADING_CANCELLED, PLACEHOLDER_TAG};
+pub use manifest::{FrameManifest, ManifestFrame, MANIFEST_FILE, MANIFEST_VERSION};
+pub use overlay::{format_timecode, Anchor, OverlayContext, OverlayElement, OverlayKind, OverlayScene, TimeRange};
+pub use parser::{detect_byte_order, encode_cframe, parse_cframe, parse_cframe_text, parse_cframe_with, parse_packed_cframes, parse_packed_cframes_with, split_cframe_extension, ByteOrder, ParseError, ParseLocation, ParseOptions, ParseRegion, CFRAME_EXT_FLAG_HAS_BG};
 pub use player::FramePlayer;
-pub use render::{RenderConfig, RenderResult};
-pub use sizing::FontSizing;
+pub use prefetch::{CacheWarmer, PlaybackDirection, Prefetcher};
+pub use render::cache::RenderCache;
+pub use region::{region_outlines, Region};
+pub use replay::{frame_set_checksum, ControllerCommand, InteractionLog, LoggedCommand};
+pub use render::post::{PostEffect, PostRect};
+pub use render::viewport::Viewport;
+pub use render::{CompositeMode, FontStyle, Glow, Letterbox, RenderConfig, RenderDiff, RenderLayer, RenderMode, RenderResult, RenderStats};
+pub use sequence::{ConcatOptions, FrameSequence, RetimeMode};
+pub use sizing::{CharAdvances, FitMode, FontFit, FontSizing, FontSnap, ResizeTracker, MAX_CANVAS_DIMENSION};
+pub use source::FrameSource;
+pub use stats::{FrameStats, BRIGHTNESS_BINS};
+pub use timeline::{detect_scene_cuts, Bookmark, Chapter, Thumbnail, Timeline, TIMELINE_VERSION};
 
 #[cfg(feature = "web")]
 pub use loader::yield_to_event_loop;
 #[cfg(feature = "web")]
 pub use render::web::render_to_canvas;
 #[cfg(feature = "web")]
-pub use render::web::{draw_cached_canvas, draw_frame_from_cache, render_text_to_canvas, render_to_offscreen_canvas, FrameCanvasCache};
+pub use render::web::{calibrate_font_sizing, draw_cached_canvas, draw_cached_canvas_scaled, draw_layer, draw_frame_from_cache, draw_frame_from_cache_scaled, measure_char_advances, measure_font_metrics, measure_text_cached, render_diff_to_canvas, render_text_frame_to_canvas, render_text_to_canvas, render_to_canvas_over, render_to_offscreen_canvas, Backdrop, FrameCanvasCache, TextMeasureCache};
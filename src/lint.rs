@@ -0,0 +1,293 @@
+//! Project linting: run every validator over a frame set and report issues.
+//!
+//! This backs a `cascii check` style command in downstream CLIs. Validators
+//! never stop at the first problem; [`lint_project`] returns a
+//! [`LintReport`] with one [`LintIssue`] per finding, each with a severity.
+
+use std::collections::BTreeSet;
+
+use crate::{DetailsWarning, Frame, FrameFile, ProjectDetails};
+
+/// How serious a lint finding is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Worth knowing, playback is unaffected
+    Info,
+    /// Likely a mistake; playback may look wrong
+    Warning,
+    /// The project is broken or inconsistent
+    Error,
+}
+
+/// Which validator produced a finding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LintCode {
+    /// Frame dimensions differ from the first frame
+    DimensionMismatch,
+    /// Color data dimensions differ from the frame's text
+    ColorDimensionMismatch,
+    /// Frame file indices skip numbers
+    IndexGap,
+    /// Two frame files share an index
+    DuplicateIndex,
+    /// Frame content doesn't match its expected checksum
+    ChecksumMismatch,
+    /// Lines within one frame have different lengths
+    RaggedLines,
+    /// Frame exceeds the configured maximum size
+    OversizedFrame,
+    /// Color string in `details.toml` can't be parsed
+    InvalidColor,
+    /// `details.toml` disagrees with the frames on disk
+    DetailsMismatch,
+    /// `details.toml` has a `version` that isn't a version number
+    UnknownVersion,
+}
+
+impl LintCode {
+    /// Stable kebab-case identifier, e.g. for CLI output or allow-lists.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintCode::DimensionMismatch => "dimension-mismatch",
+            LintCode::ColorDimensionMismatch => "color-dimension-mismatch",
+            LintCode::IndexGap => "index-gap",
+            LintCode::DuplicateIndex => "duplicate-index",
+            LintCode::ChecksumMismatch => "checksum-mismatch",
+            LintCode::RaggedLines => "ragged-lines",
+            LintCode::OversizedFrame => "oversized-frame",
+            LintCode::InvalidColor => "invalid-color",
+            LintCode::DetailsMismatch => "details-mismatch",
+            LintCode::UnknownVersion => "unknown-version",
+        }
+    }
+}
+
+/// One lint finding.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LintIssue {
+    /// How serious the finding is
+    pub severity: Severity,
+    /// Validator that produced it
+    pub code: LintCode,
+    /// Frame index the finding refers to, if any
+    pub frame: Option<usize>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl std::fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match self.frame {
+            Some(frame) => write!(f, "{}[{}] frame {}: {}", severity, self.code.as_str(), frame, self.message),
+            None => write!(f, "{}[{}]: {}", severity, self.code.as_str(), self.message),
+        }
+    }
+}
+
+/// The result of [`lint_project`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LintReport {
+    /// All findings, in validator order
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    /// Returns `true` when no finding is an error.
+    pub fn is_ok(&self) -> bool {
+        !self.issues.iter().any(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Number of findings with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues.iter().filter(|issue| issue.severity == severity).count()
+    }
+
+    /// Highest severity in the report, or `None` when it is clean.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues.iter().map(|issue| issue.severity).max()
+    }
+
+    fn push(&mut self, severity: Severity, code: LintCode, frame: Option<usize>, message: String) {
+        self.issues.push(LintIssue {severity, code, frame, message});
+    }
+}
+
+/// Everything the linter looks at. Leave parts empty / `None` to skip their
+/// validators.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LintProject<'a> {
+    /// Loaded frames, in playback order
+    pub frames: &'a [Frame],
+    /// Frame files on disk, used for index gap / duplicate checks
+    pub frame_files: &'a [FrameFile],
+    /// Project metadata
+    pub details: Option<&'a ProjectDetails>,
+    /// Expected [`Frame::checksum`] per frame
+    pub checksums: Option<&'a [u64]>,
+}
+
+/// Limits used by the size validator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LintOptions {
+    /// Maximum frame width in columns
+    pub max_width: usize,
+    /// Maximum frame height in rows
+    pub max_height: usize,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {max_width: 1024, max_height: 1024}
+    }
+}
+
+/// Run all validators over a project.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{lint_project, Frame, LintCode, LintOptions, LintProject};
+///
+/// let frames = vec![Frame::text_only("ab\ncd\n".into()), Frame::text_only("abc\nd\n".into())];
+/// let report = lint_project(&LintProject { frames: &frames, ..LintProject::default() }, &LintOptions::default());
+///
+/// assert!(!report.is_ok());
+/// assert!(report.issues.iter().any(|issue| issue.code == LintCode::RaggedLines));
+/// ```
+pub fn lint_project(project: &LintProject<'_>, options: &LintOptions) -> LintReport {
+    let mut report = LintReport::default();
+    check_dimensions(&mut report, project.frames, options);
+    check_indices(&mut report, project.frame_files);
+    if let Some(checksums) = project.checksums {
+        check_checksums(&mut report, project.frames, checksums);
+    }
+    if let Some(details) = project.details {
+        check_details(&mut report, project.frames, details);
+    }
+    report
+}
+
+fn check_dimensions(report: &mut LintReport, frames: &[Frame], options: &LintOptions) {
+    let mut expected = None;
+    for (index, frame) in frames.iter().enumerate() {
+        let lengths: Vec<usize> = frame.content.lines().map(crate::display_width).collect();
+        let (cols, rows) = frame.dimensions();
+        if lengths.iter().any(|&len| len != cols) {
+            let shortest = lengths.iter().min().copied().unwrap_or(0);
+            report.push(Severity::Warning, LintCode::RaggedLines, Some(index), format!("line lengths range from {} to {}", shortest, cols));
+        }
+        if cols > options.max_width || rows > options.max_height {
+            report.push(Severity::Warning, LintCode::OversizedFrame, Some(index), format!("{}x{} exceeds {}x{}", cols, rows, options.max_width, options.max_height));
+        }
+        if let Some(cframe) = &frame.cframe {
+            if (cframe.width as usize, cframe.height as usize) != (cols, rows) {
+                report.push(Severity::Warning, LintCode::ColorDimensionMismatch, Some(index), format!("text is {}x{} but color data is {}x{}", cols, rows, cframe.width, cframe.height));
+            }
+        }
+        match expected {
+            None => expected = Some((cols, rows)),
+            Some((ecols, erows)) if (ecols, erows) != (cols, rows) => {
+                report.push(Severity::Error, LintCode::DimensionMismatch, Some(index), format!("{}x{} differs from first frame {}x{}", cols, rows, ecols, erows));
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_indices(report: &mut LintReport, files: &[FrameFile]) {
+    let mut seen = BTreeSet::new();
+    for file in files {
+        if !seen.insert(file.index) {
+            report.push(Severity::Error, LintCode::DuplicateIndex, Some(file.index as usize), format!("{} reuses index {}", file.name, file.index));
+        }
+    }
+    let indices: Vec<u32> = seen.into_iter().collect();
+    for pair in indices.windows(2) {
+        if pair[1] > pair[0] + 1 {
+            let missing = if pair[1] == pair[0] + 2 { format!("{}", pair[0] + 1) } else { format!("{}-{}", pair[0] + 1, pair[1] - 1) };
+            report.push(Severity::Warning, LintCode::IndexGap, None, format!("missing frame index {}", missing));
+        }
+    }
+}
+
+fn check_checksums(report: &mut LintReport, frames: &[Frame], checksums: &[u64]) {
+    if checksums.len() != frames.len() {
+        report.push(Severity::Error, LintCode::ChecksumMismatch, None, format!("{} checksums for {} frames", checksums.len(), frames.len()));
+    }
+    for (index, (frame, &expected)) in frames.iter().zip(checksums).enumerate() {
+        let actual = frame.checksum();
+        if actual != expected {
+            report.push(Severity::Error, LintCode::ChecksumMismatch, Some(index), format!("expected {:016x}, got {:016x}", expected, actual));
+        }
+    }
+}
+
+fn check_details(report: &mut LintReport, frames: &[Frame], details: &ProjectDetails) {
+    for warning in details.validate(Some(frames.len())) {
+        let (severity, code) = match warning {
+            DetailsWarning::ZeroFps => (Severity::Error, LintCode::DetailsMismatch),
+            DetailsWarning::FrameCountMismatch {..} => (Severity::Warning, LintCode::DetailsMismatch),
+            DetailsWarning::InvalidColor {..} => (Severity::Error, LintCode::InvalidColor),
+            DetailsWarning::UnknownVersion(_) => (Severity::Info, LintCode::UnknownVersion),
+            DetailsWarning::InvalidSection {..} => (Severity::Warning, LintCode::DetailsMismatch),
+        };
+        report.push(severity, code, None, warning.to_string());
+    }
+    if let (Some(columns), Some(first)) = (details.columns, frames.first()) {
+        let (cols, _) = first.dimensions();
+        if columns as usize != cols {
+            report.push(Severity::Warning, LintCode::DetailsMismatch, None, format!("details.toml declares {} columns, frames have {}", columns, cols));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CFrameData;
+
+    fn file(index: u32) -> FrameFile {
+        FrameFile::new(format!("frames/frame_{:04}.txt", index), format!("frame_{:04}.txt", index), index)
+    }
+
+    #[test]
+    fn test_clean_project() {
+        let frames = vec![Frame::text_only("ab\n".into()), Frame::text_only("cd\n".into())];
+        let files = vec![file(1), file(2)];
+        let details = ProjectDetails {frames: Some(2), columns: Some(2), color: Some("white".into()), ..ProjectDetails::default()};
+        let checksums: Vec<u64> = frames.iter().map(Frame::checksum).collect();
+        let project = LintProject {frames: &frames, frame_files: &files, details: Some(&details), checksums: Some(&checksums)};
+
+        let report = lint_project(&project, &LintOptions::default());
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert_eq!(report.max_severity(), None);
+    }
+
+    #[test]
+    fn test_reports_every_validator() {
+        let frames = vec![
+            Frame::text_only("ab\n".into()),
+            Frame::with_color("abc\nab\n".into(), CFrameData::new(1, 1, vec![b'a'], vec![0; 3])),
+        ];
+        let files = vec![file(1), file(4), file(4)];
+        let details = ProjectDetails {version: Some("next".into()), frames: Some(3), background_color: Some("nope".into()), ..ProjectDetails::default()};
+        let project = LintProject {frames: &frames, frame_files: &files, details: Some(&details), checksums: Some(&[0, 0])};
+        let report = lint_project(&project, &LintOptions {max_width: 2, max_height: 10});
+
+        let codes: Vec<LintCode> = report.issues.iter().map(|issue| issue.code).collect();
+        for code in [LintCode::RaggedLines, LintCode::OversizedFrame, LintCode::ColorDimensionMismatch, LintCode::DimensionMismatch, LintCode::DuplicateIndex, LintCode::IndexGap, LintCode::ChecksumMismatch, LintCode::InvalidColor, LintCode::DetailsMismatch, LintCode::UnknownVersion] {
+            assert!(codes.contains(&code), "missing {:?}", code);
+        }
+        assert!(!report.is_ok());
+        assert_eq!(report.issues.iter().find(|i| i.code == LintCode::IndexGap).unwrap().to_string(), "warning[index-gap]: missing frame index 2-3");
+    }
+}
@@ -4,7 +4,220 @@
 //! 1. Text frames (fast) - enables immediate playback
 //! 2. Color data (background) - progressive enhancement
 
-use crate::{CFrameData, Frame, FrameFile};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::Poll;
+
+use crate::{CFrameData, Frame, FrameFile, FrameManifest, SharedFrame};
+
+/// Error returned by loaders stopped through a [`CancellationToken`].
+pub const LOADING_CANCELLED: &str = "Loading cancelled";
+
+/// Shared flag that stops in-flight loaders between frames.
+///
+/// Clones share the flag, so a host keeps one clone and hands another to
+/// [`load_text_frames`] / [`load_color_frames`] in [`LoadOptions`]. After
+/// [`cancel`](Self::cancel) the loader returns `Err(LOADING_CANCELLED)`
+/// before reading the next frame and never calls its callback again, so a
+/// project switch mid-load can't write stale frames into new state.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every loader holding a clone of this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) was called.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// `Err(LOADING_CANCELLED)` once cancelled, for use with `?`.
+    #[inline]
+    pub fn check(&self) -> LoadResult<()> {
+        if self.is_cancelled() { Err(LOADING_CANCELLED.to_string()) } else { Ok(()) }
+    }
+}
+
+/// How loaders retry a failed provider read.
+///
+/// A read that fails with a [`retryable`](Self::retryable) error is tried
+/// again up to [`attempts`](Self::attempts) times in total, waiting
+/// [`backoff_ms`](Self::backoff_ms) before the first retry and
+/// [`backoff_factor`](Self::backoff_factor) times longer before each
+/// following one. The waits go through [`LoadOptions::sleep`]; without a
+/// timer the retries run back to back. Cancellation is never retried.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Tries per read, including the first (1 disables retries)
+    pub attempts: u32,
+    /// Wait before the first retry, in milliseconds
+    pub backoff_ms: u32,
+    /// Growth of the wait after each retry
+    pub backoff_factor: f64,
+    /// Upper bound on a single wait, in milliseconds
+    pub max_backoff_ms: u32,
+    /// Whether an error is worth retrying
+    pub retryable: fn(&str) -> bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three tries, waiting 200 ms then 400 ms, retrying
+    /// [transient](is_transient_error) errors.
+    fn default() -> Self {
+        Self {attempts: 3, backoff_ms: 200, backoff_factor: 2.0, max_backoff_ms: 5_000, retryable: is_transient_error}
+    }
+}
+
+impl RetryPolicy {
+    /// Fail on the first error.
+    pub fn none() -> Self {
+        Self {attempts: 1, ..Self::default()}
+    }
+
+    /// Wait before retry number `retry` (0 for the first retry), in milliseconds.
+    pub fn delay_ms(&self, retry: u32) -> u32 {
+        let delay = self.backoff_ms as f64 * self.backoff_factor.powi(retry.min(i32::MAX as u32) as i32);
+        delay.clamp(0.0, self.max_backoff_ms as f64) as u32
+    }
+
+    /// Whether a read that failed with `error` after `tries` tries should be
+    /// tried again.
+    pub fn should_retry(&self, tries: u32, error: &str) -> bool {
+        tries < self.attempts && error != LOADING_CANCELLED && (self.retryable)(error)
+    }
+}
+
+/// Whether a retry might fix `error`: everything except HTTP 4xx statuses
+/// other than 408 and 429, missing files and checksum mismatches.
+///
+/// The default [`RetryPolicy::retryable`].
+pub fn is_transient_error(error: &str) -> bool {
+    let status = error.rsplit_once("HTTP ").and_then(|(_, rest)| rest.get(..3)?.parse::<u16>().ok());
+    if matches!(status, Some(400..=407 | 409..=428 | 430..=499)) {
+        return false;
+    }
+    !error.starts_with(CHECKSUM_MISMATCH) && !error.to_ascii_lowercase().contains("not found")
+}
+
+/// Prefix of the error a payload failing its checksum is rejected with.
+const CHECKSUM_MISMATCH: &str = "Checksum mismatch";
+
+/// A frame whose read still failed after every retry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameFailure {
+    /// Index of the frame in the file list
+    pub index: usize,
+    /// Path that was read
+    pub path: String,
+    /// Number of tries made
+    pub attempts: u32,
+    /// Error of the last try
+    pub error: String,
+}
+
+/// Cancellation and retry settings shared by the loader functions.
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    /// Checked between frames; see [`CancellationToken`]
+    pub cancel: CancellationToken,
+    /// How failed reads are retried
+    pub retry: RetryPolicy,
+    /// Retry payloads that fail their checksum under `retry`, like failed
+    /// reads; otherwise a mismatch fails the frame at once
+    pub retry_integrity_failures: bool,
+    /// Timer for the waits between retries, resolving after the given
+    /// milliseconds. `None` uses the browser's `setTimeout` with the `web`
+    /// feature and retries without waiting otherwise; the loaders never
+    /// block the thread.
+    pub sleep: Option<fn(u32) -> BoxFuture<'static, ()>>,
+}
+
+impl LoadOptions {
+    /// Whether a read that failed with `error` after `tries` tries should be
+    /// tried again.
+    fn should_retry(&self, tries: u32, error: &str) -> bool {
+        let integrity = self.retry_integrity_failures && error.starts_with(CHECKSUM_MISMATCH) && tries < self.retry.attempts;
+        integrity || self.retry.should_retry(tries, error)
+    }
+}
+
+/// Run `read` under `options.retry`, returning its last result and the
+/// number of tries made.
+async fn retrying<T, R, Fut>(options: &LoadOptions, mut read: R) -> (LoadResult<T>, u32) where R: FnMut() -> Fut, Fut: std::future::Future<Output = LoadResult<T>> {
+    let mut tries = 1;
+    loop {
+        match read().await {
+            Err(error) if options.should_retry(tries, &error) => {
+                sleep_ms(options, options.retry.delay_ms(tries - 1)).await;
+                if let Err(error) = options.cancel.check() {
+                    return (Err(error), tries);
+                }
+                tries += 1;
+            }
+            result => return (result, tries),
+        }
+    }
+}
+
+/// Like [`retrying`], but checks every payload with `verify`; mismatches
+/// are retried only with `options.retry_integrity_failures`.
+async fn retrying_verified<T, R, Fut, V>(options: &LoadOptions, read: R, verify: V) -> (LoadResult<T>, u32) where R: Fn() -> Fut, Fut: std::future::Future<Output = LoadResult<T>>, V: Fn(&T) -> LoadResult<()> {
+    if options.retry_integrity_failures {
+        let (read, verify) = (&read, &verify);
+        retrying(options, || async move {
+            let value = read().await?;
+            verify(&value).map(|()| value)
+        }).await
+    } else {
+        let (result, tries) = retrying(options, read).await;
+        (result.and_then(|value| verify(&value).map(|()| value)), tries)
+    }
+}
+
+/// Compare a fetched payload with its expected [`payload_checksum`](crate::payload_checksum),
+/// reporting a mismatch to `on_event`.
+fn verify_payload<E: Fn(LoadingEvent)>(phase: LoadingPhase, index: usize, path: &str, expected: Option<u64>, bytes: &[u8], on_event: &E) -> LoadResult<()> {
+    let Some(expected) = expected else { return Ok(()); };
+    let actual = crate::payload_checksum(bytes);
+    if actual == expected {
+        return Ok(());
+    }
+    on_event(LoadingEvent::IntegrityFailure {phase, index, path: path.to_string(), expected, actual});
+    Err(format!("{} for {}: expected {:016x}, got {:016x}", CHECKSUM_MISMATCH, path, expected, actual))
+}
+
+/// Wait `ms` milliseconds with `options.sleep`, on the browser event loop
+/// with the `web` feature, or not at all.
+async fn sleep_ms(options: &LoadOptions, ms: u32) {
+    if ms == 0 {
+        return;
+    }
+    if let Some(sleep) = options.sleep {
+        return sleep(ms).await;
+    }
+    #[cfg(feature = "web")]
+    {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            if let Some(window) = web_sys::window() {
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms.min(i32::MAX as u32) as i32);
+            } else {
+                let _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+            }
+        });
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }
+}
 
 /// Loading phase indicator
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -19,6 +232,142 @@ pub enum LoadingPhase {
     Complete,
 }
 
+/// Something that happened during a load, reported by the loader functions
+/// as it happens.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadingEvent {
+    /// A loader started a phase: [`load_text_frames`] announces
+    /// `LoadingText`, the color loaders `LoadingColors` and finally
+    /// `Complete`.
+    PhaseChanged(LoadingPhase),
+    /// One more frame of `phase` finished loading.
+    FrameLoaded {
+        /// Phase the frame belongs to
+        phase: LoadingPhase,
+        /// Index of the frame in the file list
+        index: usize,
+        /// Frames of this phase loaded so far
+        loaded: usize,
+        /// Frames this phase loads in total
+        total: usize,
+        /// Average throughput of the phase so far
+        frames_per_second: f64,
+        /// Estimated time until the phase completes, once a rate is known
+        eta_ms: Option<f64>,
+        /// Bytes read for the frame (text, or raw `.cframe` data)
+        bytes: usize,
+        /// Time spent decoding the frame's color data, in milliseconds
+        decode_ms: f64,
+    },
+    /// A frame's read failed even after retrying (see [`RetryPolicy`]).
+    FrameFailed(FrameFailure),
+    /// A fetched payload didn't match its manifest checksum (see
+    /// [`FrameFile::text_checksum`]); the payload is discarded and the read
+    /// fails, or is retried with [`LoadOptions::retry_integrity_failures`].
+    IntegrityFailure {
+        /// Phase the payload belongs to
+        phase: LoadingPhase,
+        /// Index of the frame in the file list
+        index: usize,
+        /// Text frame path the payload belongs to
+        path: String,
+        /// Checksum listed in the manifest
+        expected: u64,
+        /// Checksum of the received bytes
+        actual: u64,
+    },
+}
+
+/// Frame counter of one phase, producing [`LoadingEvent::FrameLoaded`].
+struct Throughput {
+    phase: LoadingPhase,
+    total: usize,
+    loaded: usize,
+    started_ms: f64,
+}
+
+impl Throughput {
+    fn start(phase: LoadingPhase, total: usize) -> Self {
+        Self {phase, total, loaded: 0, started_ms: crate::clock::now_ms()}
+    }
+
+    fn frame_loaded(&mut self, index: usize, bytes: usize, decode_ms: f64) -> LoadingEvent {
+        self.frame_loaded_at(index, bytes, decode_ms, crate::clock::now_ms())
+    }
+
+    fn frame_loaded_at(&mut self, index: usize, bytes: usize, decode_ms: f64, now_ms: f64) -> LoadingEvent {
+        self.loaded += 1;
+        let elapsed_ms = now_ms - self.started_ms;
+        let frames_per_second = if elapsed_ms > 0.0 { self.loaded as f64 * 1000.0 / elapsed_ms } else { 0.0 };
+        let eta_ms = (frames_per_second > 0.0).then(|| self.total.saturating_sub(self.loaded) as f64 * 1000.0 / frames_per_second);
+        LoadingEvent::FrameLoaded {phase: self.phase, index, loaded: self.loaded, total: self.total, frames_per_second, eta_ms, bytes, decode_ms}
+    }
+}
+
+/// Network and decode statistics of a load, accumulated from its
+/// [`LoadingEvent`]s by [`FrameLoaderState::record_event`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoaderMetrics {
+    /// Bytes of text frames read
+    pub text_bytes: u64,
+    /// Bytes of `.cframe` data read
+    pub color_bytes: u64,
+    /// Total time spent decoding color data, in milliseconds
+    pub decode_ms: f64,
+    /// Frames loaded, over both phases
+    pub frames_loaded: usize,
+    /// Frames whose read failed even after retrying
+    pub frames_failed: usize,
+    /// Payloads that failed their checksum, counting every retry
+    pub integrity_failures: usize,
+    /// Latest text phase throughput
+    pub text_frames_per_second: f64,
+    /// Latest color phase throughput
+    pub color_frames_per_second: f64,
+    /// Time the text phase has taken so far, in milliseconds
+    pub text_elapsed_ms: f64,
+    /// Time the color phase has taken so far, in milliseconds
+    pub color_elapsed_ms: f64,
+}
+
+impl LoaderMetrics {
+    /// Bytes read over both phases.
+    #[inline]
+    pub fn total_bytes(&self) -> u64 {
+        self.text_bytes + self.color_bytes
+    }
+
+    /// Average transfer rate over both phases, once any time has passed.
+    pub fn bytes_per_second(&self) -> Option<f64> {
+        let elapsed_ms = self.text_elapsed_ms + self.color_elapsed_ms;
+        (elapsed_ms > 0.0).then(|| self.total_bytes() as f64 * 1000.0 / elapsed_ms)
+    }
+
+    /// Average decode time per color frame, in milliseconds.
+    pub fn decode_ms_per_frame(&self, color_frames: usize) -> f64 {
+        if color_frames == 0 { 0.0 } else { self.decode_ms / color_frames as f64 }
+    }
+
+    /// Add one loader event to the totals.
+    pub fn record(&mut self, event: &LoadingEvent) {
+        match *event {
+            LoadingEvent::FrameLoaded {phase, loaded, frames_per_second, bytes, decode_ms, ..} => {
+                let elapsed_ms = if frames_per_second > 0.0 { loaded as f64 * 1000.0 / frames_per_second } else { 0.0 };
+                self.frames_loaded += 1;
+                self.decode_ms += decode_ms;
+                match phase {
+                    LoadingPhase::LoadingText => (self.text_bytes, self.text_frames_per_second, self.text_elapsed_ms) = (self.text_bytes + bytes as u64, frames_per_second, elapsed_ms),
+                    LoadingPhase::LoadingColors => (self.color_bytes, self.color_frames_per_second, self.color_elapsed_ms) = (self.color_bytes + bytes as u64, frames_per_second, elapsed_ms),
+                    LoadingPhase::Idle | LoadingPhase::Complete => {}
+                }
+            }
+            LoadingEvent::FrameFailed(_) => self.frames_failed += 1,
+            LoadingEvent::IntegrityFailure {..} => self.integrity_failures += 1,
+            LoadingEvent::PhaseChanged(_) => {}
+        }
+    }
+}
+
 /// Progress information for frame loading
 #[derive(Clone, Debug, Default)]
 pub struct LoadingProgress {
@@ -88,6 +437,18 @@ impl LoadingProgress {
         }
     }
 
+    /// Update the counts from a loader's [`LoadingEvent`], so a progress UI
+    /// can follow the loader without polling.
+    pub fn apply(&mut self, event: &LoadingEvent) {
+        if let LoadingEvent::FrameLoaded {phase, loaded, total, ..} = *event {
+            match phase {
+                LoadingPhase::LoadingText => (self.text_loaded, self.text_total) = (loaded, total),
+                LoadingPhase::LoadingColors => (self.color_loaded, self.color_total) = (loaded, total),
+                LoadingPhase::Idle | LoadingPhase::Complete => {}
+            }
+        }
+    }
+
     /// Format color loading message (returns None if not loading colors)
     pub fn color_message(&self) -> Option<String> {
         if self.color_total > 0 && !self.color_complete() {
@@ -98,6 +459,32 @@ impl LoadingProgress {
     }
 }
 
+/// How many text frames must be loaded before playback may start.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayableThreshold {
+    /// Wait for every text frame
+    #[default]
+    AllFrames,
+    /// Start after the first `n` frames
+    Frames(usize),
+    /// Start after this fraction (0.0 - 1.0) of the frames
+    Fraction(f32),
+}
+
+impl PlayableThreshold {
+    /// Number of frames, out of `total`, that must be loaded. Always at
+    /// least one frame and at most `total`.
+    pub fn frames_needed(&self, total: usize) -> usize {
+        let needed = match *self {
+            PlayableThreshold::AllFrames => total,
+            PlayableThreshold::Frames(frames) => frames,
+            PlayableThreshold::Fraction(fraction) => (total as f64 * fraction.clamp(0.0, 1.0) as f64).ceil() as usize,
+        };
+        needed.clamp(1, total.max(1))
+    }
+}
+
 /// State for managing frame loading
 #[derive(Clone, Debug)]
 pub struct FrameLoaderState {
@@ -105,12 +492,18 @@ pub struct FrameLoaderState {
     pub phase: LoadingPhase,
     /// Loading progress
     pub progress: LoadingProgress,
-    /// Loaded frames
-    pub frames: Vec<Frame>,
+    /// Loaded frames, shared so consumers can hold them without copying
+    pub frames: Vec<SharedFrame>,
     /// Frame file paths (for color loading phase)
     pub frame_paths: Vec<String>,
     /// Error message if loading failed
     pub error: Option<String>,
+    /// Text frames needed before [`can_play`](Self::can_play) turns true;
+    /// kept across [`reset`](Self::reset)
+    pub playable_threshold: PlayableThreshold,
+    /// Frames already reported by [`take_appended`](Self::take_appended)
+    appended: usize,
+    metrics: LoaderMetrics,
 }
 
 impl Default for FrameLoaderState {
@@ -128,9 +521,18 @@ impl FrameLoaderState {
             frames: Vec::new(),
             frame_paths: Vec::new(),
             error: None,
+            playable_threshold: PlayableThreshold::default(),
+            appended: 0,
+            metrics: LoaderMetrics::default(),
         }
     }
 
+    /// Create a loader state that allows playback once `threshold` text
+    /// frames are loaded.
+    pub fn with_playable_threshold(threshold: PlayableThreshold) -> Self {
+        Self {playable_threshold: threshold, ..Self::new()}
+    }
+
     /// Reset state for a new loading session
     pub fn reset(&mut self) {
         self.phase = LoadingPhase::Idle;
@@ -138,6 +540,30 @@ impl FrameLoaderState {
         self.frames.clear();
         self.frame_paths.clear();
         self.error = None;
+        self.appended = 0;
+        self.metrics = LoaderMetrics::default();
+    }
+
+    /// Byte counts, decode time, and throughput of the current load, from
+    /// the events passed to [`record_event`](Self::record_event).
+    #[inline]
+    pub fn metrics(&self) -> &LoaderMetrics {
+        &self.metrics
+    }
+
+    /// Record a loader's [`LoadingEvent`] in [`metrics`](Self::metrics);
+    /// pass every event the loaders' `on_event` receives.
+    pub fn record_event(&mut self, event: &LoadingEvent) {
+        self.metrics.record(event);
+    }
+
+    /// Number of text frames added since the last call, to pass to
+    /// [`AnimationController::append_frames`](crate::AnimationController::append_frames)
+    /// while playback runs ahead of loading.
+    pub fn take_appended(&mut self) -> usize {
+        let added = self.frames.len().saturating_sub(self.appended);
+        self.appended = self.frames.len();
+        added
     }
 
     /// Start loading with the given frame files
@@ -150,10 +576,29 @@ impl FrameLoaderState {
 
     /// Add a text-only frame (Phase 1)
     pub fn add_text_frame(&mut self, content: String) {
-        self.frames.push(Frame::text_only(content));
+        self.add_shared_frame(Frame::text_only(content).into());
+    }
+
+    /// Add an already loaded frame (Phase 1) without copying it, e.g. one
+    /// that is also held by a cache.
+    pub fn add_shared_frame(&mut self, frame: SharedFrame) {
+        self.frames.push(frame);
         self.progress.text_loaded += 1;
     }
 
+    /// Add frame `index` of `total` as
+    /// [`load_text_frames_streaming`]'s `on_frame` delivers it. The first
+    /// frame resets the state and starts the text phase, so
+    /// [`can_play`](Self::can_play) turns true at the threshold.
+    pub fn add_streamed_frame(&mut self, index: usize, total: usize, frame: SharedFrame) {
+        if index == 0 {
+            self.reset();
+            self.phase = LoadingPhase::LoadingText;
+            self.progress.reset(total);
+        }
+        self.add_shared_frame(frame);
+    }
+
     /// Finish text loading phase and start color loading
     pub fn finish_text_loading(&mut self) {
         if self.frames.is_empty() {
@@ -167,7 +612,7 @@ impl FrameLoaderState {
     /// Update a frame with color data (Phase 2)
     pub fn set_frame_color(&mut self, index: usize, cframe: CFrameData) {
         if index < self.frames.len() {
-            self.frames[index].cframe = Some(cframe);
+            self.frames[index].make_mut().cframe = Some(cframe);
         }
         self.progress.color_loaded += 1;
 
@@ -192,10 +637,17 @@ impl FrameLoaderState {
         self.phase = LoadingPhase::Idle;
     }
 
-    /// Check if playback can start (text loading complete)
+    /// Check if playback can start: text loading is complete, or enough
+    /// text frames for the [`playable_threshold`](Self::playable_threshold)
+    /// have arrived while the rest keep loading (e.g. from
+    /// [`load_text_frames_streaming`]).
     pub fn can_play(&self) -> bool {
         !self.frames.is_empty()
-            && (self.phase == LoadingPhase::LoadingColors || self.phase == LoadingPhase::Complete)
+            && match self.phase {
+                LoadingPhase::LoadingColors | LoadingPhase::Complete => true,
+                LoadingPhase::LoadingText => self.frames.len() >= self.playable_threshold.frames_needed(self.progress.text_total),
+                LoadingPhase::Idle => false,
+            }
     }
 
     /// Check if any frame has color data
@@ -205,7 +657,12 @@ impl FrameLoaderState {
 
     /// Get the frame at the given index
     pub fn get_frame(&self, index: usize) -> Option<&Frame> {
-        self.frames.get(index)
+        self.frames.get(index).map(|frame| &**frame)
+    }
+
+    /// Get a shared handle to the frame at the given index.
+    pub fn shared_frame(&self, index: usize) -> Option<SharedFrame> {
+        self.frames.get(index).cloned()
     }
 
     /// Get the number of loaded frames
@@ -240,24 +697,321 @@ pub trait FrameDataProvider {
     /// Returns `Ok(None)` if no .cframe file exists for this frame.
     /// The caller (orchestrator) handles parsing via `parse_cframe`.
     fn read_cframe_bytes(&self, txt_path: &str) -> impl std::future::Future<Output = LoadResult<Option<Vec<u8>>>>;
+
+    /// Read the frame manifest (usually [`MANIFEST_FILE`](crate::MANIFEST_FILE))
+    /// of a directory.
+    ///
+    /// When this returns a manifest, loaders take the frame order and
+    /// timing from it instead of [`get_frame_files`](Self::get_frame_files).
+    /// The default reports no manifest, so loaders fall back to listing the
+    /// directory.
+    fn read_manifest(&self, _directory: &str) -> impl std::future::Future<Output = LoadResult<Option<FrameManifest>>> {
+        async { Ok(None) }
+    }
+
+    /// Validator (e.g. an HTTP `ETag`) of the current color data for the
+    /// given text frame path, used by caching wrappers to tell a stored copy
+    /// is still fresh.
+    ///
+    /// The default reports none, so caches treat stored copies as fresh.
+    fn cframe_etag(&self, _txt_path: &str) -> impl std::future::Future<Output = LoadResult<Option<String>>> {
+        async { Ok(None) }
+    }
+}
+
+/// A boxed future as returned by [`DynFrameDataProvider`]. No `Send` bound,
+/// like [`FrameDataProvider`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Object-safe form of [`FrameDataProvider`], for choosing a backend at
+/// runtime.
+///
+/// Every [`FrameDataProvider`] implements it, and `Box<dyn
+/// DynFrameDataProvider>` implements [`FrameDataProvider`] again, so a boxed
+/// provider works with every loader and with the player:
+///
+/// ```rust
+/// use cascii_core_view::{Bundle, DynFrameDataProvider};
+///
+/// let providers: Vec<Box<dyn DynFrameDataProvider>> = vec![Box::new(Bundle::new())];
+/// assert_eq!(providers.len(), 1);
+/// ```
+pub trait DynFrameDataProvider {
+    /// Boxed [`FrameDataProvider::get_frame_files`]
+    fn get_frame_files_boxed<'a>(&'a self, directory: &'a str) -> BoxFuture<'a, LoadResult<Vec<FrameFile>>>;
+
+    /// Boxed [`FrameDataProvider::read_frame_text`]
+    fn read_frame_text_boxed<'a>(&'a self, path: &'a str) -> BoxFuture<'a, LoadResult<String>>;
+
+    /// Boxed [`FrameDataProvider::read_cframe_bytes`]
+    fn read_cframe_bytes_boxed<'a>(&'a self, txt_path: &'a str) -> BoxFuture<'a, LoadResult<Option<Vec<u8>>>>;
+
+    /// Boxed [`FrameDataProvider::read_manifest`]
+    fn read_manifest_boxed<'a>(&'a self, directory: &'a str) -> BoxFuture<'a, LoadResult<Option<FrameManifest>>>;
+
+    /// Boxed [`FrameDataProvider::cframe_etag`]
+    fn cframe_etag_boxed<'a>(&'a self, txt_path: &'a str) -> BoxFuture<'a, LoadResult<Option<String>>>;
+}
+
+impl<P: FrameDataProvider> DynFrameDataProvider for P {
+    fn get_frame_files_boxed<'a>(&'a self, directory: &'a str) -> BoxFuture<'a, LoadResult<Vec<FrameFile>>> {
+        Box::pin(self.get_frame_files(directory))
+    }
+
+    fn read_frame_text_boxed<'a>(&'a self, path: &'a str) -> BoxFuture<'a, LoadResult<String>> {
+        Box::pin(self.read_frame_text(path))
+    }
+
+    fn read_cframe_bytes_boxed<'a>(&'a self, txt_path: &'a str) -> BoxFuture<'a, LoadResult<Option<Vec<u8>>>> {
+        Box::pin(self.read_cframe_bytes(txt_path))
+    }
+
+    fn read_manifest_boxed<'a>(&'a self, directory: &'a str) -> BoxFuture<'a, LoadResult<Option<FrameManifest>>> {
+        Box::pin(self.read_manifest(directory))
+    }
+
+    fn cframe_etag_boxed<'a>(&'a self, txt_path: &'a str) -> BoxFuture<'a, LoadResult<Option<String>>> {
+        Box::pin(self.cframe_etag(txt_path))
+    }
+}
+
+impl FrameDataProvider for Box<dyn DynFrameDataProvider + '_> {
+    async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+        self.as_ref().get_frame_files_boxed(directory).await
+    }
+
+    async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+        self.as_ref().read_frame_text_boxed(path).await
+    }
+
+    async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+        self.as_ref().read_cframe_bytes_boxed(txt_path).await
+    }
+
+    async fn read_manifest(&self, directory: &str) -> LoadResult<Option<FrameManifest>> {
+        self.as_ref().read_manifest_boxed(directory).await
+    }
+
+    async fn cframe_etag(&self, txt_path: &str) -> LoadResult<Option<String>> {
+        self.as_ref().cframe_etag_boxed(txt_path).await
+    }
+}
+
+/// The frame list of `directory`: from its manifest when the provider has
+/// one, otherwise from the directory listing.
+async fn list_frames<P: FrameDataProvider>(provider: &P, directory: &str) -> LoadResult<(Vec<FrameFile>, Option<FrameManifest>)> {
+    match provider.read_manifest(directory).await? {
+        Some(manifest) => Ok((manifest.frame_files(), Some(manifest))),
+        None => Ok((provider.get_frame_files(directory).await?, None)),
+    }
+}
+
+/// Attach the manifest's per-frame metadata to `frame`.
+fn with_manifest_meta(mut frame: Frame, manifest: Option<&FrameManifest>, index: usize) -> Frame {
+    frame.meta = manifest.and_then(|manifest| manifest.frames.get(index)).and_then(|entry| entry.meta());
+    frame
 }
 
 /// Phase 1: load all text frames sequentially, return them along with the
 /// file list (needed for Phase 2 color loading).
-pub async fn load_text_frames<P: FrameDataProvider>(provider: &P, directory: &str) -> LoadResult<(Vec<Frame>, Vec<FrameFile>)> {
-    let frame_files = provider.get_frame_files(directory).await?;
+///
+/// Frames come in manifest order, with manifest durations as
+/// [`FrameMeta`](crate::FrameMeta), when the provider has a manifest.
+/// `options.cancel` is checked before each frame, each read is retried
+/// under `options.retry`, and progress is reported to `on_event`.
+pub async fn load_text_frames<P, E>(provider: &P, directory: &str, options: &LoadOptions, on_event: E) -> LoadResult<(Vec<Frame>, Vec<FrameFile>)> where P: FrameDataProvider, E: Fn(LoadingEvent) {
+    load_text_frames_concurrent(provider, directory, 1, options, on_event).await
+}
+
+/// An in-flight text frame read, resolving to its index, content, and the
+/// number of tries made.
+type TextRead<'a> = BoxFuture<'a, (usize, LoadResult<String>, u32)>;
+
+/// Tag in the [`FrameMeta`](crate::FrameMeta) of placeholder frames that
+/// [`load_text_frames_partial`] put in place of frames it couldn't read.
+pub const PLACEHOLDER_TAG: &str = "placeholder";
+
+/// Text frames loaded by [`load_text_frames_partial`], with the frames that
+/// could not be read.
+#[derive(Clone, Debug)]
+pub struct TextLoadReport {
+    /// One frame per file; failed frames are blank placeholders tagged
+    /// [`PLACEHOLDER_TAG`], sized like the nearest earlier loaded frame
+    pub frames: Vec<Frame>,
+    /// The file list, needed for Phase 2 color loading
+    pub frame_files: Vec<FrameFile>,
+    /// Frames whose read failed even after retrying, in frame order
+    pub failures: Vec<FrameFailure>,
+}
+
+impl TextLoadReport {
+    /// Whether every frame was read.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Fraction (0.0 - 1.0) of the frames that were read.
+    pub fn loaded_fraction(&self) -> f64 {
+        if self.frame_files.is_empty() { 1.0 } else { 1.0 - self.failures.len() as f64 / self.frame_files.len() as f64 }
+    }
+
+    /// The frames and file list, placeholders included.
+    pub fn into_parts(self) -> (Vec<Frame>, Vec<FrameFile>) {
+        (self.frames, self.frame_files)
+    }
+
+    /// The frames and file list when every frame was read, otherwise the
+    /// first failure's error.
+    pub fn into_complete(self) -> LoadResult<(Vec<Frame>, Vec<FrameFile>)> {
+        match self.failures.into_iter().next() {
+            Some(failure) => Err(failure.error),
+            None => Ok((self.frames, self.frame_files)),
+        }
+    }
+}
+
+/// Phase 1 with up to `max_in_flight` text frame reads running at once.
+///
+/// Behaves like [`load_text_frames`], but overlaps the provider's round
+/// trips (e.g. over HTTP). Reads may finish in any order; finished frames
+/// are buffered so `FrameLoaded` events still arrive in frame order, each
+/// one meaning every earlier frame is loaded too. The first read that keeps
+/// failing aborts the load.
+pub async fn load_text_frames_concurrent<P, E>(provider: &P, directory: &str, max_in_flight: usize, options: &LoadOptions, on_event: E) -> LoadResult<(Vec<Frame>, Vec<FrameFile>)> where P: FrameDataProvider, E: Fn(LoadingEvent) {
+    read_text_frames(provider, directory, max_in_flight, false, options, |_, _, _| {}, on_event).await?.into_complete()
+}
+
+/// Phase 1 handing each frame to the caller as it arrives.
+///
+/// Behaves like [`load_text_frames_concurrent`], and also calls
+/// `on_frame(index, total, &frame)` in frame order as soon as a frame and
+/// every earlier one are loaded. Feeding the frames to a
+/// [`FrameLoaderState`] (see
+/// [`add_streamed_frame`](FrameLoaderState::add_streamed_frame)) lets
+/// playback start at its [`playable_threshold`](FrameLoaderState::playable_threshold)
+/// while the rest keep loading.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// let state = RefCell::new(FrameLoaderState::with_playable_threshold(PlayableThreshold::Frames(48)));
+/// load_text_frames_streaming(&provider, "clip", 4, &LoadOptions::default(), |index, total, frame| {
+///     state.borrow_mut().add_streamed_frame(index, total, frame.clone().into());
+/// }, |_| {}).await?;
+/// ```
+pub async fn load_text_frames_streaming<P, F, E>(provider: &P, directory: &str, max_in_flight: usize, options: &LoadOptions, on_frame: F, on_event: E) -> LoadResult<(Vec<Frame>, Vec<FrameFile>)> where P: FrameDataProvider, F: Fn(usize, usize, &Frame), E: Fn(LoadingEvent) {
+    read_text_frames(provider, directory, max_in_flight, false, options, on_frame, on_event).await?.into_complete()
+}
+
+/// Phase 1 that keeps going past frames it can't read.
+///
+/// Like [`load_text_frames_concurrent`], but a frame whose read keeps
+/// failing is reported to `on_event` as `FrameFailed`, listed in the
+/// report, and replaced by a placeholder, so the caller can decide whether
+/// the partial animation is good enough (see
+/// [`TextLoadReport::loaded_fraction`]). Listing the directory and
+/// cancellation still fail the whole load.
+pub async fn load_text_frames_partial<P, E>(provider: &P, directory: &str, max_in_flight: usize, options: &LoadOptions, on_event: E) -> LoadResult<TextLoadReport> where P: FrameDataProvider, E: Fn(LoadingEvent) {
+    read_text_frames(provider, directory, max_in_flight, true, options, |_, _, _| {}, on_event).await
+}
+
+/// Shared body of the text loaders; `keep_going` collects failed reads
+/// instead of returning the first one.
+async fn read_text_frames<P, F, E>(provider: &P, directory: &str, max_in_flight: usize, keep_going: bool, options: &LoadOptions, on_frame: F, on_event: E) -> LoadResult<TextLoadReport> where P: FrameDataProvider, F: Fn(usize, usize, &Frame), E: Fn(LoadingEvent) {
+    let (frame_files, manifest) = list_frames(provider, directory).await?;
 
     if frame_files.is_empty() {
         return Err("No frames found in directory".to_string());
     }
 
-    let mut frames = Vec::with_capacity(frame_files.len());
-    for frame_file in &frame_files {
-        let content = provider.read_frame_text(&frame_file.path).await?;
-        frames.push(Frame::text_only(content));
+    on_event(LoadingEvent::PhaseChanged(LoadingPhase::LoadingText));
+    let total = frame_files.len();
+    let mut throughput = Throughput::start(LoadingPhase::LoadingText, total);
+    // `Some(None)` marks a frame that could not be read
+    let mut contents: Vec<Option<Option<String>>> = vec![None; total];
+    let mut frames: Vec<Frame> = Vec::with_capacity(total);
+    let mut failures = Vec::new();
+    {
+        let on_event = &on_event;
+        let mut in_flight: Vec<TextRead<'_>> = Vec::new();
+        let (mut next, mut ready) = (0, 0);
+        while ready < total {
+            while in_flight.len() < max_in_flight.max(1) && next < total {
+                options.cancel.check()?;
+                let (index, file) = (next, &frame_files[next]);
+                in_flight.push(Box::pin(async move {
+                    let verify = |text: &String| verify_payload(LoadingPhase::LoadingText, index, &file.path, file.text_checksum, text.as_bytes(), on_event);
+                    let (content, attempts) = retrying_verified(options, || provider.read_frame_text(&file.path), verify).await;
+                    (index, content, attempts)
+                }));
+                next += 1;
+            }
+            let (index, content, attempts) = std::future::poll_fn(|cx| {
+                for i in 0..in_flight.len() {
+                    if let Poll::Ready(done) = in_flight[i].as_mut().poll(cx) {
+                        drop(in_flight.swap_remove(i));
+                        return Poll::Ready(done);
+                    }
+                }
+                Poll::Pending
+            }).await;
+            contents[index] = Some(match content {
+                Ok(content) => Some(content),
+                Err(error) if keep_going => {
+                    options.cancel.check()?;
+                    let failure = FrameFailure {index, path: frame_files[index].path.clone(), attempts, error};
+                    on_event(LoadingEvent::FrameFailed(failure.clone()));
+                    failures.push(failure);
+                    None
+                }
+                Err(error) => return Err(error),
+            });
+            while let Some(content) = contents.get_mut(ready).and_then(Option::take) {
+                let bytes = content.as_ref().map_or(0, String::len);
+                let frame = match content {
+                    Some(content) => with_manifest_meta(Frame::text_only(content), manifest.as_ref(), ready),
+                    None => placeholder_frame(frames.last(), manifest.as_ref(), ready),
+                };
+                on_frame(ready, total, &frame);
+                frames.push(frame);
+                on_event(throughput.frame_loaded(ready, bytes, 0.0));
+                ready += 1;
+            }
+        }
     }
 
-    Ok((frames, frame_files))
+    failures.sort_by_key(|failure| failure.index);
+    Ok(TextLoadReport {frames, frame_files, failures})
+}
+
+/// A blank frame standing in for unreadable frame `index`, sized like
+/// `previous` and tagged [`PLACEHOLDER_TAG`].
+fn placeholder_frame(previous: Option<&Frame>, manifest: Option<&FrameManifest>, index: usize) -> Frame {
+    let (cols, rows) = previous.map(Frame::dimensions).unwrap_or((0, 0));
+    let mut frame = with_manifest_meta(Frame::text_only(format!("{}\n", " ".repeat(cols)).repeat(rows)), manifest, index);
+    frame.meta.get_or_insert_with(Default::default).tags.push(PLACEHOLDER_TAG.to_string());
+    frame
+}
+
+/// Poster mode: list the directory but load only the frame at `index`
+/// (clamped to the last frame), with its color data when available.
+///
+/// Returns the poster frame, its clamped index, and the full file list so
+/// the remaining frames can be loaded later with [`load_text_frames`].
+pub async fn load_poster_frame<P: FrameDataProvider>(provider: &P, directory: &str, index: usize) -> LoadResult<(Frame, usize, Vec<FrameFile>)> {
+    let (frame_files, manifest) = list_frames(provider, directory).await?;
+    let Some(index) = frame_files.len().checked_sub(1).map(|last| index.min(last)) else {
+        return Err("No frames found in directory".to_string());
+    };
+
+    let path = &frame_files[index].path;
+    let content = provider.read_frame_text(path).await?;
+    let frame = match provider.read_cframe_bytes(path).await?.and_then(|bytes| crate::parse_cframe(&bytes).ok()) {
+        Some(cframe) => Frame::with_color(content, cframe),
+        None => Frame::text_only(content),
+    };
+    Ok((with_manifest_meta(frame, manifest.as_ref(), index), index, frame_files))
 }
 
 /// Phase 2: load color data in the background.
@@ -266,23 +1020,143 @@ pub async fn load_text_frames<P: FrameDataProvider>(provider: &P, directory: &st
 /// `parse_cframe`, then calls `on_frame(index, total, Option<CFrameData>)`
 /// so the caller can store the result. Calls `yield_fn()` before and after
 /// each frame to keep the UI responsive (important in single-threaded WASM
-/// contexts).
-pub async fn load_color_frames<P, F, Y, YFut>(provider: &P, frame_files: &[FrameFile], on_frame: F, yield_fn: Y) -> LoadResult<()> where P: FrameDataProvider, F: Fn(usize, usize, Option<CFrameData>), Y: Fn() -> YFut, YFut: std::future::Future<Output = ()> {
+/// contexts). `options.cancel` is checked after every yield, so `on_frame`
+/// is never called once it is cancelled.
+///
+/// Each read is retried under `options.retry`. A frame whose read keeps failing is
+/// reported to `on_frame` without color and listed in the returned
+/// failures, instead of aborting the load. Progress and failures are
+/// reported to `on_event`.
+pub async fn load_color_frames<P, F, Y, YFut, E>(provider: &P, frame_files: &[FrameFile], on_frame: F, yield_fn: Y, options: &LoadOptions, on_event: E) -> LoadResult<Vec<FrameFailure>> where P: FrameDataProvider, F: Fn(usize, usize, Option<CFrameData>), Y: Fn() -> YFut, YFut: std::future::Future<Output = ()>, E: Fn(LoadingEvent) {
     let total = frame_files.len();
+    let mut failures = Vec::new();
+    on_event(LoadingEvent::PhaseChanged(LoadingPhase::LoadingColors));
+    let mut throughput = Throughput::start(LoadingPhase::LoadingColors, total);
     for (i, frame_file) in frame_files.iter().enumerate() {
         // Let animation/input callbacks run before potentially heavy read+parse work.
         yield_fn().await;
+        options.cancel.check()?;
 
-        let cframe = match provider.read_cframe_bytes(&frame_file.path).await? {
-            Some(bytes) => crate::parse_cframe(&bytes).ok(),
-            None => None,
-        };
+        let bytes = read_color_bytes(provider, frame_file, i, options, &mut failures, &on_event).await?;
+        let (len, started_ms) = (bytes.as_ref().map_or(0, Vec::len), crate::clock::now_ms());
+        let cframe = bytes.and_then(|bytes| crate::parse_cframe(&bytes).ok());
+        let decode_ms = crate::clock::now_ms() - started_ms;
+        options.cancel.check()?;
         on_frame(i, total, cframe);
+        on_event(throughput.frame_loaded(i, len, decode_ms));
 
         // Yield again after storing the decoded frame.
         yield_fn().await;
     }
-    Ok(())
+    on_event(LoadingEvent::PhaseChanged(LoadingPhase::Complete));
+    Ok(failures)
+}
+
+/// Read frame `index`'s raw color data, recording a read that keeps
+/// failing in `failures` and reporting it to `on_event`.
+async fn read_color_bytes<P, E>(provider: &P, frame_file: &FrameFile, index: usize, options: &LoadOptions, failures: &mut Vec<FrameFailure>, on_event: &E) -> LoadResult<Option<Vec<u8>>> where P: FrameDataProvider, E: Fn(LoadingEvent) {
+    let verify = |bytes: &Option<Vec<u8>>| bytes.as_ref().map_or(Ok(()), |bytes| verify_payload(LoadingPhase::LoadingColors, index, &frame_file.path, frame_file.cframe_checksum, bytes, on_event));
+    match retrying_verified(options, || provider.read_cframe_bytes(&frame_file.path), verify).await {
+        (Ok(bytes), _) => Ok(bytes),
+        (Err(error), attempts) => {
+            options.cancel.check()?;
+            let failure = FrameFailure {index, path: frame_file.path.clone(), attempts, error};
+            on_event(LoadingEvent::FrameFailed(failure.clone()));
+            failures.push(failure);
+            Ok(None)
+        }
+    }
+}
+
+/// Picks which color frame to load next, nearest to the playhead first.
+///
+/// The current frame comes first, then frames at growing distance from it
+/// (ahead before behind, since playback moves forward), and finally the
+/// rest. Because the playhead is re-read for every pick, a seek to frame 800
+/// makes frame 800 and its neighbours the next frames to arrive.
+#[derive(Clone, Debug)]
+pub struct LoadSchedule {
+    loaded: Vec<bool>,
+    remaining: usize,
+}
+
+impl LoadSchedule {
+    /// Schedule `total` frames, none loaded yet.
+    pub fn new(total: usize) -> Self {
+        Self {loaded: vec![false; total], remaining: total}
+    }
+
+    /// Number of frames not yet handed out.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Mark frame `index` as loaded so it is never scheduled.
+    pub fn mark_loaded(&mut self, index: usize) {
+        if let Some(loaded) = self.loaded.get_mut(index).filter(|loaded| !**loaded) {
+            *loaded = true;
+            self.remaining -= 1;
+        }
+    }
+
+    /// The unloaded frame closest to `current`, marked as loaded, or `None`
+    /// once every frame has been handed out.
+    pub fn next(&mut self, current: usize) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let total = self.loaded.len();
+        let current = current.min(total - 1);
+        let index = (0..total).flat_map(|distance| [current.checked_add(distance).filter(|&i| i < total), current.checked_sub(distance).filter(|_| distance > 0)])
+            .flatten()
+            .find(|&index| !self.loaded[index])?;
+        self.mark_loaded(index);
+        Some(index)
+    }
+}
+
+/// Phase 2 with playhead priority: like [`load_color_frames`], but frames
+/// are loaded in [`LoadSchedule`] order around `current_frame()`, which is
+/// called before each frame so seeks take effect immediately.
+pub async fn load_color_frames_prioritized<P, C, F, Y, YFut, E>(provider: &P, frame_files: &[FrameFile], current_frame: C, on_frame: F, yield_fn: Y, options: &LoadOptions, on_event: E) -> LoadResult<Vec<FrameFailure>> where P: FrameDataProvider, C: Fn() -> usize, F: Fn(usize, usize, Option<CFrameData>), Y: Fn() -> YFut, YFut: std::future::Future<Output = ()>, E: Fn(LoadingEvent) {
+    let decoding_ms = std::cell::Cell::new(0.0);
+    load_color_bytes_prioritized(provider, frame_files, current_frame, |index, total, bytes| {
+        let started_ms = crate::clock::now_ms();
+        let cframe = bytes.and_then(|bytes| crate::parse_cframe(&bytes).ok());
+        decoding_ms.set(crate::clock::now_ms() - started_ms);
+        on_frame(index, total, cframe);
+    }, yield_fn, options, |mut event| {
+        if let LoadingEvent::FrameLoaded {decode_ms, ..} = &mut event {
+            *decode_ms = decoding_ms.take();
+        }
+        on_event(event)
+    }).await
+}
+
+/// Lazy Phase 2: like [`load_color_frames_prioritized`], but hands
+/// `on_bytes(index, total, Option<Vec<u8>>)` the raw `.cframe` bytes
+/// without parsing them, so decoding can wait until a frame is first shown
+/// (see [`FramePlayer::set_lazy_colors`](crate::FramePlayer::set_lazy_colors)).
+pub async fn load_color_bytes_prioritized<P, C, F, Y, YFut, E>(provider: &P, frame_files: &[FrameFile], current_frame: C, on_bytes: F, yield_fn: Y, options: &LoadOptions, on_event: E) -> LoadResult<Vec<FrameFailure>> where P: FrameDataProvider, C: Fn() -> usize, F: Fn(usize, usize, Option<Vec<u8>>), Y: Fn() -> YFut, YFut: std::future::Future<Output = ()>, E: Fn(LoadingEvent) {
+    let total = frame_files.len();
+    let mut schedule = LoadSchedule::new(total);
+    let mut failures = Vec::new();
+    on_event(LoadingEvent::PhaseChanged(LoadingPhase::LoadingColors));
+    let mut throughput = Throughput::start(LoadingPhase::LoadingColors, total);
+    loop {
+        yield_fn().await;
+        options.cancel.check()?;
+        let Some(i) = schedule.next(current_frame()) else { break; };
+
+        let bytes = read_color_bytes(provider, &frame_files[i], i, options, &mut failures, &on_event).await?;
+        let len = bytes.as_ref().map_or(0, Vec::len);
+        options.cancel.check()?;
+        on_bytes(i, total, bytes);
+        on_event(throughput.frame_loaded(i, len, 0.0));
+    }
+    on_event(LoadingEvent::PhaseChanged(LoadingPhase::Complete));
+    Ok(failures)
 }
 
 /// Yield control back to the browser event loop.
@@ -302,9 +1176,65 @@ pub async fn yield_to_event_loop() {
     let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
 }
 
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Drive a future that never actually waits (in-memory providers) to completion.
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    /// In-memory provider keyed by text frame path.
+    #[derive(Default)]
+    pub(crate) struct MemoryProvider {
+        pub(crate) texts: BTreeMap<String, String>,
+        pub(crate) cframes: BTreeMap<String, Vec<u8>>,
+        pub(crate) manifest: Option<FrameManifest>,
+    }
+
+    impl MemoryProvider {
+        /// Frames named `frame_0001.txt`, `frame_0002.txt`, ... with the given contents.
+        pub(crate) fn with_texts(texts: &[&str]) -> Self {
+            let texts = texts.iter().enumerate().map(|(i, text)| (format!("frame_{:04}.txt", i + 1), text.to_string())).collect();
+            Self {texts, cframes: BTreeMap::new(), manifest: None}
+        }
+    }
+
+    impl FrameDataProvider for MemoryProvider {
+        async fn get_frame_files(&self, _directory: &str) -> LoadResult<Vec<FrameFile>> {
+            Ok(self.texts.keys().enumerate().map(|(i, name)| FrameFile::new(name.clone(), name.clone(), i as u32 + 1)).collect())
+        }
+
+        async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+            self.texts.get(path).cloned().ok_or_else(|| format!("missing {}", path))
+        }
+
+        async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+            Ok(self.cframes.get(txt_path).cloned())
+        }
+
+        async fn read_manifest(&self, _directory: &str) -> LoadResult<Option<FrameManifest>> {
+            Ok(self.manifest.clone())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::test_support::{block_on, MemoryProvider};
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_loading_progress() {
@@ -351,6 +1281,51 @@ mod tests {
         assert_eq!(state.phase, LoadingPhase::Complete);
     }
 
+    #[test]
+    fn test_playable_threshold() {
+        assert_eq!(PlayableThreshold::AllFrames.frames_needed(200), 200);
+        assert_eq!(PlayableThreshold::Frames(48).frames_needed(200), 48);
+        assert_eq!(PlayableThreshold::Frames(48).frames_needed(10), 10);
+        assert_eq!(PlayableThreshold::Fraction(0.1).frames_needed(95), 10);
+        assert_eq!(PlayableThreshold::Fraction(0.0).frames_needed(95), 1);
+
+        let mut state = FrameLoaderState::with_playable_threshold(PlayableThreshold::Frames(2));
+        let files: Vec<FrameFile> = (1..=4).map(|i| FrameFile::new(format!("{}.txt", i), format!("{}.txt", i), i)).collect();
+        state.start_loading(&files);
+        state.add_text_frame("1".into());
+        assert!(!state.can_play());
+        state.add_text_frame("2".into());
+        assert!(state.can_play());
+        assert_eq!(state.phase, LoadingPhase::LoadingText);
+        assert_eq!(state.playable_threshold, PlayableThreshold::Frames(2));
+
+        let mut controller = crate::AnimationController::new(24);
+        controller.append_frames(state.take_appended());
+        controller.play();
+        controller.tick();
+        state.add_text_frame("3".into());
+        controller.append_frames(state.take_appended());
+        assert_eq!((controller.frame_count(), controller.current_frame()), (3, 1));
+        assert_eq!(state.take_appended(), 0);
+    }
+
+    #[test]
+    fn test_streaming_text_frames_reach_threshold() {
+        let provider = MemoryProvider::with_texts(&["A", "B", "C", "D"]);
+        let state = std::cell::RefCell::new(FrameLoaderState::with_playable_threshold(PlayableThreshold::Frames(2)));
+        let playable = std::cell::RefCell::new(Vec::new());
+        let (frames, _) = block_on(load_text_frames_streaming(&provider, "", 2, &LoadOptions::default(), |index, total, frame| {
+            let mut state = state.borrow_mut();
+            state.add_streamed_frame(index, total, frame.clone().into());
+            playable.borrow_mut().push((index, state.can_play()));
+        }, |_| {})).unwrap();
+
+        assert_eq!(frames.len(), 4);
+        assert_eq!(playable.into_inner(), [(0, false), (1, true), (2, true), (3, true)]);
+        let state = state.into_inner();
+        assert_eq!((state.frame_count(), state.phase, state.progress.text_total), (4, LoadingPhase::LoadingText, 4));
+    }
+
     #[test]
     fn test_loader_state_with_colors() {
         let mut state = FrameLoaderState::new();
@@ -369,4 +1344,303 @@ mod tests {
         assert!(state.frames[0].has_color());
         assert_eq!(state.phase, LoadingPhase::Complete);
     }
+
+    #[test]
+    fn test_load_schedule_expands_around_playhead() {
+        let mut schedule = LoadSchedule::new(6);
+        schedule.mark_loaded(4);
+        let order: Vec<usize> = std::iter::from_fn(|| schedule.next(3)).collect();
+        assert_eq!(order, [3, 2, 5, 1, 0]);
+        assert_eq!(schedule.remaining(), 0);
+    }
+
+    #[test]
+    fn test_load_color_frames_prioritized_follows_seek() {
+        let mut provider = MemoryProvider::with_texts(&["a", "b", "c", "d"]);
+        for name in provider.texts.keys().cloned().collect::<Vec<_>>() {
+            provider.cframes.insert(name, crate::encode_cframe(&CFrameData::new(1, 1, vec![b'x'], vec![0; 3])).unwrap());
+        }
+        let files = block_on(provider.get_frame_files("")).unwrap();
+        let order = std::cell::RefCell::new(Vec::new());
+        let playhead = std::cell::Cell::new(2);
+        block_on(load_color_frames_prioritized(&provider, &files, || playhead.get(), |index, total, cframe| {
+            assert!(cframe.is_some() && total == 4);
+            order.borrow_mut().push(index);
+            playhead.set(0);
+        }, || async {}, &LoadOptions::default(), |_| {})).unwrap();
+        assert_eq!(order.into_inner(), [2, 0, 1, 3]);
+    }
+
+    #[test]
+    fn test_cancellation_stops_loading() {
+        let provider = MemoryProvider::with_texts(&["a", "b", "c"]);
+        let options = LoadOptions::default();
+        let files = block_on(provider.get_frame_files("")).unwrap();
+        let loaded = std::cell::Cell::new(0);
+        let result = block_on(load_color_frames(&provider, &files, |_, _, _| {
+            loaded.set(loaded.get() + 1);
+            options.cancel.cancel();
+        }, || async {}, &options, |_| {}));
+        assert_eq!(result, Err(LOADING_CANCELLED.to_string()));
+        assert_eq!(loaded.get(), 1);
+
+        assert!(block_on(load_text_frames(&provider, "", &options, |_| {})).is_err());
+        assert_eq!(block_on(load_text_frames(&provider, "", &LoadOptions::default(), |_| {})).unwrap().0.len(), 3);
+    }
+
+    /// Fails the first `failures` reads of every path.
+    struct FlakyProvider {
+        inner: MemoryProvider,
+        failures: u32,
+        tries: std::cell::RefCell<BTreeMap<String, u32>>,
+    }
+
+    impl FlakyProvider {
+        fn try_read(&self, path: &str) -> LoadResult<()> {
+            let mut tries = self.tries.borrow_mut();
+            let count = tries.entry(path.to_string()).or_default();
+            *count += 1;
+            if *count <= self.failures { Err(format!("flaky {}", path)) } else { Ok(()) }
+        }
+    }
+
+    impl FrameDataProvider for FlakyProvider {
+        async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+            self.inner.get_frame_files(directory).await
+        }
+
+        async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+            self.try_read(path)?;
+            self.inner.read_frame_text(path).await
+        }
+
+        async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+            self.try_read(&format!("{}.cframe", txt_path))?;
+            self.inner.read_cframe_bytes(txt_path).await
+        }
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let policy = RetryPolicy {max_backoff_ms: 500, ..RetryPolicy::default()};
+        assert_eq!((policy.delay_ms(0), policy.delay_ms(1), policy.delay_ms(2)), (200, 400, 500));
+        assert!(policy.should_retry(2, "timeout") && !policy.should_retry(3, "timeout"));
+        assert!(!policy.should_retry(1, LOADING_CANCELLED));
+        assert!(!RetryPolicy {retryable: |error| error.contains("timeout"), ..policy}.should_retry(1, "404"));
+
+        assert!(policy.should_retry(1, "GET a.txt failed with HTTP 503") && policy.should_retry(1, "GET a.txt failed with HTTP 429"));
+        assert!(!policy.should_retry(1, "GET a.txt failed with HTTP 403"));
+        assert!(!policy.should_retry(1, "https://host/a.txt not found"));
+        assert!(!policy.should_retry(1, "Checksum mismatch for a.txt: expected 0000000000000001, got 0000000000000002"));
+    }
+
+    #[test]
+    fn test_retry_waits_with_sleep_hook() {
+        thread_local! {
+            static WAITS: std::cell::RefCell<Vec<u32>> = const { std::cell::RefCell::new(Vec::new()) };
+        }
+        let provider = FlakyProvider {inner: MemoryProvider::with_texts(&["a"]), failures: 2, tries: Default::default()};
+        let options = LoadOptions {sleep: Some(|ms| {
+            WAITS.with(|waits| waits.borrow_mut().push(ms));
+            Box::pin(async {})
+        }), ..LoadOptions::default()};
+        assert!(block_on(load_text_frames(&provider, "", &options, |_| {})).is_ok());
+        assert_eq!(WAITS.with(|waits| waits.take()), [200, 400]);
+    }
+
+    #[test]
+    fn test_loaders_retry_flaky_reads() {
+        let provider = FlakyProvider {inner: MemoryProvider::with_texts(&["a", "b"]), failures: 2, tries: Default::default()};
+        let options = LoadOptions {retry: RetryPolicy {backoff_ms: 0, ..RetryPolicy::default()}, ..LoadOptions::default()};
+        let (frames, files) = block_on(load_text_frames(&provider, "", &options, |_| {})).unwrap();
+        assert_eq!(frames.len(), 2);
+
+        let provider = FlakyProvider {failures: 5, tries: Default::default(), ..provider};
+        assert_eq!(block_on(load_text_frames(&provider, "", &options, |_| {})).unwrap_err(), "flaky frame_0001.txt");
+        let colored = std::cell::Cell::new(0);
+        let failures = block_on(load_color_frames(&provider, &files, |_, _, _| colored.set(colored.get() + 1), || async {}, &options, |_| {})).unwrap();
+        assert_eq!(colored.get(), 2);
+        assert_eq!(failures.iter().map(|failure| (failure.index, failure.attempts)).collect::<Vec<_>>(), [(0, 3), (1, 3)]);
+        assert_eq!(failures[1].error, "flaky frame_0002.txt.cframe");
+    }
+
+    #[test]
+    fn test_loading_events() {
+        let mut throughput = Throughput {phase: LoadingPhase::LoadingText, total: 4, loaded: 0, started_ms: 0.0};
+        assert_eq!(throughput.frame_loaded_at(0, 3, 0.0, 0.0), LoadingEvent::FrameLoaded {phase: LoadingPhase::LoadingText, index: 0, loaded: 1, total: 4, frames_per_second: 0.0, eta_ms: None, bytes: 3, decode_ms: 0.0});
+        let LoadingEvent::FrameLoaded {frames_per_second, eta_ms, ..} = throughput.frame_loaded_at(1, 3, 0.0, 500.0) else { unreachable!() };
+        assert_eq!((frames_per_second, eta_ms), (4.0, Some(500.0)));
+
+        let provider = FlakyProvider {inner: MemoryProvider::with_texts(&["a", "b"]), failures: 0, tries: Default::default()};
+        let events = std::cell::RefCell::new(Vec::new());
+        let options = LoadOptions {retry: RetryPolicy::none(), ..LoadOptions::default()};
+        let (_, files) = block_on(load_text_frames(&provider, "", &options, |event| events.borrow_mut().push(event))).unwrap();
+        let broken = FlakyProvider {failures: 1, tries: Default::default(), ..provider};
+        block_on(load_color_frames(&broken, &files, |_, _, _| {}, || async {}, &options, |event| events.borrow_mut().push(event))).unwrap();
+
+        let events = events.into_inner();
+        assert_eq!(events.len(), 9);
+        assert_eq!(events[0], LoadingEvent::PhaseChanged(LoadingPhase::LoadingText));
+        assert_eq!(events[3], LoadingEvent::PhaseChanged(LoadingPhase::LoadingColors));
+        assert!(matches!(&events[4], LoadingEvent::FrameFailed(failure) if failure.index == 0));
+        assert_eq!(events[8], LoadingEvent::PhaseChanged(LoadingPhase::Complete));
+
+        let mut progress = LoadingProgress::new();
+        events.iter().for_each(|event| progress.apply(event));
+        assert!(progress.text_complete() && progress.color_complete());
+    }
+
+    #[test]
+    fn test_loader_metrics() {
+        let mut provider = MemoryProvider::with_texts(&["ab", "cde"]);
+        let cframe = crate::encode_cframe(&CFrameData::new(1, 1, vec![b'c'], vec![1, 2, 3])).unwrap();
+        provider.cframes.insert("frame_0002.txt".into(), cframe.clone());
+        let state = std::cell::RefCell::new(FrameLoaderState::new());
+        let record = |event: LoadingEvent| state.borrow_mut().record_event(&event);
+        let (_, files) = block_on(load_text_frames(&provider, "", &LoadOptions::default(), record)).unwrap();
+        block_on(load_color_frames(&provider, &files, |_, _, _| {}, || async {}, &LoadOptions::default(), record)).unwrap();
+
+        let mut state = state.into_inner();
+        let metrics = state.metrics().clone();
+        assert_eq!((metrics.text_bytes, metrics.color_bytes, metrics.total_bytes()), (5, cframe.len() as u64, 5 + cframe.len() as u64));
+        assert_eq!((metrics.frames_loaded, metrics.frames_failed), (4, 0));
+        assert!(metrics.decode_ms >= 0.0);
+
+        let mut timed = LoaderMetrics::default();
+        timed.record(&Throughput {phase: LoadingPhase::LoadingColors, total: 2, loaded: 0, started_ms: 0.0}.frame_loaded_at(0, 500, 2.0, 250.0));
+        assert_eq!((timed.color_elapsed_ms, timed.bytes_per_second(), timed.decode_ms_per_frame(1)), (250.0, Some(2000.0), 2.0));
+        state.reset();
+        assert_eq!(state.metrics(), &LoaderMetrics::default());
+    }
+
+    #[test]
+    fn test_boxed_providers() {
+        let mut with_manifest = MemoryProvider::with_texts(&["x", "y"]);
+        with_manifest.manifest = Some(FrameManifest::new(vec![crate::ManifestFrame::new("frame_0002.txt")]));
+        let providers: Vec<Box<dyn DynFrameDataProvider>> = vec![Box::new(MemoryProvider::with_texts(&["a", "b", "c"])), Box::new(with_manifest)];
+        let loaded: Vec<Vec<String>> = providers.iter().map(|provider| {
+            let (frames, _) = block_on(load_text_frames(provider, "", &LoadOptions::default(), |_| {})).unwrap();
+            frames.into_iter().map(|frame| frame.content).collect()
+        }).collect();
+        assert_eq!(loaded, [vec!["a", "b", "c"], vec!["y"]]);
+        assert_eq!(block_on(providers[0].cframe_etag("frame_0001.txt")), Ok(None));
+    }
+
+    /// Provider whose reads take longer the lower the frame number, so
+    /// concurrent reads finish in reverse order.
+    struct SlowProvider {
+        inner: MemoryProvider,
+        in_flight: std::cell::Cell<usize>,
+        max_in_flight: std::cell::Cell<usize>,
+    }
+
+    impl FrameDataProvider for SlowProvider {
+        async fn get_frame_files(&self, directory: &str) -> LoadResult<Vec<FrameFile>> {
+            self.inner.get_frame_files(directory).await
+        }
+
+        async fn read_frame_text(&self, path: &str) -> LoadResult<String> {
+            self.in_flight.set(self.in_flight.get() + 1);
+            self.max_in_flight.set(self.max_in_flight.get().max(self.in_flight.get()));
+            let mut polls = 10 - FrameFile::extract_index(path.trim_end_matches(".txt"), 0).min(10);
+            std::future::poll_fn(|_| if polls == 0 { Poll::Ready(()) } else { polls -= 1; Poll::Pending }).await;
+            self.in_flight.set(self.in_flight.get() - 1);
+            self.inner.read_frame_text(path).await
+        }
+
+        async fn read_cframe_bytes(&self, txt_path: &str) -> LoadResult<Option<Vec<u8>>> {
+            self.inner.read_cframe_bytes(txt_path).await
+        }
+    }
+
+    #[test]
+    fn test_load_text_frames_concurrent() {
+        let provider = SlowProvider {inner: MemoryProvider::with_texts(&["a", "b", "c", "d", "e"]), in_flight: Default::default(), max_in_flight: Default::default()};
+        let loaded = std::cell::RefCell::new(Vec::new());
+        let on_event = |event| if let LoadingEvent::FrameLoaded {index, ..} = event { loaded.borrow_mut().push(index) };
+        let (frames, files) = block_on(load_text_frames_concurrent(&provider, "", 3, &LoadOptions::default(), on_event)).unwrap();
+        assert_eq!(frames.iter().map(|frame| frame.content.as_str()).collect::<Vec<_>>(), ["a", "b", "c", "d", "e"]);
+        assert_eq!(files.len(), 5);
+        assert_eq!(loaded.into_inner(), [0, 1, 2, 3, 4]);
+        assert_eq!(provider.max_in_flight.get(), 3);
+
+        let missing = SlowProvider {inner: MemoryProvider::default(), ..provider};
+        assert!(block_on(load_text_frames_concurrent(&missing, "", 3, &LoadOptions::default(), |_| {})).is_err());
+    }
+
+    #[test]
+    fn test_load_text_frames_partial() {
+        let mut provider = MemoryProvider::with_texts(&["ab\ncd\n", "ef\ngh\n"]);
+        let frames = ["frame_0001.txt", "missing.txt", "frame_0002.txt"].map(crate::ManifestFrame::new);
+        provider.manifest = Some(FrameManifest::new(frames.to_vec()));
+        let failed = std::cell::RefCell::new(Vec::new());
+        let on_event = |event| if let LoadingEvent::FrameFailed(failure) = event { failed.borrow_mut().push(failure.index) };
+        let options = LoadOptions {retry: RetryPolicy::none(), ..LoadOptions::default()};
+
+        let report = block_on(load_text_frames_partial(&provider, "", 2, &options, on_event)).unwrap();
+        assert!(!report.is_complete());
+        assert_eq!(failed.into_inner(), [1]);
+        assert_eq!((report.failures.len(), report.failures[0].path.as_str()), (1, "missing.txt"));
+        assert!((report.loaded_fraction() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(report.frames.iter().map(|frame| frame.content.as_str()).collect::<Vec<_>>(), ["ab\ncd\n", "  \n  \n", "ef\ngh\n"]);
+        assert!(report.frames[1].meta.as_ref().is_some_and(|meta| meta.tags == [PLACEHOLDER_TAG]));
+        assert_eq!(report.clone().into_parts().0.len(), 3);
+        assert_eq!(report.into_complete().unwrap_err(), "missing missing.txt");
+        assert!(block_on(load_text_frames(&provider, "", &options, |_| {})).is_err());
+    }
+
+    #[test]
+    fn test_checksum_verification() {
+        let mut provider = MemoryProvider::with_texts(&["a", "b"]);
+        let cframe = crate::encode_cframe(&CFrameData::new(1, 1, vec![b'a'], vec![1, 2, 3])).unwrap();
+        provider.cframes.insert("frame_0001.txt".into(), cframe.clone());
+        let good = crate::ManifestFrame {text_checksum: Some(crate::payload_checksum(b"a")), cframe_checksum: Some(crate::payload_checksum(&cframe) ^ 1), ..crate::ManifestFrame::new("frame_0001.txt")};
+        let corrupt = crate::ManifestFrame {text_checksum: Some(7), ..crate::ManifestFrame::new("frame_0002.txt")};
+        provider.manifest = Some(FrameManifest::new(vec![good, corrupt]));
+
+        let mismatches = std::cell::RefCell::new(Vec::new());
+        let on_event = |event| if let LoadingEvent::IntegrityFailure {phase, index, expected, ..} = event { mismatches.borrow_mut().push((phase, index, expected)) };
+        let options = LoadOptions {retry: RetryPolicy {backoff_ms: 0, ..RetryPolicy::default()}, ..LoadOptions::default()};
+        let error = block_on(load_text_frames(&provider, "", &options, on_event)).unwrap_err();
+        assert!(error.starts_with("Checksum mismatch for frame_0002.txt"));
+        assert_eq!(mismatches.take(), [(LoadingPhase::LoadingText, 1, 7)]);
+
+        let retried = LoadOptions {retry_integrity_failures: true, ..options.clone()};
+        let report = block_on(load_text_frames_partial(&provider, "", 1, &retried, on_event)).unwrap();
+        assert_eq!((mismatches.take().len(), report.failures[0].attempts), (3, 3));
+        assert!(report.frames[1].meta.as_ref().is_some_and(|meta| meta.tags == [PLACEHOLDER_TAG]));
+
+        let colors = std::cell::RefCell::new(Vec::new());
+        let failures = block_on(load_color_frames(&provider, &report.frame_files, |index, _, cframe| colors.borrow_mut().push((index, cframe.is_some())), || async {}, &options, on_event)).unwrap();
+        assert_eq!(colors.into_inner(), [(0, false), (1, false)]);
+        assert_eq!((failures.len(), mismatches.take()[0].0), (1, LoadingPhase::LoadingColors));
+    }
+
+    #[test]
+    fn test_load_text_frames_from_manifest() {
+        let mut provider = MemoryProvider::with_texts(&["a", "b", "c"]);
+        let frames = vec![crate::ManifestFrame {duration_ms: Some(250.0), ..crate::ManifestFrame::new("frame_0003.txt")}, crate::ManifestFrame::new("frame_0001.txt")];
+        provider.manifest = Some(FrameManifest::new(frames));
+
+        let (frames, files) = block_on(load_text_frames(&provider, "", &LoadOptions::default(), |_| {})).unwrap();
+        let contents: Vec<&str> = frames.iter().map(|frame| frame.content.as_str()).collect();
+        assert_eq!(contents, ["c", "a"]);
+        assert_eq!(files[1].path, "frame_0001.txt");
+        assert_eq!(crate::frame_durations(&frames), [Some(250), None]);
+    }
+
+    #[test]
+    fn test_load_poster_frame() {
+        let mut provider = MemoryProvider::with_texts(&["A", "B", "C"]);
+        let cframe = CFrameData::new(1, 1, vec![b'B'], vec![255, 0, 0]);
+        provider.cframes.insert("frame_0002.txt".into(), crate::encode_cframe(&cframe).unwrap());
+
+        let (frame, index, files) = block_on(load_poster_frame(&provider, "", 1)).unwrap();
+        assert_eq!((frame.content.as_str(), index, files.len()), ("B", 1, 3));
+        assert!(frame.has_color());
+
+        let (frame, index, _) = block_on(load_poster_frame(&provider, "", 99)).unwrap();
+        assert_eq!((frame.content.as_str(), index), ("C", 2));
+        assert!(block_on(load_poster_frame(&MemoryProvider::default(), "", 0)).is_err());
+    }
 }
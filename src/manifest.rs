@@ -0,0 +1,135 @@
+//! Frame manifests (`frames.json`).
+//!
+//! A [`FrameManifest`] lists an animation's frames explicitly: their order,
+//! per-frame durations, dimensions, and checksums. Loaders use it instead of
+//! listing a directory and parsing frame numbers out of file names, and its
+//! durations feed the controller's variable-timing mode. Providers return
+//! one from [`FrameDataProvider::read_manifest`](crate::FrameDataProvider::read_manifest).
+
+use crate::{FrameFile, FrameMeta};
+
+/// Conventional manifest file name inside a frame directory.
+pub const MANIFEST_FILE: &str = "frames.json";
+
+/// Current manifest format version.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// One frame listed in a manifest.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ManifestFrame {
+    /// Text frame path, as accepted by the provider's `read_frame_text`
+    pub path: String,
+    /// How long the frame stays on screen, in milliseconds
+    pub duration_ms: Option<f64>,
+    /// Frame width in characters
+    pub width: Option<u32>,
+    /// Frame height in characters
+    pub height: Option<u32>,
+    /// Expected [`payload_checksum`](crate::payload_checksum) of the text
+    /// file; loaders reject frames that don't match
+    pub text_checksum: Option<u64>,
+    /// Expected [`payload_checksum`](crate::payload_checksum) of the
+    /// `.cframe` file; loaders reject color data that doesn't match
+    pub cframe_checksum: Option<u64>,
+}
+
+impl ManifestFrame {
+    /// A manifest entry for `path` with no other information.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {path: path.into(), ..Self::default()}
+    }
+
+    /// Metadata to attach to the loaded frame, if the entry has timing.
+    pub fn meta(&self) -> Option<FrameMeta> {
+        self.duration_ms.map(|duration_ms| FrameMeta {duration_ms: Some(duration_ms), ..FrameMeta::default()})
+    }
+}
+
+/// The frames of one animation, in playback order.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameManifest {
+    /// Manifest format version
+    pub version: u32,
+    /// Default playback rate for frames without a duration
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fps: Option<u32>,
+    /// Frames in playback order
+    pub frames: Vec<ManifestFrame>,
+}
+
+impl FrameManifest {
+    /// Create a manifest listing `frames` in order.
+    pub fn new(frames: Vec<ManifestFrame>) -> Self {
+        Self {version: MANIFEST_VERSION, fps: None, frames}
+    }
+
+    /// Number of frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` when the manifest lists no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The frame list in manifest order, numbered from 1, carrying the
+    /// payload checksums.
+    pub fn frame_files(&self) -> Vec<FrameFile> {
+        self.frames.iter().enumerate().map(|(i, frame)| {
+            let name = frame.path.rsplit('/').next().unwrap_or(&frame.path).to_string();
+            FrameFile {text_checksum: frame.text_checksum, cframe_checksum: frame.cframe_checksum, ..FrameFile::new(frame.path.clone(), name, i as u32 + 1)}
+        }).collect()
+    }
+
+    /// Serialize as `frames.json`.
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parse a `frames.json` written by [`to_json_string`](Self::to_json_string).
+    /// Manifests from a newer format than [`MANIFEST_VERSION`] are rejected.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        let manifest: Self = serde_json::from_str(s)?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(serde::de::Error::custom(format!("unsupported manifest version {} (newest supported is {})", manifest.version, MANIFEST_VERSION)));
+        }
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_frame_files() {
+        let manifest = FrameManifest::new(vec![
+            ManifestFrame {duration_ms: Some(80.0), text_checksum: Some(7), ..ManifestFrame::new("clip/intro.txt")},
+            ManifestFrame::new("clip/b.txt"),
+        ]);
+        let files = manifest.frame_files();
+        assert_eq!((files[0].path.as_str(), files[0].name.as_str(), files[0].index), ("clip/intro.txt", "intro.txt", 1));
+        assert_eq!(files[1].index, 2);
+        assert_eq!(manifest.frames[0].meta().and_then(|meta| meta.duration_ms), Some(80.0));
+        assert_eq!(manifest.frames[1].meta(), None);
+        assert_eq!((files[0].text_checksum, files[1].text_checksum), (Some(7), None));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_manifest_json() {
+        let manifest = FrameManifest::from_json_str(r#"{"version": 1, "frames": [{"path": "a.txt", "duration_ms": 120, "width": 4, "height": 2}]}"#).unwrap();
+        assert_eq!((manifest.fps, manifest.frames[0].width, manifest.frames[0].text_checksum), (None, Some(4), None));
+        assert_eq!(FrameManifest::from_json_str(&manifest.to_json_string().unwrap()).unwrap(), manifest);
+        let newer = FrameManifest::from_json_str(r#"{"version": 2, "frames": []}"#).unwrap_err();
+        assert!(newer.to_string().contains("unsupported manifest version 2"));
+    }
+}
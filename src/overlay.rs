@@ -0,0 +1,346 @@
+//! Declarative overlay scenes (HUDs / OSDs) drawn on top of frames.
+//!
+//! Hosts describe overlays as data — a list of [`OverlayElement`]s with an
+//! anchor, an offset, and an optional time range — and the render pipeline
+//! turns the active elements into a [`RenderLayer`] of plain rectangles and
+//! text runs. No custom render pass is needed per host.
+
+use crate::render::{render_cframe, CellRectBatch, RenderConfig, RenderLayer, RenderResult, TextBatch};
+use crate::CFrameData;
+
+/// Where an overlay element is pinned within the canvas.
+///
+/// Offsets are applied inward from the anchored edge(s), so `(8.0, 8.0)`
+/// with [`Anchor::BottomRight`] keeps the element 8px away from both the
+/// right and bottom edges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Resolve the top-left position of an element of size `(w, h)` inside a
+    /// canvas of size `(canvas_w, canvas_h)`.
+    pub fn resolve(self, canvas_w: f64, canvas_h: f64, w: f64, h: f64, offset: (f64, f64)) -> (f64, f64) {
+        let (dx, dy) = offset;
+        let x = match self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => dx,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => (canvas_w - w) / 2.0 + dx,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => canvas_w - w - dx,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => dy,
+            Anchor::Left | Anchor::Center | Anchor::Right => (canvas_h - h) / 2.0 + dy,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => canvas_h - h - dy,
+        };
+        (x, y)
+    }
+}
+
+/// Playback time window (in milliseconds) during which an element is shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeRange {
+    /// Inclusive start time
+    pub start_ms: f64,
+    /// Exclusive end time; `None` keeps the element visible until the end
+    pub end_ms: Option<f64>,
+}
+
+impl TimeRange {
+    /// Create a time range from `start_ms` to `end_ms`.
+    pub fn new(start_ms: f64, end_ms: Option<f64>) -> Self {
+        Self {start_ms, end_ms}
+    }
+
+    /// Returns `true` when `time_ms` falls inside the range.
+    #[inline]
+    pub fn contains(&self, time_ms: f64) -> bool {
+        time_ms >= self.start_ms && self.end_ms.map(|end| time_ms < end).unwrap_or(true)
+    }
+}
+
+/// The visual content of an overlay element.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverlayKind {
+    /// One or more lines of text in the render config's font metrics
+    Text { text: String, color: (u8, u8, u8) },
+    /// A filled rectangle, in pixels
+    Box { width: f64, height: f64, color: (u8, u8, u8) },
+    /// A horizontal bar filled according to [`OverlayContext::progress`]
+    ProgressBar { width: f64, height: f64, fill: (u8, u8, u8), track: Option<(u8, u8, u8)> },
+    /// A nested ASCII frame (e.g. a logo or picture-in-picture)
+    ImageFrame { frame: CFrameData },
+    /// A pause icon while [`OverlayContext::playing`], a play icon otherwise;
+    /// `size` defaults to the config's line height
+    PlayPauseIcon { size: Option<f64>, color: (u8, u8, u8) },
+    /// The playback time as `m:ss`, followed by ` / m:ss` of
+    /// [`OverlayContext::duration_ms`] when `show_duration` is set
+    Timecode { color: (u8, u8, u8), show_duration: bool },
+}
+
+/// One element of an [`OverlayScene`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverlayElement {
+    /// What to draw
+    pub kind: OverlayKind,
+    /// Where to pin the element
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub anchor: Anchor,
+    /// Pixel offset from the anchored edge(s)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub offset: (f64, f64),
+    /// When the element is visible; `None` means always
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_range: Option<TimeRange>,
+}
+
+impl OverlayElement {
+    /// Create an always-visible element pinned to the top-left corner.
+    pub fn new(kind: OverlayKind) -> Self {
+        Self {kind, anchor: Anchor::TopLeft, offset: (0.0, 0.0), time_range: None}
+    }
+
+    /// Set the anchor and offset.
+    pub fn anchored(mut self, anchor: Anchor, offset: (f64, f64)) -> Self {
+        self.anchor = anchor;
+        self.offset = offset;
+        self
+    }
+
+    /// Limit the element to a time range.
+    pub fn during(mut self, range: TimeRange) -> Self {
+        self.time_range = Some(range);
+        self
+    }
+
+    /// Returns `true` when the element should be drawn at `time_ms`.
+    #[inline]
+    pub fn is_active(&self, time_ms: f64) -> bool {
+        self.time_range.map(|range| range.contains(time_ms)).unwrap_or(true)
+    }
+}
+
+/// Playback state an overlay scene is evaluated against.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OverlayContext {
+    /// Current playback time in milliseconds
+    pub time_ms: f64,
+    /// Playback progress in 0.0 - 1.0, used by progress bars
+    pub progress: f64,
+    /// Whether playback is running, used by play/pause icons
+    pub playing: bool,
+    /// Total playback duration in milliseconds, used by timecodes
+    pub duration_ms: f64,
+}
+
+/// Format milliseconds as `m:ss`, or `h:mm:ss` from one hour on.
+pub fn format_timecode(ms: f64) -> String {
+    let total = (ms.max(0.0) / 1000.0) as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// A declarative list of overlay elements, painted in order.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverlayScene {
+    /// Elements, back to front
+    pub elements: Vec<OverlayElement>,
+}
+
+impl OverlayScene {
+    /// Create an empty scene.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an element (builder style).
+    pub fn with(mut self, element: OverlayElement) -> Self {
+        self.elements.push(element);
+        self
+    }
+
+    /// Append an element.
+    pub fn push(&mut self, element: OverlayElement) {
+        self.elements.push(element);
+    }
+
+    /// Build the overlay layer for a canvas of `width × height` pixels.
+    pub fn build_layer(&self, width: f64, height: f64, config: &RenderConfig, ctx: &OverlayContext) -> RenderLayer {
+        let mut layer = RenderLayer::default();
+        for element in self.elements.iter().filter(|e| e.is_active(ctx.time_ms)) {
+            push_element(&mut layer, element, width, height, config, ctx);
+        }
+        layer
+    }
+
+    /// Append the active elements to `result.overlay`, sized to the result canvas.
+    pub fn apply(&self, result: &mut RenderResult, config: &RenderConfig, ctx: &OverlayContext) {
+        let layer = self.build_layer(result.width, result.height, config, ctx);
+        result.overlay.extend_offset(&layer, 0.0, 0.0);
+    }
+}
+
+fn push_text(layer: &mut RenderLayer, element: &OverlayElement, text: &str, color: (u8, u8, u8), (canvas_w, canvas_h): (f64, f64), config: &RenderConfig) {
+    let lines: Vec<&str> = text.lines().collect();
+    let cols = lines.iter().map(|l| crate::display_width(l)).max().unwrap_or(0);
+    let (w, h) = (cols as f64 * config.char_width(), lines.len() as f64 * config.line_height());
+    let (x, y) = element.anchor.resolve(canvas_w, canvas_h, w, h, element.offset);
+    for (row, line) in lines.iter().enumerate().filter(|(_, l)| !l.is_empty()) {
+        layer.batches.push(TextBatch {text: line.to_string(), x, y: y + row as f64 * config.line_height(), color, opacity: 1.0});
+    }
+}
+
+/// Pause bars, or a play triangle built from horizontal slices.
+fn push_play_pause(layer: &mut RenderLayer, (x, y): (f64, f64), size: f64, playing: bool, color: (u8, u8, u8)) {
+    if playing {
+        let bar = size * 0.35;
+        layer.rects.push(CellRectBatch {x, y, width: bar, height: size, color});
+        layer.rects.push(CellRectBatch {x: x + size - bar, y, width: bar, height: size, color});
+        return;
+    }
+    let slices = (size / 2.0).clamp(4.0, 32.0) as usize;
+    let slice_height = size / slices as f64;
+    for i in 0..slices {
+        let t = (i as f64 + 0.5) / slices as f64;
+        let width = size * (1.0 - (2.0 * t - 1.0).abs());
+        layer.rects.push(CellRectBatch {x, y: y + i as f64 * slice_height, width, height: slice_height, color});
+    }
+}
+
+fn push_element(layer: &mut RenderLayer, element: &OverlayElement, canvas_w: f64, canvas_h: f64, config: &RenderConfig, ctx: &OverlayContext) {
+    match &element.kind {
+        OverlayKind::Text { text, color } => push_text(layer, element, text, *color, (canvas_w, canvas_h), config),
+        OverlayKind::Timecode { color, show_duration } => {
+            let mut text = format_timecode(ctx.time_ms);
+            if *show_duration {
+                text.push_str(" / ");
+                text.push_str(&format_timecode(ctx.duration_ms));
+            }
+            push_text(layer, element, &text, *color, (canvas_w, canvas_h), config);
+        }
+        OverlayKind::PlayPauseIcon { size, color } => {
+            let size = size.unwrap_or_else(|| config.line_height());
+            let position = element.anchor.resolve(canvas_w, canvas_h, size, size, element.offset);
+            push_play_pause(layer, position, size, ctx.playing, *color);
+        }
+        OverlayKind::Box { width, height, color } => {
+            let (x, y) = element.anchor.resolve(canvas_w, canvas_h, *width, *height, element.offset);
+            layer.rects.push(CellRectBatch {x, y, width: *width, height: *height, color: *color});
+        }
+        OverlayKind::ProgressBar { width, height, fill, track } => {
+            let (x, y) = element.anchor.resolve(canvas_w, canvas_h, *width, *height, element.offset);
+            if let Some(track) = track {
+                layer.rects.push(CellRectBatch {x, y, width: *width, height: *height, color: *track});
+            }
+            let filled = width * ctx.progress.clamp(0.0, 1.0);
+            if filled > 0.0 {
+                layer.rects.push(CellRectBatch {x, y, width: filled, height: *height, color: *fill});
+            }
+        }
+        OverlayKind::ImageFrame { frame } => {
+            let rendered = render_cframe(frame, config);
+            let (x, y) = element.anchor.resolve(canvas_w, canvas_h, rendered.width, rendered.height, element.offset);
+            let inner = RenderLayer {rects: rendered.background_batches, batches: rendered.batches};
+            layer.extend_offset(&inner, x, y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_resolve() {
+        assert_eq!(Anchor::TopLeft.resolve(100.0, 50.0, 10.0, 10.0, (2.0, 3.0)), (2.0, 3.0));
+        assert_eq!(Anchor::BottomRight.resolve(100.0, 50.0, 10.0, 10.0, (2.0, 3.0)), (88.0, 37.0));
+        assert_eq!(Anchor::Center.resolve(100.0, 50.0, 10.0, 10.0, (0.0, 0.0)), (45.0, 20.0));
+    }
+
+    #[test]
+    fn test_time_range_filters_elements() {
+        let scene = OverlayScene::new()
+            .with(OverlayElement::new(OverlayKind::Text {text: "intro".into(), color: (255, 255, 255)}).during(TimeRange::new(0.0, Some(1000.0))))
+            .with(OverlayElement::new(OverlayKind::Box {width: 4.0, height: 4.0, color: (1, 2, 3)}));
+        let config = RenderConfig::new(10.0);
+
+        let early = scene.build_layer(100.0, 100.0, &config, &OverlayContext {time_ms: 500.0, ..OverlayContext::default()});
+        assert_eq!(early.batches.len(), 1);
+        assert_eq!(early.rects.len(), 1);
+
+        let late = scene.build_layer(100.0, 100.0, &config, &OverlayContext {time_ms: 1000.0, ..OverlayContext::default()});
+        assert!(late.batches.is_empty());
+        assert_eq!(late.rects.len(), 1);
+    }
+
+    #[test]
+    fn test_progress_bar_fill() {
+        let scene = OverlayScene::new().with(OverlayElement::new(OverlayKind::ProgressBar {width: 80.0, height: 4.0, fill: (255, 0, 0), track: Some((40, 40, 40))}).anchored(Anchor::Bottom, (0.0, 0.0)));
+        let config = RenderConfig::new(10.0);
+        let layer = scene.build_layer(100.0, 50.0, &config, &OverlayContext {progress: 0.25, ..OverlayContext::default()});
+
+        assert_eq!(layer.rects.len(), 2);
+        assert_eq!(layer.rects[0].color, (40, 40, 40));
+        assert_eq!(layer.rects[1].width, 20.0);
+        assert_eq!((layer.rects[1].x, layer.rects[1].y), (10.0, 46.0));
+    }
+
+    #[test]
+    fn test_format_timecode() {
+        assert_eq!(format_timecode(0.0), "0:00");
+        assert_eq!(format_timecode(65_999.0), "1:05");
+        assert_eq!(format_timecode(3_725_000.0), "1:02:05");
+    }
+
+    #[test]
+    fn test_osd_primitives() {
+        let scene = OverlayScene::new()
+            .with(OverlayElement::new(OverlayKind::PlayPauseIcon {size: Some(16.0), color: (255, 255, 255)}).anchored(Anchor::BottomLeft, (4.0, 4.0)))
+            .with(OverlayElement::new(OverlayKind::Timecode {color: (200, 200, 200), show_duration: true}).anchored(Anchor::BottomRight, (0.0, 0.0)));
+        let config = RenderConfig::new(10.0);
+        let mut ctx = OverlayContext {time_ms: 61_000.0, progress: 0.5, playing: true, duration_ms: 122_000.0};
+
+        let playing = scene.build_layer(200.0, 100.0, &config, &ctx);
+        assert_eq!(playing.rects.len(), 2);
+        assert_eq!((playing.rects[0].x, playing.rects[0].y, playing.rects[0].height), (4.0, 80.0, 16.0));
+        assert_eq!(playing.batches[0].text, "1:01 / 2:02");
+        assert!((playing.batches[0].x - (200.0 - 11.0 * config.char_width())).abs() < 1e-9);
+
+        ctx.playing = false;
+        let paused = scene.build_layer(200.0, 100.0, &config, &ctx);
+        assert_eq!(paused.rects.len(), 8);
+        assert!(paused.rects[3].width > paused.rects[0].width);
+        assert!(paused.rects.iter().all(|rect| rect.width <= 16.0));
+    }
+
+    #[test]
+    fn test_image_frame_is_offset() {
+        let logo = CFrameData::new(1, 1, vec![b'X'], vec![255, 255, 255]);
+        let scene = OverlayScene::new().with(OverlayElement::new(OverlayKind::ImageFrame {frame: logo}).anchored(Anchor::TopRight, (0.0, 0.0)));
+        let config = RenderConfig::new(10.0);
+        let mut result = RenderResult {width: 60.0, height: 20.0, background: None, background_batches: Vec::new(), batches: Vec::new(), overlay: RenderLayer::default(), letterbox: Default::default(), glow: None, post: Vec::new(), stats: None};
+        scene.apply(&mut result, &config, &OverlayContext::default());
+
+        assert_eq!(result.overlay.batches.len(), 1);
+        assert_eq!(result.overlay.batches[0].text, "X");
+        assert!((result.overlay.batches[0].x - 54.0).abs() < 0.001); // 60 - 1 * 10 * 0.6
+    }
+}
@@ -1,20 +1,27 @@
 //! Binary format parsing for `.cframe` files and packed multi-frame blobs.
 
-use crate::{CFrameData, PackedCFrameBlob};
+use crate::{CFrameData, CFrameError, PackedCFrameBlob};
 
 /// Error type for parsing operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseError {
     /// File is too small to contain required header
     FileTooSmall { expected: usize, actual: usize },
-    /// File size doesn't match expected size based on header
-    SizeMismatch { expected: usize, actual: usize },
+    /// File size doesn't match expected size based on header.
+    /// `location` points at the first missing byte when parsing a file.
+    SizeMismatch { expected: usize, actual: usize, location: Option<ParseLocation> },
     /// Invalid dimensions in header
     InvalidDimensions { width: u32, height: u32 },
     /// Packed blob declared zero frames
     InvalidFrameCount { count: u32 },
     /// Frame data count doesn't match existing text frame count
     FrameCountMismatch { expected: usize, actual: usize },
+    /// Header fits the data in neither little- nor big-endian byte order.
+    /// `width` / `height` are the little-endian reading.
+    UnsupportedEncoding { width: u32, height: u32, actual: usize },
+    /// Header or input size exceeds the configured [`ParseOptions`] limits.
+    /// `bytes` is the input length.
+    DimensionsTooLarge { width: u32, height: u32, bytes: usize },
 }
 
 impl std::fmt::Display for ParseError {
@@ -23,9 +30,12 @@ impl std::fmt::Display for ParseError {
             ParseError::FileTooSmall { expected, actual } => {
                 write!(f, "File too small: expected at least {} bytes, got {}", expected, actual)
             }
-            ParseError::SizeMismatch { expected, actual } => {
+            ParseError::SizeMismatch { expected, actual, location: None } => {
                 write!(f, "File size mismatch: expected {} bytes, got {}", expected, actual)
             }
+            ParseError::SizeMismatch { expected, actual, location: Some(location) } => {
+                write!(f, "File size mismatch: expected {} bytes, got {}; truncated at {}", expected, actual, location)
+            }
             ParseError::InvalidDimensions { width, height } => {
                 write!(f, "Invalid dimensions: {}x{}", width, height)
             }
@@ -35,12 +45,253 @@ impl std::fmt::Display for ParseError {
             ParseError::FrameCountMismatch { expected, actual } => {
                 write!(f, "Frame count mismatch: expected {}, got {}", expected, actual)
             }
+            ParseError::UnsupportedEncoding { width, height, actual } => {
+                write!(f, "Unsupported header encoding: {}x{} does not fit {} bytes in either byte order", width, height, actual)
+            }
+            ParseError::DimensionsTooLarge { width, height, bytes } => {
+                write!(f, "Dimensions too large: {}x{} ({} bytes) exceeds parse limits", width, height, bytes)
+            }
         }
     }
 }
 
 impl std::error::Error for ParseError {}
 
+impl From<CFrameError> for ParseError {
+    fn from(err: CFrameError) -> Self {
+        match err {
+            CFrameError::DimensionsOverflow { width, height } => ParseError::InvalidDimensions {width, height},
+            CFrameError::CharCountMismatch { expected, actual } => ParseError::FrameCountMismatch {expected, actual},
+            CFrameError::RgbLengthMismatch { expected, actual } | CFrameError::BackgroundLengthMismatch { expected, actual } => ParseError::SizeMismatch {expected, actual, location: None},
+        }
+    }
+}
+
+impl ParseError {
+    /// Where in the input the error was detected, when known.
+    pub fn location(&self) -> Option<ParseLocation> {
+        match self {
+            ParseError::FileTooSmall { actual, .. } => Some(ParseLocation::header(*actual)),
+            ParseError::SizeMismatch { location, .. } => *location,
+            ParseError::InvalidFrameCount { .. } | ParseError::UnsupportedEncoding { .. } | ParseError::DimensionsTooLarge { .. } => Some(ParseLocation::header(0)),
+            ParseError::InvalidDimensions { .. } | ParseError::FrameCountMismatch { .. } => None,
+        }
+    }
+
+    /// Hex dump of the bytes around [`location`](Self::location) in `data`.
+    ///
+    /// The byte at the error offset is bracketed; `<EOF>` marks an offset at
+    /// or past the end of the input. Returns `None` when the error has no
+    /// location.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::parse_cframe;
+    ///
+    /// let bytes = vec![1, 0, 0, 0, 2, 0, 0, 0, b'A', 255, 0, 0];
+    /// let err = parse_cframe(&bytes).unwrap_err();
+    /// assert_eq!(err.source_snippet(&bytes).unwrap(), "0x00000004: 02 00 00 00 41 ff 00 00 <EOF>");
+    /// ```
+    pub fn source_snippet(&self, data: &[u8]) -> Option<String> {
+        const CONTEXT: usize = 8;
+        let offset = self.location()?.offset;
+        let start = offset.saturating_sub(CONTEXT).min(data.len());
+        let end = offset.saturating_add(CONTEXT + 1).min(data.len());
+
+        let mut parts = Vec::with_capacity(end - start + 1);
+        for (i, byte) in data[start..end].iter().enumerate() {
+            if start + i == offset {
+                parts.push(format!("[{:02x}]", byte));
+            } else {
+                parts.push(format!("{:02x}", byte));
+            }
+        }
+        if offset >= data.len() {
+            parts.push("<EOF>".to_string());
+        }
+        Some(format!("0x{:08x}: {}", start, parts.join(" ")))
+    }
+}
+
+/// Which part of a `.cframe` file or packed blob an error points into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseRegion {
+    /// The fixed-size `u32` header
+    Header,
+    /// Foreground `(char, r, g, b)` cells
+    Pixels,
+}
+
+/// Byte offset and structured context of a parse error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseLocation {
+    /// Byte offset into the input
+    pub offset: usize,
+    /// Region of the format the offset falls into
+    pub region: ParseRegion,
+    /// Frame index, for packed blobs
+    pub frame: Option<usize>,
+    /// Cell row within the frame, for the pixel region
+    pub row: Option<usize>,
+}
+
+impl ParseLocation {
+    fn header(offset: usize) -> Self {
+        Self {offset, region: ParseRegion::Header, frame: None, row: None}
+    }
+}
+
+impl std::fmt::Display for ParseLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "byte {}", self.offset)?;
+        match self.region {
+            ParseRegion::Header => write!(f, " (header)"),
+            ParseRegion::Pixels => {
+                write!(f, " (pixels")?;
+                if let Some(frame) = self.frame {
+                    write!(f, ", frame {}", frame)?;
+                }
+                if let Some(row) = self.row {
+                    write!(f, ", row {}", row)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Limits applied while parsing, for untrusted input.
+///
+/// The default has no limits, matching [`parse_cframe`] and
+/// [`parse_packed_cframes`]. Web viewers parsing user uploads should use
+/// [`ParseOptions::untrusted`] or their own limits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Maximum frame width in cells
+    pub max_width: Option<u32>,
+    /// Maximum frame height in cells
+    pub max_height: Option<u32>,
+    /// Maximum input size in bytes
+    pub max_bytes: Option<usize>,
+}
+
+impl ParseOptions {
+    /// Conservative limits for untrusted uploads: 1024×1024 cells, 64 MiB.
+    pub fn untrusted() -> Self {
+        Self {max_width: Some(1024), max_height: Some(1024), max_bytes: Some(64 * 1024 * 1024)}
+    }
+
+    fn check(&self, width: u32, height: u32, bytes: usize) -> Result<(), ParseError> {
+        let too_wide = self.max_width.map(|max| width > max).unwrap_or(false);
+        let too_tall = self.max_height.map(|max| height > max).unwrap_or(false);
+        let too_big = self.max_bytes.map(|max| bytes > max).unwrap_or(false);
+        if too_wide || too_tall || too_big {
+            return Err(ParseError::DimensionsTooLarge {width, height, bytes});
+        }
+        Ok(())
+    }
+}
+
+/// Byte order of the `u32` header fields of a `.cframe` file or packed blob.
+///
+/// The canonical format is little-endian, and [`encode_cframe`] always
+/// writes it. Some third-party producers write big-endian headers; parsers
+/// detect those by checking which interpretation fits the payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+fn read_fields<const N: usize>(data: &[u8], order: ByteOrder) -> [u32; N] {
+    let mut fields = [0u32; N];
+    for (i, field) in fields.iter_mut().enumerate() {
+        let bytes = [data[i * 4], data[i * 4 + 1], data[i * 4 + 2], data[i * 4 + 3]];
+        *field = match order {
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        };
+    }
+    fields
+}
+
+/// Header size plus `4 bytes × product(fields)`, or `None` on overflow.
+fn required_size<const N: usize>(fields: &[u32; N]) -> Option<usize> {
+    fields.iter().try_fold(4usize, |acc, &field| acc.checked_mul(field as usize))?.checked_add(N * 4)
+}
+
+/// Read an `N`-field header, picking the byte order whose required size fits `data`.
+///
+/// Callers must have checked that `data` holds the header and that no field
+/// is zero (zero-ness doesn't depend on byte order). Returns the fields, the
+/// detected order, and the size of the header plus foreground body.
+fn resolve_header<const N: usize>(data: &[u8]) -> Result<([u32; N], ByteOrder, usize), ParseError> {
+    let le = read_fields::<N>(data, ByteOrder::LittleEndian);
+    let le_size = required_size(&le);
+    if let Some(size) = le_size.filter(|&size| data.len() >= size) {
+        return Ok((le, ByteOrder::LittleEndian, size));
+    }
+
+    let be = read_fields::<N>(data, ByteOrder::BigEndian);
+    let be_size = required_size(&be);
+    if let Some(size) = be_size.filter(|&size| data.len() >= size) {
+        return Ok((be, ByteOrder::BigEndian, size));
+    }
+
+    match (le_size, be_size) {
+        // The little-endian reading is the more plausible one: plain truncation.
+        (Some(le_size), Some(be_size)) if le_size <= be_size => Err(truncated(&le, le_size, data.len())),
+        (Some(le_size), None) => Err(truncated(&le, le_size, data.len())),
+        _ => Err(ParseError::UnsupportedEncoding {width: le[N - 2], height: le[N - 1], actual: data.len()}),
+    }
+}
+
+/// Size mismatch for a body that ends at `actual`, located by frame and row.
+fn truncated<const N: usize>(fields: &[u32; N], expected: usize, actual: usize) -> ParseError {
+    let body = actual.saturating_sub(N * 4);
+    let row_size = fields[N - 2] as usize * 4;
+    let frame_size = row_size * fields[N - 1] as usize;
+    let (frame, row) = match N {
+        3 => (Some(body / frame_size), body % frame_size / row_size),
+        _ => (None, body / row_size),
+    };
+    let location = ParseLocation {offset: actual, region: ParseRegion::Pixels, frame, row: Some(row)};
+    ParseError::SizeMismatch {expected, actual, location: Some(location)}
+}
+
+/// Read and validate a `.cframe` header: `(width, height, byte order, body end)`.
+fn read_cframe_header(data: &[u8], options: &ParseOptions) -> Result<(u32, u32, ByteOrder, usize), ParseError> {
+    const HEADER_SIZE: usize = 8;
+
+    if data.len() < HEADER_SIZE {
+        return Err(ParseError::FileTooSmall {expected: HEADER_SIZE, actual: data.len()});
+    }
+
+    let [width, height] = read_fields::<2>(data, ByteOrder::LittleEndian);
+    if width == 0 || height == 0 {
+        return Err(ParseError::InvalidDimensions { width, height });
+    }
+
+    let ([width, height], order, body_end) = resolve_header::<2>(data)?;
+    options.check(width, height, data.len())?;
+    Ok((width, height, order, body_end))
+}
+
+/// Detect the header byte order of a `.cframe` file.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{detect_byte_order, ByteOrder};
+///
+/// let bytes = vec![0, 0, 0, 1, 0, 0, 0, 1, b'A', 255, 0, 0];
+/// assert_eq!(detect_byte_order(&bytes), Ok(ByteOrder::BigEndian));
+/// ```
+pub fn detect_byte_order(data: &[u8]) -> Result<ByteOrder, ParseError> {
+    read_cframe_header(data, &ParseOptions::default()).map(|(_, _, order, _)| order)
+}
+
 /// Parse a .cframe binary file into CFrameData.
 ///
 /// ## Format
@@ -56,6 +307,10 @@ impl std::error::Error for ParseError {}
 ///
 /// Total size: 8 + (width × height × 4) bytes
 ///
+/// Big-endian headers are detected automatically (see [`ByteOrder`]); a
+/// header that fits in neither byte order yields
+/// [`ParseError::UnsupportedEncoding`] instead of a huge allocation.
+///
 /// ## Example
 ///
 /// ```rust
@@ -75,25 +330,27 @@ impl std::error::Error for ParseError {}
 /// assert_eq!(cframe.rgb, vec![255, 0, 0, 0, 255, 0]);
 /// ```
 pub fn parse_cframe(data: &[u8]) -> Result<CFrameData, ParseError> {
-    const HEADER_SIZE: usize = 8;
-
-    if data.len() < HEADER_SIZE {
-        return Err(ParseError::FileTooSmall {expected: HEADER_SIZE, actual: data.len()});
-    }
-
-    let width = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
-    let height = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    parse_cframe_with(data, &ParseOptions::default())
+}
 
-    if width == 0 || height == 0 {
-        return Err(ParseError::InvalidDimensions { width, height });
-    }
+/// Parse a .cframe binary file, rejecting headers beyond `options` limits
+/// with [`ParseError::DimensionsTooLarge`] before anything is allocated.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{parse_cframe_with, ParseError, ParseOptions};
+///
+/// let mut bytes = vec![0, 8, 0, 0, 1, 0, 0, 0]; // 2048x1 header
+/// bytes.resize(8 + 2048 * 4, 0);
+/// let result = parse_cframe_with(&bytes, &ParseOptions::untrusted());
+/// assert!(matches!(result, Err(ParseError::DimensionsTooLarge { width: 2048, .. })));
+/// ```
+pub fn parse_cframe_with(data: &[u8], options: &ParseOptions) -> Result<CFrameData, ParseError> {
+    const HEADER_SIZE: usize = 8;
 
+    let (width, height, _, expected_size) = read_cframe_header(data, options)?;
     let pixel_count = width as usize * height as usize;
-    let expected_size = HEADER_SIZE + pixel_count * 4;
-
-    if data.len() < expected_size {
-        return Err(ParseError::SizeMismatch {expected: expected_size, actual: data.len()});
-    }
 
     let mut chars = Vec::with_capacity(pixel_count);
     let mut rgb = Vec::with_capacity(pixel_count * 3);
@@ -113,14 +370,14 @@ pub fn parse_cframe(data: &[u8]) -> Result<CFrameData, ParseError> {
         // New format: leading flag byte announces the bg payload.
         if trailing > trailing_bg_size && (data[ext_offset] & CFRAME_EXT_FLAG_HAS_BG) != 0 {
             let bg_start = ext_offset + 1;
-            return Ok(CFrameData::with_background(width, height, chars, rgb, data[bg_start..bg_start + trailing_bg_size].to_vec()));
+            return Ok(CFrameData::try_with_background(width, height, chars, rgb, data[bg_start..bg_start + trailing_bg_size].to_vec())?);
         }
         // Legacy bg-augmented format: exact bg-sized trailing block, no flag byte.
         if trailing == trailing_bg_size {
-            return Ok(CFrameData::with_background(width, height, chars, rgb, data[ext_offset..ext_offset + trailing_bg_size].to_vec()));
+            return Ok(CFrameData::try_with_background(width, height, chars, rgb, data[ext_offset..ext_offset + trailing_bg_size].to_vec())?);
         }
     }
-    Ok(CFrameData::new(width, height, chars, rgb))
+    Ok(CFrameData::try_new(width, height, chars, rgb)?)
 }
 
 /// Trailing extension flag bits used after the legacy `8 + w*h*4` body of a
@@ -145,17 +402,8 @@ pub fn encode_cframe(frame: &CFrameData) -> Result<Vec<u8>, ParseError> {
     if frame.width == 0 || frame.height == 0 {
         return Err(ParseError::InvalidDimensions {width: frame.width, height: frame.height});
     }
-    if frame.chars.len() != pixel_count {
-        return Err(ParseError::FrameCountMismatch {expected: pixel_count, actual: frame.chars.len()});
-    }
-    if frame.rgb.len() != pixel_count * 3 {
-        return Err(ParseError::SizeMismatch {expected: pixel_count * 3, actual: frame.rgb.len()});
-    }
-    let bg_payload = match frame.bg_rgb.as_ref() {
-        Some(bg) if bg.len() == pixel_count * 3 => Some(bg.as_slice()),
-        Some(bg) => return Err(ParseError::SizeMismatch {expected: pixel_count * 3, actual: bg.len()}),
-        None => None,
-    };
+    frame.validate()?;
+    let bg_payload = frame.bg_rgb.as_deref();
 
     let mut out = Vec::with_capacity(8 + pixel_count * 4 + bg_payload.map(|bg| 1 + bg.len()).unwrap_or(0));
     out.extend_from_slice(&frame.width.to_le_bytes());
@@ -190,19 +438,7 @@ pub fn encode_cframe(frame: &CFrameData) -> Result<Vec<u8>, ParseError> {
 /// [`encode_cframe`] instead, since the bg payload size is tied to the cell
 /// count.
 pub fn split_cframe_extension(data: &[u8]) -> Result<(&[u8], &[u8]), ParseError> {
-    const HEADER_SIZE: usize = 8;
-    if data.len() < HEADER_SIZE {
-        return Err(ParseError::FileTooSmall {expected: HEADER_SIZE, actual: data.len()});
-    }
-    let width = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
-    let height = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
-    if width == 0 || height == 0 {
-        return Err(ParseError::InvalidDimensions {width, height});
-    }
-    let legacy_size = HEADER_SIZE + width as usize * height as usize * 4;
-    if data.len() < legacy_size {
-        return Err(ParseError::SizeMismatch {expected: legacy_size, actual: data.len()});
-    }
+    let (_, _, _, legacy_size) = read_cframe_header(data, &ParseOptions::default())?;
     Ok(data.split_at(legacy_size))
 }
 
@@ -231,23 +467,9 @@ pub fn split_cframe_extension(data: &[u8]) -> Result<(&[u8], &[u8]), ParseError>
 pub fn parse_cframe_text(data: &[u8]) -> Result<String, ParseError> {
     const HEADER_SIZE: usize = 8;
 
-    if data.len() < HEADER_SIZE {
-        return Err(ParseError::FileTooSmall {expected: HEADER_SIZE, actual: data.len()});
-    }
-
-    let width = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
-    let height = u32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
-
-    if width == 0 || height == 0 {
-        return Err(ParseError::InvalidDimensions {width: width as u32, height: height as u32});
-    }
-
+    let (width, height, _, _) = read_cframe_header(data, &ParseOptions::default())?;
+    let (width, height) = (width as usize, height as usize);
     let pixel_count = width * height;
-    let expected_size = HEADER_SIZE + pixel_count * 4;
-
-    if data.len() < expected_size {
-        return Err(ParseError::SizeMismatch {expected: expected_size, actual: data.len()});
-    }
 
     let mut text = String::with_capacity(pixel_count + height);
 
@@ -283,19 +505,24 @@ pub fn parse_cframe_text(data: &[u8]) -> Result<String, ParseError> {
 ///     - `width × height × 3` bytes: background RGB
 ///
 /// Foreground-only blobs remain `12 + (frame_count × width × height × 4)`
-/// bytes. The parser also accepts legacy packed background blobs where each
+/// bytes. Big-endian headers are detected the same way as for
+/// [`parse_cframe`]. The parser also accepts legacy packed background blobs where each
 /// frame stores the background RGB block directly after its foreground body
 /// without the flag byte.
 pub fn parse_packed_cframes(data: &[u8]) -> Result<PackedCFrameBlob, ParseError> {
+    parse_packed_cframes_with(data, &ParseOptions::default())
+}
+
+/// Parse a packed multi-frame blob, enforcing `options` limits on the shared
+/// frame dimensions and the blob size.
+pub fn parse_packed_cframes_with(data: &[u8], options: &ParseOptions) -> Result<PackedCFrameBlob, ParseError> {
     const HEADER_SIZE: usize = 12;
 
     if data.len() < HEADER_SIZE {
         return Err(ParseError::FileTooSmall {expected: HEADER_SIZE, actual: data.len()});
     }
 
-    let frame_count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
-    let width = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
-    let height = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+    let [frame_count, width, height] = read_fields::<3>(data, ByteOrder::LittleEndian);
 
     if frame_count == 0 {
         return Err(ParseError::InvalidFrameCount { count: frame_count });
@@ -305,15 +532,12 @@ pub fn parse_packed_cframes(data: &[u8]) -> Result<PackedCFrameBlob, ParseError>
         return Err(ParseError::InvalidDimensions { width, height });
     }
 
+    let ([frame_count, width, height], _, expected_size) = resolve_header::<3>(data)?;
+    options.check(width, height, data.len())?;
     let cell_count = width as usize * height as usize;
     let frame_size = cell_count * 4;
     let background_size = cell_count * 3;
     let frame_count_usize = frame_count as usize;
-    let expected_size = HEADER_SIZE + frame_count_usize * frame_size;
-
-    if data.len() < expected_size {
-        return Err(ParseError::SizeMismatch {expected: expected_size, actual: data.len()});
-    }
 
     let payload = &data[HEADER_SIZE..];
     let flagged_stride = frame_size + 1 + background_size;
@@ -462,6 +686,44 @@ mod tests {
         assert_eq!(result.bg_rgb.as_deref(), Some(&[1, 2, 3, 4, 5, 6][..]));
     }
 
+    #[test]
+    fn test_parse_cframe_big_endian_header() {
+        let bytes = vec![
+            0, 0, 0, 2, // width = 2 (big-endian)
+            0, 0, 0, 1, // height = 1 (big-endian)
+            b'A', 255, 0, 0,
+            b'B', 0, 255, 0,
+        ];
+
+        assert_eq!(detect_byte_order(&bytes), Ok(ByteOrder::BigEndian));
+        let result = parse_cframe(&bytes).unwrap();
+        assert_eq!((result.width, result.height), (2, 1));
+        assert_eq!(result.chars, vec![b'A', b'B']);
+        assert_eq!(parse_cframe_text(&bytes).unwrap(), "AB\n");
+    }
+
+    #[test]
+    fn test_parse_cframe_unsupported_encoding() {
+        // Both byte orders give absurd dimensions for a 12-byte file.
+        let bytes = vec![0, 0, 2, 0, 0, 0, 1, 0, b'A', 255, 0, 0];
+        let result = parse_cframe(&bytes);
+        assert!(matches!(result, Err(ParseError::UnsupportedEncoding {width: 131072, height: 65536, actual: 12})));
+    }
+
+    #[test]
+    fn test_parse_packed_cframes_big_endian_header() {
+        let bytes = vec![
+            0, 0, 0, 2, // frame count = 2
+            0, 0, 0, 1, // width = 1
+            0, 0, 0, 1, // height = 1
+            b'A', 255, 0, 0, b'B', 0, 255, 0,
+        ];
+
+        let blob = parse_packed_cframes(&bytes).unwrap();
+        assert_eq!((blob.frame_count, blob.width, blob.height), (2, 1, 1));
+        assert_eq!(blob.decode_frame(1).unwrap().chars, vec![b'B']);
+    }
+
     #[test]
     fn test_parse_cframe_too_small() {
         let bytes = vec![1, 2, 3]; // Less than 8 bytes
@@ -480,6 +742,64 @@ mod tests {
         assert!(matches!(result, Err(ParseError::SizeMismatch { .. })));
     }
 
+    #[test]
+    fn test_parse_options_limits() {
+        let bytes = vec![3, 0, 0, 0, 1, 0, 0, 0, b'A', 0, 0, 0, b'B', 0, 0, 0, b'C', 0, 0, 0];
+        let narrow = ParseOptions {max_width: Some(2), ..ParseOptions::default()};
+        assert_eq!(parse_cframe_with(&bytes, &narrow).unwrap_err(), ParseError::DimensionsTooLarge {width: 3, height: 1, bytes: 20});
+
+        let small = ParseOptions {max_bytes: Some(16), ..ParseOptions::default()};
+        assert!(matches!(parse_cframe_with(&bytes, &small), Err(ParseError::DimensionsTooLarge { .. })));
+
+        assert!(parse_cframe_with(&bytes, &ParseOptions::untrusted()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_options_limits_packed() {
+        let mut bytes = vec![1, 0, 0, 0, 1, 0, 0, 0, 1, 4, 0, 0]; // 1x1025
+        bytes.resize(12 + 1025 * 4, 0);
+        let result = parse_packed_cframes_with(&bytes, &ParseOptions::untrusted());
+        assert!(matches!(result, Err(ParseError::DimensionsTooLarge {height: 1025, ..})));
+    }
+
+    #[test]
+    fn test_parse_options_limits_big_endian() {
+        let bytes = vec![0, 0, 0, 2, 0, 0, 0, 1, b'A', 255, 0, 0, b'B', 0, 255, 0];
+        assert_eq!(parse_cframe_with(&bytes, &ParseOptions::untrusted()).unwrap().chars, b"AB");
+
+        let packed = vec![0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, b'A', 255, 0, 0];
+        assert_eq!(parse_packed_cframes_with(&packed, &ParseOptions::untrusted()).unwrap().frame_count, 1);
+    }
+
+    #[test]
+    fn test_size_mismatch_location() {
+        let mut bytes = vec![2, 0, 0, 0, 3, 0, 0, 0];
+        bytes.extend_from_slice(&[b'A', 0, 0, 0].repeat(3)); // row 0 and half of row 1
+        let err = parse_cframe(&bytes).unwrap_err();
+
+        let location = err.location().unwrap();
+        assert_eq!(location, ParseLocation {offset: 20, region: ParseRegion::Pixels, frame: None, row: Some(1)});
+        assert_eq!(err.to_string(), "File size mismatch: expected 32 bytes, got 20; truncated at byte 20 (pixels, row 1)");
+        assert!(err.source_snippet(&bytes).unwrap().ends_with("41 00 00 00 <EOF>"));
+    }
+
+    #[test]
+    fn test_packed_size_mismatch_location() {
+        let mut bytes = vec![3, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0];
+        bytes.extend_from_slice(&[b'A', 0, 0, 0].repeat(3)); // frame 0 and row 0 of frame 1
+        let err = parse_packed_cframes(&bytes).unwrap_err();
+
+        let location = err.location().unwrap();
+        assert_eq!((location.frame, location.row), (Some(1), Some(1)));
+    }
+
+    #[test]
+    fn test_header_errors_are_located() {
+        let err = parse_cframe(&[1, 0, 0]).unwrap_err();
+        assert_eq!(err.location().map(|l| l.region), Some(ParseRegion::Header));
+        assert_eq!(err.source_snippet(&[1, 0, 0]).unwrap(), "0x00000000: 01 00 00 <EOF>");
+    }
+
     #[test]
     fn test_parse_cframe_text() {
         let bytes = vec![
@@ -1,20 +1,24 @@
 //! High-level frame player that bundles animation, sizing, and rendering.
 //!
 //! [`FramePlayer`] is the main orchestrator that consumers embed. It owns
-//! the loaded frames, animation controller, render config, and (on web)
-//! the canvas cache. Consumers only need to supply platform-specific I/O
+//! the loaded frames (or a [`FrameSource`] it reads them from), animation
+//! controller, render config, and (on web) the canvas cache. Consumers only need to supply platform-specific I/O
 //! via [`FrameDataProvider`](crate::FrameDataProvider) and their UI
 //! framework's timer / template glue.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+
 use crate::{
-    load_text_frames, render::RenderConfig, AnimationController, CFrameData, FontSizing, Frame,
-    FrameDataProvider, FrameFile, LoadResult, ParseError,
+    blend_cframes_in, load_poster_frame, load_text_frames, render::{RenderConfig, RenderLayer}, AnimationController, CFrameData, CancellationToken, ColorSpace,
+    FontSizing, Frame, FrameBlend, FrameDataProvider, FrameFile, FrameSource, IndexedCFrameData, LoadOptions, LoadResult, LoopMode, OverlayContext, OverlayScene, PaletteQuantizer, ParseError,
+    PlaybackDirection, Prefetcher, RetryPolicy,
 };
 
 /// A high-level frame player that bundles frame data, animation control,
 /// font sizing, and (on web) a canvas cache into one struct.
 pub struct FramePlayer {
-    frames: Vec<Frame>,
+    frames: PlayerFrames,
     frame_files: Vec<FrameFile>,
     controller: AnimationController,
     config: RenderConfig,
@@ -23,20 +27,204 @@ pub struct FramePlayer {
     /// `Ok(false)` so the consumer draws the text fallback.  Automatically
     /// set to `true` by [`load_colors`](Self::load_colors) on success.
     color_ready: bool,
+    /// Optional HUD / OSD drawn on top of every rendered frame.
+    overlay: Option<OverlayScene>,
+    /// Lazily computed [`Frame::checksum`] per frame, for static-frame detection.
+    frame_hashes: Vec<Option<u64>>,
+    /// What is currently on screen, if known.
+    displayed: Option<DisplayedFrame>,
+    /// Skip redrawing when the next frame is identical to the displayed one.
+    skip_static_frames: bool,
+    /// Number of draws skipped because the frame was unchanged.
+    skipped_draws: u64,
+    /// Set while only a poster frame is loaded (see [`load_poster`](Self::load_poster)).
+    poster: Option<PosterState>,
+    /// Blend adjacent colour frames for in-between ticks (see
+    /// [`set_interpolation`](Self::set_interpolation)).
+    interpolation: bool,
+    /// Space interpolated colours are mixed in.
+    blend_space: ColorSpace,
+    /// Cancelled whenever the frames are replaced, stopping loads that
+    /// target the previous frames.
+    cancel: CancellationToken,
+    /// Retry behaviour of the player's loads.
+    retry: RetryPolicy,
+    /// Store raw colour bytes in [`load_colors`](Self::load_colors) and
+    /// decode them on first render.
+    lazy_colors: bool,
+    /// Raw `.cframe` bytes per frame, not decoded yet.
+    pending_colors: Vec<Option<Vec<u8>>>,
+    /// Store colour frames palette-indexed, quantizing frames with more
+    /// than 256 colours with this quantizer.
+    indexed_colors: Option<PaletteQuantizer>,
     #[cfg(feature = "web")]
     cache: crate::render::web::FrameCanvasCache,
 }
 
+/// The player's frames, and colour stored through the player that is not
+/// kept on an owned frame.
+struct PlayerFrames {
+    store: FrameStore,
+    /// Palette-indexed colour (see
+    /// [`set_indexed_colors`](FramePlayer::set_indexed_colors)), and colour
+    /// set for frames of a source, which is never modified
+    colors: HashMap<usize, StoredColor>,
+}
+
+/// Where the player's frames live.
+enum FrameStore {
+    /// Frames loaded or set through the player, editable in place
+    Owned(Vec<Frame>),
+    /// Frames read on demand from a caller-supplied source
+    Source(Box<dyn FrameSource>),
+}
+
+/// Colour stored beside the frames.
+enum StoredColor {
+    Full(CFrameData),
+    Indexed(IndexedCFrameData),
+}
+
+impl StoredColor {
+    fn cframe(&self) -> Cow<'_, CFrameData> {
+        match self {
+            StoredColor::Full(cframe) => Cow::Borrowed(cframe),
+            StoredColor::Indexed(indexed) => Cow::Owned(indexed.decode()),
+        }
+    }
+}
+
+impl From<Vec<Frame>> for PlayerFrames {
+    fn from(frames: Vec<Frame>) -> Self {
+        FrameStore::Owned(frames).into()
+    }
+}
+
+impl From<FrameStore> for PlayerFrames {
+    fn from(store: FrameStore) -> Self {
+        Self {store, colors: HashMap::new()}
+    }
+}
+
+impl FrameSource for FrameStore {
+    fn len(&self) -> usize {
+        match self {
+            FrameStore::Owned(frames) => frames.len(),
+            FrameStore::Source(source) => source.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        match self {
+            FrameStore::Owned(frames) => FrameSource::get(frames, index),
+            FrameStore::Source(source) => source.get(index),
+        }
+    }
+
+    fn cframe(&self, index: usize) -> Option<Cow<'_, CFrameData>> {
+        match self {
+            FrameStore::Owned(frames) => frames.cframe(index),
+            FrameStore::Source(source) => source.cframe(index),
+        }
+    }
+
+    fn is_ready(&self, index: usize) -> bool {
+        match self {
+            FrameStore::Owned(frames) => index < frames.len(),
+            FrameStore::Source(source) => source.is_ready(index),
+        }
+    }
+
+    fn has_color(&self, index: usize) -> bool {
+        match self {
+            FrameStore::Owned(frames) => frames.has_color(index),
+            FrameStore::Source(source) => source.has_color(index),
+        }
+    }
+
+    fn dimensions(&self, index: usize) -> Option<(usize, usize)> {
+        match self {
+            FrameStore::Owned(frames) => frames.dimensions(index),
+            FrameStore::Source(source) => source.dimensions(index),
+        }
+    }
+}
+
+impl FrameSource for PlayerFrames {
+    fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        let frame = self.store.get(index)?;
+        match self.colors.get(&index) {
+            Some(color) => Some(Cow::Owned(Frame {cframe: Some(color.cframe().into_owned()), ..frame.into_owned()})),
+            None => Some(frame),
+        }
+    }
+
+    fn cframe(&self, index: usize) -> Option<Cow<'_, CFrameData>> {
+        match self.colors.get(&index) {
+            Some(color) => Some(color.cframe()),
+            None => self.store.cframe(index),
+        }
+    }
+
+    fn is_ready(&self, index: usize) -> bool {
+        self.store.is_ready(index)
+    }
+
+    fn has_color(&self, index: usize) -> bool {
+        self.colors.contains_key(&index) || self.store.has_color(index)
+    }
+
+    fn dimensions(&self, index: usize) -> Option<(usize, usize)> {
+        self.store.dimensions(index)
+    }
+}
+
+/// Identity of the pixels on screen: frame content, render settings, and
+/// whether colour or the text fallback was drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DisplayedFrame {
+    hash: u64,
+    render_key: u64,
+    color: bool,
+}
+
+/// Deferred-loading state of a player showing only its poster frame.
+#[derive(Clone, Debug)]
+struct PosterState {
+    directory: String,
+    /// Index of the poster within the full animation
+    index: usize,
+    /// `play()` was called before the remaining frames were loaded
+    play_requested: bool,
+}
+
 impl FramePlayer {
     /// Create a new, empty player at the given FPS.
     pub fn new(fps: u32) -> Self {
         Self {
-            frames: Vec::new(),
+            frames: Vec::new().into(),
             frame_files: Vec::new(),
             controller: AnimationController::new(fps),
             config: RenderConfig::default(),
             sizing: FontSizing::default(),
             color_ready: false,
+            overlay: None,
+            frame_hashes: Vec::new(),
+            displayed: None,
+            skip_static_frames: true,
+            skipped_draws: 0,
+            poster: None,
+            interpolation: false,
+            blend_space: ColorSpace::Srgb,
+            cancel: CancellationToken::new(),
+            retry: RetryPolicy::default(),
+            lazy_colors: false,
+            pending_colors: Vec::new(),
+            indexed_colors: None,
             #[cfg(feature = "web")]
             cache: crate::render::web::FrameCanvasCache::default(),
         }
@@ -50,28 +238,169 @@ impl FramePlayer {
     /// (text-only). Call [`frame_files`] to get the list needed for
     /// Phase 2 color loading.
     pub async fn load<P: FrameDataProvider>(&mut self, provider: &P, directory: &str) -> LoadResult<()> {
-        let (frames, frame_files) = load_text_frames(provider, directory).await?;
+        self.cancel_loading();
+        let (frames, frame_files) = load_text_frames(provider, directory, &self.load_options(), |_| {}).await?;
+        self.poster = None;
         self.controller.set_frame_count(frames.len());
         #[cfg(feature = "web")]
         {
             self.cache.resize(frames.len());
             self.cache.invalidate_all();
         }
-        self.frames = frames;
+        self.frames = frames.into();
         self.frame_files = frame_files;
         self.color_ready = false;
+        self.pending_colors.clear();
+        self.reset_frame_hashes();
+        self.sync_frame_timing();
+        Ok(())
+    }
+
+    /// Poster mode: load and show only frame `index` of `directory`.
+    ///
+    /// The rest of the animation is not fetched until the first
+    /// [`play`](Self::play), which lets pages embed many animations without
+    /// loading them all eagerly. While the poster is shown, `play()` only
+    /// records the request; the consumer then calls
+    /// [`load_deferred`](Self::load_deferred), which loads every text frame,
+    /// seeks to the poster, and starts playback.
+    pub async fn load_poster<P: FrameDataProvider>(&mut self, provider: &P, directory: &str, index: usize) -> LoadResult<()> {
+        self.cancel_loading();
+        let (frame, index, frame_files) = load_poster_frame(provider, directory, index).await?;
+        self.color_ready = frame.has_color();
+        self.frames = vec![frame].into();
+        self.frame_files = frame_files;
+        self.controller.reset();
+        self.controller.set_frame_count(1);
+        self.pending_colors.clear();
+        self.reset_frame_hashes();
+        self.sync_frame_timing();
+        #[cfg(feature = "web")]
+        {
+            self.cache.resize(1);
+            self.cache.invalidate_all();
+        }
+        self.poster = Some(PosterState {directory: directory.to_string(), index, play_requested: false});
+        Ok(())
+    }
+
+    /// Stop any in-flight [`load_colors`](Self::load_colors) for the current
+    /// frames. Called automatically whenever the frames are replaced.
+    pub fn cancel_loading(&mut self) {
+        self.cancel.cancel();
+        self.cancel = CancellationToken::new();
+    }
+
+    /// Token cancelled when the current frames are replaced, for hosts
+    /// running their own loaders against this player.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
+
+    /// Retry behaviour of [`load`](Self::load) and
+    /// [`load_colors`](Self::load_colors).
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    /// Set how [`load`](Self::load) and [`load_colors`](Self::load_colors)
+    /// retry failed reads.
+    pub fn set_retry_policy(&mut self, retry: RetryPolicy) {
+        self.retry = retry;
+    }
+
+    /// Cancellation token and retry policy for loads of the current frames.
+    pub fn load_options(&self) -> LoadOptions {
+        LoadOptions {cancel: self.cancel.clone(), retry: self.retry, ..LoadOptions::default()}
+    }
+
+    /// Whether only the poster frame is loaded.
+    pub fn is_poster(&self) -> bool {
+        self.poster.is_some()
+    }
+
+    /// Whether [`play`](Self::play) was called on a poster and the remaining
+    /// frames should now be loaded with [`load_deferred`](Self::load_deferred).
+    pub fn needs_deferred_load(&self) -> bool {
+        self.poster.as_ref().map(|poster| poster.play_requested).unwrap_or(false)
+    }
+
+    /// Load the frames deferred by [`load_poster`](Self::load_poster).
+    ///
+    /// The poster keeps its colour data; colour for the other frames is
+    /// loaded as usual through Phase 2. Playback starts at the poster frame
+    /// if `play()` was requested in the meantime. No-op without a poster.
+    pub async fn load_deferred<P: FrameDataProvider>(&mut self, provider: &P) -> LoadResult<()> {
+        let Some(poster) = self.poster.clone() else { return Ok(()); };
+        let poster_color = self.frames.cframe(0).map(Cow::into_owned);
+        self.load(provider, &poster.directory).await?;
+        if let Some(cframe) = poster_color {
+            self.set_frame_color(poster.index, cframe);
+        }
+        self.controller.set_current_frame(poster.index);
+        if poster.play_requested {
+            self.controller.play();
+        }
         Ok(())
     }
 
+    /// Index of the frame shown as the poster, if any.
+    pub fn poster_index(&self) -> Option<usize> {
+        self.poster.as_ref().map(|poster| poster.index)
+    }
+
     /// Replace the current contents with in-memory text frames.
     ///
     /// Useful when the caller already fetched / generated all frame text.
     pub fn set_text_frames(&mut self, contents: Vec<String>) {
-        self.frames = contents.into_iter().map(Frame::text_only).collect();
-        self.frame_files.clear();
-        self.color_ready = false;
+        self.set_frames(contents.into_iter().map(Frame::text_only).collect());
+    }
+
+    /// Replace the current contents with in-memory frames.
+    ///
+    /// Frames whose [`FrameMeta`](crate::FrameMeta) carries timing switch the
+    /// controller to variable timing; see [`current_interval_ms`](Self::current_interval_ms).
+    pub fn set_frames(&mut self, frames: Vec<Frame>) {
+        self.color_ready = !frames.is_empty() && frames.iter().all(Frame::has_color);
+        self.replace_frames(frames.into());
+    }
+
+    /// Play the frames of `source`, reading each one only when it is shown.
+    ///
+    /// Nothing is copied up front: packed blobs and atlases decode a frame
+    /// when it is rendered, and playback holds on frames the source doesn't
+    /// have [ready](FrameSource::is_ready) yet. Colour rendering is enabled
+    /// when the first frame has colour. Per-frame durations from frame
+    /// metadata are not applied; use
+    /// [`controller_mut`](Self::controller_mut) to set them. Colour stored
+    /// through the player (e.g. [`set_frame_color`](Self::set_frame_color))
+    /// is kept beside the source, which is never modified.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{FramePlayer, PackedCFrameBlob};
+    ///
+    /// let blob = PackedCFrameBlob::new(2, 1, 1, vec![b'a', 255, 0, 0, b'b', 0, 255, 0]);
+    /// let mut player = FramePlayer::new(10);
+    /// player.set_source(blob);
+    /// assert_eq!((player.frame_count(), player.color_ready()), (2, true));
+    /// assert_eq!(player.text_at(1).as_deref(), Some("b\n"));
+    /// ```
+    pub fn set_source<S: FrameSource + 'static>(&mut self, source: S) {
+        self.color_ready = source.cframe(0).is_some();
+        self.replace_frames(FrameStore::Source(Box::new(source)).into());
+    }
+
+    fn replace_frames(&mut self, frames: PlayerFrames) {
+        self.cancel_loading();
+        self.frames = frames;
+        self.poster = None;
         self.controller.reset();
         self.controller.set_frame_count(self.frames.len());
+        self.pending_colors.clear();
+        self.reset_frame_hashes();
+        self.sync_frame_timing();
         #[cfg(feature = "web")]
         {
             self.cache.resize(self.frames.len());
@@ -86,48 +415,167 @@ impl FramePlayer {
     pub fn load_packed_colors(&mut self, data: &[u8]) -> Result<(), ParseError> {
         let blob = crate::parse_packed_cframes(data)?;
         let frame_count = blob.len();
+        if !self.frames.is_empty() && self.frames.len() != frame_count {
+            return Err(ParseError::FrameCountMismatch {expected: self.frames.len(), actual: frame_count});
+        }
+        let cframes = (0..frame_count).map(|index| blob.decode_frame(index).expect("packed blob frame index should be valid")).collect::<Vec<_>>();
 
         if self.frames.is_empty() {
-            self.frames = (0..frame_count)
-                .map(|index| {
-                    let cframe = blob.decode_frame(index).expect("packed blob frame index should be valid");
-                    let text = cframe.to_text();
-                    Frame::with_color(text, cframe)
-                })
-                .collect();
+            self.cancel_loading();
+            self.frames = cframes.iter().map(|cframe| Frame::text_only(cframe.to_text())).collect::<Vec<_>>().into();
             self.frame_files.clear();
             self.controller.reset();
             self.controller.set_frame_count(self.frames.len());
+            self.sync_frame_timing();
             #[cfg(feature = "web")]
             self.cache.resize(self.frames.len());
-        } else if self.frames.len() != frame_count {
-            return Err(ParseError::FrameCountMismatch {expected: self.frames.len(), actual: frame_count});
-        } else {
-            for index in 0..frame_count {
-                let cframe = blob.decode_frame(index).expect("packed blob frame index should be valid");
-                self.frames[index].cframe = Some(cframe);
-            }
+        }
+        for (index, cframe) in cframes.into_iter().enumerate() {
+            self.set_frame_color(index, cframe);
         }
 
         self.color_ready = true;
+        self.pending_colors.clear();
+        self.reset_frame_hashes();
         #[cfg(feature = "web")]
         self.cache.invalidate_all();
         Ok(())
     }
 
+    /// Fetch colour for the frames `prefetcher` expects next, skipping
+    /// frames that already have colour, and (on web, once colour rendering
+    /// is enabled) pre-render them into the canvas cache. Call from the
+    /// playback timer when colour is decoded lazily. Returns the number of
+    /// frames requested.
+    pub async fn prefetch<P: FrameDataProvider>(player: &std::rc::Rc<std::cell::RefCell<Self>>, provider: &P, prefetcher: &mut Prefetcher, direction: PlaybackDirection) -> LoadResult<usize> {
+        let (frame_files, controller, cancel) = {
+            let this = player.borrow();
+            for index in prefetcher.upcoming(&this.controller, direction) {
+                if this.has_color_at(index) || this.pending_colors.get(index).is_some_and(Option::is_some) {
+                    prefetcher.mark_ready(index);
+                }
+            }
+            (this.frame_files.clone(), this.controller.clone(), this.cancellation_token())
+        };
+        prefetcher.fetch(provider, &frame_files, &controller, direction, |index, cframe| {
+            let Some(cframe) = cframe.filter(|_| !cancel.is_cancelled()) else { return; };
+            let mut this = player.borrow_mut();
+            this.set_frame_color(index, cframe);
+            #[cfg(feature = "web")]
+            if this.color_ready {
+                this.pre_cache_frame(index);
+            }
+        }).await
+    }
+
     /// The frame file list returned by Phase 1, needed for
     /// [`load_color_frames`](crate::load_color_frames).
     pub fn frame_files(&self) -> &[FrameFile] {
         &self.frame_files
     }
 
-    /// Phase 2 callback: store colour data for one frame.
+    /// Phase 2 callback: store colour data for one frame, palette-indexed
+    /// when [`set_indexed_colors`](Self::set_indexed_colors) is enabled.
     pub fn set_frame_color(&mut self, index: usize, cframe: CFrameData) {
+        if index >= self.frames.len() {
+            return;
+        }
+        let indexed = self.indexed_colors.as_ref().and_then(|quantizer| IndexedCFrameData::from_cframe(&cframe).or_else(|| IndexedCFrameData::quantize(&cframe, quantizer)));
+        let PlayerFrames {store, colors} = &mut self.frames;
+        match (indexed, store) {
+            (Some(indexed), store) => {
+                if let FrameStore::Owned(frames) = store {
+                    frames[index].cframe = None;
+                }
+                colors.insert(index, StoredColor::Indexed(indexed));
+            }
+            (None, FrameStore::Owned(frames)) => {
+                frames[index].cframe = Some(cframe);
+                colors.remove(&index);
+            }
+            (None, FrameStore::Source(_)) => {
+                colors.insert(index, StoredColor::Full(cframe));
+            }
+        }
+        if let Some(hash) = self.frame_hashes.get_mut(index) {
+            *hash = None;
+        }
+        if let Some(pending) = self.pending_colors.get_mut(index) {
+            *pending = None;
+        }
+    }
+
+    /// Lazy Phase 2 callback: store one frame's raw `.cframe` bytes, to be
+    /// parsed by [`decode_color`](Self::decode_color) when the frame is
+    /// first needed.
+    pub fn set_frame_color_bytes(&mut self, index: usize, bytes: Vec<u8>) {
         if index < self.frames.len() {
-            self.frames[index].cframe = Some(cframe);
+            self.pending_colors.resize(self.frames.len(), None);
+            self.pending_colors[index] = Some(bytes);
         }
     }
 
+    /// Parse frame `index`'s pending raw colour data, if any, and keep the
+    /// result. Returns whether the frame has colour afterwards.
+    ///
+    /// The web render methods call this themselves; hosts drawing frames
+    /// on their own call it before reading [`frames`](Self::frames).
+    pub fn decode_color(&mut self, index: usize) -> bool {
+        if let Some(bytes) = self.pending_colors.get_mut(index).and_then(Option::take) {
+            if let Ok(cframe) = crate::parse_cframe(&bytes) {
+                self.set_frame_color(index, cframe);
+            }
+        }
+        self.has_color_at(index)
+    }
+
+    /// Number of frames whose raw colour data has not been decoded yet.
+    pub fn pending_color_count(&self) -> usize {
+        self.pending_colors.iter().filter(|bytes| bytes.is_some()).count()
+    }
+
+    /// Whether [`load_colors`](Self::load_colors) stores raw colour bytes
+    /// for on-demand decoding.
+    pub fn lazy_colors(&self) -> bool {
+        self.lazy_colors
+    }
+
+    /// Make [`load_colors`](Self::load_colors) only fetch and store raw
+    /// `.cframe` bytes; each frame is parsed on first render (see
+    /// [`decode_color`](Self::decode_color)) and the result kept. Saves the
+    /// up-front decoding of frames that are never shown.
+    pub fn set_lazy_colors(&mut self, lazy: bool) {
+        self.lazy_colors = lazy;
+    }
+
+    /// Quantizer used for frames stored palette-indexed, if enabled.
+    pub fn indexed_colors(&self) -> Option<&PaletteQuantizer> {
+        self.indexed_colors.as_ref()
+    }
+
+    /// Keep colour stored from now on as [`IndexedCFrameData`], about a
+    /// third of the memory of full colour frames; `None` stores full frames.
+    /// Frames with more than 256 colours are mapped to `quantizer`'s
+    /// palette, which is lossy. Frames are decoded again when rendered, and
+    /// [`frames`](Self::frames) leaves out indexed colour; read it through
+    /// [`source`](Self::source).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{CFrameData, FramePlayer, FrameSource, Palette};
+    ///
+    /// let mut player = FramePlayer::new(10);
+    /// player.set_text_frames(vec!["ab".into()]);
+    /// player.set_indexed_colors(Some(Palette::ansi256().quantizer().clone()));
+    /// player.set_frame_color(0, CFrameData::new(2, 1, b"ab".to_vec(), vec![255, 0, 0, 0, 0, 255]));
+    /// assert!(player.frames()[0].cframe.is_none());
+    /// assert_eq!(player.source().cframe(0).unwrap().rgb, [255, 0, 0, 0, 0, 255]);
+    /// ```
+    pub fn set_indexed_colors(&mut self, quantizer: Option<PaletteQuantizer>) {
+        self.indexed_colors = quantizer;
+    }
+
     /// Whether colour rendering is enabled.
     ///
     /// When `false`, [`render_frame`](Self::render_frame) always returns
@@ -147,7 +595,14 @@ impl FramePlayer {
     // ── Playback (delegates to AnimationController) ─────────────────
 
     /// Start or resume playback.
+    ///
+    /// On a poster this only records the request (see
+    /// [`needs_deferred_load`](Self::needs_deferred_load)).
     pub fn play(&mut self) {
+        if let Some(poster) = self.poster.as_mut() {
+            poster.play_requested = true;
+            return;
+        }
         self.controller.play();
     }
 
@@ -158,6 +613,10 @@ impl FramePlayer {
 
     /// Toggle play / pause.
     pub fn toggle(&mut self) {
+        if self.poster.is_some() {
+            self.play();
+            return;
+        }
         self.controller.toggle();
     }
 
@@ -167,8 +626,11 @@ impl FramePlayer {
     }
 
     /// Advance one frame. Returns `true` if the frame changed.
+    ///
+    /// Holds on the current frame while the next one isn't
+    /// [ready](FrameSource::is_ready) in the player's source.
     pub fn tick(&mut self) -> bool {
-        self.controller.tick()
+        self.controller.tick_source(&self.frames)
     }
 
     /// Step forward one frame (pauses playback).
@@ -201,6 +663,12 @@ impl FramePlayer {
         self.controller.interval_ms()
     }
 
+    /// Milliseconds the current frame stays on screen, honoring per-frame
+    /// durations from frame metadata.
+    pub fn current_interval_ms(&self) -> u32 {
+        self.controller.current_interval_ms()
+    }
+
     /// Whether the player is currently playing.
     pub fn is_playing(&self) -> bool {
         self.controller.is_playing()
@@ -220,45 +688,58 @@ impl FramePlayer {
 
     /// Text content of the current frame.
     pub fn current_text(&self) -> Option<&str> {
-        self.frames
-            .get(self.controller.current_frame())
-            .map(|f| f.content.as_str())
+        self.get_text(self.controller.current_frame())
     }
 
-    /// Text content of an arbitrary frame.
+    /// Text content of an arbitrary frame. `None` for frames a
+    /// [source](Self::set_source) decodes on request; use
+    /// [`text_at`](Self::text_at) to read those too.
     pub fn get_text(&self, index: usize) -> Option<&str> {
-        self.frames.get(index).map(|f| f.content.as_str())
+        match self.frames.store.get(index)? {
+            Cow::Borrowed(frame) => Some(frame.content.as_str()),
+            Cow::Owned(_) => None,
+        }
+    }
+
+    /// Text content of an arbitrary frame, owned when the frame was
+    /// decoded from a [source](Self::set_source).
+    pub fn text_at(&self, index: usize) -> Option<Cow<'_, str>> {
+        self.frames.store.get(index).map(|frame| match frame {
+            Cow::Borrowed(frame) => Cow::Borrowed(frame.content.as_str()),
+            Cow::Owned(frame) => Cow::Owned(frame.content),
+        })
     }
 
     /// Whether a specific frame has colour data.
     pub fn has_color_at(&self, index: usize) -> bool {
-        self.frames
-            .get(index)
-            .map(|f| f.has_color())
-            .unwrap_or(false)
+        self.frames.has_color(index)
     }
 
     /// Whether any loaded frame has colour data.
     pub fn has_any_color(&self) -> bool {
-        self.frames.iter().any(|f| f.has_color())
+        (0..self.frames.len()).any(|index| self.has_color_at(index))
     }
 
     /// (cols, rows) from the first frame, or `None` if empty.
     pub fn dimensions(&self) -> Option<(usize, usize)> {
-        self.frames.first().map(|f| f.dimensions())
+        self.frames.dimensions(0)
     }
 
     // ── Sizing ──────────────────────────────────────────────────────
 
     /// Recalculate the font size to fit within the given container
-    /// dimensions (in pixels). Invalidates the canvas cache on web.
+    /// dimensions (in pixels). Invalidates the canvas cache on web when the
+    /// size changed; with [`FontSnap`](crate::FontSnap) small resizes keep
+    /// the cached frames.
     pub fn fit_to_container(&mut self, width: f64, height: f64) {
         if let Some((cols, rows)) = self.dimensions() {
             let font_size = self.sizing.calculate_font_size(cols, rows, width, height);
+            #[cfg(feature = "web")]
+            if FontSizing::font_size_key(font_size) != FontSizing::font_size_key(self.config.font_size) {
+                self.cache.invalidate_all();
+            }
             self.config.font_size = font_size;
             self.config.sizing = self.sizing.clone();
-            #[cfg(feature = "web")]
-            self.cache.invalidate_all();
         }
     }
 
@@ -295,6 +776,186 @@ impl FramePlayer {
         }
     }
 
+    // ── Overlay ─────────────────────────────────────────────────────
+
+    /// Set (or clear) the overlay scene drawn on top of every frame.
+    ///
+    /// Overlays are painted after the frame itself, so they never end up in
+    /// the canvas cache and time-ranged elements stay accurate.
+    pub fn set_overlay(&mut self, overlay: Option<OverlayScene>) {
+        self.overlay = overlay;
+    }
+
+    /// Borrow the current overlay scene.
+    pub fn overlay(&self) -> Option<&OverlayScene> {
+        self.overlay.as_ref()
+    }
+
+    /// Playback time, progress, play state, and duration for the current frame.
+    pub fn overlay_context(&self) -> OverlayContext {
+        let frame_ms = 1000.0 / self.controller.fps() as f64;
+        let time_ms = self.controller.current_frame() as f64 * frame_ms;
+        OverlayContext {time_ms, progress: self.controller.position(), playing: self.is_playing(), duration_ms: self.frame_count() as f64 * frame_ms}
+    }
+
+    /// Build the overlay layer for the current frame, sized to the frame
+    /// canvas at the current font size. Returns `None` without a scene.
+    pub fn overlay_layer(&self) -> Option<RenderLayer> {
+        let scene = self.overlay.as_ref()?;
+        let (cols, rows) = self.dimensions().unwrap_or((0, 0));
+        let (width, height) = self.config.sizing.canvas_dimensions(cols, rows, self.config.font_size);
+        Some(scene.build_layer(width, height, &self.config, &self.overlay_context()))
+    }
+
+    // ── Static-frame detection ──────────────────────────────────────
+
+    /// Enable or disable skipping draws of frames identical to the one on
+    /// screen (enabled by default).
+    ///
+    /// Recordings with long idle periods otherwise redraw the same pixels on
+    /// every tick. Frames are compared by [`Frame::checksum`] together with
+    /// the render settings, so a font-size change still redraws.
+    pub fn set_skip_static_frames(&mut self, skip: bool) {
+        self.skip_static_frames = skip;
+    }
+
+    /// Whether static-frame skipping is enabled.
+    pub fn skip_static_frames(&self) -> bool {
+        self.skip_static_frames
+    }
+
+    /// Number of draws skipped because the frame was unchanged.
+    pub fn skipped_draws(&self) -> u64 {
+        self.skipped_draws
+    }
+
+    /// Whether drawing frame `index` would change what is on screen.
+    ///
+    /// Always `true` when skipping is disabled, nothing has been drawn yet,
+    /// or an overlay is set (overlays can change every frame).
+    pub fn needs_redraw(&mut self, index: usize) -> bool {
+        if !self.skip_static_frames || self.overlay.is_some() {
+            return true;
+        }
+        match (self.displayed, self.displayed_key(index)) {
+            (Some(displayed), Some(next)) => displayed != next,
+            _ => true,
+        }
+    }
+
+    /// Record that frame `index` is now on screen.
+    ///
+    /// Call this after drawing a frame yourself (e.g. the `<pre>` text
+    /// fallback or a native backend); the web helpers call it automatically.
+    pub fn mark_drawn(&mut self, index: usize) {
+        self.displayed = self.displayed_key(index);
+    }
+
+    /// Forget what is on screen so the next frame is always drawn, e.g.
+    /// after the canvas was cleared or resized externally, or the device
+    /// pixel ratio changed.
+    pub fn invalidate_displayed(&mut self) {
+        self.displayed = None;
+    }
+
+    fn displayed_key(&mut self, index: usize) -> Option<DisplayedFrame> {
+        let hash = self.frame_hash(index)?;
+        let color = self.color_ready && self.has_color_at(index);
+        Some(DisplayedFrame {hash, render_key: self.config.cache_key(), color})
+    }
+
+    fn frame_hash(&mut self, index: usize) -> Option<u64> {
+        if self.frame_hashes.len() != self.frames.len() {
+            self.frame_hashes = vec![None; self.frames.len()];
+        }
+        let hash = self.frame_hashes.get_mut(index)?;
+        if hash.is_none() {
+            *hash = Some(self.frames.get(index)?.checksum());
+        }
+        *hash
+    }
+
+    fn sync_frame_timing(&mut self) {
+        let durations = match &self.frames.store {
+            FrameStore::Owned(frames) => crate::frame_durations(frames),
+            FrameStore::Source(_) => Vec::new(),
+        };
+        self.controller.set_frame_durations(durations);
+    }
+
+    fn reset_frame_hashes(&mut self) {
+        self.frame_hashes = vec![None; self.frames.len()];
+        self.displayed = None;
+    }
+
+    // ── Temporal interpolation ──────────────────────────────────────
+
+    /// Enable or disable colour interpolation between frames (disabled by
+    /// default).
+    ///
+    /// When the host redraws faster than the source FPS, the ticks between
+    /// two frames show a blend of their colours instead of repeating the
+    /// earlier one. See [`cframe_at_time`](Self::cframe_at_time).
+    pub fn set_interpolation(&mut self, enabled: bool) {
+        self.interpolation = enabled;
+    }
+
+    /// Whether colour interpolation is enabled.
+    pub fn interpolation(&self) -> bool {
+        self.interpolation
+    }
+
+    /// Mix interpolated colours in `space`; [`ColorSpace::Linear`] avoids
+    /// the dark midpoints of sRGB blends.
+    pub fn set_blend_space(&mut self, space: ColorSpace) {
+        self.blend_space = space;
+    }
+
+    /// Space interpolated colours are mixed in.
+    pub fn blend_space(&self) -> ColorSpace {
+        self.blend_space
+    }
+
+    /// The current frame and the one [`tick`](Self::tick) moves to next,
+    /// `elapsed_ms` after the current frame came on screen.
+    ///
+    /// The blend factor is `elapsed_ms` as a fraction of the current
+    /// frame's [interval](AnimationController::current_interval_ms), so
+    /// per-frame durations are honoured. The next frame wraps to the start
+    /// of the [range](AnimationController::range_frames) when looping and
+    /// holds on the last frame otherwise.
+    pub fn blend_at(&self, elapsed_ms: f64) -> Option<FrameBlend> {
+        if self.controller.frame_count() == 0 {
+            return None;
+        }
+        let from = self.controller.current_frame();
+        let (start, end) = self.controller.range_frames();
+        let to = match self.controller.loop_mode() {
+            _ if from < end => from + 1,
+            LoopMode::Loop => start,
+            LoopMode::Once => from,
+        };
+        let t = if to == from { 0.0 } else { (elapsed_ms / self.controller.current_interval_ms() as f64).clamp(0.0, 1.0) };
+        Some(FrameBlend {from, to, t})
+    }
+
+    /// The colour frame to show `elapsed_ms` after the current frame came
+    /// on screen.
+    ///
+    /// With interpolation enabled this blends the current frame into the
+    /// next (see [`blend_at`](Self::blend_at)); otherwise, or when either
+    /// frame lacks colour or their sizes differ, it is the current frame
+    /// unchanged.
+    pub fn cframe_at_time(&self, elapsed_ms: f64) -> Option<Cow<'_, CFrameData>> {
+        let blend = self.blend_at(elapsed_ms)?;
+        let from = self.frames.cframe(blend.from)?;
+        if !self.interpolation || blend.t <= 0.0 || blend.from == blend.to {
+            return Some(from);
+        }
+        let blended = self.frames.cframe(blend.to).and_then(|to| blend_cframes_in(&from, &to, blend.t, self.blend_space));
+        Some(blended.map_or(from, Cow::Owned))
+    }
+
     // ── Advanced access ─────────────────────────────────────────────
 
     /// Borrow the animation controller.
@@ -307,8 +968,20 @@ impl FramePlayer {
         &mut self.controller
     }
 
-    /// Borrow the loaded frames.
+    /// Borrow the frames held in memory. Empty while the player reads from
+    /// a [source](Self::set_source), and without the colour of frames stored
+    /// [palette-indexed](Self::set_indexed_colors); use
+    /// [`source`](Self::source) to read frames either way.
     pub fn frames(&self) -> &[Frame] {
+        match &self.frames.store {
+            FrameStore::Owned(frames) => frames,
+            FrameStore::Source(_) => &[],
+        }
+    }
+
+    /// The frames being played, whether held in memory or read from a
+    /// [source](Self::set_source).
+    pub fn source(&self) -> &dyn FrameSource {
         &self.frames
     }
 }
@@ -340,22 +1013,46 @@ impl FramePlayer {
 
         // Try cache first
         if crate::render::web::draw_frame_from_cache(canvas, &self.cache, index)? {
+            self.draw_overlay(canvas)?;
+            self.mark_drawn(index);
             return Ok(true);
         }
 
         // No cache hit – try to render the colour frame
-        let cframe = match self.frames.get(index).and_then(|f| f.cframe.as_ref()) {
+        self.decode_color(index);
+        let cframe = match self.frames.cframe(index) {
             Some(cf) => cf,
             None => return Ok(false),
         };
 
         // Render to an offscreen canvas, cache it, then blit to the target
-        let offscreen = crate::render::web::render_to_offscreen_canvas(cframe, &self.config)?;
+        let offscreen = crate::render::web::render_to_offscreen_canvas(&cframe, &self.config)?;
         self.cache.store(index, offscreen.clone());
         crate::render::web::draw_cached_canvas(canvas, &offscreen)?;
+        self.draw_overlay(canvas)?;
+        self.mark_drawn(index);
         Ok(true)
     }
 
+    /// Measure the configured font on `canvas` and calibrate the player's
+    /// sizing with it (see [`FontSizing::calibrate`]). Call once the web font
+    /// has loaded, before [`fit_to_container`](Self::fit_to_container).
+    pub fn calibrate_font(&mut self, canvas: &web_sys::HtmlCanvasElement) -> Result<(), String> {
+        crate::render::web::calibrate_font_sizing(canvas, &mut self.config)?;
+        self.sizing = self.config.sizing.clone();
+        self.cache.invalidate_all();
+        self.invalidate_displayed();
+        Ok(())
+    }
+
+    /// Paint the overlay scene (if any) on top of the canvas contents.
+    pub fn draw_overlay(&self, canvas: &web_sys::HtmlCanvasElement) -> Result<(), String> {
+        match self.overlay_layer() {
+            Some(layer) => crate::render::web::draw_layer(canvas, &layer, &self.config),
+            None => Ok(()),
+        }
+    }
+
     /// Pre-render one frame to the cache. Returns `true` if the frame was
     /// successfully cached (i.e. it has colour data and wasn't cached yet).
     pub fn pre_cache_frame(&mut self, index: usize) -> bool {
@@ -365,11 +1062,12 @@ impl FramePlayer {
         if self.cache.has(index) {
             return false;
         }
-        let cframe = match self.frames.get(index).and_then(|f| f.cframe.as_ref()) {
+        self.decode_color(index);
+        let cframe = match self.frames.cframe(index) {
             Some(cf) => cf,
             None => return false,
         };
-        match crate::render::web::render_to_offscreen_canvas(cframe, &self.config) {
+        match crate::render::web::render_to_offscreen_canvas(&cframe, &self.config) {
             Ok(offscreen) => {
                 self.cache.store(index, offscreen);
                 true
@@ -378,19 +1076,58 @@ impl FramePlayer {
         }
     }
 
+    /// Render the colour frame `elapsed_ms` after the current frame came on
+    /// screen, blending it into the next frame when
+    /// [`interpolation`](Self::interpolation) is enabled.
+    ///
+    /// Call this from the display's refresh (e.g. `requestAnimationFrame`)
+    /// between the [`tick`](Self::tick)s of the playback timer. Frames that
+    /// land exactly on a source frame go through the cache; blended ones are
+    /// rendered directly. Returns `Ok(false)` when the consumer should use
+    /// the text fallback.
+    pub fn render_at_time(&mut self, elapsed_ms: f64, canvas: &web_sys::HtmlCanvasElement) -> Result<bool, String> {
+        if !self.color_ready {
+            return Ok(false);
+        }
+        if let Some(blend) = self.blend_at(elapsed_ms) {
+            self.decode_color(blend.from);
+            self.decode_color(blend.to);
+        }
+        let Some(cframe) = self.cframe_at_time(elapsed_ms) else {
+            return Ok(false);
+        };
+        let Cow::Owned(blended) = cframe else {
+            let index = self.blend_at(elapsed_ms).map_or(0, |blend| blend.from);
+            return self.render_frame(index, canvas);
+        };
+        crate::render::web::render_to_canvas(&blended, canvas, &self.config)?;
+        self.draw_overlay(canvas)?;
+        // A blend matches no source frame.
+        self.invalidate_displayed();
+        Ok(true)
+    }
+
     /// Advance one animation step and render the current frame to `canvas`.
     ///
     /// Handles the full render pipeline: colour frame from cache / render,
-    /// or plain-text fallback.  No-op when the player is paused.
+    /// or plain-text fallback.  No-op when the player is paused, and the
+    /// draw is skipped when the new frame is identical to the one on screen
+    /// (see [`set_skip_static_frames`](Self::set_skip_static_frames)).
     pub fn tick_and_render(&mut self, canvas: &web_sys::HtmlCanvasElement) -> Result<(), String> {
         if !self.is_playing() {
             return Ok(());
         }
         self.tick();
         let idx = self.current_frame();
+        if !self.needs_redraw(idx) {
+            self.skipped_draws += 1;
+            return Ok(());
+        }
         if !self.render_current(canvas)? {
-            if let Some(text) = self.frames.get(idx).map(|f| f.content.as_str()) {
-                crate::render::web::render_text_to_canvas(canvas, text, &self.config)?;
+            if let Some(text) = self.text_at(idx) {
+                crate::render::web::render_text_to_canvas(canvas, &text, &self.config)?;
+                self.draw_overlay(canvas)?;
+                self.mark_drawn(idx);
             }
         }
         Ok(())
@@ -402,20 +1139,40 @@ impl FramePlayer {
     /// needs mutable access), the player must be wrapped in
     /// `Rc<RefCell<FramePlayer>>`.  Pass that handle here.
     ///
+    /// Frames are loaded around the playhead first (see
+    /// [`LoadSchedule`](crate::LoadSchedule)), so after a seek the frames
+    /// being shown get their colour before the rest. Replacing the frames
+    /// (e.g. loading another project) stops the loop with
+    /// [`Err(LOADING_CANCELLED)`](crate::LOADING_CANCELLED).
+    ///
     /// Does **not** enable colour rendering automatically — call
     /// [`pre_cache_all`](Self::pre_cache_all) (which sets `color_ready`)
     /// for stutter-free first-loop playback, or
     /// [`set_color_ready(true)`](Self::set_color_ready) to start
     /// immediately (first loop will be slower while frames are cached
     /// on demand).
-    pub async fn load_colors<P: FrameDataProvider>(player: &std::rc::Rc<std::cell::RefCell<Self>>, provider: &P) -> LoadResult<()> {
+    ///
+    /// With [`set_lazy_colors`](Self::set_lazy_colors) only the raw bytes
+    /// are stored, and each frame is decoded on first render.
+    ///
+    /// Returns the frames whose colour could not be read even after
+    /// retrying; they keep playing as text.
+    pub async fn load_colors<P: FrameDataProvider>(player: &std::rc::Rc<std::cell::RefCell<Self>>, provider: &P) -> LoadResult<Vec<crate::FrameFailure>> {
         let frame_files = player.borrow().frame_files().to_vec();
+        let options = player.borrow().load_options();
         let player_cb = player.clone();
-        crate::load_color_frames(provider, &frame_files, |index, _total, cframe_opt| {
+        if player.borrow().lazy_colors {
+            return crate::load_color_bytes_prioritized(provider, &frame_files, || player.borrow().current_frame(), |index, _total, bytes| {
+                if let Some(bytes) = bytes {
+                    player_cb.borrow_mut().set_frame_color_bytes(index, bytes);
+                }
+            }, crate::yield_to_event_loop, &options, |_| {}).await;
+        }
+        crate::load_color_frames_prioritized(provider, &frame_files, || player.borrow().current_frame(), |index, _total, cframe_opt| {
             if let Some(cframe) = cframe_opt {
                 player_cb.borrow_mut().set_frame_color(index, cframe);
             }
-        }, crate::yield_to_event_loop).await
+        }, crate::yield_to_event_loop, &options, |_| {}).await
     }
 
     /// Pre-render all colour frames to the canvas cache, then enable
@@ -434,6 +1191,18 @@ impl FramePlayer {
         Ok(())
     }
 
+    /// Pre-render the frames `warmer` expects next into the canvas cache,
+    /// yielding to the event loop between slices. Call while playback is
+    /// paused or from idle callbacks; stops early once the load is
+    /// cancelled. Returns the number of frames rendered.
+    pub async fn warm_cache(player: &std::rc::Rc<std::cell::RefCell<Self>>, warmer: &crate::CacheWarmer, direction: PlaybackDirection) -> usize {
+        let (controller, cancel) = {
+            let this = player.borrow();
+            (this.controller.clone(), this.cancellation_token())
+        };
+        warmer.run(&controller, direction, |index| player.borrow_mut().pre_cache_frame(index), || cancel.is_cancelled(), crate::yield_to_event_loop).await
+    }
+
     /// Borrow the canvas cache.
     pub fn cache(&self) -> &crate::render::web::FrameCanvasCache {
         &self.cache
@@ -449,6 +1218,84 @@ impl FramePlayer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cframe_at_time_interpolates() {
+        let mut player = FramePlayer::new(10);
+        player.set_text_frames(vec!["A".into(), "B".into()]);
+        player.set_frame_color(0, CFrameData::new(1, 1, vec![b'A'], vec![0, 0, 0]));
+        player.set_frame_color(1, CFrameData::new(1, 1, vec![b'B'], vec![200, 100, 0]));
+
+        assert_eq!(player.cframe_at_time(50.0).unwrap().rgb, [0, 0, 0]);
+        player.set_interpolation(true);
+        assert!(matches!(player.cframe_at_time(0.0), Some(Cow::Borrowed(_))));
+        let blended = player.cframe_at_time(50.0).unwrap();
+        assert_eq!((blended.chars[0], blended.rgb.as_slice()), (b'B', &[100, 50, 0][..]));
+        // Looping blends the last frame back into the first.
+        player.play();
+        player.tick();
+        assert_eq!(player.cframe_at_time(75.0).unwrap().rgb, [50, 25, 0]);
+        player.controller_mut().set_current_frame(0);
+        player.set_blend_space(ColorSpace::Linear);
+        assert_eq!(player.cframe_at_time(50.0).unwrap().rgb, [146, 71, 0]);
+    }
+
+    #[test]
+    fn test_blend_at_follows_playhead() {
+        let mut player = FramePlayer::new(10);
+        player.set_text_frames(vec!["A".into(), "B".into(), "C".into(), "D".into()]);
+        player.controller_mut().set_frame_durations(vec![None, Some(400)]);
+        player.controller_mut().set_range_frames(1, 2);
+        player.play();
+
+        assert_eq!(player.current_frame(), 1);
+        assert_eq!(player.blend_at(100.0), Some(FrameBlend {from: 1, to: 2, t: 0.25}));
+        player.tick();
+        // The next frame wraps inside the range, not to frame 3 or 0.
+        assert_eq!(player.blend_at(50.0), Some(FrameBlend {from: 2, to: 1, t: 0.5}));
+        player.controller_mut().set_loop_mode(LoopMode::Once);
+        assert_eq!(player.blend_at(50.0), Some(FrameBlend {from: 2, to: 2, t: 0.0}));
+        assert_eq!(FramePlayer::new(10).blend_at(0.0), None);
+    }
+
+    #[test]
+    fn test_player_reads_frames_from_source() {
+        let cframes = [CFrameData::new(1, 1, vec![b'a'], vec![1, 2, 3]), CFrameData::new(1, 1, vec![b'b'], vec![4, 5, 6])];
+        let mut player = FramePlayer::new(10);
+        player.set_source(crate::FrameAtlas::from_cframes(&cframes));
+
+        assert!(player.frames().is_empty());
+        assert_eq!((player.frame_count(), player.dimensions(), player.color_ready()), (2, Some((1, 1)), true));
+        assert_eq!(player.current_text(), None);
+        assert!(matches!(player.text_at(0), Some(Cow::Owned(text)) if text == "a\n"));
+        assert_eq!(player.source().cframe(1).unwrap().rgb, [4, 5, 6]);
+
+        // Stored colour is kept beside the source.
+        player.set_frame_color(0, CFrameData::new(1, 1, vec![b'z'], vec![0; 3]));
+        assert_eq!((player.frames().len(), player.frame_count()), (0, 2));
+        assert_eq!(player.source().cframe(0).unwrap().rgb, [0; 3]);
+        assert_eq!(player.source().get(0).unwrap().cframe.as_ref().unwrap().chars, b"z");
+        assert_eq!(player.source().cframe(1).unwrap().rgb, [4, 5, 6]);
+    }
+
+    #[test]
+    fn test_player_holds_on_pending_source_frames() {
+        let mut loader = crate::FrameLoaderState::new();
+        loader.progress.reset(3);
+        loader.frames.push(Frame::text_only("a".into()).into());
+
+        let mut player = FramePlayer::new(10);
+        player.set_source(loader);
+        player.play();
+        assert_eq!(player.frame_count(), 3);
+        assert!(!player.tick());
+        assert_eq!(player.current_frame(), 0);
+
+        // Colour for a frame still loading doesn't drop the pending frames.
+        player.set_frame_color(2, CFrameData::new(1, 1, vec![b'c'], vec![0; 3]));
+        assert_eq!((player.frame_count(), player.current_text(), player.has_color_at(2)), (3, Some("a"), true));
+        assert!(player.has_any_color());
+    }
+
     #[test]
     fn test_new_player() {
         let player = FramePlayer::new(24);
@@ -465,7 +1312,7 @@ mod tests {
         player.frames = vec![
             Frame::text_only("Hello\nWorld".into()),
             Frame::text_only("Frame 2".into()),
-        ];
+        ].into();
         player.controller.set_frame_count(2);
 
         assert_eq!(player.frame_count(), 2);
@@ -482,7 +1329,7 @@ mod tests {
             Frame::text_only("A".into()),
             Frame::text_only("B".into()),
             Frame::text_only("C".into()),
-        ];
+        ].into();
         player.controller.set_frame_count(3);
 
         player.play();
@@ -504,7 +1351,7 @@ mod tests {
     #[test]
     fn test_player_seek() {
         let mut player = FramePlayer::new(24);
-        player.frames = vec![Frame::text_only("A".into()); 100];
+        player.frames = vec![Frame::text_only("A".into()); 100].into();
         player.controller.set_frame_count(100);
 
         player.seek(0.5);
@@ -515,7 +1362,7 @@ mod tests {
     #[test]
     fn test_player_set_color() {
         let mut player = FramePlayer::new(24);
-        player.frames = vec![Frame::text_only("AB".into())];
+        player.frames = vec![Frame::text_only("AB".into())].into();
         player.controller.set_frame_count(1);
 
         assert!(!player.has_color_at(0));
@@ -530,7 +1377,7 @@ mod tests {
     #[test]
     fn test_player_fit_to_container() {
         let mut player = FramePlayer::new(24);
-        player.frames = vec![Frame::text_only("ABCDEFGHIJ\n1234567890".into())];
+        player.frames = vec![Frame::text_only("ABCDEFGHIJ\n1234567890".into())].into();
         player.controller.set_frame_count(1);
 
         player.fit_to_container(800.0, 600.0);
@@ -540,7 +1387,7 @@ mod tests {
     #[test]
     fn test_player_font_size_css() {
         let mut player = FramePlayer::new(24);
-        player.frames = vec![Frame::text_only("ABC\nDEF".into())];
+        player.frames = vec![Frame::text_only("ABC\nDEF".into())].into();
         player.controller.set_frame_count(1);
 
         player.config.font_size = 10.0;
@@ -574,7 +1421,7 @@ mod tests {
     #[test]
     fn test_player_toggle_stop() {
         let mut player = FramePlayer::new(24);
-        player.frames = vec![Frame::text_only("A".into()), Frame::text_only("B".into())];
+        player.frames = vec![Frame::text_only("A".into()), Frame::text_only("B".into())].into();
         player.controller.set_frame_count(2);
 
         player.toggle();
@@ -592,9 +1439,153 @@ mod tests {
         assert_eq!(player.current_frame(), 0);
     }
 
+    #[test]
+    fn test_player_overlay_layer() {
+        let mut player = FramePlayer::new(10);
+        player.frames = vec![Frame::text_only("AB".into()); 11].into();
+        player.controller.set_frame_count(11);
+        assert!(player.overlay_layer().is_none());
+
+        let scene = OverlayScene::new().with(crate::OverlayElement::new(crate::OverlayKind::ProgressBar {width: 10.0, height: 1.0, fill: (255, 255, 255), track: None}));
+        player.set_overlay(Some(scene));
+        player.seek(0.5);
+
+        assert_eq!(player.overlay_context().time_ms, 500.0);
+        let layer = player.overlay_layer().unwrap();
+        assert_eq!(layer.rects.len(), 1);
+        assert_eq!(layer.rects[0].width, 5.0);
+    }
+
+    #[test]
+    fn test_player_static_frame_detection() {
+        let mut player = FramePlayer::new(24);
+        player.set_text_frames(vec!["idle".into(), "idle".into(), "move".into()]);
+
+        assert!(player.needs_redraw(0));
+        player.mark_drawn(0);
+        assert!(!player.needs_redraw(1));
+        assert!(player.needs_redraw(2));
+
+        player.config.font_size += 1.0;
+        assert!(player.needs_redraw(1));
+        player.mark_drawn(1);
+
+        player.set_frame_color(1, CFrameData::new(4, 1, b"idle".to_vec(), vec![255; 12]));
+        player.set_color_ready(true);
+        assert!(player.needs_redraw(1));
+
+        player.invalidate_displayed();
+        player.set_skip_static_frames(false);
+        player.mark_drawn(0);
+        assert!(player.needs_redraw(0));
+    }
+
+    #[test]
+    fn test_player_poster_defers_loading() {
+        use crate::loader::test_support::{block_on, MemoryProvider};
+
+        let provider = MemoryProvider::with_texts(&["A", "B", "C", "D"]);
+        let mut player = FramePlayer::new(10);
+        block_on(player.load_poster(&provider, "", 2)).unwrap();
+        assert!(player.is_poster());
+        assert_eq!((player.frame_count(), player.current_text()), (1, Some("C")));
+        assert_eq!(player.frame_files().len(), 4);
+
+        assert!(!player.needs_deferred_load());
+        player.play();
+        assert!(!player.is_playing());
+        assert!(player.needs_deferred_load());
+
+        block_on(player.load_deferred(&provider)).unwrap();
+        assert!(!player.is_poster());
+        assert_eq!(player.frame_count(), 4);
+        assert_eq!(player.current_text(), Some("C"));
+        assert!(player.is_playing());
+    }
+
+    #[test]
+    fn test_player_prefetch_sets_upcoming_colors() {
+        use crate::loader::test_support::{block_on, MemoryProvider};
+
+        let mut provider = MemoryProvider::with_texts(&["A", "B", "C"]);
+        for name in ["frame_0002.txt", "frame_0003.txt"] {
+            provider.cframes.insert(name.into(), crate::encode_cframe(&CFrameData::new(1, 1, vec![b'x'], vec![9; 3])).unwrap());
+        }
+        let player = std::rc::Rc::new(std::cell::RefCell::new(FramePlayer::new(10)));
+        block_on(player.borrow_mut().load(&provider, "")).unwrap();
+
+        let mut prefetcher = Prefetcher::new(1);
+        assert_eq!(block_on(FramePlayer::prefetch(&player, &provider, &mut prefetcher, PlaybackDirection::Forward)).unwrap(), 1);
+        assert!(player.borrow().has_color_at(1));
+        assert!(!player.borrow().has_color_at(2));
+    }
+
+    #[test]
+    fn test_player_lazy_color_decoding() {
+        use crate::loader::test_support::{block_on, MemoryProvider};
+
+        let mut provider = MemoryProvider::with_texts(&["A", "B", "C"]);
+        for name in ["frame_0001.txt", "frame_0003.txt"] {
+            provider.cframes.insert(name.into(), crate::encode_cframe(&CFrameData::new(1, 1, vec![b'x'], vec![9; 3])).unwrap());
+        }
+        let player = std::cell::RefCell::new(FramePlayer::new(10));
+        block_on(player.borrow_mut().load(&provider, "")).unwrap();
+        let files = player.borrow().frame_files().to_vec();
+        block_on(crate::load_color_bytes_prioritized(&provider, &files, || 0, |index, _, bytes| {
+            if let Some(bytes) = bytes {
+                player.borrow_mut().set_frame_color_bytes(index, bytes);
+            }
+        }, || async {}, &LoadOptions::default(), |_| {})).unwrap();
+
+        let mut player = player.into_inner();
+        assert_eq!(player.pending_color_count(), 2);
+        assert!(!player.has_color_at(0));
+        assert!(player.decode_color(0));
+        assert!(!player.decode_color(1));
+        assert_eq!((player.pending_color_count(), player.has_color_at(2)), (1, false));
+
+        player.set_text_frames(vec!["A".into()]);
+        assert_eq!(player.pending_color_count(), 0);
+    }
+
+    #[test]
+    fn test_player_indexed_colors() {
+        let mut player = FramePlayer::new(10);
+        player.set_text_frames(vec!["x".repeat(300), "ab".into()]);
+        player.set_indexed_colors(Some(crate::PaletteQuantizer::new(vec![(0, 0, 0), (255, 0, 0)])));
+
+        let rgb: Vec<u8> = (0..300u32).flat_map(|i| [(i % 256) as u8, (i / 256) as u8, 0]).collect();
+        player.set_frame_color(0, CFrameData::new(300, 1, vec![b'x'; 300], rgb));
+        let small = CFrameData::new(2, 1, b"ab".to_vec(), vec![10, 20, 30, 40, 50, 60]);
+        player.set_frame_color(1, small.clone());
+
+        assert!(player.frames().iter().all(|frame| frame.cframe.is_none()));
+        assert!(player.has_color_at(0) && player.has_color_at(1));
+        let quantized = player.source().cframe(0).unwrap();
+        assert_eq!((&quantized.rgb[..3], &quantized.rgb[200 * 3..201 * 3]), (&[0, 0, 0][..], &[255, 0, 0][..]));
+        assert_eq!(player.source().get(1).unwrap().cframe.as_ref().map(CFrameData::checksum), Some(small.checksum()));
+
+        player.set_indexed_colors(None);
+        player.set_frame_color(1, small.clone());
+        assert_eq!(player.frames()[1].cframe.as_ref().map(CFrameData::checksum), Some(small.checksum()));
+    }
+
     #[test]
     fn test_player_interval_ms() {
         let player = FramePlayer::new(24);
         assert_eq!(player.interval_ms(), 41);
     }
+
+    #[test]
+    fn test_player_variable_timing_from_meta() {
+        let mut player = FramePlayer::new(10);
+        let meta = crate::FrameMeta {duration_ms: Some(400.0), ..Default::default()};
+        player.set_frames(vec![Frame::text_only("A".into()).with_meta(meta), Frame::text_only("B".into())]);
+        assert_eq!(player.current_interval_ms(), 400);
+        player.controller.set_current_frame(1);
+        assert_eq!(player.current_interval_ms(), 100);
+
+        player.set_text_frames(vec!["A".into()]);
+        assert_eq!(player.current_interval_ms(), 100);
+    }
 }
@@ -0,0 +1,232 @@
+//! Playhead prefetching for lazily decoded frames.
+//!
+//! A [`Prefetcher`] looks a fixed number of frames ahead of the
+//! [`AnimationController`]'s playhead, in the direction of playback and
+//! within its range and loop mode, and requests those frames' color data
+//! from a [`FrameDataProvider`] before they are shown. Each frame is only
+//! requested once until [`reset`](Prefetcher::reset).
+//!
+//! A [`CacheWarmer`] uses the same look-ahead to pre-render frames into a
+//! render cache while the host is idle (paused, or between timer ticks),
+//! yielding between slices of work so it never blocks input.
+
+use crate::{AnimationController, CFrameData, FrameDataProvider, FrameFile, LoadResult, LoopMode};
+
+/// Which way the playhead is moving.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlaybackDirection {
+    /// Towards higher frame indices
+    #[default]
+    Forward,
+    /// Towards lower frame indices
+    Backward,
+}
+
+/// Requests the next `window` frames ahead of the playhead.
+#[derive(Clone, Debug)]
+pub struct Prefetcher {
+    /// Number of frames to keep ready ahead of the playhead
+    pub window: usize,
+    requested: Vec<bool>,
+}
+
+impl Prefetcher {
+    /// Create a prefetcher that looks `window` frames ahead.
+    pub fn new(window: usize) -> Self {
+        Self {window, requested: Vec::new()}
+    }
+
+    /// Forget which frames were requested, e.g. after the frames changed.
+    pub fn reset(&mut self) {
+        self.requested.clear();
+    }
+
+    /// Record frame `index` as already available so it is never requested.
+    pub fn mark_ready(&mut self, index: usize) {
+        if index >= self.requested.len() {
+            self.requested.resize(index + 1, false);
+        }
+        self.requested[index] = true;
+    }
+
+    /// Whether frame `index` was requested or marked ready.
+    #[inline]
+    pub fn is_requested(&self, index: usize) -> bool {
+        self.requested.get(index).copied().unwrap_or(false)
+    }
+
+    /// The `window` frames that follow the playhead, nearest first.
+    ///
+    /// Looping playback wraps around the controller's range; `Once`
+    /// playback stops at the range edge.
+    pub fn upcoming(&self, controller: &AnimationController, direction: PlaybackDirection) -> Vec<usize> {
+        if controller.frame_count() == 0 {
+            return Vec::new();
+        }
+        let (start, end) = controller.range_frames();
+        let mut frame = controller.current_frame().clamp(start, end);
+        let mut frames = Vec::with_capacity(self.window);
+        for _ in 0..self.window.min(end - start) {
+            frame = match (direction, controller.loop_mode()) {
+                (PlaybackDirection::Forward, _) if frame < end => frame + 1,
+                (PlaybackDirection::Backward, _) if frame > start => frame - 1,
+                (PlaybackDirection::Forward, LoopMode::Loop) => start,
+                (PlaybackDirection::Backward, LoopMode::Loop) => end,
+                (_, LoopMode::Once) => break,
+            };
+            frames.push(frame);
+        }
+        frames
+    }
+
+    /// The upcoming frames not requested yet, marked as requested.
+    pub fn plan(&mut self, controller: &AnimationController, direction: PlaybackDirection) -> Vec<usize> {
+        let frames: Vec<usize> = self.upcoming(controller, direction).into_iter().filter(|&index| !self.is_requested(index)).collect();
+        for &index in &frames {
+            self.mark_ready(index);
+        }
+        frames
+    }
+
+    /// Read and decode the planned frames through `provider`, calling
+    /// `on_frame(index, cframe)` for each so the caller can store or
+    /// pre-render it. Returns the number of frames requested.
+    pub async fn fetch<P, F>(&mut self, provider: &P, frame_files: &[FrameFile], controller: &AnimationController, direction: PlaybackDirection, mut on_frame: F) -> LoadResult<usize> where P: FrameDataProvider, F: FnMut(usize, Option<CFrameData>) {
+        let planned = self.plan(controller, direction);
+        let mut requested = 0;
+        for index in planned {
+            let Some(frame_file) = frame_files.get(index) else { continue; };
+            let cframe = match provider.read_cframe_bytes(&frame_file.path).await? {
+                Some(bytes) => crate::parse_cframe(&bytes).ok(),
+                None => None,
+            };
+            on_frame(index, cframe);
+            requested += 1;
+        }
+        Ok(requested)
+    }
+}
+
+/// Pre-renders the frames ahead of the playhead during idle time.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{AnimationController, CFrameData, CacheWarmer, PlaybackDirection, RenderCache, RenderConfig};
+///
+/// let cframes = vec![CFrameData::new(1, 1, vec![b'A'], vec![255, 255, 255]); 4];
+/// let mut controller = AnimationController::new(10);
+/// controller.set_frame_count(cframes.len());
+/// let (mut cache, config) = (RenderCache::default(), RenderConfig::new(12.0));
+///
+/// let warmer = CacheWarmer::new(2);
+/// let warm = warmer.run(&controller, PlaybackDirection::Forward, |index| {
+///     let fresh = cache.get(index, config.cache_key()).is_none();
+///     cache.get_or_render(index, &cframes[index], &config);
+///     fresh
+/// }, || false, || async {});
+/// # let rendered = block_on(warm);
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut future = std::pin::pin!(future);
+/// #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop { if let std::task::Poll::Ready(out) = future.as_mut().poll(&mut cx) { return out; } }
+/// # }
+/// assert_eq!(rendered, 2);
+/// assert_eq!(cache.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CacheWarmer {
+    /// Number of frames ahead of the playhead to keep rendered
+    pub window: usize,
+    /// Frames rendered between two yields
+    pub frames_per_slice: usize,
+}
+
+impl CacheWarmer {
+    /// Create a warmer that keeps `window` frames ahead rendered, one frame
+    /// per slice.
+    pub fn new(window: usize) -> Self {
+        Self {window, frames_per_slice: 1}
+    }
+
+    /// Render the upcoming frames, nearest first.
+    ///
+    /// `render(index)` renders frame `index` into the cache and returns
+    /// whether it did any work (`false` when cached already or without
+    /// color). After every `frames_per_slice` renders `yield_fn()` is
+    /// awaited (e.g. [`yield_to_event_loop`](crate::yield_to_event_loop)),
+    /// and warming stops as soon as `should_stop()` returns `true`, e.g.
+    /// when the project changed. Returns the number of frames rendered.
+    pub async fn run<R, S, Y, YFut>(&self, controller: &AnimationController, direction: PlaybackDirection, mut render: R, should_stop: S, yield_fn: Y) -> usize where R: FnMut(usize) -> bool, S: Fn() -> bool, Y: Fn() -> YFut, YFut: std::future::Future<Output = ()> {
+        let mut rendered = 0;
+        for index in Prefetcher::new(self.window).upcoming(controller, direction) {
+            if should_stop() {
+                break;
+            }
+            if render(index) {
+                rendered += 1;
+                if rendered % self.frames_per_slice.max(1) == 0 {
+                    yield_fn().await;
+                }
+            }
+        }
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::test_support::{block_on, MemoryProvider};
+
+    #[test]
+    fn test_prefetch_window_follows_direction_and_loop() {
+        let mut controller = AnimationController::new(10);
+        controller.set_frame_count(6);
+        controller.set_current_frame(4);
+        let mut prefetcher = Prefetcher::new(3);
+
+        assert_eq!(prefetcher.upcoming(&controller, PlaybackDirection::Forward), [5, 0, 1]);
+        assert_eq!(prefetcher.upcoming(&controller, PlaybackDirection::Backward), [3, 2, 1]);
+        controller.set_loop_mode(LoopMode::Once);
+        assert_eq!(prefetcher.upcoming(&controller, PlaybackDirection::Forward), [5]);
+
+        prefetcher.mark_ready(2);
+        assert_eq!(prefetcher.plan(&controller, PlaybackDirection::Backward), [3, 1]);
+        assert!(prefetcher.plan(&controller, PlaybackDirection::Backward).is_empty());
+    }
+
+    #[test]
+    fn test_cache_warmer_yields_and_stops() {
+        let mut controller = AnimationController::new(10);
+        controller.set_frame_count(8);
+        let mut cached = vec![false; 8];
+        cached[2] = true;
+        let yields = std::cell::Cell::new(0);
+        let warmer = CacheWarmer {window: 5, frames_per_slice: 2};
+        let render = |index: usize| !std::mem::replace(&mut cached[index], true);
+        let rendered = block_on(warmer.run(&controller, PlaybackDirection::Forward, render, || false, || async { yields.set(yields.get() + 1) }));
+        assert_eq!((rendered, yields.get()), (4, 2));
+        assert_eq!(cached, [false, true, true, true, true, true, false, false]);
+
+        let renders = std::cell::Cell::new(0);
+        let stopped = block_on(warmer.run(&controller, PlaybackDirection::Backward, |_| { renders.set(renders.get() + 1); true }, || renders.get() >= 1, || async {}));
+        assert_eq!(stopped, 1);
+    }
+
+    #[test]
+    fn test_prefetch_fetches_through_provider() {
+        let mut provider = MemoryProvider::with_texts(&["a", "b", "c"]);
+        provider.cframes.insert("frame_0002.txt".into(), crate::encode_cframe(&CFrameData::new(1, 1, vec![b'b'], vec![0; 3])).unwrap());
+        let files = block_on(provider.get_frame_files("")).unwrap();
+        let mut controller = AnimationController::new(10);
+        controller.set_frame_count(3);
+
+        let mut fetched = Vec::new();
+        let mut prefetcher = Prefetcher::new(2);
+        let count = block_on(prefetcher.fetch(&provider, &files, &controller, PlaybackDirection::Forward, |index, cframe| fetched.push((index, cframe.is_some())))).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(fetched, [(1, true), (2, false)]);
+    }
+}
@@ -0,0 +1,195 @@
+//! Python bindings (pyo3) for data-pipeline use.
+//!
+//! Exposes parsing, conversion, cell editing, and export so scripts can
+//! batch-convert and analyze cascii projects while staying byte-compatible
+//! with the viewer. Build a wheel with maturin, enabling
+//! `pyo3/extension-module`:
+//!
+//! ```text
+//! maturin build --release --features python,pyo3/extension-module
+//! ```
+//!
+//! ```python
+//! import cascii_core_view as cv
+//!
+//! frame = cv.parse_cframe(open("frame_0001.cframe", "rb").read())
+//! frame.set_cell(0, 0, "#", (255, 0, 0))
+//! open("frame_0001.cframe", "wb").write(frame.encode())
+//! ```
+
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::render::raster::rasterize_cframe;
+use crate::render::sixel::render_to_sixel;
+use crate::{CFrameData, ParseError, RenderConfig};
+
+fn to_py_err(err: ParseError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// A color frame (`CFrameData`) exposed to Python as `CFrame`.
+#[pyclass(name = "CFrame")]
+#[derive(Clone)]
+pub struct PyCFrame {
+    inner: CFrameData,
+}
+
+impl PyCFrame {
+    fn index(&self, row: usize, col: usize) -> PyResult<usize> {
+        if row >= self.inner.height as usize || col >= self.inner.width as usize {
+            return Err(PyIndexError::new_err(format!("cell ({}, {}) out of bounds", row, col)));
+        }
+        Ok(row * self.inner.width as usize + col)
+    }
+}
+
+#[pymethods]
+impl PyCFrame {
+    /// Create a blank frame filled with spaces in `color`.
+    #[new]
+    #[pyo3(signature = (width, height, color = (255, 255, 255)))]
+    fn new(width: u32, height: u32, color: (u8, u8, u8)) -> Self {
+        let count = width as usize * height as usize;
+        let rgb = [color.0, color.1, color.2].repeat(count);
+        Self {inner: CFrameData::new(width, height, vec![b' '; count], rgb)}
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.inner.width
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.inner.height
+    }
+
+    #[getter]
+    fn chars<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.chars)
+    }
+
+    #[getter]
+    fn rgb<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.rgb)
+    }
+
+    #[getter]
+    fn bg_rgb<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyBytes>> {
+        self.inner.bg_rgb.as_ref().map(|bg| PyBytes::new(py, bg))
+    }
+
+    /// `(char, (r, g, b))` of one cell.
+    fn cell(&self, row: usize, col: usize) -> PyResult<(char, (u8, u8, u8))> {
+        self.index(row, col)?;
+        Ok((self.inner.chars[row * self.inner.width as usize + col] as char, self.inner.rgb_at(row, col).unwrap_or((0, 0, 0))))
+    }
+
+    /// Overwrite one cell's character and foreground color.
+    fn set_cell(&mut self, row: usize, col: usize, ch: char, color: (u8, u8, u8)) -> PyResult<()> {
+        let idx = self.index(row, col)?;
+        if !ch.is_ascii() {
+            return Err(PyValueError::new_err("cell characters must be ASCII"));
+        }
+        self.inner.chars[idx] = ch as u8;
+        self.inner.rgb[idx * 3..idx * 3 + 3].copy_from_slice(&[color.0, color.1, color.2]);
+        Ok(())
+    }
+
+    /// Plain text with one line per row, optionally without trailing spaces.
+    #[pyo3(signature = (trim_trailing_spaces = false))]
+    fn to_text(&self, trim_trailing_spaces: bool) -> String {
+        self.inner.to_text_with(crate::TextOptions {trim_trailing_spaces, ..Default::default()})
+    }
+
+    /// Encode back to `.cframe` bytes.
+    fn encode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let bytes = crate::encode_cframe(&self.inner).map_err(to_py_err)?;
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    /// Rasterize to `(width, height, rgb_bytes)` at `font_size` pixels.
+    #[pyo3(signature = (font_size = 12.0))]
+    fn rasterize<'py>(&self, py: Python<'py>, font_size: f64) -> (u32, u32, Bound<'py, PyBytes>) {
+        let image = rasterize_cframe(&self.inner, &RenderConfig::new(font_size));
+        (image.width, image.height, PyBytes::new(py, &image.pixels))
+    }
+
+    /// Encode as a SIXEL escape sequence.
+    #[pyo3(signature = (font_size = 12.0))]
+    fn to_sixel(&self, font_size: f64) -> String {
+        render_to_sixel(&self.inner, &RenderConfig::new(font_size))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CFrame({}x{})", self.inner.width, self.inner.height)
+    }
+}
+
+/// Parse `.cframe` bytes.
+#[pyfunction]
+fn parse_cframe(data: &[u8]) -> PyResult<PyCFrame> {
+    crate::parse_cframe(data).map(|inner| PyCFrame {inner}).map_err(to_py_err)
+}
+
+/// Extract the plain text of `.cframe` bytes.
+#[pyfunction]
+fn parse_cframe_text(data: &[u8]) -> PyResult<String> {
+    crate::parse_cframe_text(data).map_err(to_py_err)
+}
+
+/// Parse a packed multi-frame blob into a list of frames.
+#[pyfunction]
+fn parse_packed_cframes(data: &[u8]) -> PyResult<Vec<PyCFrame>> {
+    let blob = crate::parse_packed_cframes(data).map_err(to_py_err)?;
+    Ok((0..blob.len()).filter_map(|i| blob.decode_frame(i)).map(|inner| PyCFrame {inner}).collect())
+}
+
+#[pymodule]
+fn cascii_core_view(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyCFrame>()?;
+    m.add_function(wrap_pyfunction!(parse_cframe, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_cframe_text, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_packed_cframes, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_cell_editing() {
+        let mut frame = PyCFrame::new(2, 1, (1, 2, 3));
+        assert_eq!((frame.width(), frame.height(), frame.__repr__()), (2, 1, "CFrame(2x1)".to_string()));
+
+        frame.set_cell(0, 1, '#', (255, 0, 0)).unwrap();
+        assert_eq!(frame.cell(0, 1).unwrap(), ('#', (255, 0, 0)));
+        assert_eq!(frame.cell(0, 0).unwrap(), (' ', (1, 2, 3)));
+        assert_eq!((frame.to_text(false), frame.to_text(true)), (" #\n".to_string(), " #\n".to_string()));
+
+        assert!(frame.cell(1, 0).is_err());
+        assert!(frame.set_cell(0, 0, 'é', (0, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_python_parse_round_trip() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut frame = PyCFrame::new(1, 1, (0, 0, 0));
+            frame.set_cell(0, 0, 'A', (9, 8, 7)).unwrap();
+            let bytes = frame.encode(py).unwrap();
+
+            let parsed = parse_cframe(bytes.as_bytes()).unwrap();
+            assert_eq!(parsed.rgb(py).as_bytes(), [9, 8, 7]);
+            assert_eq!(parse_cframe_text(bytes.as_bytes()).unwrap(), "A\n");
+            assert!(parse_cframe(&bytes.as_bytes()[..4]).is_err_and(|err| err.is_instance_of::<PyValueError>(py)));
+
+            let packed = parse_packed_cframes(&[1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, b'x', 1, 2, 3]).unwrap();
+            assert_eq!(packed.len(), 1);
+            assert_eq!(packed[0].cell(0, 0).unwrap(), ('x', (1, 2, 3)));
+        });
+    }
+}
@@ -0,0 +1,88 @@
+//! Named regions of interest on frames.
+//!
+//! A [`Region`] marks a rectangle of cells (e.g. "face" or "logo") in a
+//! frame's [`FrameMeta`](crate::FrameMeta). Regions can be hit-tested by cell,
+//! so hosts can react to clicks on parts of an ASCII scene, and drawn as
+//! outlines through [`region_outlines`].
+
+use crate::render::{CellRectBatch, RenderConfig, RenderLayer};
+
+/// A named rectangle of cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Region {
+    /// Name used to look the region up
+    pub name: String,
+    /// Leftmost column
+    pub x: u32,
+    /// Top row
+    pub y: u32,
+    /// Width in columns
+    pub width: u32,
+    /// Height in rows
+    pub height: u32,
+}
+
+impl Region {
+    /// Create a region covering `width` x `height` cells from (`x`, `y`).
+    pub fn new(name: impl Into<String>, x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {name: name.into(), x, y, width, height}
+    }
+
+    /// Returns `true` when cell (`col`, `row`) lies inside the region.
+    #[inline]
+    pub fn contains(&self, col: usize, row: usize) -> bool {
+        let (x, y) = (self.x as usize, self.y as usize);
+        col >= x && col < x + self.width as usize && row >= y && row < y + self.height as usize
+    }
+}
+
+/// Outline each region with `thickness`-pixel lines in `color`.
+///
+/// Positions use `config`'s cell size with the frame's top-left cell at the
+/// origin; shift the layer by the letterbox margins with
+/// [`RenderLayer::extend_offset`] before adding it to a
+/// [`RenderResult::overlay`](crate::RenderResult::overlay).
+pub fn region_outlines(regions: &[Region], config: &RenderConfig, thickness: f64, color: (u8, u8, u8)) -> RenderLayer {
+    let (cw, lh) = (config.char_width(), config.line_height());
+    let mut layer = RenderLayer::default();
+    for region in regions.iter().filter(|region| region.width > 0 && region.height > 0) {
+        let (x, y) = (region.x as f64 * cw, region.y as f64 * lh);
+        let (width, height) = (region.width as f64 * cw, region.height as f64 * lh);
+        let line = thickness.min(width / 2.0).min(height / 2.0);
+        let mut push = |x, y, width, height| layer.rects.push(CellRectBatch {x, y, width, height, color});
+        push(x, y, width, line);
+        push(x, y + height - line, width, line);
+        push(x, y + line, line, height - 2.0 * line);
+        push(x + width - line, y + line, line, height - 2.0 * line);
+    }
+    layer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frame, FrameMeta};
+
+    #[test]
+    fn test_region_hit_testing() {
+        let meta = FrameMeta {regions: vec![Region::new("scene", 0, 0, 10, 5), Region::new("face", 2, 1, 3, 2)], ..FrameMeta::default()};
+        let frame = Frame::text_only("x\n".into()).with_meta(meta);
+
+        assert_eq!(frame.region_at(3, 2).map(|region| region.name.as_str()), Some("face"));
+        assert_eq!(frame.region_at(5, 2).map(|region| region.name.as_str()), Some("scene"));
+        assert_eq!(frame.region_at(10, 0), None);
+        assert_eq!(frame.meta.as_ref().unwrap().region("face").map(|region| region.x), Some(2));
+    }
+
+    #[test]
+    fn test_region_outlines() {
+        let config = RenderConfig::new(10.0);
+        let (cw, lh) = (config.char_width(), config.line_height());
+        let layer = region_outlines(&[Region::new("logo", 1, 1, 2, 1), Region::new("empty", 0, 0, 0, 3)], &config, 1.0, (255, 0, 0));
+
+        assert_eq!(layer.rects.len(), 4);
+        assert_eq!((layer.rects[0].x, layer.rects[0].y, layer.rects[0].width, layer.rects[0].height), (cw, lh, 2.0 * cw, 1.0));
+        assert_eq!(layer.rects[3].x, 3.0 * cw - 1.0);
+    }
+}
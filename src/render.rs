@@ -1,6 +1,144 @@
 //! Rendering logic for ASCII frames.
 
-use crate::{CFrameData, FontSizing};
+use std::borrow::Cow;
+
+use crate::{CFrameData, CharAdvances, ColorAdjust, ColorMetric, ColorVision, FontSizing, Frame, FrameColors, FrameSource, Palette};
+use post::{PostEffect, PostRect};
+use viewport::Viewport;
+
+pub mod cache;
+pub mod dense;
+mod font;
+pub mod post;
+pub mod raster;
+pub mod sixel;
+pub mod svg;
+pub mod terminal;
+pub mod viewport;
+#[cfg(feature = "webgl")]
+pub mod webgl;
+
+/// How cells are turned into draw commands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RenderMode {
+    /// Glyphs drawn in their foreground color
+    #[default]
+    Text,
+    /// Each visible cell drawn as a filled rectangle in its foreground color,
+    /// for a mosaic / pixel-art look. With `show_chars`, glyphs are drawn on
+    /// top in the cell's background color.
+    Blocks { show_chars: bool },
+}
+
+/// CSS `font-style` used for glyphs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FontStyle {
+    /// Upright glyphs
+    #[default]
+    Normal,
+    /// The font's italic face
+    Italic,
+    /// Slanted upright glyphs
+    Oblique,
+}
+
+impl FontStyle {
+    /// The CSS keyword for this style.
+    pub fn as_css(self) -> &'static str {
+        match self {
+            FontStyle::Normal => "normal",
+            FontStyle::Italic => "italic",
+            FontStyle::Oblique => "oblique",
+        }
+    }
+}
+
+/// Normal CSS `font-weight`.
+pub const FONT_WEIGHT_NORMAL: u16 = 400;
+
+/// Bold CSS `font-weight`.
+pub const FONT_WEIGHT_BOLD: u16 = 700;
+
+/// Glow (text-shadow) drawn behind glyphs, for a phosphor-terminal look.
+///
+/// Only the 2D canvas renderer draws it (as `shadowBlur`); the WebGL, SVG,
+/// and raster backends ignore it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Glow {
+    /// Shadow color; `None` glows in each batch's own color
+    pub color: Option<(u8, u8, u8)>,
+    /// Blur radius in logical pixels, scaled by the device pixel ratio
+    /// when drawn
+    pub blur_radius: f64,
+}
+
+impl Glow {
+    /// A glow of `blur_radius` pixels in each glyph's own color.
+    pub fn new(blur_radius: f64) -> Self {
+        Self {color: None, blur_radius}
+    }
+
+    /// Shadow color for a batch drawn in `text_color`.
+    #[inline]
+    pub fn color_for(&self, text_color: (u8, u8, u8)) -> (u8, u8, u8) {
+        self.color.unwrap_or(text_color)
+    }
+}
+
+/// How the frame is composited over what is already on the target
+/// (the backdrop, on canvas), mirroring `globalCompositeOperation`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CompositeMode {
+    /// Draw on top
+    #[default]
+    SourceOver,
+    /// Draw underneath existing content
+    DestinationOver,
+    /// Multiply colors (darkens)
+    Multiply,
+    /// Screen colors (lightens)
+    Screen,
+    /// Multiply or screen depending on the backdrop
+    Overlay,
+    /// Add colors
+    Lighter,
+}
+
+impl CompositeMode {
+    /// The canvas `globalCompositeOperation` value for this mode.
+    pub fn as_css(self) -> &'static str {
+        match self {
+            CompositeMode::SourceOver => "source-over",
+            CompositeMode::DestinationOver => "destination-over",
+            CompositeMode::Multiply => "multiply",
+            CompositeMode::Screen => "screen",
+            CompositeMode::Overlay => "overlay",
+            CompositeMode::Lighter => "lighter",
+        }
+    }
+
+    /// Blend a `top` color onto a `base` color, per channel.
+    pub fn blend(self, base: (u8, u8, u8), top: (u8, u8, u8)) -> (u8, u8, u8) {
+        let channel = |a: u8, b: u8| -> u8 {
+            let (a, b) = (a as u32, b as u32);
+            let value = match self {
+                CompositeMode::SourceOver => b,
+                CompositeMode::DestinationOver => a,
+                CompositeMode::Multiply => a * b / 255,
+                CompositeMode::Screen => 255 - (255 - a) * (255 - b) / 255,
+                CompositeMode::Overlay if a < 128 => 2 * a * b / 255,
+                CompositeMode::Overlay => 255 - 2 * (255 - a) * (255 - b) / 255,
+                CompositeMode::Lighter => (a + b).min(255),
+            };
+            value as u8
+        };
+        (channel(base.0, top.0), channel(base.1, top.1), channel(base.2, top.2))
+    }
+}
 
 /// Configuration for rendering a frame.
 #[derive(Clone, Debug)]
@@ -11,16 +149,59 @@ pub struct RenderConfig {
     pub sizing: FontSizing,
     /// CSS font family used for web canvas rendering
     pub font_family: String,
+    /// CSS font weight (100 - 900, [`FONT_WEIGHT_NORMAL`] by default)
+    pub font_weight: u16,
+    /// CSS font style
+    pub font_style: FontStyle,
     /// Stroke width in CSS pixels applied before filling glyphs.
     pub text_stroke_width: f64,
-    /// Optional background color for web canvas rendering
+    /// Optional background color; emitted as [`RenderResult::background`]
     pub background_color: Option<(u8, u8, u8)>,
+    /// Text or block (mosaic) rendering
+    pub mode: RenderMode,
+    /// Device pixel ratio for canvas backing stores. `None` auto-detects
+    /// `window.devicePixelRatio` on web. [`RenderResult`] coordinates stay in
+    /// logical (CSS) pixels either way.
+    pub device_pixel_ratio: Option<f64>,
+    /// Global opacity in 0.0 - 1.0 applied to the whole frame, e.g. for fades
+    /// during transitions or while colors are still loading
+    pub opacity: f64,
+    /// Brightness / contrast / gamma / saturation applied to cell colors
+    /// before batching
+    pub adjust: ColorAdjust,
+    /// Color-blindness simulation or daltonization of cell colors (after
+    /// `adjust`, before `palette`)
+    pub color_vision: Option<ColorVision>,
+    /// Remap every cell color to the nearest palette color (after `adjust`)
+    /// before batching
+    pub palette: Option<Palette>,
+    /// Neighbouring cells whose colors are within this distance of a run's
+    /// first color are drawn in that color and merged into one batch. `0.0`
+    /// (the default) only merges identical colors.
+    pub color_tolerance: f64,
+    /// Metric used for `color_tolerance`
+    pub color_metric: ColorMetric,
+    /// Only render the cells inside this window, scaled by its zoom.
+    /// Coordinates in the result are relative to the viewport.
+    pub viewport: Option<Viewport>,
+    /// Center the frame inside a container of this logical size in pixels;
+    /// the margins are reported as [`RenderResult::letterbox`]
+    pub container: Option<(f64, f64)>,
+    /// Glow behind glyphs, emitted as [`RenderResult::glow`]; 2D canvas
+    /// only
+    pub glow: Option<Glow>,
+    /// Scanline / vignette post-processing, emitted as [`RenderResult::post`]
+    pub post_effect: Option<PostEffect>,
+    /// Record [`RenderStats`] in [`RenderResult::stats`]
+    pub collect_stats: bool,
+    /// Compositing of the frame over the canvas backdrop
+    pub composite: CompositeMode,
 }
 
 impl RenderConfig {
     /// Create a new render config with the given font size.
     pub fn new(font_size: f64) -> Self {
-        Self {font_size, sizing: FontSizing::default(), font_family: "monospace".to_string(), text_stroke_width: 0.0, background_color: None}
+        Self {font_size, sizing: FontSizing::default(), font_family: "monospace".to_string(), font_weight: FONT_WEIGHT_NORMAL, font_style: FontStyle::Normal, text_stroke_width: 0.0, background_color: None, mode: RenderMode::Text, device_pixel_ratio: None, opacity: 1.0, adjust: ColorAdjust::default(), color_vision: None, palette: None, color_tolerance: 0.0, color_metric: ColorMetric::Rgb, viewport: None, container: None, glow: None, post_effect: None, collect_stats: false, composite: CompositeMode::SourceOver}
     }
 
     /// Get the character width for this config.
@@ -35,10 +216,86 @@ impl RenderConfig {
         self.sizing.line_height(self.font_size)
     }
 
+    /// Reduce `font_size` so a `cols` x `rows` frame fits the sizing's
+    /// maximum canvas dimensions, scaled by the device pixel ratio override
+    /// or else the sizing's scale factor. Returns `true` when the size was
+    /// reduced.
+    pub fn clamp_to_max_canvas(&mut self, cols: usize, rows: usize) -> bool {
+        let scale = self.device_pixel_ratio.filter(|dpr| dpr.is_finite() && *dpr > 0.0).unwrap_or_else(|| self.sizing.effective_scale_factor());
+        let limit = self.sizing.max_font_size_for_canvas_at(cols, rows, scale);
+        if self.font_size > limit {
+            self.font_size = limit;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Hash of every field that affects layout or output, for keying caches
+    /// of rendered frames.
+    pub fn cache_key(&self) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        self.font_size.to_bits().hash(&mut hasher);
+        self.sizing.char_width_ratio.to_bits().hash(&mut hasher);
+        self.sizing.line_height_ratio.to_bits().hash(&mut hasher);
+        self.font_family.hash(&mut hasher);
+        self.font_weight.hash(&mut hasher);
+        self.font_style.hash(&mut hasher);
+        self.text_stroke_width.to_bits().hash(&mut hasher);
+        self.background_color.hash(&mut hasher);
+        self.mode.hash(&mut hasher);
+        self.opacity.to_bits().hash(&mut hasher);
+        for value in [self.adjust.brightness, self.adjust.contrast, self.adjust.gamma, self.adjust.saturation] {
+            value.to_bits().hash(&mut hasher);
+        }
+        self.adjust.space.hash(&mut hasher);
+        self.color_vision.hash(&mut hasher);
+        self.palette.as_ref().map(|palette| palette.colors()).hash(&mut hasher);
+        self.color_tolerance.to_bits().hash(&mut hasher);
+        self.color_metric.hash(&mut hasher);
+        if let Some(viewport) = &self.viewport {
+            (viewport.offset_x, viewport.offset_y, viewport.cols, viewport.rows, viewport.zoom.to_bits()).hash(&mut hasher);
+        }
+        self.container.map(|(width, height)| (width.to_bits(), height.to_bits())).hash(&mut hasher);
+        self.glow.map(|glow| (glow.color, glow.blur_radius.to_bits())).hash(&mut hasher);
+        self.composite.hash(&mut hasher);
+        if let Some(advances) = &self.sizing.advances {
+            advances.ratios().iter().for_each(|ratio| ratio.to_bits().hash(&mut hasher));
+        }
+        if let Some(effect) = &self.post_effect {
+            for value in [effect.scanline_spacing, effect.scanline_thickness, effect.scanline_opacity, effect.vignette] {
+                value.to_bits().hash(&mut hasher);
+            }
+            (effect.vignette_steps, effect.color).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// The glow to draw, if any has a positive blur radius.
+    #[inline]
+    pub(crate) fn active_glow(&self) -> Option<Glow> {
+        self.glow.filter(|glow| glow.blur_radius > 0.0)
+    }
+
     /// Build the CSS font string for this config.
     #[inline]
+    ///
+    /// Style and weight are only included when they differ from the defaults,
+    /// e.g. `"italic 700 12.00px monospace"`.
     pub fn font_string(&self) -> String {
-        format!("{:.2}px {}", self.font_size, self.font_family)
+        let mut font = String::new();
+        if self.font_style != FontStyle::Normal {
+            font.push_str(self.font_style.as_css());
+            font.push(' ');
+        }
+        if self.font_weight != FONT_WEIGHT_NORMAL {
+            font.push_str(&format!("{} ", self.font_weight));
+        }
+        font.push_str(&format!("{:.2}px {}", self.font_size, self.font_family));
+        font
     }
 }
 
@@ -53,22 +310,163 @@ impl Default for RenderConfig {
 /// This is a platform-agnostic representation of what needs to be drawn.
 /// Each consumer can interpret these commands for their rendering backend.
 ///
-/// Rendering order is fixed: paint `background_batches` first, then
-/// `batches` (text) on top.
+/// Rendering order is fixed: paint the full-canvas `background` fill first,
+/// then `background_batches`, then `batches` (text) on top, then the `post`
+/// effect rects, then the `overlay` layer. In [`RenderMode::Blocks`], the cell blocks follow the
+/// per-cell backgrounds inside `background_batches`.
+///
+/// With the `serde` feature the result can be computed on a server or worker
+/// and shipped to a thin client that only executes the draw commands.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RenderResult {
     /// Canvas width in pixels
     pub width: f64,
     /// Canvas height in pixels
     pub height: f64,
-    /// Per-cell background rectangle batches (drawn first)
+    /// Full-canvas fill from [`RenderConfig::background_color`] (drawn first)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub background: Option<CellRectBatch>,
+    /// Per-cell background rectangle batches (drawn after the fill)
     pub background_batches: Vec<CellRectBatch>,
     /// Text batches to draw (drawn after backgrounds)
     pub batches: Vec<TextBatch>,
+    /// Overlay primitives drawn on top of the frame (HUDs, OSDs)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub overlay: RenderLayer,
+    /// Margins around the frame when it is centered in
+    /// [`RenderConfig::container`]; cell batches are already offset by them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub letterbox: Letterbox,
+    /// Glow the 2D canvas renderer applies while drawing `batches` (not
+    /// backgrounds or the overlay)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub glow: Option<Glow>,
+    /// Translucent post-processing rects (scanlines, vignette) drawn over
+    /// the frame, below the overlay
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub post: Vec<PostRect>,
+    /// Profiling counters, when [`RenderConfig::collect_stats`] is set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stats: Option<RenderStats>,
+}
+
+/// Profiling counters for one [`render_cframe`] call.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderStats {
+    /// Number of text batches
+    pub batch_count: usize,
+    /// Number of per-cell background and block rect batches
+    pub background_batch_count: usize,
+    /// Characters emitted across all text batches
+    pub chars_drawn: usize,
+    /// Cells that produced no glyph (empty or skipped as invisible)
+    pub cells_skipped: usize,
+    /// Wall-clock time spent in `render_cframe`, in milliseconds
+    pub elapsed_ms: f64,
+}
+
+impl RenderStats {
+    /// Total draw calls a canvas backend issues for the result.
+    #[inline]
+    pub fn draw_calls(&self) -> usize {
+        self.batch_count + self.background_batch_count
+    }
+}
+
+impl RenderResult {
+    /// Map a point in canvas pixels to the `(col, row)` of a `cols` x `rows`
+    /// frame, accounting for the letterbox margins. Returns `None` for
+    /// points on the margins or outside the canvas.
+    pub fn cell_at(&self, x: f64, y: f64, cols: usize, rows: usize) -> Option<(usize, usize)> {
+        let content_width = self.width - self.letterbox.left - self.letterbox.right;
+        let content_height = self.height - self.letterbox.top - self.letterbox.bottom;
+        let (x, y) = (x - self.letterbox.left, y - self.letterbox.top);
+        if cols == 0 || rows == 0 || !(0.0..content_width).contains(&x) || !(0.0..content_height).contains(&y) {
+            return None;
+        }
+        let col = (x / (content_width / cols as f64)) as usize;
+        let row = (y / (content_height / rows as f64)) as usize;
+        Some((col.min(cols - 1), row.min(rows - 1)))
+    }
+}
+
+/// Margins in pixels around a frame centered inside a larger container.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Letterbox {
+    /// Margin left of the frame
+    pub left: f64,
+    /// Margin above the frame
+    pub top: f64,
+    /// Margin right of the frame
+    pub right: f64,
+    /// Margin below the frame
+    pub bottom: f64,
+}
+
+impl Letterbox {
+    /// Margins that center a `width` x `height` frame in `container`. Axes
+    /// where the frame doesn't fit get no margin (the frame overflows).
+    pub fn center(width: f64, height: f64, container: (f64, f64)) -> Self {
+        let left = ((container.0 - width) / 2.0).max(0.0).floor();
+        let top = ((container.1 - height) / 2.0).max(0.0).floor();
+        Self {left, top, right: (container.0 - width - left).max(0.0), bottom: (container.1 - height - top).max(0.0)}
+    }
+
+    /// Margins for `config.container`, or none when it is unset.
+    pub(crate) fn for_config(width: f64, height: f64, config: &RenderConfig) -> Self {
+        config.container.map(|container| Self::center(width, height, container)).unwrap_or_default()
+    }
+
+    /// Returns `true` when there are no margins.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn offset_cells(background_batches: &mut [CellRectBatch], batches: &mut [TextBatch], letterbox: &Letterbox) {
+    for batch in background_batches {
+        batch.x += letterbox.left;
+        batch.y += letterbox.top;
+    }
+    for batch in batches {
+        batch.x += letterbox.left;
+        batch.y += letterbox.top;
+    }
+}
+
+/// A self-contained group of draw primitives.
+///
+/// Within a layer, `rects` are painted first and `batches` on top.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderLayer {
+    /// Filled rectangles
+    pub rects: Vec<CellRectBatch>,
+    /// Text runs
+    pub batches: Vec<TextBatch>,
+}
+
+impl RenderLayer {
+    /// Returns `true` when the layer has nothing to draw.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty() && self.batches.is_empty()
+    }
+
+    /// Append every primitive of `other`, shifted by `(dx, dy)` pixels.
+    pub fn extend_offset(&mut self, other: &RenderLayer, dx: f64, dy: f64) {
+        self.rects.extend(other.rects.iter().map(|r| CellRectBatch {x: r.x + dx, y: r.y + dy, ..r.clone()}));
+        self.batches.extend(other.batches.iter().map(|b| TextBatch {x: b.x + dx, y: b.y + dy, ..b.clone()}));
+    }
 }
 
 /// A batch of text with the same color to be drawn at a position.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextBatch {
     /// Text content to draw
     pub text: String,
@@ -78,12 +476,25 @@ pub struct TextBatch {
     pub y: f64,
     /// RGB color as (r, g, b)
     pub color: (u8, u8, u8),
+    /// Opacity in 0.0 - 1.0, multiplied with [`RenderConfig::opacity`]
+    #[cfg_attr(feature = "serde", serde(default = "opaque"))]
+    pub opacity: f64,
+}
+
+#[cfg(feature = "serde")]
+fn opaque() -> f64 {
+    1.0
 }
 
 impl TextBatch {
-    /// Get the color as a CSS-compatible string "rgb(r,g,b)"
+    /// Get the color as a CSS-compatible string: "rgb(r,g,b)", or
+    /// "rgba(r,g,b,a)" when the batch is translucent
     pub fn color_string(&self) -> String {
-        format!("rgb({},{},{})", self.color.0, self.color.1, self.color.2)
+        if self.opacity < 1.0 {
+            format!("rgba({},{},{},{})", self.color.0, self.color.1, self.color.2, self.opacity.clamp(0.0, 1.0))
+        } else {
+            format!("rgb({},{},{})", self.color.0, self.color.1, self.color.2)
+        }
     }
 }
 
@@ -93,6 +504,7 @@ impl TextBatch {
 /// rectangles, drastically cutting the number of draw calls when large
 /// regions share a color.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CellRectBatch {
     /// X position of the leftmost cell in this run, in pixels
     pub x: f64,
@@ -142,60 +554,386 @@ impl CellRectBatch {
 /// assert_eq!(result.batches.len(), 2);
 /// ```
 pub fn render_cframe(cframe: &CFrameData, config: &RenderConfig) -> RenderResult {
+    let started = config.collect_stats.then(crate::clock::now_ms);
+    let (cframe, config) = viewport::apply(cframe, config);
+    let (cframe, config) = (cframe.as_ref(), config.as_ref());
+    let char_width = config.char_width();
+    let line_height = config.line_height();
+    let frame_width = cframe.width as f64 * char_width;
+    let frame_height = cframe.height as f64 * line_height;
+    let letterbox = Letterbox::for_config(frame_width, frame_height, config);
+    let canvas_width = frame_width + letterbox.left + letterbox.right;
+    let canvas_height = frame_height + letterbox.top + letterbox.bottom;
+
+    let (mut background_batches, mut batches) = build_cell_batches(cframe, config, None);
+    offset_cells(&mut background_batches, &mut batches, &letterbox);
+    let background = config.background_color.map(|color| CellRectBatch {x: 0.0, y: 0.0, width: canvas_width, height: canvas_height, color});
+
+    let post = config.post_effect.map(|effect| effect.rects((letterbox.left, letterbox.top, frame_width, frame_height))).unwrap_or_default();
+
+    let stats = started.map(|started| {
+        let chars_drawn = batches.iter().map(|batch| batch.text.len()).sum();
+        RenderStats {batch_count: batches.len(), background_batch_count: background_batches.len(), chars_drawn, cells_skipped: cframe.pixel_count().saturating_sub(chars_drawn), elapsed_ms: crate::clock::now_ms() - started}
+    });
+
+    RenderResult {width: canvas_width, height: canvas_height, background, background_batches, batches, overlay: RenderLayer::default(), letterbox, glow: config.active_glow(), post, stats}
+}
+
+/// Generate render commands for a text-only frame.
+///
+/// Each non-blank line becomes one batch in `colors.foreground` (after the
+/// config's color adjustment and palette), and the full-canvas fill uses
+/// [`RenderConfig::background_color`], falling back to `colors.background`.
+/// This lets text-only playback share the [`RenderResult`] pipeline with
+/// color frames, including the [`RenderConfig::viewport`] zoom and pan.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{Frame, FrameColors, RenderConfig};
+/// use cascii_core_view::render::render_text_frame;
+///
+/// let frame = Frame::text_only("ab\n\n c".to_string());
+/// let colors = FrameColors {foreground: (0, 255, 0), background: (0, 0, 0)};
+/// let result = render_text_frame(&frame, &colors, &RenderConfig::new(12.0));
+/// assert_eq!(result.batches.len(), 2);
+/// assert_eq!(result.batches[1].text, " c");
+/// ```
+pub fn render_text_frame(frame: &Frame, colors: &FrameColors, config: &RenderConfig) -> RenderResult {
+    let started = config.collect_stats.then(crate::clock::now_ms);
+    let (frame, config) = viewport::apply_text(frame, config);
+    let (frame, config) = (frame.as_ref(), config.as_ref());
     let char_width = config.char_width();
     let line_height = config.line_height();
-    let canvas_width = cframe.width as f64 * char_width;
-    let canvas_height = cframe.height as f64 * line_height;
+    let (cols, rows) = frame.dimensions();
+    let frame_width = cols as f64 * char_width;
+    let frame_height = rows as f64 * line_height;
+    let letterbox = Letterbox::for_config(frame_width, frame_height, config);
+    let canvas_width = frame_width + letterbox.left + letterbox.right;
+    let canvas_height = frame_height + letterbox.top + letterbox.bottom;
+
+    let mut color = config.adjust.apply(colors.foreground);
+    if let Some(vision) = config.color_vision {
+        color = vision.apply(color);
+    }
+    if let Some(palette) = &config.palette {
+        color = palette.nearest(color);
+    }
+    let mut batches: Vec<TextBatch> = frame.content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).map(|(row, line)| {
+        TextBatch {text: line.to_string(), x: 0.0, y: row as f64 * line_height, color, opacity: 1.0}
+    }).collect();
+    if let Some(advances) = &config.sizing.advances {
+        batches = align_to_advances(batches, advances, config);
+    }
+    offset_cells(&mut [], &mut batches, &letterbox);
+    let fill = config.background_color.unwrap_or(colors.background);
+    let background = Some(CellRectBatch {x: 0.0, y: 0.0, width: canvas_width, height: canvas_height, color: fill});
+    let post = config.post_effect.map(|effect| effect.rects((letterbox.left, letterbox.top, frame_width, frame_height))).unwrap_or_default();
+
+    let stats = started.map(|started| {
+        let chars_drawn = batches.iter().map(|batch| batch.text.chars().filter(|ch| !ch.is_whitespace()).count()).sum();
+        RenderStats {batch_count: batches.len(), background_batch_count: 0, chars_drawn, cells_skipped: (cols * rows).saturating_sub(chars_drawn), elapsed_ms: crate::clock::now_ms() - started}
+    });
+
+    RenderResult {width: canvas_width, height: canvas_height, background, background_batches: Vec::new(), batches, overlay: RenderLayer::default(), letterbox, glow: config.active_glow(), post, stats}
+}
+
+/// Generate render commands for frame `index` of any [`FrameSource`].
+///
+/// Returns `None` when the frame is out of range, not ready yet, or has no
+/// color data.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, Frame, RenderConfig};
+/// use cascii_core_view::render::render_source_frame;
+///
+/// let frames = vec![Frame::text_only("hi".into()), Frame::with_color("A".into(), CFrameData::new(1, 1, vec![b'A'], vec![255, 0, 0]))];
+/// let config = RenderConfig::new(12.0);
+/// assert!(render_source_frame(&frames, 0, &config).is_none());
+/// assert_eq!(render_source_frame(&frames, 1, &config).unwrap().batches[0].color, (255, 0, 0));
+/// assert!(render_source_frame(&frames, 2, &config).is_none());
+/// ```
+pub fn render_source_frame<S: FrameSource + ?Sized>(source: &S, index: usize, config: &RenderConfig) -> Option<RenderResult> {
+    if !source.is_ready(index) {
+        return None;
+    }
+    source.cframe(index).map(|cframe| render_cframe(&cframe, config))
+}
+
+/// Like [`render_source_frame`], but text-only frames are rendered through
+/// [`render_text_frame`] with `colors` instead of being skipped.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, Frame, FrameColors, RenderConfig};
+/// use cascii_core_view::render::render_source_frame_or_text;
+///
+/// let frames = vec![Frame::text_only("hi".into()), Frame::with_color("A".into(), CFrameData::new(1, 1, vec![b'A'], vec![255, 0, 0]))];
+/// let colors = FrameColors {foreground: (0, 255, 0), background: (0, 0, 0)};
+/// let config = RenderConfig::new(12.0);
+/// assert_eq!(render_source_frame_or_text(&frames, 0, &colors, &config).unwrap().batches[0].text, "hi");
+/// assert_eq!(render_source_frame_or_text(&frames, 1, &colors, &config).unwrap().batches[0].color, (255, 0, 0));
+/// assert!(render_source_frame_or_text(&frames, 2, &colors, &config).is_none());
+/// ```
+pub fn render_source_frame_or_text<S: FrameSource + ?Sized>(source: &S, index: usize, colors: &FrameColors, config: &RenderConfig) -> Option<RenderResult> {
+    if !source.is_ready(index) {
+        return None;
+    }
+    let frame = source.get(index)?;
+    Some(match &frame.cframe {
+        Some(cframe) => render_cframe(cframe, config),
+        None => render_text_frame(&frame, colors, config),
+    })
+}
 
+/// Build background and text batches for the cells selected by `mask`
+/// (row-major, one flag per cell), or for every cell when `mask` is `None`.
+fn build_cell_batches(cframe: &CFrameData, config: &RenderConfig, mask: Option<&[bool]>) -> (Vec<CellRectBatch>, Vec<TextBatch>) {
+    // Adjust colors up front so cells that become equal batch together.
+    let cframe = adjusted_cframe(cframe, config);
+    let cframe = cframe.as_ref();
+    let char_width = config.char_width();
+    let line_height = config.line_height();
     let width = cframe.width as usize;
     let height = cframe.height as usize;
 
-    let background_batches = build_background_batches(cframe, char_width, line_height, width, height);
-    let batches = build_text_batches(cframe, char_width, line_height, width, height);
+    let mut background_batches = build_background_batches(cframe, mask, char_width, line_height, width, height);
+    let batches = match config.mode {
+        RenderMode::Text => build_text_batches(cframe, mask, char_width, line_height, width, height),
+        RenderMode::Blocks { show_chars } => {
+            background_batches.extend(build_block_batches(cframe, mask, char_width, line_height, width, height));
+            if show_chars {
+                build_block_text_batches(cframe, mask, config.background_color.unwrap_or((0, 0, 0)), char_width, line_height, width, height)
+            } else {
+                Vec::new()
+            }
+        }
+    };
+    let batches = match &config.sizing.advances {
+        Some(advances) => align_to_advances(batches, advances, config),
+        None => batches,
+    };
+    (background_batches, batches)
+}
+
+/// Split text batches so every glyph whose advance differs from the cell
+/// width is drawn on its own, centered in its cell. Runs of glyphs that do
+/// advance by one cell stay together, so their columns cannot drift.
+fn align_to_advances(batches: Vec<TextBatch>, advances: &CharAdvances, config: &RenderConfig) -> Vec<TextBatch> {
+    let char_width = config.char_width();
+    let deviation = |ch: char| advances.ratio(ch).map(|ratio| ratio * config.font_size).filter(|advance| (advance - char_width).abs() > char_width * 0.01);
+    let mut aligned = Vec::with_capacity(batches.len());
+    for batch in batches {
+        if !batch.text.chars().any(|ch| deviation(ch).is_some()) {
+            aligned.push(batch);
+            continue;
+        }
+        let mut run = String::new();
+        let mut run_col = 0;
+        for (col, ch) in batch.text.chars().enumerate() {
+            let Some(advance) = deviation(ch) else {
+                if run.is_empty() {
+                    run_col = col;
+                }
+                run.push(ch);
+                continue;
+            };
+            if !run.is_empty() {
+                aligned.push(TextBatch {text: std::mem::take(&mut run), x: batch.x + run_col as f64 * char_width, ..batch.clone()});
+            }
+            if !ch.is_whitespace() {
+                aligned.push(TextBatch {text: ch.to_string(), x: batch.x + col as f64 * char_width + (char_width - advance) / 2.0, ..batch.clone()});
+            }
+        }
+        if !run.is_empty() {
+            aligned.push(TextBatch {text: run, x: batch.x + run_col as f64 * char_width, ..batch});
+        }
+    }
+    aligned
+}
 
-    RenderResult {width: canvas_width, height: canvas_height, background_batches, batches}
+/// `cframe` with the config's color adjustment, palette, and tolerance
+/// merging applied to its foreground and background colors, borrowed
+/// unchanged when none is set.
+fn adjusted_cframe<'a>(cframe: &'a CFrameData, config: &RenderConfig) -> Cow<'a, CFrameData> {
+    let palette = config.palette.as_ref().filter(|palette| !palette.is_empty());
+    let merge = config.color_tolerance > 0.0;
+    if config.adjust.is_identity() && config.color_vision.is_none() && palette.is_none() && !merge {
+        return Cow::Borrowed(cframe);
+    }
+    let mut adjusted = cframe.clone();
+    let width = adjusted.width as usize;
+    let buffers = std::iter::once(&mut adjusted.rgb).chain(adjusted.bg_rgb.as_mut());
+    for buffer in buffers {
+        config.adjust.apply_in_place(buffer);
+        if let Some(vision) = config.color_vision {
+            vision.apply_in_place(buffer);
+        }
+        if let Some(palette) = palette {
+            palette.remap_in_place(buffer);
+        }
+        if merge {
+            crate::color::merge_similar_runs(buffer, width, config.color_tolerance, config.color_metric);
+        }
+    }
+    Cow::Owned(adjusted)
 }
 
-fn build_background_batches(cframe: &CFrameData, char_width: f64, line_height: f64, width: usize, height: usize) -> Vec<CellRectBatch> {
+/// Frames with at least this many cells are batched row-parallel with the
+/// `rayon` feature; below it the thread hand-off costs more than it saves.
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+const PARALLEL_MIN_CELLS: usize = 16_384;
+
+/// Run `build_row` for every row and concatenate the per-row output in row
+/// order.
+#[cfg(not(all(feature = "rayon", not(target_arch = "wasm32"))))]
+fn collect_rows<T>(_cells: usize, height: usize, build_row: impl Fn(usize, &mut Vec<T>)) -> Vec<T> {
+    let mut out = Vec::new();
+    for row in 0..height {
+        build_row(row, &mut out);
+    }
+    out
+}
+
+/// Run `build_row` for every row and concatenate the per-row output in row
+/// order, splitting frames of at least [`PARALLEL_MIN_CELLS`] across threads.
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+fn collect_rows<T: Send>(cells: usize, height: usize, build_row: impl Fn(usize, &mut Vec<T>) + Sync) -> Vec<T> {
+    use rayon::prelude::*;
+
+    if cells < PARALLEL_MIN_CELLS {
+        let mut out = Vec::new();
+        for row in 0..height {
+            build_row(row, &mut out);
+        }
+        return out;
+    }
+    let rows: Vec<Vec<T>> = (0..height).into_par_iter().map(|row| {
+        let mut out = Vec::new();
+        build_row(row, &mut out);
+        out
+    }).collect();
+    rows.into_iter().flatten().collect()
+}
+
+/// The first cell in `start + 1..end` whose RGB triple differs from cell
+/// `start`, or `end` when the whole range shares its color.
+///
+/// Cell `i` matches cell `i - 1` exactly when the bytes at `3i` match the
+/// bytes three earlier, so the run ends at the first mismatch between the
+/// buffer and itself shifted by one cell. That comparison is done eight
+/// bytes at a time with packed `u64` XORs.
+#[inline]
+pub(crate) fn color_run_end(rgb: &[u8], start: usize, end: usize) -> usize {
+    if end <= start + 1 {
+        return end.max(start);
+    }
+    let (Some(prev), Some(next)) = (rgb.get(start * 3..end * 3 - 3), rgb.get(start * 3 + 3..end * 3)) else {
+        return start + 1;
+    };
+    let mismatch = |offset: usize| start + 1 + offset / 3;
+    let mut offset = 0;
+    for (a, b) in prev.chunks_exact(8).zip(next.chunks_exact(8)) {
+        let diff = u64::from_le_bytes(a.try_into().unwrap()) ^ u64::from_le_bytes(b.try_into().unwrap());
+        if diff != 0 {
+            return mismatch(offset + diff.trailing_zeros() as usize / 8);
+        }
+        offset += 8;
+    }
+    match prev[offset..].iter().zip(&next[offset..]).position(|(a, b)| a != b) {
+        Some(position) => mismatch(offset + position),
+        None => end,
+    }
+}
+
+#[inline]
+fn included(mask: Option<&[bool]>, idx: usize) -> bool {
+    mask.map(|mask| mask.get(idx).copied().unwrap_or(false)).unwrap_or(true)
+}
+
+fn build_background_batches(cframe: &CFrameData, mask: Option<&[bool]>, char_width: f64, line_height: f64, width: usize, height: usize) -> Vec<CellRectBatch> {
     let Some(bg) = cframe.bg_rgb.as_ref() else { return Vec::new(); };
     if bg.len() != width * height * 3 {
         return Vec::new();
     }
-    let mut batches = Vec::new();
-    for row in 0..height {
+    collect_rows(width * height, height, |row, batches| {
         let mut col = 0;
         while col < width {
             let idx = row * width + col;
+            if !included(mask, idx) {
+                col += 1;
+                continue;
+            }
             let r = bg[idx * 3];
             let g = bg[idx * 3 + 1];
             let b = bg[idx * 3 + 2];
 
             let start_col = col;
+            let run_end = color_run_end(bg, idx, (row + 1) * width) - row * width;
             col += 1;
-            while col < width {
-                let next_idx = row * width + col;
-                let nr = bg[next_idx * 3];
-                let ng = bg[next_idx * 3 + 1];
-                let nb = bg[next_idx * 3 + 2];
-                if nr == r && ng == g && nb == b {
-                    col += 1;
-                } else {
-                    break;
-                }
+            while col < run_end && included(mask, row * width + col) {
+                col += 1;
             }
 
             batches.push(CellRectBatch {x: start_col as f64 * char_width, y: row as f64 * line_height, width: (col - start_col) as f64 * char_width, height: line_height, color: (r, g, b)});
         }
-    }
-    batches
+    })
 }
 
-fn build_text_batches(cframe: &CFrameData, char_width: f64, line_height: f64, width: usize, height: usize) -> Vec<TextBatch> {
-    let mut batches = Vec::new();
-    for row in 0..height {
+/// Block mode: one rect per run of visible cells sharing a foreground color.
+fn build_block_batches(cframe: &CFrameData, mask: Option<&[bool]>, char_width: f64, line_height: f64, width: usize, height: usize) -> Vec<CellRectBatch> {
+    let visible = |row: usize, col: usize| included(mask, row * width + col) && cframe.has_visible_foreground(row, col);
+    collect_rows(width * height, height, |row, batches| {
         let mut col = 0;
         while col < width {
-            if !cframe.has_visible_foreground(row, col) {
+            let Some(color) = cframe.rgb_at(row, col).filter(|_| visible(row, col)) else {
+                col += 1;
+                continue;
+            };
+            let start_col = col;
+            let run_end = color_run_end(&cframe.rgb, row * width + col, (row + 1) * width) - row * width;
+            col += 1;
+            while col < run_end && visible(row, col) {
+                col += 1;
+            }
+            batches.push(CellRectBatch {x: start_col as f64 * char_width, y: row as f64 * line_height, width: (col - start_col) as f64 * char_width, height: line_height, color});
+        }
+    })
+}
+
+/// Block mode glyphs: visible cells drawn in their background color so they
+/// read as cut-outs of the block underneath.
+fn build_block_text_batches(cframe: &CFrameData, mask: Option<&[bool]>, fallback: (u8, u8, u8), char_width: f64, line_height: f64, width: usize, height: usize) -> Vec<TextBatch> {
+    let visible = |row: usize, col: usize| included(mask, row * width + col) && cframe.has_visible_foreground(row, col);
+    let ink = |row: usize, col: usize| cframe.bg_rgb_at(row, col).unwrap_or(fallback);
+    collect_rows(width * height, height, |row, batches| {
+        let mut col = 0;
+        while col < width {
+            if !visible(row, col) {
+                col += 1;
+                continue;
+            }
+            let color = ink(row, col);
+            let start_col = col;
+            let mut text = String::new();
+            while col < width && visible(row, col) && ink(row, col) == color {
+                text.push(cframe.chars[row * width + col] as char);
+                col += 1;
+            }
+            batches.push(TextBatch {text, x: start_col as f64 * char_width, y: row as f64 * line_height, color, opacity: 1.0});
+        }
+    })
+}
+
+fn build_text_batches(cframe: &CFrameData, mask: Option<&[bool]>, char_width: f64, line_height: f64, width: usize, height: usize) -> Vec<TextBatch> {
+    let visible = |row: usize, col: usize| included(mask, row * width + col) && cframe.has_visible_foreground(row, col);
+    collect_rows(width * height, height, |row, batches| {
+        let mut col = 0;
+        while col < width {
+            if !visible(row, col) {
                 col += 1;
                 continue;
             }
@@ -207,35 +945,155 @@ fn build_text_batches(cframe: &CFrameData, char_width: f64, line_height: f64, wi
             let mut batch_text = String::new();
             batch_text.push(cframe.chars[idx] as char);
             let start_col = col;
+            let run_end = color_run_end(&cframe.rgb, idx, (row + 1) * width) - row * width;
             col += 1;
 
-            while col < width {
-                if !cframe.has_visible_foreground(row, col) {
-                    break;
-                }
-                let next_idx = row * width + col;
-                let nr = cframe.rgb[next_idx * 3];
-                let ng = cframe.rgb[next_idx * 3 + 1];
-                let nb = cframe.rgb[next_idx * 3 + 2];
-                if nr == r && ng == g && nb == b {
-                    batch_text.push(cframe.chars[next_idx] as char);
-                    col += 1;
-                } else {
-                    break;
-                }
+            while col < run_end && visible(row, col) {
+                batch_text.push(cframe.chars[row * width + col] as char);
+                col += 1;
             }
 
-            batches.push(TextBatch {text: batch_text, x: start_col as f64 * char_width, y: row as f64 * line_height, color: (r, g, b)});
+            batches.push(TextBatch {text: batch_text, x: start_col as f64 * char_width, y: row as f64 * line_height, color: (r, g, b), opacity: 1.0});
+        }
+    })
+}
+
+/// A cell-aligned region to reset to the canvas background before redrawing.
+///
+/// Consumers fill it with [`RenderConfig::background_color`] when set, or
+/// clear it to transparent otherwise.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClearRect {
+    /// X position in pixels
+    pub x: f64,
+    /// Y position in pixels
+    pub y: f64,
+    /// Width in pixels
+    pub width: f64,
+    /// Height in pixels
+    pub height: f64,
+}
+
+/// Draw commands that turn an already-drawn frame into the next one.
+///
+/// Paint order: `clears`, then `background_batches`, then `batches`. When
+/// `full` is set (the frames differ in size) the batches describe the whole
+/// frame and the canvas must be re-laid out instead.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderDiff {
+    /// Canvas width in pixels
+    pub width: f64,
+    /// Canvas height in pixels
+    pub height: f64,
+    /// `true` when the previous frame can't be patched
+    pub full: bool,
+    /// Runs of changed cells, reset before redrawing
+    pub clears: Vec<ClearRect>,
+    /// Per-cell background batches for changed cells
+    pub background_batches: Vec<CellRectBatch>,
+    /// Text batches for changed cells
+    pub batches: Vec<TextBatch>,
+}
+
+impl RenderDiff {
+    /// Returns `true` when nothing changed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.full && self.clears.is_empty()
+    }
+
+    /// Number of draw calls needed to apply the diff.
+    #[inline]
+    pub fn draw_call_count(&self) -> usize {
+        self.clears.len() + self.background_batches.len() + self.batches.len()
+    }
+}
+
+/// Generate draw commands only for the cells that differ between `prev` and `next`.
+///
+/// Every changed cell is covered by a [`ClearRect`] (so stale glyph pixels
+/// are erased) and then redrawn from `next`; cells that became empty are
+/// only cleared. For talking-head style animations, where most of the frame
+/// is static, this cuts per-frame draw calls dramatically.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, RenderConfig};
+/// use cascii_core_view::render::render_cframe_diff;
+///
+/// let prev = CFrameData::new(3, 1, vec![b'A', b'B', b'C'], vec![255; 9]);
+/// let next = CFrameData::new(3, 1, vec![b'A', b'X', b' '], vec![255; 9]);
+///
+/// let diff = render_cframe_diff(&prev, &next, &RenderConfig::new(10.0));
+/// assert_eq!(diff.clears.len(), 1); // cells 1-2 as one run
+/// assert_eq!(diff.batches.len(), 1); // "X"
+/// assert_eq!(diff.batches[0].text, "X");
+/// ```
+pub fn render_cframe_diff(prev: &CFrameData, next: &CFrameData, config: &RenderConfig) -> RenderDiff {
+    let (prev, _) = viewport::apply(prev, config);
+    let (next, config) = viewport::apply(next, config);
+    let (prev, next, config) = (prev.as_ref(), next.as_ref(), config.as_ref());
+    let char_width = config.char_width();
+    let line_height = config.line_height();
+    let frame_width = next.width as f64 * char_width;
+    let frame_height = next.height as f64 * line_height;
+    let letterbox = Letterbox::for_config(frame_width, frame_height, config);
+    let canvas_width = frame_width + letterbox.left + letterbox.right;
+    let canvas_height = frame_height + letterbox.top + letterbox.bottom;
+
+    if prev.width != next.width || prev.height != next.height || prev.has_background() != next.has_background() {
+        let (mut background_batches, mut batches) = build_cell_batches(next, config, None);
+        offset_cells(&mut background_batches, &mut batches, &letterbox);
+        return RenderDiff {width: canvas_width, height: canvas_height, full: true, clears: Vec::new(), background_batches, batches};
+    }
+
+    let width = next.width as usize;
+    let height = next.height as usize;
+    let changed: Vec<bool> = (0..width * height).map(|idx| cell_changed(prev, next, idx)).collect();
+
+    let mut clears = Vec::new();
+    for row in 0..height {
+        let mut col = 0;
+        while col < width {
+            if !changed[row * width + col] {
+                col += 1;
+                continue;
+            }
+            let start_col = col;
+            while col < width && changed[row * width + col] {
+                col += 1;
+            }
+            clears.push(ClearRect {x: letterbox.left + start_col as f64 * char_width, y: letterbox.top + row as f64 * line_height, width: (col - start_col) as f64 * char_width, height: line_height});
         }
     }
-    batches
+
+    let (mut background_batches, mut batches) = build_cell_batches(next, config, Some(&changed));
+    offset_cells(&mut background_batches, &mut batches, &letterbox);
+    RenderDiff {width: canvas_width, height: canvas_height, full: false, clears, background_batches, batches}
+}
+
+#[inline]
+fn cell_changed(prev: &CFrameData, next: &CFrameData, idx: usize) -> bool {
+    let rgb = idx * 3..idx * 3 + 3;
+    if prev.chars.get(idx) != next.chars.get(idx) || prev.rgb.get(rgb.clone()) != next.rgb.get(rgb.clone()) {
+        return true;
+    }
+    match (&prev.bg_rgb, &next.bg_rgb) {
+        (Some(a), Some(b)) => a.get(rgb.clone()) != b.get(rgb),
+        _ => false,
+    }
 }
 
 /// Web-specific rendering implementation.
 #[cfg(feature = "web")]
 pub mod web {
     use super::*;
+    use std::cell::RefCell;
     use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
     use std::hash::{Hash, Hasher};
     use wasm_bindgen::JsCast;
     use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
@@ -294,6 +1152,8 @@ pub mod web {
         }
 
         /// Backwards-compatible invalidation helper based only on font size.
+        ///
+        /// Derive the key with [`FontSizing::font_size_key`](crate::FontSizing::font_size_key).
         pub fn invalidate_for_font_size_key(&mut self, font_size_key: i32) -> bool {
             self.invalidate_for_render_key(font_size_key as u64)
         }
@@ -316,6 +1176,159 @@ pub mod web {
         }
     }
 
+    /// Default number of entries kept by a [`TextMeasureCache`].
+    pub const DEFAULT_TEXT_MEASURE_CAPACITY: usize = 4096;
+
+    /// Cache of `measureText` widths keyed by (font key, text).
+    ///
+    /// The canvas renderer measures every text batch it draws, so runs that
+    /// shape wider than their cells can be squeezed back onto the grid.
+    /// Recordings with static headers / footers produce the same batch text
+    /// frame after frame; caching the measurement skips the text shaping
+    /// work behind `measureText` on every repeat. When the cache is full it
+    /// is cleared wholesale, which keeps the bookkeeping free.
+    #[derive(Clone, Debug)]
+    pub struct TextMeasureCache {
+        entries: HashMap<u64, HashMap<String, f64>>,
+        len: usize,
+        capacity: usize,
+        hits: u64,
+        misses: u64,
+    }
+
+    impl Default for TextMeasureCache {
+        fn default() -> Self {
+            Self::with_capacity(DEFAULT_TEXT_MEASURE_CAPACITY)
+        }
+    }
+
+    impl TextMeasureCache {
+        /// Create an empty cache holding at most `capacity` entries.
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {entries: HashMap::new(), len: 0, capacity: capacity.max(1), hits: 0, misses: 0}
+        }
+
+        /// Stable key for a CSS font string.
+        pub fn font_key(font: &str) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            font.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        /// Look up a cached width.
+        pub fn get(&mut self, font_key: u64, text: &str) -> Option<f64> {
+            let width = self.entries.get(&font_key).and_then(|widths| widths.get(text)).copied();
+            if width.is_some() {
+                self.hits += 1;
+            } else {
+                self.misses += 1;
+            }
+            width
+        }
+
+        /// Store a measured width.
+        pub fn insert(&mut self, font_key: u64, text: &str, width: f64) {
+            if self.len >= self.capacity {
+                self.entries.clear();
+                self.len = 0;
+            }
+            if self.entries.entry(font_key).or_default().insert(text.to_string(), width).is_none() {
+                self.len += 1;
+            }
+        }
+
+        /// Measure `text` with the context's current font, using the cache.
+        pub fn measure(&mut self, ctx: &CanvasRenderingContext2d, font_key: u64, text: &str) -> Result<f64, String> {
+            if let Some(width) = self.get(font_key, text) {
+                return Ok(width);
+            }
+            let width = ctx.measure_text(text).map_err(|_| "Failed to measure text")?.width();
+            self.insert(font_key, text, width);
+            Ok(width)
+        }
+
+        /// Number of cached measurements.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Returns `true` when nothing is cached.
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// Drop all cached measurements and reset the hit counters.
+        pub fn clear(&mut self) {
+            self.entries.clear();
+            self.len = 0;
+            self.hits = 0;
+            self.misses = 0;
+        }
+
+        /// Lookups answered from the cache.
+        pub fn hits(&self) -> u64 {
+            self.hits
+        }
+
+        /// Lookups that required a `measureText` call.
+        pub fn misses(&self) -> u64 {
+            self.misses
+        }
+    }
+
+    thread_local! {
+        static TEXT_MEASURE_CACHE: RefCell<TextMeasureCache> = RefCell::new(TextMeasureCache::default());
+    }
+
+    /// Measure `text` in the config's font, sharing the renderer's cache.
+    pub fn measure_text_cached(canvas: &HtmlCanvasElement, config: &RenderConfig, text: &str) -> Result<f64, String> {
+        let ctx = get_2d_context(canvas)?;
+        let font = config.font_string();
+        ctx.set_font(&font);
+        let font_key = TextMeasureCache::font_key(&font);
+        TEXT_MEASURE_CACHE.with(|cache| cache.borrow_mut().measure(&ctx, font_key, text))
+    }
+
+    /// Measure one cell of the config's font as `(char_width, line_height)`
+    /// in CSS pixels, for [`FontSizing::calibrate`](crate::FontSizing::calibrate).
+    ///
+    /// The line height comes from the font's bounding box; browsers that do
+    /// not report it fall back to the configured ratio.
+    pub fn measure_font_metrics(canvas: &HtmlCanvasElement, config: &RenderConfig) -> Result<(f64, f64), String> {
+        let ctx = get_2d_context(canvas)?;
+        ctx.set_font(&config.font_string());
+        let metrics = ctx.measure_text("M").map_err(|_| "Failed to measure text")?;
+        let char_width = metrics.width();
+        let line_height = metrics.font_bounding_box_ascent() + metrics.font_bounding_box_descent();
+        let char_width = if char_width.is_finite() && char_width > 0.0 { char_width } else { config.char_width() };
+        let line_height = if line_height.is_finite() && line_height > 0.0 { line_height } else { config.line_height() };
+        Ok((char_width, line_height))
+    }
+
+    /// Measure the advance of every printable ASCII character in the
+    /// config's font, for [`FontSizing::advances`](crate::FontSizing::advances)
+    /// when the chosen font is not truly monospace.
+    pub fn measure_char_advances(canvas: &HtmlCanvasElement, config: &RenderConfig) -> Result<CharAdvances, String> {
+        let mut advances = Vec::with_capacity(95);
+        for ch in (b' '..=b'~').map(char::from) {
+            advances.push((ch, measure_text_cached(canvas, config, ch.encode_utf8(&mut [0; 4]))?));
+        }
+        Ok(CharAdvances::from_measured(advances, config.font_size))
+    }
+
+    /// Measure the config's font and calibrate `config.sizing` with it, so
+    /// layout, caching, and fitting all use the real glyph metrics.
+    pub fn calibrate_font_sizing(canvas: &HtmlCanvasElement, config: &mut RenderConfig) -> Result<(), String> {
+        let (char_width, line_height) = measure_font_metrics(canvas, config)?;
+        config.sizing.calibrate(char_width, line_height, config.font_size);
+        Ok(())
+    }
+
+    /// Clear the renderer's shared text measurement cache.
+    pub fn clear_text_measure_cache() {
+        TEXT_MEASURE_CACHE.with(|cache| cache.borrow_mut().clear());
+    }
+
     #[derive(Clone, Copy, Debug)]
     struct CanvasLayout {
         logical_width: f64,
@@ -325,30 +1338,29 @@ pub mod web {
     }
 
     pub(crate) fn current_render_key(config: &RenderConfig) -> u64 {
-        render_key(config, current_device_pixel_ratio())
+        render_key(config, device_pixel_ratio(config))
     }
 
     fn render_key(config: &RenderConfig, dpr: f64) -> u64 {
         let mut hasher = DefaultHasher::new();
-        config.font_size.to_bits().hash(&mut hasher);
-        config.sizing.char_width_ratio.to_bits().hash(&mut hasher);
-        config.sizing.line_height_ratio.to_bits().hash(&mut hasher);
-        config.font_family.hash(&mut hasher);
-        config.text_stroke_width.to_bits().hash(&mut hasher);
-        config.background_color.hash(&mut hasher);
+        config.cache_key().hash(&mut hasher);
         dpr.to_bits().hash(&mut hasher);
         hasher.finish()
     }
 
-    fn current_device_pixel_ratio() -> f64 {
-        web_sys::window().map(|window| window.device_pixel_ratio()).unwrap_or(1.0).max(1.0)
+    /// The config's device pixel ratio override, or the window's ratio.
+    pub(crate) fn device_pixel_ratio(config: &RenderConfig) -> f64 {
+        match config.device_pixel_ratio.filter(|dpr| dpr.is_finite() && *dpr > 0.0) {
+            Some(dpr) => dpr,
+            None => web_sys::window().map(|window| window.device_pixel_ratio()).unwrap_or(1.0).max(1.0),
+        }
     }
 
     fn get_2d_context(canvas: &HtmlCanvasElement) -> Result<CanvasRenderingContext2d, String> {
-        Ok(canvas.get_context("2d").map_err(|_| "Failed to get 2d context".to_string())?.ok_or_else(|| "No 2d context available".to_string())?.dyn_into::<CanvasRenderingContext2d>().map_err(|_| "Failed to cast to CanvasRenderingContext2d".to_string())?)
+        canvas.get_context("2d").map_err(|_| "Failed to get 2d context".to_string())?.ok_or_else(|| "No 2d context available".to_string())?.dyn_into::<CanvasRenderingContext2d>().map_err(|_| "Failed to cast to CanvasRenderingContext2d".to_string())
     }
 
-    fn apply_logical_size(canvas: &HtmlCanvasElement, logical_width: f64, logical_height: f64) -> Result<(), String> {
+    pub(crate) fn apply_logical_size(canvas: &HtmlCanvasElement, logical_width: f64, logical_height: f64) -> Result<(), String> {
         let style = canvas.style();
         style.set_property("width", &format!("{logical_width:.1}px")).map_err(|_| "Failed to set canvas width style")?;
         style.set_property("height", &format!("{logical_height:.1}px")).map_err(|_| "Failed to set canvas height style")?;
@@ -356,9 +1368,7 @@ pub mod web {
     }
 
     fn measure_char_width(canvas: &HtmlCanvasElement, config: &RenderConfig) -> Result<f64, String> {
-        let ctx = get_2d_context(canvas)?;
-        ctx.set_font(&config.font_string());
-        let measured = ctx.measure_text("M").map_err(|_| "Failed to measure text")?.width();
+        let measured = measure_text_cached(canvas, config, "M")?;
         if measured > 0.0 {
             Ok(measured)
         } else {
@@ -367,14 +1377,22 @@ pub mod web {
     }
 
     fn layout_canvas(canvas: &HtmlCanvasElement, cols: usize, rows: usize, config: &RenderConfig) -> Result<(CanvasRenderingContext2d, CanvasLayout), String> {
-        let dpr = current_device_pixel_ratio();
+        let dpr = device_pixel_ratio(config);
         let char_width = measure_char_width(canvas, config)?;
         let line_height = config.line_height();
-        let logical_width = cols as f64 * char_width;
-        let logical_height = rows as f64 * line_height;
-
-        canvas.set_width((logical_width * dpr).ceil() as u32);
-        canvas.set_height((logical_height * dpr).ceil() as u32);
+        let frame_width = cols as f64 * char_width;
+        let frame_height = rows as f64 * line_height;
+        let letterbox = Letterbox::for_config(frame_width, frame_height, config);
+        let logical_width = frame_width + letterbox.left + letterbox.right;
+        let logical_height = frame_height + letterbox.top + letterbox.bottom;
+
+        // Resizing wipes the canvas, so leave matching sizes alone for
+        // backdrops that composite over the existing contents.
+        let (pixel_width, pixel_height) = ((logical_width * dpr).ceil() as u32, (logical_height * dpr).ceil() as u32);
+        if canvas.width() != pixel_width || canvas.height() != pixel_height {
+            canvas.set_width(pixel_width);
+            canvas.set_height(pixel_height);
+        }
         apply_logical_size(canvas, logical_width, logical_height)?;
 
         let ctx = get_2d_context(canvas)?;
@@ -386,11 +1404,10 @@ pub mod web {
     }
 
     fn clear_or_fill_background(ctx: &CanvasRenderingContext2d, layout: &CanvasLayout, config: &RenderConfig) {
+        ctx.clear_rect(0.0, 0.0, layout.logical_width, layout.logical_height);
         if let Some((r, g, b)) = config.background_color {
             ctx.set_fill_style_str(&format!("rgb({r},{g},{b})"));
             ctx.fill_rect(0.0, 0.0, layout.logical_width, layout.logical_height);
-        } else {
-            ctx.clear_rect(0.0, 0.0, layout.logical_width, layout.logical_height);
         }
     }
 
@@ -416,36 +1433,211 @@ pub mod web {
     /// render_to_canvas(&cframe, &canvas, &RenderConfig::new(12.0))?;
     /// ```
     pub fn render_to_canvas(cframe: &CFrameData, canvas: &HtmlCanvasElement, config: &RenderConfig) -> Result<(), String> {
+        render_to_canvas_over(cframe, canvas, Backdrop::Clear, config)
+    }
+
+    /// What a frame is drawn over by [`render_to_canvas_over`].
+    #[derive(Clone, Copy, Debug)]
+    pub enum Backdrop<'a> {
+        /// Clear the canvas to transparent first (what [`render_to_canvas`] does)
+        Clear,
+        /// Keep whatever the canvas already shows
+        Keep,
+        /// Draw another canvas, stretched to the frame size
+        Canvas(&'a HtmlCanvasElement),
+        /// Tile another canvas as a repeating pattern
+        Pattern(&'a HtmlCanvasElement),
+    }
+
+    /// Render a CFrameData over a backdrop, compositing the frame with
+    /// [`RenderConfig::composite`]. Useful for frames with transparent cells
+    /// or block-mode backgrounds over a wallpaper or another layer.
+    pub fn render_to_canvas_over(cframe: &CFrameData, canvas: &HtmlCanvasElement, backdrop: Backdrop<'_>, config: &RenderConfig) -> Result<(), String> {
+        let (cframe, config) = super::viewport::apply(cframe, config);
+        let (cframe, config) = (cframe.as_ref(), config.as_ref());
         let (ctx, layout) = layout_canvas(canvas, cframe.width as usize, cframe.height as usize, config)?;
         let mut measured_config = config.clone();
         if config.font_size > 0.0 {
             measured_config.sizing.char_width_ratio = layout.char_width / config.font_size;
         }
         let result = render_cframe(cframe, &measured_config);
+        paint_backdrop(&ctx, &layout, backdrop)?;
+        paint_result(&ctx, &result, layout.char_width, config)
+    }
 
-        clear_or_fill_background(&ctx, &layout, config);
+    fn paint_backdrop(ctx: &CanvasRenderingContext2d, layout: &CanvasLayout, backdrop: Backdrop<'_>) -> Result<(), String> {
+        if !matches!(backdrop, Backdrop::Keep) {
+            ctx.clear_rect(0.0, 0.0, layout.logical_width, layout.logical_height);
+        }
+        match backdrop {
+            Backdrop::Clear | Backdrop::Keep => {}
+            Backdrop::Canvas(source) => {
+                ctx.draw_image_with_html_canvas_element_and_dw_and_dh(source, 0.0, 0.0, layout.logical_width, layout.logical_height).map_err(|_| "Failed to draw backdrop canvas")?;
+            }
+            Backdrop::Pattern(source) => {
+                let pattern = ctx.create_pattern_with_html_canvas_element(source, "repeat").map_err(|_| "Failed to create backdrop pattern")?.ok_or("Backdrop pattern unavailable")?;
+                ctx.set_fill_style_canvas_pattern(&pattern);
+                ctx.fill_rect(0.0, 0.0, layout.logical_width, layout.logical_height);
+            }
+        }
+        Ok(())
+    }
+
+    /// Render a text-only frame (see [`render_text_frame`]) to an HTML
+    /// canvas through the same pipeline as color frames.
+    pub fn render_text_frame_to_canvas(frame: &Frame, colors: &FrameColors, canvas: &HtmlCanvasElement, config: &RenderConfig) -> Result<(), String> {
+        let (frame, config) = super::viewport::apply_text(frame, config);
+        let (frame, config) = (frame.as_ref(), config.as_ref());
+        let (cols, rows) = frame.dimensions();
+        let (ctx, layout) = layout_canvas(canvas, cols, rows, config)?;
+        let mut measured_config = config.clone();
+        if config.font_size > 0.0 {
+            measured_config.sizing.char_width_ratio = layout.char_width / config.font_size;
+        }
+        let result = render_text_frame(frame, colors, &measured_config);
+        paint_backdrop(&ctx, &layout, Backdrop::Clear)?;
+        paint_result(&ctx, &result, layout.char_width, config)
+    }
+
+    fn paint_result(ctx: &CanvasRenderingContext2d, result: &RenderResult, char_width: f64, config: &RenderConfig) -> Result<(), String> {
+        ctx.set_global_composite_operation(config.composite.as_css()).map_err(|_| "Failed to set composite operation")?;
+        ctx.set_global_alpha(config.opacity.clamp(0.0, 1.0));
+        if let Some(fill) = &result.background {
+            ctx.set_fill_style_str(&fill.color_string());
+            ctx.fill_rect(fill.x, fill.y, fill.width, fill.height);
+        }
 
+        let painted = paint_cells(ctx, &result.background_batches, &result.batches, char_width, config, result.glow.as_ref());
+        ctx.set_global_alpha(1.0);
+        let _ = ctx.set_global_composite_operation("source-over");
+        painted?;
+        for rect in &result.post {
+            ctx.set_fill_style_str(&rect.color_string());
+            ctx.fill_rect(rect.x, rect.y, rect.width, rect.height);
+        }
+        paint_layer(ctx, &result.overlay)
+    }
+
+    fn paint_cells(ctx: &CanvasRenderingContext2d, background_batches: &[CellRectBatch], batches: &[TextBatch], char_width: f64, config: &RenderConfig, glow: Option<&Glow>) -> Result<(), String> {
         // Per-cell background fills (drawn first so glyphs composite on top).
-        for batch in &result.background_batches {
+        for batch in background_batches {
             ctx.set_fill_style_str(&batch.color_string());
             ctx.fill_rect(batch.x, batch.y, batch.width, batch.height);
         }
 
-        // Draw all text batches
-        for batch in &result.batches {
+        if let Some(glow) = glow {
+            // shadowBlur ignores the canvas transform, so scale it to device pixels.
+            ctx.set_shadow_blur(glow.blur_radius * device_pixel_ratio(config));
+            ctx.set_shadow_offset_x(0.0);
+            ctx.set_shadow_offset_y(0.0);
+        }
+        // Draw all text batches. Runs whose shaped width overflows their
+        // cells (fallback glyphs, kerning) are squeezed back onto the grid.
+        let font_key = TextMeasureCache::font_key(&config.font_string());
+        let painted = batches.iter().try_for_each(|batch| {
+            let cells = batch.text.chars().count();
+            let span = cells as f64 * char_width;
+            let width = TEXT_MEASURE_CACHE.with(|cache| cache.borrow_mut().measure(ctx, font_key, &batch.text))?;
+            let max_width = (cells > 1 && width > span + 0.5).then_some(span);
             ctx.set_fill_style_str(&batch.color_string());
+            if let Some(glow) = glow {
+                let (r, g, b) = glow.color_for(batch.color);
+                ctx.set_shadow_color(&format!("rgb({r},{g},{b})"));
+            }
             if config.text_stroke_width > 0.0 {
                 ctx.set_stroke_style_str(&batch.color_string());
                 ctx.set_line_width(config.text_stroke_width);
-                ctx.stroke_text(&batch.text, batch.x, batch.y).map_err(|_| "Failed to stroke text")?;
+                match max_width {
+                    Some(max_width) => ctx.stroke_text_with_max_width(&batch.text, batch.x, batch.y, max_width),
+                    None => ctx.stroke_text(&batch.text, batch.x, batch.y),
+                }.map_err(|_| "Failed to stroke text")?;
             }
-            ctx.fill_text(&batch.text, batch.x, batch.y)
-                .map_err(|_| "Failed to fill text")?;
+            match max_width {
+                Some(max_width) => ctx.fill_text_with_max_width(&batch.text, batch.x, batch.y, max_width),
+                None => ctx.fill_text(&batch.text, batch.x, batch.y),
+            }.map_err(|_| "Failed to fill text".to_string())
+        });
+        if glow.is_some() {
+            ctx.set_shadow_blur(0.0);
+            ctx.set_shadow_color("transparent");
+        }
+        painted
+    }
+
+    /// Patch a canvas that shows `prev` (drawn by [`render_to_canvas`]) so it
+    /// shows `next`, touching only the cells that changed.
+    ///
+    /// Falls back to a full [`render_to_canvas`] when the frames differ in
+    /// size or a [`RenderConfig::post_effect`] is set (the translucent rects
+    /// would otherwise pile up over patched cells).
+    pub fn render_diff_to_canvas(prev: &CFrameData, next: &CFrameData, canvas: &HtmlCanvasElement, config: &RenderConfig) -> Result<(), String> {
+        let (prev, _) = super::viewport::apply(prev, config);
+        let (next, config) = super::viewport::apply(next, config);
+        let (prev, next, config) = (prev.as_ref(), next.as_ref(), config.as_ref());
+        let char_width = measure_char_width(canvas, config)?;
+        let mut measured_config = config.clone();
+        if config.font_size > 0.0 {
+            measured_config.sizing.char_width_ratio = char_width / config.font_size;
+        }
+        let diff = render_cframe_diff(prev, next, &measured_config);
+        if diff.full || config.post_effect.is_some_and(|effect| !effect.is_noop()) {
+            return render_to_canvas(next, canvas, config);
+        }
+        if diff.is_empty() {
+            return Ok(());
         }
 
+        let dpr = device_pixel_ratio(config);
+        let ctx = get_2d_context(canvas)?;
+        ctx.set_transform(dpr, 0.0, 0.0, dpr, 0.0, 0.0).map_err(|_| "Failed to apply DPR transform")?;
+        ctx.set_font(&config.font_string());
+        ctx.set_text_baseline("top");
+        ctx.set_global_alpha(config.opacity.clamp(0.0, 1.0));
+
+        if let Some((r, g, b)) = config.background_color {
+            ctx.set_fill_style_str(&format!("rgb({r},{g},{b})"));
+        }
+        for clear in &diff.clears {
+            // Clear first: a translucent fill alone would blend with the old cells.
+            ctx.clear_rect(clear.x, clear.y, clear.width, clear.height);
+            if config.background_color.is_some() {
+                ctx.fill_rect(clear.x, clear.y, clear.width, clear.height);
+            }
+        }
+        let painted = paint_cells(&ctx, &diff.background_batches, &diff.batches, char_width, config, config.active_glow().as_ref());
+        ctx.set_global_alpha(1.0);
+        painted
+    }
+
+    fn paint_layer(ctx: &CanvasRenderingContext2d, layer: &RenderLayer) -> Result<(), String> {
+        for rect in &layer.rects {
+            ctx.set_fill_style_str(&rect.color_string());
+            ctx.fill_rect(rect.x, rect.y, rect.width, rect.height);
+        }
+        for batch in &layer.batches {
+            ctx.set_fill_style_str(&batch.color_string());
+            ctx.fill_text(&batch.text, batch.x, batch.y).map_err(|_| "Failed to fill overlay text")?;
+        }
         Ok(())
     }
 
+    /// Draw a [`RenderLayer`] on top of whatever the canvas already shows.
+    ///
+    /// Coordinates are logical (CSS) pixels; the device pixel ratio transform
+    /// is applied here so layers line up with frames drawn by
+    /// [`render_to_canvas`] or blitted from the cache.
+    pub fn draw_layer(canvas: &HtmlCanvasElement, layer: &RenderLayer, config: &RenderConfig) -> Result<(), String> {
+        if layer.is_empty() {
+            return Ok(());
+        }
+        let dpr = device_pixel_ratio(config);
+        let ctx = get_2d_context(canvas)?;
+        ctx.set_transform(dpr, 0.0, 0.0, dpr, 0.0, 0.0).map_err(|_| "Failed to apply DPR transform")?;
+        ctx.set_font(&config.font_string());
+        ctx.set_text_baseline("top");
+        paint_layer(&ctx, layer)
+    }
+
     /// Render a CFrameData to a newly created offscreen canvas.
     ///
     /// The resulting canvas can be cached and quickly drawn to the visible canvas
@@ -467,13 +1659,35 @@ pub mod web {
 
         let ctx = get_2d_context(target)?;
 
-        // Reset any existing transform state before drawing cached content.
+        // Reset any existing transform / alpha state before drawing cached content.
         ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0).map_err(|_| "Failed to reset transform")?;
+        ctx.set_global_alpha(1.0);
         ctx.clear_rect(0.0, 0.0, target.width() as f64, target.height() as f64);
         ctx.draw_image_with_html_canvas_element(cached, 0.0, 0.0).map_err(|_| "Failed to draw cached canvas")?;
         Ok(())
     }
 
+    /// Draw a pre-rendered offscreen canvas scaled into `(x, y, width, height)`
+    /// of `target`, in backing-store pixels.
+    ///
+    /// Unlike [`draw_cached_canvas`] the target keeps its size, so cached
+    /// frames can be reused while the window is resized and only re-rendered
+    /// once the size settles. `smoothing` toggles bilinear filtering; turn it
+    /// off for crisp, blocky glyphs when scaling up.
+    pub fn draw_cached_canvas_scaled(target: &HtmlCanvasElement, cached: &HtmlCanvasElement, (x, y, width, height): (f64, f64, f64, f64), smoothing: bool) -> Result<(), String> {
+        let ctx = get_2d_context(target)?;
+        ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0).map_err(|_| "Failed to reset transform")?;
+        ctx.set_global_alpha(1.0);
+        ctx.clear_rect(0.0, 0.0, target.width() as f64, target.height() as f64);
+        if width <= 0.0 || height <= 0.0 || cached.width() == 0 || cached.height() == 0 {
+            return Ok(());
+        }
+        ctx.set_image_smoothing_enabled(smoothing);
+        let drawn = ctx.draw_image_with_html_canvas_element_and_dw_and_dh(cached, x, y, width, height).map_err(|_| "Failed to draw scaled cached canvas".to_string());
+        ctx.set_image_smoothing_enabled(true);
+        drawn
+    }
+
     /// Draw a frame directly from cache when available.
     ///
     /// Returns `Ok(true)` when the frame was present in cache and drawn.
@@ -486,6 +1700,17 @@ pub mod web {
         }
     }
 
+    /// Like [`draw_frame_from_cache`], but scaled into a rect of the target
+    /// (see [`draw_cached_canvas_scaled`]).
+    pub fn draw_frame_from_cache_scaled(target: &HtmlCanvasElement, cache: &FrameCanvasCache, frame_index: usize, rect: (f64, f64, f64, f64), smoothing: bool) -> Result<bool, String> {
+        if let Some(cached) = cache.get(frame_index) {
+            draw_cached_canvas_scaled(target, &cached, rect, smoothing)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
     /// Render plain text to a canvas element.
     ///
     /// This is used as a fallback when no colour frame data is available.
@@ -493,8 +1718,9 @@ pub mod web {
     pub fn render_text_to_canvas(canvas: &HtmlCanvasElement, text: &str, config: &RenderConfig) -> Result<(), String> {
         let lines: Vec<&str> = text.lines().collect();
         let rows = lines.len();
-        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let cols = lines.iter().map(|l| crate::display_width(l)).max().unwrap_or(0);
         let (ctx, layout) = layout_canvas(canvas, cols, rows, config)?;
+        ctx.set_global_alpha(config.opacity.clamp(0.0, 1.0));
         clear_or_fill_background(&ctx, &layout, config);
         ctx.set_fill_style_str("white");
         ctx.set_stroke_style_str("white");
@@ -510,6 +1736,7 @@ pub mod web {
             }
         }
 
+        ctx.set_global_alpha(1.0);
         Ok(())
     }
 }
@@ -631,6 +1858,200 @@ mod tests {
         assert!((result.background_batches[0].width - 18.0).abs() < 0.01); // 3 cells * 10 * 0.6
     }
 
+    #[test]
+    fn test_batch_color_string_opacity() {
+        let mut batch = TextBatch {text: "A".into(), x: 0.0, y: 0.0, color: (1, 2, 3), opacity: 1.0};
+        assert_eq!(batch.color_string(), "rgb(1,2,3)");
+        batch.opacity = 0.5;
+        assert_eq!(batch.color_string(), "rgba(1,2,3,0.5)");
+
+        let mut config = RenderConfig::new(12.0);
+        let key = config.cache_key();
+        config.opacity = 0.5;
+        assert_ne!(config.cache_key(), key);
+    }
+
+    #[test]
+    fn test_color_adjust_merges_batches() {
+        // Two distinct reds that clamp to the same color under high contrast
+        // batch as one run.
+        let cframe = CFrameData::new(2, 1, vec![b'A', b'B'], vec![250, 10, 10, 255, 0, 0]);
+        let mut config = RenderConfig::new(12.0);
+        assert_eq!(render_cframe(&cframe, &config).batches.len(), 2);
+
+        config.adjust = ColorAdjust {contrast: 10.0, ..ColorAdjust::default()};
+        let result = render_cframe(&cframe, &config);
+        assert_eq!(result.batches.len(), 1);
+        assert_eq!(result.batches[0].color, (255, 0, 0));
+    }
+
+    #[test]
+    fn test_color_vision_applies_to_cells() {
+        let cframe = CFrameData::new(1, 1, vec![b'A'], vec![200, 40, 40]);
+        let mut config = RenderConfig::new(12.0);
+        let key = config.cache_key();
+        let vision = ColorVision::Simulate(crate::ColorBlindness::Deuteranopia);
+        config.color_vision = Some(vision);
+        assert_ne!(config.cache_key(), key);
+        assert_eq!(render_cframe(&cframe, &config).batches[0].color, vision.apply((200, 40, 40)));
+    }
+
+    #[test]
+    fn test_glow_instruction() {
+        let cframe = CFrameData::new(1, 1, vec![b'A'], vec![0, 255, 0]);
+        let mut config = RenderConfig::new(12.0);
+        assert!(render_cframe(&cframe, &config).glow.is_none());
+
+        config.glow = Some(Glow::new(6.0));
+        let result = render_cframe(&cframe, &config);
+        assert_eq!(result.glow, Some(Glow {color: None, blur_radius: 6.0}));
+        assert_eq!(result.glow.unwrap().color_for(result.batches[0].color), (0, 255, 0));
+        assert_ne!(config.cache_key(), RenderConfig::new(12.0).cache_key());
+
+        config.glow = Some(Glow {color: Some((255, 0, 0)), blur_radius: 0.0});
+        assert!(render_cframe(&cframe, &config).glow.is_none());
+    }
+
+    #[test]
+    fn test_char_advances_align_columns() {
+        let cframe = CFrameData::new(4, 1, b"MiMW".to_vec(), vec![200; 12]);
+        let mut config = RenderConfig::new(10.0);
+        config.sizing.advances = Some(CharAdvances::from_measured([('i', 3.0), ('M', 6.0), ('W', 9.0)], 10.0));
+
+        let result = render_cframe(&cframe, &config);
+        let placed: Vec<(&str, f64)> = result.batches.iter().map(|b| (b.text.as_str(), b.x)).collect();
+        // Cells are 6px; 'i' and 'W' are centered in their own cells.
+        assert_eq!(placed, [("M", 0.0), ("i", 7.5), ("M", 12.0), ("W", 16.5)]);
+        assert_ne!(config.cache_key(), RenderConfig::new(10.0).cache_key());
+
+        let frame = Frame::text_only("iMM".to_string());
+        let texts: Vec<String> = render_text_frame(&frame, &FrameColors {foreground: (255, 255, 255), background: (0, 0, 0)}, &config).batches.into_iter().map(|b| b.text).collect();
+        assert_eq!(texts, ["i", "MM"]);
+    }
+
+    #[test]
+    fn test_composite_mode_blend() {
+        let (base, top) = ((200, 100, 0), (100, 255, 50));
+        assert_eq!(CompositeMode::SourceOver.blend(base, top), top);
+        assert_eq!(CompositeMode::DestinationOver.blend(base, top), base);
+        assert_eq!(CompositeMode::Multiply.blend(base, top), (78, 100, 0));
+        assert_eq!(CompositeMode::Screen.blend(base, top), (222, 255, 50));
+        assert_eq!(CompositeMode::Lighter.blend(base, top), (255, 255, 50));
+        assert_eq!(CompositeMode::Overlay.blend(base, top), (189, 200, 0));
+    }
+
+    #[test]
+    fn test_clamp_to_max_canvas() {
+        let mut config = RenderConfig::new(40.0);
+        config.device_pixel_ratio = Some(2.0);
+        // 1000 cols x 0.6 x 40px x 2 = 48000 backing pixels
+        assert!(config.clamp_to_max_canvas(1000, 10));
+        assert!(config.font_size * config.sizing.char_width_ratio * 1000.0 * 2.0 <= crate::MAX_CANVAS_DIMENSION + 1e-6);
+        assert!(!config.clamp_to_max_canvas(1000, 10));
+    }
+
+    #[test]
+    fn test_font_string_weight_and_style() {
+        let mut config = RenderConfig::new(12.0);
+        assert_eq!(config.font_string(), "12.00px monospace");
+        let key = config.cache_key();
+
+        config.font_weight = FONT_WEIGHT_BOLD;
+        assert_eq!(config.font_string(), "700 12.00px monospace");
+        config.font_style = FontStyle::Italic;
+        config.font_family = "'Fira Code', monospace".into();
+        assert_eq!(config.font_string(), "italic 700 12.00px 'Fira Code', monospace");
+        assert_ne!(config.cache_key(), key);
+    }
+
+    #[test]
+    fn test_container_letterbox() {
+        let cframe = CFrameData::new(2, 1, vec![b'A', b'B'], vec![255, 0, 0, 0, 255, 0]);
+        let mut config = RenderConfig::new(10.0);
+        config.background_color = Some((0, 0, 0));
+        let (frame_width, line_height) = (2.0 * config.char_width(), config.line_height());
+        config.container = Some((frame_width + 40.0, line_height + 21.0));
+
+        let result = render_cframe(&cframe, &config);
+        assert_eq!(result.letterbox, Letterbox {left: 20.0, top: 10.0, right: 20.0, bottom: 11.0});
+        assert_eq!((result.width, result.height), (frame_width + 40.0, line_height + 21.0));
+        assert_eq!((result.batches[0].x, result.batches[0].y), (20.0, 10.0));
+        assert_eq!(result.background.as_ref().map(|fill| fill.width), Some(result.width));
+
+        assert_eq!(result.cell_at(21.0, 11.0, 2, 1), Some((0, 0)));
+        assert_eq!(result.cell_at(20.0 + frame_width - 0.5, 11.0, 2, 1), Some((1, 0)));
+        assert_eq!(result.cell_at(5.0, 11.0, 2, 1), None);
+
+        let next = CFrameData::new(2, 1, vec![b'A', b'C'], vec![255, 0, 0, 0, 255, 0]);
+        let diff = render_cframe_diff(&cframe, &next, &config);
+        assert_eq!(diff.clears[0].x, 20.0 + config.char_width());
+
+        // Frames larger than the container aren't offset.
+        config.container = Some((1.0, 1.0));
+        assert!(render_cframe(&cframe, &config).letterbox.is_empty());
+    }
+
+    #[test]
+    fn test_color_tolerance_merges_dithered_runs() {
+        let cframe = CFrameData::new(4, 1, vec![b'A'; 4], vec![100, 100, 100, 101, 99, 100, 100, 101, 101, 40, 200, 40]);
+        let mut config = RenderConfig::new(12.0);
+        assert_eq!(render_cframe(&cframe, &config).batches.len(), 4);
+
+        config.color_tolerance = 2.0;
+        let result = render_cframe(&cframe, &config);
+        assert_eq!(result.batches.len(), 2);
+        assert_eq!((result.batches[0].text.as_str(), result.batches[0].color), ("AAA", (100, 100, 100)));
+
+        config.color_metric = ColorMetric::DeltaE;
+        assert_eq!(render_cframe(&cframe, &config).batches.len(), 2);
+    }
+
+    #[test]
+    fn test_palette_remap_before_batching() {
+        let cframe = CFrameData::new(3, 1, vec![b'A', b'B', b'C'], vec![250, 10, 5, 240, 0, 20, 0, 0, 250]);
+        let mut config = RenderConfig::new(12.0);
+        config.palette = Some(crate::Palette::ansi16());
+        let result = render_cframe(&cframe, &config);
+
+        assert_eq!(result.batches.len(), 2);
+        assert_eq!((result.batches[0].text.as_str(), result.batches[0].color), ("AB", (255, 0, 0)));
+        assert_eq!(result.batches[1].color, (0, 0, 255));
+        assert_ne!(config.cache_key(), RenderConfig::new(12.0).cache_key());
+    }
+
+    #[cfg(feature = "web")]
+    #[test]
+    fn test_device_pixel_ratio_override() {
+        let mut config = RenderConfig::new(12.0);
+        config.device_pixel_ratio = Some(2.0);
+        assert_eq!(web::device_pixel_ratio(&config), 2.0);
+        let retina = web::current_render_key(&config);
+
+        config.device_pixel_ratio = Some(3.0);
+        assert_ne!(web::current_render_key(&config), retina);
+        // logical coordinates don't depend on the ratio
+        assert_eq!(config.cache_key(), RenderConfig::new(12.0).cache_key());
+    }
+
+    #[cfg(feature = "web")]
+    #[test]
+    fn test_text_measure_cache() {
+        let mut cache = web::TextMeasureCache::with_capacity(2);
+        let key = web::TextMeasureCache::font_key("12.00px monospace");
+        assert_eq!(cache.get(key, "HEADER"), None);
+
+        cache.insert(key, "HEADER", 43.2);
+        assert_eq!(cache.get(key, "HEADER"), Some(43.2));
+        assert_eq!(cache.get(web::TextMeasureCache::font_key("14.00px monospace"), "HEADER"), None);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+
+        cache.insert(key, "HEADER", 43.2);
+        assert_eq!(cache.len(), 1);
+        cache.insert(key, "A", 7.2);
+        cache.insert(key, "B", 7.2); // full: cleared before inserting
+        assert_eq!(cache.len(), 1);
+    }
+
     #[test]
     fn test_black_background_is_rendered() {
         let cframe = CFrameData::with_background(2, 1, vec![b' ', b' '], vec![0; 6], vec![0, 0, 0, 12, 12, 12]);
@@ -643,4 +2064,174 @@ mod tests {
         assert_eq!(result.background_batches[1].color, (12, 12, 12));
         assert!(result.batches.is_empty());
     }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_render_result_roundtrip() {
+        let cframe = CFrameData::with_background(2, 1, vec![b'A', b'B'], vec![255, 0, 0, 0, 255, 0], vec![1, 2, 3, 1, 2, 3]);
+        let result = render_cframe(&cframe, &RenderConfig::new(10.0));
+
+        let encoded = toml::to_string(&result).unwrap();
+        let decoded: RenderResult = toml::from_str(&encoded).unwrap();
+        assert_eq!(decoded.batches.len(), result.batches.len());
+        assert_eq!(decoded.batches[1].text, "B");
+        assert_eq!(decoded.batches[1].color, (0, 255, 0));
+        assert_eq!(decoded.background_batches.len(), 1);
+        assert!(decoded.overlay.is_empty());
+    }
+
+    #[test]
+    fn test_composite_mode() {
+        assert_eq!(CompositeMode::default().as_css(), "source-over");
+        assert_eq!(CompositeMode::Multiply.as_css(), "multiply");
+        let mut config = RenderConfig::new(10.0);
+        let key = config.cache_key();
+        config.composite = CompositeMode::Screen;
+        assert_ne!(config.cache_key(), key);
+    }
+
+    #[test]
+    fn test_render_text_frame() {
+        let frame = Frame::text_only("AB\n   \n  C".to_string());
+        let colors = FrameColors {foreground: (200, 200, 200), background: (1, 2, 3)};
+        let mut config = RenderConfig::new(10.0);
+        config.collect_stats = true;
+        let result = render_text_frame(&frame, &colors, &config);
+
+        assert_eq!((result.width, result.height), (3.0 * config.char_width(), 3.0 * config.line_height()));
+        assert_eq!(result.background.as_ref().map(|fill| fill.color), Some((1, 2, 3)));
+        let batches: Vec<(&str, f64)> = result.batches.iter().map(|batch| (batch.text.as_str(), batch.y)).collect();
+        assert_eq!(batches, [("AB", 0.0), ("  C", 2.0 * config.line_height())]);
+        assert!(result.batches.iter().all(|batch| batch.color == (200, 200, 200)));
+        assert_eq!(result.stats.map(|stats| (stats.chars_drawn, stats.cells_skipped)), Some((3, 6)));
+
+        config.background_color = Some((9, 9, 9));
+        config.palette = Some(Palette::ansi16());
+        let result = render_text_frame(&frame, &colors, &config);
+        assert_eq!(result.background.map(|fill| fill.color), Some((9, 9, 9)));
+        assert_eq!(result.batches[0].color, (192, 192, 192));
+    }
+
+    #[test]
+    fn test_color_run_end() {
+        let mut rgb = [7u8, 8, 9].repeat(20);
+        assert_eq!(color_run_end(&rgb, 0, 20), 20);
+        assert_eq!(color_run_end(&rgb, 5, 12), 12);
+        assert_eq!(color_run_end(&rgb, 19, 20), 20);
+
+        // A mismatch in each channel, inside and past the first 8-byte chunk.
+        for (cell, channel) in [(1, 0), (2, 2), (3, 1), (11, 2), (17, 0)] {
+            let mut changed = rgb.clone();
+            changed[cell * 3 + channel] ^= 1;
+            assert_eq!(color_run_end(&changed, 0, 20), cell, "cell {cell} channel {channel}");
+        }
+        rgb[9 * 3] = 0;
+        assert_eq!(color_run_end(&rgb, 4, 20), 9);
+        assert_eq!(color_run_end(&rgb, 9, 20), 10);
+        // Truncated buffers fall back to single-cell runs.
+        assert_eq!(color_run_end(&rgb[..10], 0, 20), 1);
+    }
+
+    #[test]
+    fn test_large_frame_batches_stay_in_row_order() {
+        let (width, height) = (400usize, 120usize);
+        let chars = vec![b'#'; width * height];
+        let rgb = (0..width * height).flat_map(|idx| [(idx / 7 % 200 + 50) as u8, (idx / width) as u8, 0]).collect();
+        let cframe = CFrameData::with_background(width as u32, height as u32, chars, rgb, vec![7; width * height * 3]);
+        let config = RenderConfig::new(4.0);
+        let result = render_cframe(&cframe, &config);
+
+        assert_eq!(result.batches.iter().map(|batch| batch.text.len()).sum::<usize>(), width * height);
+        assert!(result.batches.windows(2).all(|pair| (pair[0].y, pair[0].x) < (pair[1].y, pair[1].x)));
+        assert_eq!(result.background_batches.len(), height);
+        assert_eq!(result.background_batches[height - 1].y, (height - 1) as f64 * config.line_height());
+    }
+
+    #[test]
+    fn test_render_stats() {
+        let cframe = CFrameData::new(4, 1, b"AB C".to_vec(), vec![255, 0, 0, 255, 0, 0, 255, 0, 0, 0, 255, 0]);
+        let mut config = RenderConfig::new(10.0);
+        assert!(render_cframe(&cframe, &config).stats.is_none());
+
+        config.collect_stats = true;
+        let stats = render_cframe(&cframe, &config).stats.unwrap();
+        assert_eq!((stats.batch_count, stats.background_batch_count, stats.chars_drawn, stats.cells_skipped), (2, 0, 3, 1));
+        assert_eq!(stats.draw_calls(), 2);
+        assert!(stats.elapsed_ms >= 0.0);
+    }
+
+    #[test]
+    fn test_post_effect_covers_frame_area() {
+        let cframe = CFrameData::new(2, 2, b"ABCD".to_vec(), vec![255; 12]);
+        let mut config = RenderConfig::new(10.0);
+        config.container = Some((100.0, 100.0));
+        assert!(render_cframe(&cframe, &config).post.is_empty());
+
+        config.post_effect = Some(PostEffect::scanlines());
+        let result = render_cframe(&cframe, &config);
+        assert!(!result.post.is_empty());
+        assert!(result.post.iter().all(|rect| rect.x == result.letterbox.left && rect.width == 2.0 * config.char_width()));
+        assert!(result.post.iter().all(|rect| rect.y >= result.letterbox.top && rect.y < result.letterbox.top + 2.0 * config.line_height()));
+    }
+
+    #[test]
+    fn test_background_fill_instruction() {
+        let cframe = CFrameData::new(3, 2, vec![b'A'; 6], vec![255; 18]);
+        let mut config = RenderConfig::new(10.0);
+        assert!(render_cframe(&cframe, &config).background.is_none());
+
+        config.background_color = Some((10, 20, 30));
+        let result = render_cframe(&cframe, &config);
+        let fill = result.background.unwrap();
+        assert_eq!((fill.x, fill.y), (0.0, 0.0));
+        assert_eq!((fill.width, fill.height), (result.width, result.height));
+        assert_eq!(fill.color, (10, 20, 30));
+    }
+
+    #[test]
+    fn test_block_mode_emits_rects() {
+        let cframe = CFrameData::with_background(3, 1, vec![b'#', b'#', b' '], vec![255, 0, 0, 255, 0, 0, 0, 255, 0], vec![1, 1, 1, 2, 2, 2, 3, 3, 3]);
+        let mut config = RenderConfig::new(10.0);
+        config.mode = RenderMode::Blocks {show_chars: false};
+        let result = render_cframe(&cframe, &config);
+
+        assert!(result.batches.is_empty());
+        let block = result.background_batches.last().unwrap();
+        assert_eq!(block.color, (255, 0, 0));
+        assert!((block.width - 12.0).abs() < 0.01); // 2 cells * 10 * 0.6
+
+        config.mode = RenderMode::Blocks {show_chars: true};
+        let result = render_cframe(&cframe, &config);
+        assert_eq!(result.batches.len(), 2);
+        assert_eq!((result.batches[0].text.as_str(), result.batches[0].color), ("#", (1, 1, 1)));
+    }
+
+    #[test]
+    fn test_render_cframe_diff_only_changed_cells() {
+        let prev = CFrameData::new(4, 2, b"ABCDEFGH".to_vec(), vec![200; 24]);
+        let mut next = prev.clone();
+        next.chars[5] = b'Z';
+        next.rgb[6 * 3] = 10; // cell 6 changes color only
+        let config = RenderConfig::new(10.0);
+        let diff = render_cframe_diff(&prev, &next, &config);
+
+        assert!(!diff.full);
+        assert_eq!(diff.clears.len(), 1);
+        assert!((diff.clears[0].x - 6.0).abs() < 0.01);
+        assert!((diff.clears[0].width - 12.0).abs() < 0.01);
+        assert_eq!(diff.batches.len(), 2); // "Z" and recolored "G"
+        assert_eq!(diff.batches[0].text, "Z");
+
+        assert!(render_cframe_diff(&next, &next, &config).is_empty());
+    }
+
+    #[test]
+    fn test_render_cframe_diff_resize_is_full() {
+        let prev = CFrameData::new(1, 1, vec![b'A'], vec![255; 3]);
+        let next = CFrameData::new(2, 1, vec![b'A', b'B'], vec![255; 6]);
+        let diff = render_cframe_diff(&prev, &next, &RenderConfig::new(10.0));
+        assert!(diff.full);
+        assert_eq!(diff.batches.len(), 1);
+        assert_eq!(diff.batches[0].text, "AB");
+    }
 }
@@ -0,0 +1,159 @@
+//! Platform-agnostic cache of computed [`RenderResult`]s.
+//!
+//! The web backend caches rendered frames as offscreen canvases; this cache
+//! gives native and terminal consumers the same "render once, replay
+//! cheaply" behavior by storing the draw commands themselves.
+
+use std::collections::HashMap;
+
+use super::{render_cframe, RenderConfig, RenderResult};
+use crate::CFrameData;
+
+/// Default number of cached render results.
+pub const DEFAULT_RENDER_CACHE_CAPACITY: usize = 256;
+
+/// LRU cache of render results keyed by frame index and
+/// [`RenderConfig::cache_key`].
+#[derive(Clone, Debug)]
+pub struct RenderCache {
+    entries: HashMap<(usize, u64), (RenderResult, u64)>,
+    capacity: usize,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_RENDER_CACHE_CAPACITY)
+    }
+}
+
+impl RenderCache {
+    /// Create a cache holding at most `capacity` results (minimum 1).
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {entries: HashMap::new(), capacity: capacity.max(1), clock: 0, hits: 0, misses: 0}
+    }
+
+    /// Look up a cached result, marking it as recently used.
+    pub fn get(&mut self, index: usize, key: u64) -> Option<&RenderResult> {
+        self.clock += 1;
+        match self.entries.get_mut(&(index, key)) {
+            Some((result, used)) => {
+                *used = self.clock;
+                self.hits += 1;
+                Some(result)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store a result, evicting the least recently used entry when full.
+    pub fn insert(&mut self, index: usize, key: u64, result: RenderResult) {
+        self.clock += 1;
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&(index, key)) {
+            if let Some(oldest) = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| *k) {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert((index, key), (result, self.clock));
+    }
+
+    /// Return the cached result for frame `index`, rendering and storing it on a miss.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{CFrameData, RenderCache, RenderConfig};
+    ///
+    /// let mut cache = RenderCache::with_capacity(8);
+    /// let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 255, 255]);
+    /// let config = RenderConfig::new(12.0);
+    ///
+    /// assert_eq!(cache.get_or_render(0, &cframe, &config).batches.len(), 1);
+    /// cache.get_or_render(0, &cframe, &config);
+    /// assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    /// ```
+    pub fn get_or_render(&mut self, index: usize, cframe: &CFrameData, config: &RenderConfig) -> &RenderResult {
+        let key = config.cache_key();
+        if self.get(index, key).is_none() {
+            self.insert(index, key, render_cframe(cframe, config));
+        }
+        &self.entries[&(index, key)].0
+    }
+
+    /// Drop every cached result for frame `index` (e.g. after editing it).
+    pub fn invalidate_frame(&mut self, index: usize) {
+        self.entries.retain(|(i, _), _| *i != index);
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Maximum number of cached results.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Drop every cached result.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of lookups that found a cached result.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups that missed.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lru_eviction() {
+        let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 255, 255]);
+        let config = RenderConfig::new(10.0);
+        let mut cache = RenderCache::with_capacity(2);
+
+        cache.get_or_render(0, &cframe, &config);
+        cache.get_or_render(1, &cframe, &config);
+        cache.get_or_render(0, &cframe, &config); // 1 is now least recently used
+        cache.get_or_render(2, &cframe, &config);
+
+        let key = config.cache_key();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(0, key).is_some());
+        assert!(cache.get(1, key).is_none());
+        assert!(cache.get(2, key).is_some());
+    }
+
+    #[test]
+    fn test_font_size_is_part_of_key() {
+        let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 255, 255]);
+        let mut cache = RenderCache::default();
+
+        let small = cache.get_or_render(0, &cframe, &RenderConfig::new(10.0)).width;
+        let large = cache.get_or_render(0, &cframe, &RenderConfig::new(20.0)).width;
+        assert!(large > small);
+        assert_eq!(cache.len(), 2);
+
+        cache.invalidate_frame(0);
+        assert!(cache.is_empty());
+    }
+}
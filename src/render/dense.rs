@@ -0,0 +1,237 @@
+//! High-density output that packs several cframe cells into one character.
+//!
+//! [`DensePacking::HalfBlock`] stacks two cells vertically into `▀` / `▄`
+//! with separate foreground and background colors. [`DensePacking::Braille`]
+//! maps a 2×4 block of cells onto the dots of one braille pattern and blends
+//! their colors. Both show large frames in small terminals or thumbnails.
+
+use super::terminal::{push_ansi_row, AnsiColors, CURSOR_HOME};
+use super::{CellRectBatch, RenderConfig, RenderLayer, RenderResult, TextBatch};
+use crate::CFrameData;
+
+/// How many cells are packed into one output character.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DensePacking {
+    /// 1×2 cells per character (`▀` / `▄`)
+    #[default]
+    HalfBlock,
+    /// 2×4 cells per character (U+2800 braille patterns)
+    Braille,
+}
+
+impl DensePacking {
+    /// `(columns, rows)` of cells covered by one output character.
+    #[inline]
+    pub fn cell_size(self) -> (usize, usize) {
+        match self {
+            DensePacking::HalfBlock => (1, 2),
+            DensePacking::Braille => (2, 4),
+        }
+    }
+}
+
+/// One packed output character.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DenseCell {
+    /// Output character (space when nothing is visible)
+    pub ch: char,
+    /// Ink color, `None` when the character has no ink
+    pub fg: Option<(u8, u8, u8)>,
+    /// Fill color behind the character, if any
+    pub bg: Option<(u8, u8, u8)>,
+}
+
+/// A grid of packed characters, row-major.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenseGrid {
+    /// Characters per row
+    pub width: usize,
+    /// Number of rows
+    pub height: usize,
+    /// `width * height` cells
+    pub cells: Vec<DenseCell>,
+}
+
+/// Braille dot bit for cell `(col, row)` inside a 2×4 block.
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Pack a color frame into a grid of dense characters.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::CFrameData;
+/// use cascii_core_view::render::dense::{pack_cells, DensePacking};
+///
+/// let cframe = CFrameData::new(2, 4, vec![b'#'; 8], vec![255; 24]);
+/// let grid = pack_cells(&cframe, DensePacking::Braille);
+/// assert_eq!((grid.width, grid.height), (1, 1));
+/// assert_eq!(grid.cells[0].ch, '⣿');
+/// ```
+pub fn pack_cells(cframe: &CFrameData, packing: DensePacking) -> DenseGrid {
+    let (cols, rows) = packing.cell_size();
+    let width = (cframe.width as usize).div_ceil(cols);
+    let height = (cframe.height as usize).div_ceil(rows);
+    let mut cells = Vec::with_capacity(width * height);
+    for out_row in 0..height {
+        for out_col in 0..width {
+            let (row, col) = (out_row * rows, out_col * cols);
+            cells.push(match packing {
+                DensePacking::HalfBlock => half_block(cframe, row, col),
+                DensePacking::Braille => braille(cframe, row, col),
+            });
+        }
+    }
+    DenseGrid {width, height, cells}
+}
+
+fn half_block(cframe: &CFrameData, row: usize, col: usize) -> DenseCell {
+    match (cell_color(cframe, row, col), cell_color(cframe, row + 1, col)) {
+        (Some(top), bottom) => DenseCell {ch: '▀', fg: Some(top), bg: bottom},
+        (None, Some(bottom)) => DenseCell {ch: '▄', fg: Some(bottom), bg: None},
+        (None, None) => DenseCell {ch: ' ', fg: None, bg: None},
+    }
+}
+
+fn braille(cframe: &CFrameData, row: usize, col: usize) -> DenseCell {
+    let mut bits = 0;
+    let mut ink = Vec::with_capacity(8);
+    let mut fill = Vec::with_capacity(8);
+    for (dx, dots) in BRAILLE_DOTS.iter().enumerate() {
+        for (dy, &dot) in dots.iter().enumerate() {
+            let (r, c) = (row + dy, col + dx);
+            if cframe.has_visible_foreground(r, c) {
+                bits |= dot;
+                ink.extend(cframe.rgb_at(r, c));
+            }
+            fill.extend(cframe.bg_rgb_at(r, c));
+        }
+    }
+    let ch = char::from_u32(0x2800 + bits).unwrap_or(' ');
+    DenseCell {ch, fg: average(&ink), bg: average(&fill)}
+}
+
+/// Visible color of one cell: its foreground when it has ink, otherwise
+/// its per-cell background.
+fn cell_color(cframe: &CFrameData, row: usize, col: usize) -> Option<(u8, u8, u8)> {
+    if row >= cframe.height as usize || col >= cframe.width as usize {
+        return None;
+    }
+    if cframe.has_visible_foreground(row, col) {
+        return cframe.rgb_at(row, col);
+    }
+    cframe.bg_rgb_at(row, col)
+}
+
+fn average(colors: &[(u8, u8, u8)]) -> Option<(u8, u8, u8)> {
+    if colors.is_empty() {
+        return None;
+    }
+    let n = colors.len() as u32;
+    let (r, g, b) = colors.iter().fold((0u32, 0u32, 0u32), |(r, g, b), c| (r + c.0 as u32, g + c.1 as u32, b + c.2 as u32));
+    Some(((r / n) as u8, (g / n) as u8, (b / n) as u8))
+}
+
+/// Generate draw commands for a packed frame.
+///
+/// Each output character occupies one `char_width × line_height` cell of
+/// `config`. Fills are batched into `background_batches` and same-colored
+/// runs of characters into `batches`, like [`render_cframe`](super::render_cframe).
+pub fn render_dense(cframe: &CFrameData, packing: DensePacking, config: &RenderConfig) -> RenderResult {
+    let grid = pack_cells(cframe, packing);
+    let char_width = config.char_width();
+    let line_height = config.line_height();
+    let canvas_width = grid.width as f64 * char_width;
+    let canvas_height = grid.height as f64 * line_height;
+
+    let mut background_batches = Vec::new();
+    let mut batches = Vec::new();
+    for row in 0..grid.height {
+        let cells = &grid.cells[row * grid.width..(row + 1) * grid.width];
+        let y = row as f64 * line_height;
+        let mut col = 0;
+        while col < cells.len() {
+            let Some(color) = cells[col].bg else { col += 1; continue; };
+            let start = col;
+            while col < cells.len() && cells[col].bg == Some(color) {
+                col += 1;
+            }
+            background_batches.push(CellRectBatch {x: start as f64 * char_width, y, width: (col - start) as f64 * char_width, height: line_height, color});
+        }
+        let mut col = 0;
+        while col < cells.len() {
+            let Some(color) = cells[col].fg else { col += 1; continue; };
+            let start = col;
+            let mut text = String::new();
+            while col < cells.len() && cells[col].fg == Some(color) {
+                text.push(cells[col].ch);
+                col += 1;
+            }
+            batches.push(TextBatch {text, x: start as f64 * char_width, y, color, opacity: 1.0});
+        }
+    }
+
+    let background = config.background_color.map(|color| CellRectBatch {x: 0.0, y: 0.0, width: canvas_width, height: canvas_height, color});
+    RenderResult {width: canvas_width, height: canvas_height, background, background_batches, batches, overlay: RenderLayer::default(), letterbox: Default::default(), glow: config.active_glow(), post: Vec::new(), stats: None}
+}
+
+/// Render a packed frame as truecolor ANSI text, with the same cursor-home
+/// and row handling as [`render_to_ansi`](super::terminal::render_to_ansi).
+pub fn render_dense_to_ansi(cframe: &CFrameData, packing: DensePacking) -> String {
+    let grid = pack_cells(cframe, packing);
+    let mut out = String::with_capacity(CURSOR_HOME.len() + grid.cells.len() * 4);
+    out.push_str(CURSOR_HOME);
+    for row in 0..grid.height {
+        let cells = &grid.cells[row * grid.width..(row + 1) * grid.width];
+        push_ansi_row(&mut out, cells.iter().map(|cell| (cell.ch, cell.fg, cell.bg)), AnsiColors::TrueColor);
+        if row + 1 < grid.height {
+            out.push_str("\r\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_block_packing() {
+        // Column 0: red over green; column 1: blank over blue; odd third row.
+        let cframe = CFrameData::new(
+            2,
+            3,
+            b"# ### ".to_vec(),
+            vec![255, 0, 0, 0, 0, 0, 0, 255, 0, 0, 0, 255, 9, 9, 9, 0, 0, 0],
+        );
+        let grid = pack_cells(&cframe, DensePacking::HalfBlock);
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.cells[0], DenseCell {ch: '▀', fg: Some((255, 0, 0)), bg: Some((0, 255, 0))});
+        assert_eq!(grid.cells[1], DenseCell {ch: '▄', fg: Some((0, 0, 255)), bg: None});
+        assert_eq!(grid.cells[2], DenseCell {ch: '▀', fg: Some((9, 9, 9)), bg: None});
+        assert_eq!(grid.cells[3].ch, ' ');
+
+        let ansi = render_dense_to_ansi(&cframe, DensePacking::HalfBlock);
+        assert!(ansi.starts_with("\x1b[H\x1b[48;2;0;255;0m\x1b[38;2;255;0;0m▀"));
+    }
+
+    #[test]
+    fn test_braille_blends_colors() {
+        let mut chars = vec![b' '; 8];
+        chars[0] = b'#'; // (row 0, col 0)
+        chars[7] = b'#'; // (row 3, col 1)
+        let mut rgb = vec![0; 24];
+        rgb[0..3].copy_from_slice(&[200, 0, 100]);
+        rgb[21..24].copy_from_slice(&[0, 100, 100]);
+        let cframe = CFrameData::new(2, 4, chars, rgb);
+
+        let cell = pack_cells(&cframe, DensePacking::Braille).cells[0];
+        assert_eq!(cell.ch, char::from_u32(0x2800 + 0x01 + 0x80).unwrap());
+        assert_eq!(cell.fg, Some((100, 50, 100)));
+
+        let result = render_dense(&cframe, DensePacking::Braille, &RenderConfig::new(10.0));
+        assert_eq!(result.batches.len(), 1);
+        assert_eq!(result.width, RenderConfig::new(10.0).char_width());
+    }
+}
@@ -0,0 +1,140 @@
+//! CRT-style post-processing.
+//!
+//! A [`PostEffect`] turns into translucent [`PostRect`]s (scanlines and a
+//! vignette built from nested rings) that are painted over the frame. Every
+//! backend that can fill a rectangle with an alpha can reproduce the look.
+
+/// A translucent rectangle painted over the frame.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PostRect {
+    /// X position in pixels
+    pub x: f64,
+    /// Y position in pixels
+    pub y: f64,
+    /// Width in pixels
+    pub width: f64,
+    /// Height in pixels
+    pub height: f64,
+    /// RGB color as (r, g, b)
+    pub color: (u8, u8, u8),
+    /// Opacity in 0.0 - 1.0
+    pub opacity: f64,
+}
+
+impl PostRect {
+    /// Get the color as a CSS-compatible string "rgba(r,g,b,a)"
+    pub fn color_string(&self) -> String {
+        format!("rgba({},{},{},{})", self.color.0, self.color.1, self.color.2, self.opacity.clamp(0.0, 1.0))
+    }
+}
+
+/// Scanline and vignette settings. The default is a subtle CRT look.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PostEffect {
+    /// Distance in pixels between scanlines; `0.0` disables them
+    pub scanline_spacing: f64,
+    /// Height of each scanline in pixels
+    pub scanline_thickness: f64,
+    /// Scanline opacity in 0.0 - 1.0
+    pub scanline_opacity: f64,
+    /// Opacity of the vignette at the frame edges; `0.0` disables it
+    pub vignette: f64,
+    /// Number of rings the vignette fades over
+    pub vignette_steps: u32,
+    /// Color of the scanlines and vignette
+    pub color: (u8, u8, u8),
+}
+
+impl Default for PostEffect {
+    fn default() -> Self {
+        Self {scanline_spacing: 3.0, scanline_thickness: 1.0, scanline_opacity: 0.25, vignette: 0.35, vignette_steps: 8, color: (0, 0, 0)}
+    }
+}
+
+impl PostEffect {
+    /// Scanlines only, without a vignette.
+    pub fn scanlines() -> Self {
+        Self {vignette: 0.0, ..Self::default()}
+    }
+
+    /// Returns `true` when the effect draws nothing.
+    #[inline]
+    pub fn is_noop(&self) -> bool {
+        !self.has_scanlines() && !self.has_vignette()
+    }
+
+    fn has_scanlines(&self) -> bool {
+        self.scanline_spacing > 0.0 && self.scanline_thickness > 0.0 && self.scanline_opacity > 0.0
+    }
+
+    fn has_vignette(&self) -> bool {
+        self.vignette > 0.0 && self.vignette_steps > 0
+    }
+
+    /// The rectangles that draw this effect over a frame occupying
+    /// `(x, y, width, height)` pixels. Scanlines come first, then the
+    /// vignette rings from the outside in.
+    pub fn rects(&self, (x, y, width, height): (f64, f64, f64, f64)) -> Vec<PostRect> {
+        let mut rects = Vec::new();
+        if width <= 0.0 || height <= 0.0 {
+            return rects;
+        }
+        if self.has_scanlines() {
+            let mut line = y + self.scanline_spacing - self.scanline_thickness.min(self.scanline_spacing);
+            while line < y + height {
+                let thickness = self.scanline_thickness.min(y + height - line);
+                rects.push(PostRect {x, y: line, width, height: thickness, color: self.color, opacity: self.scanline_opacity});
+                line += self.scanline_spacing;
+            }
+        }
+        if self.has_vignette() {
+            // The vignette fades over the outer quarter of the shorter side.
+            let steps = self.vignette_steps;
+            let ring = width.min(height) / 4.0 / steps as f64;
+            for step in 0..steps {
+                let falloff = (steps - step) as f64 / steps as f64;
+                let opacity = self.vignette * falloff * falloff;
+                let inset = step as f64 * ring;
+                let (left, top) = (x + inset, y + inset);
+                let (inner_width, inner_height) = (width - 2.0 * inset, height - 2.0 * inset);
+                let side_height = inner_height - 2.0 * ring;
+                let mut push = |x, y, width, height| rects.push(PostRect {x, y, width, height, color: self.color, opacity});
+                push(left, top, inner_width, ring);
+                push(left, top + inner_height - ring, inner_width, ring);
+                push(left, top + ring, ring, side_height);
+                push(left + inner_width - ring, top + ring, ring, side_height);
+            }
+        }
+        rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanline_rects() {
+        let rects = PostEffect::scanlines().rects((10.0, 0.0, 100.0, 9.0));
+        let rows: Vec<f64> = rects.iter().map(|rect| rect.y).collect();
+        assert_eq!(rows, [2.0, 5.0, 8.0]);
+        assert!(rects.iter().all(|rect| rect.x == 10.0 && rect.width == 100.0 && rect.height == 1.0));
+        assert_eq!(rects[0].color_string(), "rgba(0,0,0,0.25)");
+    }
+
+    #[test]
+    fn test_vignette_rings_fade_inwards() {
+        let effect = PostEffect {scanline_spacing: 0.0, vignette_steps: 2, ..PostEffect::default()};
+        let rects = effect.rects((0.0, 0.0, 80.0, 40.0));
+        assert_eq!(rects.len(), 8);
+        // ring width: 40 / 4 / 2
+        assert_eq!(rects[0], PostRect {x: 0.0, y: 0.0, width: 80.0, height: 5.0, color: (0, 0, 0), opacity: 0.35});
+        assert_eq!((rects[4].x, rects[4].y, rects[4].width), (5.0, 5.0, 70.0));
+        assert!(rects[4].opacity < rects[0].opacity);
+
+        assert!(PostEffect {vignette: 0.0, ..effect}.is_noop());
+    }
+}
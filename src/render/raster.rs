@@ -0,0 +1,249 @@
+//! Software rasterizer for [`RenderResult`] draw commands.
+//!
+//! Turns the same batches the canvas backend consumes into an RGB pixel
+//! buffer, using an embedded 8×8 bitmap font scaled to the cell size. This
+//! is what pixel-based exporters (SIXEL, images) build on, so they all agree
+//! with the batching logic in [`render_cframe`].
+
+use super::font::glyph;
+use super::{render_cframe, CellRectBatch, RenderConfig, RenderLayer, RenderResult, TextBatch};
+use crate::CFrameData;
+
+/// An 8-bit RGB image, row-major with no padding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RasterImage {
+    /// Image width in pixels
+    pub width: u32,
+    /// Image height in pixels
+    pub height: u32,
+    /// Pixel data as `[r, g, b, r, g, b, ...]` (width * height * 3)
+    pub pixels: Vec<u8>,
+}
+
+impl RasterImage {
+    /// Create an image filled with a single color.
+    pub fn new(width: u32, height: u32, fill: (u8, u8, u8)) -> Self {
+        let count = width as usize * height as usize;
+        let mut pixels = Vec::with_capacity(count * 3);
+        for _ in 0..count {
+            pixels.extend_from_slice(&[fill.0, fill.1, fill.2]);
+        }
+        Self {width, height, pixels}
+    }
+
+    /// Get the color of one pixel, or `None` when out of bounds.
+    #[inline]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<(u8, u8, u8)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 3;
+        Some((self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2]))
+    }
+
+    /// Encode as an 8-bit RGB PNG.
+    ///
+    /// Returns an empty buffer for zero-sized images, which PNG cannot represent.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Vec<u8> {
+        if self.width == 0 || self.height == 0 {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        let mut encoder = ::png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(::png::ColorType::Rgb);
+        encoder.set_depth(::png::BitDepth::Eight);
+        let written = encoder.write_header().and_then(|mut writer| writer.write_image_data(&self.pixels));
+        if written.is_err() {
+            return Vec::new();
+        }
+        out
+    }
+
+    /// Paint one pixel, blending over the existing color by `alpha`.
+    #[inline]
+    fn put(&mut self, x: i64, y: i64, color: (u8, u8, u8), alpha: f64) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let idx = (y as usize * self.width as usize + x as usize) * 3;
+        if alpha >= 1.0 {
+            self.pixels[idx] = color.0;
+            self.pixels[idx + 1] = color.1;
+            self.pixels[idx + 2] = color.2;
+            return;
+        }
+        for (dst, src) in self.pixels[idx..idx + 3].iter_mut().zip([color.0, color.1, color.2]) {
+            *dst = (*dst as f64 + (src as f64 - *dst as f64) * alpha).round() as u8;
+        }
+    }
+
+    fn fill_rect(&mut self, rect: &CellRectBatch, alpha: f64) {
+        let x0 = rect.x.round() as i64;
+        let y0 = rect.y.round() as i64;
+        let x1 = (rect.x + rect.width).round() as i64;
+        let y1 = (rect.y + rect.height).round() as i64;
+        for y in y0.max(0)..y1.min(self.height as i64) {
+            for x in x0.max(0)..x1.min(self.width as i64) {
+                self.put(x, y, rect.color, alpha);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, batch: &TextBatch, char_width: f64, glyph_height: f64, alpha: f64) {
+        let alpha = alpha * batch.opacity.clamp(0.0, 1.0);
+        for (i, ch) in batch.text.bytes().enumerate() {
+            let x0 = batch.x + i as f64 * char_width;
+            self.draw_glyph(ch, x0, batch.y, (char_width, glyph_height), batch.color, alpha);
+        }
+    }
+
+    fn draw_glyph(&mut self, ch: u8, x: f64, y: f64, (w, h): (f64, f64), color: (u8, u8, u8), alpha: f64) {
+        if w <= 0.0 || h <= 0.0 {
+            return;
+        }
+        let bitmap = glyph(ch);
+        let px0 = x.round() as i64;
+        let py0 = y.round() as i64;
+        let px1 = (x + w).round() as i64;
+        let py1 = (y + h).round() as i64;
+        for py in py0..py1 {
+            let gy = (((py - py0) as f64 + 0.5) * 8.0 / h) as usize;
+            let row = bitmap[gy.min(7)];
+            if row == 0 {
+                continue;
+            }
+            for px in px0..px1 {
+                let gx = (((px - px0) as f64 + 0.5) * 8.0 / w) as usize;
+                if row & (1 << gx.min(7)) != 0 {
+                    self.put(px, py, color, alpha);
+                }
+            }
+        }
+    }
+}
+
+/// Rasterize a render result into an RGB image.
+///
+/// The image starts black, then the background fill, background batches,
+/// text batches, and the overlay layer are painted in the same order every
+/// other backend uses. Frame primitives are blended by
+/// [`RenderConfig::opacity`]; the overlay is always opaque.
+pub fn rasterize(result: &RenderResult, config: &RenderConfig) -> RasterImage {
+    let width = result.width.ceil().max(0.0) as u32;
+    let height = result.height.ceil().max(0.0) as u32;
+    let mut image = RasterImage::new(width, height, (0, 0, 0));
+
+    let char_width = config.char_width();
+    let glyph_height = config.font_size.min(config.line_height());
+    let alpha = config.opacity.clamp(0.0, 1.0);
+
+    for rect in result.background.iter().chain(&result.background_batches) {
+        image.fill_rect(rect, alpha);
+    }
+    for batch in &result.batches {
+        image.draw_text(batch, char_width, glyph_height, alpha);
+    }
+    for rect in &result.post {
+        image.fill_rect(&CellRectBatch {x: rect.x, y: rect.y, width: rect.width, height: rect.height, color: rect.color}, rect.opacity.clamp(0.0, 1.0));
+    }
+    paint_layer(&mut image, &result.overlay, char_width, glyph_height);
+    image
+}
+
+fn paint_layer(image: &mut RasterImage, layer: &RenderLayer, char_width: f64, glyph_height: f64) {
+    for rect in &layer.rects {
+        image.fill_rect(rect, 1.0);
+    }
+    for batch in &layer.batches {
+        image.draw_text(batch, char_width, glyph_height, 1.0);
+    }
+}
+
+/// Render and rasterize a color frame in one step.
+pub fn rasterize_cframe(cframe: &CFrameData, config: &RenderConfig) -> RasterImage {
+    let (cframe, config) = super::viewport::apply(cframe, config);
+    rasterize(&render_cframe(&cframe, &config), &config)
+}
+
+/// Rasterize a color frame and encode it as PNG, for "save frame as image".
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, RenderConfig};
+/// use cascii_core_view::render::raster::export_frame_png;
+///
+/// let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 0, 0]);
+/// let png = export_frame_png(&cframe, &RenderConfig::new(12.0));
+/// assert!(png.starts_with(b"\x89PNG"));
+/// ```
+#[cfg(feature = "png")]
+pub fn export_frame_png(cframe: &CFrameData, config: &RenderConfig) -> Vec<u8> {
+    rasterize_cframe(cframe, config).to_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rasterize_dimensions_and_background() {
+        let cframe = CFrameData::new(2, 1, vec![b' ', b' '], vec![0; 6]);
+        let mut config = RenderConfig::new(10.0);
+        config.background_color = Some((1, 2, 3));
+        let image = rasterize_cframe(&cframe, &config);
+
+        assert_eq!((image.width, image.height), (12, 12)); // 2 * 6px, ceil(11.1)
+        assert_eq!(image.pixel(0, 0), Some((1, 2, 3)));
+        assert_eq!(image.pixel(12, 0), None);
+    }
+
+    #[test]
+    fn test_rasterize_draws_glyph_pixels() {
+        let cframe = CFrameData::new(1, 1, vec![b'#'], vec![255, 0, 0]);
+        let config = RenderConfig::new(16.0);
+        let image = rasterize_cframe(&cframe, &config);
+
+        let red = image.pixels.chunks_exact(3).filter(|p| p == &[255, 0, 0]).count();
+        assert!(red > 0);
+        assert!(red < (image.width * image.height) as usize);
+    }
+
+    #[test]
+    fn test_rasterize_cell_backgrounds() {
+        let cframe = CFrameData::with_background(2, 1, vec![b' ', b' '], vec![0; 6], vec![0, 255, 0, 0, 0, 255]);
+        let config = RenderConfig::new(10.0);
+        let image = rasterize_cframe(&cframe, &config);
+
+        assert_eq!(image.pixel(1, 1), Some((0, 255, 0)));
+        assert_eq!(image.pixel(7, 1), Some((0, 0, 255)));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_png_roundtrip() {
+        let cframe = CFrameData::with_background(2, 1, vec![b'#', b' '], vec![255, 0, 0, 0, 0, 0], vec![0, 0, 255, 0, 255, 0]);
+        let config = RenderConfig::new(10.0);
+        let image = rasterize_cframe(&cframe, &config);
+        let bytes = export_frame_png(&cframe, &config);
+
+        let mut reader = ::png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (image.width, image.height));
+        assert_eq!(&pixels[..info.buffer_size()], image.pixels.as_slice());
+
+        assert!(RasterImage::new(0, 0, (0, 0, 0)).to_png().is_empty());
+    }
+
+    #[test]
+    fn test_rasterize_global_opacity() {
+        let cframe = CFrameData::with_background(1, 1, vec![b' '], vec![0; 3], vec![200, 100, 0]);
+        let mut config = RenderConfig::new(10.0);
+        config.opacity = 0.5;
+        let image = rasterize_cframe(&cframe, &config);
+
+        assert_eq!(image.pixel(1, 1), Some((100, 50, 0)));
+    }
+}
@@ -0,0 +1,192 @@
+//! SIXEL export of rendered frames.
+//!
+//! Frames are rasterized with [`raster`](super::raster) and encoded as a
+//! DEC SIXEL escape sequence, giving pixel-accurate previews in terminals
+//! that support it (xterm `-ti vt340`, mlterm, WezTerm, foot, ...).
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::raster::{rasterize_cframe, RasterImage};
+use super::RenderConfig;
+use crate::CFrameData;
+
+/// Maximum number of color registers emitted.
+///
+/// 256 is the common register count for modern SIXEL terminals. Images with
+/// more distinct colors are reduced to a 6×6×6 color cube.
+pub const MAX_SIXEL_COLORS: usize = 256;
+
+/// Render a color frame and encode it as a SIXEL escape sequence.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, RenderConfig};
+/// use cascii_core_view::render::sixel::render_to_sixel;
+///
+/// let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 0, 0]);
+/// let sixel = render_to_sixel(&cframe, &RenderConfig::new(12.0));
+/// assert!(sixel.starts_with("\x1bPq"));
+/// assert!(sixel.ends_with("\x1b\\"));
+/// ```
+pub fn render_to_sixel(cframe: &CFrameData, config: &RenderConfig) -> String {
+    encode_sixel(&rasterize_cframe(cframe, config))
+}
+
+/// Encode an RGB image as a SIXEL escape sequence.
+pub fn encode_sixel(image: &RasterImage) -> String {
+    let width = image.width as usize;
+    let height = image.height as usize;
+    let (palette, indices) = build_palette(image);
+
+    let mut out = String::new();
+    let _ = write!(out, "\x1bPq\"1;1;{};{}", width, height);
+    for (register, &(r, g, b)) in palette.iter().enumerate() {
+        let _ = write!(out, "#{};2;{};{};{}", register, percent(r), percent(g), percent(b));
+    }
+
+    let mut band_bits = vec![0u8; width];
+    for band_top in (0..height).step_by(6) {
+        let band_bottom = (band_top + 6).min(height);
+        let mut present = vec![false; palette.len()];
+        for y in band_top..band_bottom {
+            for &index in &indices[y * width..(y + 1) * width] {
+                present[index as usize] = true;
+            }
+        }
+
+        let mut first = true;
+        for register in (0..palette.len()).filter(|&r| present[r]) {
+            band_bits.iter_mut().for_each(|bits| *bits = 0);
+            for y in band_top..band_bottom {
+                let bit = 1 << (y - band_top);
+                for (x, &index) in indices[y * width..(y + 1) * width].iter().enumerate() {
+                    if index as usize == register {
+                        band_bits[x] |= bit;
+                    }
+                }
+            }
+            if !first {
+                out.push('$');
+            }
+            first = false;
+            let _ = write!(out, "#{}", register);
+            push_run_length(&mut out, &band_bits);
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+#[inline]
+fn percent(channel: u8) -> u32 {
+    (channel as u32 * 100 + 127) / 255
+}
+
+/// Map every pixel to a color register, reducing to a color cube when the
+/// image has more distinct colors than [`MAX_SIXEL_COLORS`].
+fn build_palette(image: &RasterImage) -> (Vec<(u8, u8, u8)>, Vec<u16>) {
+    let mut palette = Vec::new();
+    let mut lookup: HashMap<(u8, u8, u8), u16> = HashMap::new();
+    let mut indices = Vec::with_capacity(image.pixels.len() / 3);
+
+    for px in image.pixels.chunks_exact(3) {
+        let color = (px[0], px[1], px[2]);
+        let next = palette.len() as u16;
+        let index = *lookup.entry(color).or_insert_with(|| {
+            palette.push(color);
+            next
+        });
+        if palette.len() > MAX_SIXEL_COLORS {
+            return cube_palette(image);
+        }
+        indices.push(index);
+    }
+    (palette, indices)
+}
+
+fn cube_palette(image: &RasterImage) -> (Vec<(u8, u8, u8)>, Vec<u16>) {
+    const LEVELS: [u8; 6] = [0, 51, 102, 153, 204, 255];
+    let mut palette = Vec::with_capacity(216);
+    for r in LEVELS {
+        for g in LEVELS {
+            for b in LEVELS {
+                palette.push((r, g, b));
+            }
+        }
+    }
+    let level = |c: u8| ((c as u16 + 25) / 51).min(5);
+    let indices = image.pixels.chunks_exact(3).map(|px| level(px[0]) * 36 + level(px[1]) * 6 + level(px[2])).collect();
+    (palette, indices)
+}
+
+fn push_run_length(out: &mut String, bits: &[u8]) {
+    let mut i = 0;
+    while i < bits.len() {
+        let value = bits[i];
+        let start = i;
+        while i < bits.len() && bits[i] == value {
+            i += 1;
+        }
+        let ch = (63 + value) as char;
+        match i - start {
+            1 => out.push(ch),
+            2 => {
+                out.push(ch);
+                out.push(ch);
+            }
+            run => {
+                let _ = write!(out, "!{}{}", run, ch);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_solid_image() {
+        let image = RasterImage::new(10, 6, (255, 0, 0));
+        let sixel = encode_sixel(&image);
+        assert_eq!(sixel, "\x1bPq\"1;1;10;6#0;2;100;0;0#0!10~-\x1b\\");
+    }
+
+    #[test]
+    fn test_encode_multiple_bands_and_colors() {
+        let mut image = RasterImage::new(2, 7, (0, 0, 0));
+        image.pixels[0..3].copy_from_slice(&[255, 255, 255]); // (0, 0) white
+        let sixel = encode_sixel(&image);
+
+        // Registers follow first appearance: white is #0, black is #1.
+        // Band 1: white bit 0 at x=0, black covers the rest; band 2 black only.
+        assert!(sixel.contains("#0;2;100;100;100#1;2;0;0;0"));
+        assert!(sixel.contains("#0@?$#1}~-"));
+        assert!(sixel.ends_with("#1@@-\x1b\\"));
+    }
+
+    #[test]
+    fn test_palette_falls_back_to_cube() {
+        let mut image = RasterImage::new(300, 1, (0, 0, 0));
+        for x in 0..300usize {
+            image.pixels[x * 3] = (x % 256) as u8;
+            image.pixels[x * 3 + 1] = (x / 256) as u8;
+        }
+        let (palette, indices) = build_palette(&image);
+        assert_eq!(palette.len(), 216);
+        assert_eq!(indices.len(), 300);
+    }
+
+    #[test]
+    fn test_render_to_sixel_frame() {
+        let cframe = CFrameData::new(2, 1, vec![b'A', b'B'], vec![255, 0, 0, 0, 255, 0]);
+        let sixel = render_to_sixel(&cframe, &RenderConfig::new(10.0));
+        assert!(sixel.starts_with("\x1bPq\"1;1;12;12"));
+        assert!(sixel.contains(";100;0;0"));
+        assert!(sixel.contains(";0;100;0"));
+    }
+}
@@ -0,0 +1,199 @@
+//! SVG export of rendered frames.
+//!
+//! Emits the same draw commands as [`render_cframe`] as SVG `<rect>` and
+//! `<text>` elements, giving crisp, scalable exports that can be embedded in
+//! documents. Each text run is pinned to the cell grid with `textLength`, so
+//! columns line up even when the viewer substitutes a different monospace
+//! font.
+
+use std::fmt::Write;
+
+use super::{render_cframe, CellRectBatch, FontStyle, RenderConfig, RenderLayer, RenderResult, TextBatch, FONT_WEIGHT_NORMAL};
+use crate::CFrameData;
+
+/// Render a color frame as a standalone SVG document.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, RenderConfig};
+/// use cascii_core_view::render::svg::render_to_svg;
+///
+/// let cframe = CFrameData::new(2, 1, vec![b'<', b'A'], vec![255, 0, 0, 255, 0, 0]);
+/// let svg = render_to_svg(&cframe, &RenderConfig::new(12.0));
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("&lt;A</text>"));
+/// ```
+pub fn render_to_svg(cframe: &CFrameData, config: &RenderConfig) -> String {
+    let (cframe, config) = super::viewport::apply(cframe, config);
+    render_result_to_svg(&render_cframe(&cframe, &config), &config)
+}
+
+/// Convert a render result (including its overlay layer) to SVG.
+pub fn render_result_to_svg(result: &RenderResult, config: &RenderConfig) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = fmt_num(result.width),
+        h = fmt_num(result.height)
+    );
+    let translucent = config.opacity < 1.0;
+    if translucent {
+        let _ = write!(out, r#"<g opacity="{}">"#, fmt_num(config.opacity.max(0.0)));
+    }
+    if let Some(fill) = &result.background {
+        push_rect(&mut out, fill);
+    }
+    for rect in &result.background_batches {
+        push_rect(&mut out, rect);
+    }
+    push_text_group(&mut out, &result.batches, config);
+    if translucent {
+        out.push_str("</g>");
+    }
+    for rect in &result.post {
+        let _ = write!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}"/>"#,
+            fmt_num(rect.x),
+            fmt_num(rect.y),
+            fmt_num(rect.width),
+            fmt_num(rect.height),
+            hex(rect.color),
+            fmt_num(rect.opacity.clamp(0.0, 1.0))
+        );
+    }
+    push_layer(&mut out, &result.overlay, config);
+    out.push_str("</svg>");
+    out
+}
+
+fn push_layer(out: &mut String, layer: &RenderLayer, config: &RenderConfig) {
+    for rect in &layer.rects {
+        push_rect(out, rect);
+    }
+    push_text_group(out, &layer.batches, config);
+}
+
+fn push_rect(out: &mut String, rect: &CellRectBatch) {
+    let _ = write!(
+        out,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+        fmt_num(rect.x),
+        fmt_num(rect.y),
+        fmt_num(rect.width),
+        fmt_num(rect.height),
+        hex(rect.color)
+    );
+}
+
+fn push_text_group(out: &mut String, batches: &[TextBatch], config: &RenderConfig) {
+    if batches.is_empty() {
+        return;
+    }
+    let _ = write!(
+        out,
+        r#"<g font-family="{}" font-size="{}" dominant-baseline="text-before-edge" xml:space="preserve""#,
+        escape(&config.font_family),
+        fmt_num(config.font_size)
+    );
+    if config.font_weight != FONT_WEIGHT_NORMAL {
+        let _ = write!(out, r#" font-weight="{}""#, config.font_weight);
+    }
+    if config.font_style != FontStyle::Normal {
+        let _ = write!(out, r#" font-style="{}""#, config.font_style.as_css());
+    }
+    if config.text_stroke_width > 0.0 {
+        let _ = write!(out, r#" stroke-width="{}" paint-order="stroke""#, fmt_num(config.text_stroke_width));
+    }
+    out.push('>');
+    let char_width = config.char_width();
+    let stroke = config.text_stroke_width > 0.0;
+    for batch in batches {
+        let color = hex(batch.color);
+        let _ = write!(out, r#"<text x="{}" y="{}" fill="{}""#, fmt_num(batch.x), fmt_num(batch.y), color);
+        if stroke {
+            let _ = write!(out, r#" stroke="{}""#, color);
+        }
+        if batch.opacity < 1.0 {
+            let _ = write!(out, r#" opacity="{}""#, fmt_num(batch.opacity.max(0.0)));
+        }
+        let length = crate::display_width(&batch.text) as f64 * char_width;
+        let _ = write!(out, r#" textLength="{}" lengthAdjust="spacingAndGlyphs">{}</text>"#, fmt_num(length), escape(&batch.text));
+    }
+    out.push_str("</g>");
+}
+
+fn hex(color: (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+/// Format a coordinate with at most two decimals and no trailing zeros.
+fn fmt_num(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_elements() {
+        let cframe = CFrameData::with_background(3, 1, b"AB&".to_vec(), vec![255, 0, 0, 255, 0, 0, 0, 255, 0], vec![0, 0, 255, 0, 0, 255, 0, 0, 255]);
+        let mut config = RenderConfig::new(10.0);
+        config.background_color = Some((1, 2, 3));
+        config.font_family = "'Cascadia Mono', monospace".into();
+        let svg = render_to_svg(&cframe, &config);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="18" height="11.1" viewBox="0 0 18 11.1">"#));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="18" height="11.1" fill="#010203"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="18" height="11.1" fill="#0000ff"/>"##));
+        assert!(svg.contains("font-family=\"&apos;Cascadia Mono&apos;, monospace\""));
+        assert!(svg.contains(r##"<text x="0" y="0" fill="#ff0000" textLength="12" lengthAdjust="spacingAndGlyphs">AB</text>"##));
+        assert!(svg.contains(r##"<text x="12" y="0" fill="#00ff00" textLength="6" lengthAdjust="spacingAndGlyphs">&amp;</text>"##));
+        assert!(svg.ends_with("</g></svg>"));
+        assert!(!svg.contains("font-weight"));
+
+        config.font_weight = crate::render::FONT_WEIGHT_BOLD;
+        config.font_style = FontStyle::Italic;
+        assert!(render_to_svg(&cframe, &config).contains(r#"font-weight="700" font-style="italic""#));
+    }
+
+    #[test]
+    fn test_fmt_num() {
+        assert_eq!(fmt_num(12.0), "12");
+        assert_eq!(fmt_num(13.325), "13.32");
+        assert_eq!(fmt_num(-0.001), "0");
+    }
+
+    #[test]
+    fn test_svg_opacity() {
+        let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 255, 255]);
+        let mut config = RenderConfig::new(10.0);
+        config.opacity = 0.25;
+        let mut result = render_cframe(&cframe, &config);
+        result.batches[0].opacity = 0.5;
+        let svg = render_result_to_svg(&result, &config);
+
+        assert!(svg.contains(r#"<g opacity="0.25">"#));
+        assert!(svg.contains(r#" opacity="0.5" textLength"#));
+    }
+}
@@ -0,0 +1,166 @@
+//! ANSI terminal output of color frames.
+//!
+//! Cells are emitted as truecolor SGR escape sequences (or, for terminals
+//! without truecolor, xterm 256-color / 16-color escapes through
+//! [`render_to_ansi_with`]), so native apps can play the same frames in a
+//! terminal without a second rendering implementation. Consecutive cells that share colors are batched into a
+//! single escape, mirroring the run batching of the canvas renderer.
+
+use std::fmt::Write;
+
+use crate::{rgb_to_ansi16, rgb_to_ansi256, CFrameData};
+
+/// Move the cursor to the top-left corner.
+pub const CURSOR_HOME: &str = "\x1b[H";
+
+/// Reset all SGR attributes.
+pub const SGR_RESET: &str = "\x1b[0m";
+
+/// Color escapes a terminal understands.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnsiColors {
+    /// 24-bit `38;2;r;g;b` escapes
+    #[default]
+    TrueColor,
+    /// xterm 256-color `38;5;n` escapes (see [`rgb_to_ansi256`])
+    Ansi256,
+    /// The 16 system colors, SGR 30-37 / 90-97 (see [`rgb_to_ansi16`])
+    Ansi16,
+}
+
+impl AnsiColors {
+    /// Append the SGR escape selecting `color` as the foreground, or as the
+    /// background when `background` is set.
+    fn push_escape(self, out: &mut String, (r, g, b): (u8, u8, u8), background: bool) {
+        let layer = if background { 48 } else { 38 };
+        let _ = match self {
+            AnsiColors::TrueColor => write!(out, "\x1b[{};2;{};{};{}m", layer, r, g, b),
+            AnsiColors::Ansi256 => write!(out, "\x1b[{};5;{}m", layer, rgb_to_ansi256((r, g, b))),
+            AnsiColors::Ansi16 => {
+                let index = rgb_to_ansi16((r, g, b));
+                let base = match (background, index < 8) {
+                    (false, true) => 30,
+                    (false, false) => 82,
+                    (true, true) => 40,
+                    (true, false) => 92,
+                };
+                write!(out, "\x1b[{}m", base + index)
+            }
+        };
+    }
+}
+
+/// Render a color frame as truecolor ANSI text.
+///
+/// The output starts with [`CURSOR_HOME`] so successive frames overwrite each
+/// other in place, and each row ends with [`SGR_RESET`] so cell backgrounds
+/// never bleed past the right edge. No newline follows the last row, which
+/// keeps a full-height frame from scrolling the terminal.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::CFrameData;
+/// use cascii_core_view::render::terminal::render_to_ansi;
+///
+/// let cframe = CFrameData::new(2, 1, vec![b'A', b'B'], vec![255, 0, 0, 255, 0, 0]);
+/// assert_eq!(render_to_ansi(&cframe), "\x1b[H\x1b[38;2;255;0;0mAB\x1b[0m");
+/// ```
+pub fn render_to_ansi(cframe: &CFrameData) -> String {
+    render_to_ansi_with(cframe, AnsiColors::TrueColor)
+}
+
+/// Render a color frame as ANSI text using `colors` escapes, mapping cell
+/// colors down to the nearest palette entry for 256- and 16-color
+/// terminals.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::CFrameData;
+/// use cascii_core_view::render::terminal::{render_to_ansi_with, AnsiColors};
+///
+/// let cframe = CFrameData::new(1, 1, vec![b'A'], vec![255, 0, 0]);
+/// assert_eq!(render_to_ansi_with(&cframe, AnsiColors::Ansi256), "\x1b[H\x1b[38;5;196mA\x1b[0m");
+/// assert_eq!(render_to_ansi_with(&cframe, AnsiColors::Ansi16), "\x1b[H\x1b[91mA\x1b[0m");
+/// ```
+pub fn render_to_ansi_with(cframe: &CFrameData, colors: AnsiColors) -> String {
+    let width = cframe.width as usize;
+    let height = cframe.height as usize;
+    let mut out = String::with_capacity(CURSOR_HOME.len() + cframe.pixel_count() * 2);
+    out.push_str(CURSOR_HOME);
+
+    for row in 0..height {
+        let cells = (0..width).map(|col| {
+            let ch = cframe.char_at(row, col).unwrap_or(b' ');
+            let ch = if ch.is_ascii_graphic() { ch as char } else { ' ' };
+            let fg = if ch == ' ' { None } else { Some(cframe.rgb_at(row, col).unwrap_or((255, 255, 255))) };
+            (ch, fg, cframe.bg_rgb_at(row, col))
+        });
+        push_ansi_row(&mut out, cells, colors);
+        if row + 1 < height {
+            out.push_str("\r\n");
+        }
+    }
+
+    out
+}
+
+/// Append one row of `(char, foreground, background)` cells as SGR runs,
+/// followed by [`SGR_RESET`].
+///
+/// Cells without a foreground carry no ink, so they never force a new
+/// foreground escape; a `None` background resets to the terminal default.
+pub(crate) fn push_ansi_row(out: &mut String, cells: impl Iterator<Item = (char, Option<(u8, u8, u8)>, Option<(u8, u8, u8)>)>, colors: AnsiColors) {
+    let mut fg_run: Option<(u8, u8, u8)> = None;
+    let mut bg_run: Option<(u8, u8, u8)> = None;
+    for (ch, fg, bg) in cells {
+        if bg != bg_run {
+            match bg {
+                Some(bg) => colors.push_escape(out, bg, true),
+                None => out.push_str("\x1b[49m"),
+            }
+            bg_run = bg;
+        }
+        if let Some(fg) = fg.filter(|&fg| Some(fg) != fg_run) {
+            colors.push_escape(out, fg, false);
+            fg_run = Some(fg);
+        }
+        out.push(ch);
+    }
+    out.push_str(SGR_RESET);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi_runs_and_rows() {
+        let cframe = CFrameData::new(
+            3,
+            2,
+            b"AB CDE".to_vec(),
+            vec![255, 0, 0, 255, 0, 0, 9, 9, 9, 0, 255, 0, 0, 255, 0, 0, 0, 255],
+        );
+        let ansi = render_to_ansi(&cframe);
+        assert_eq!(
+            ansi,
+            "\x1b[H\x1b[38;2;255;0;0mAB \x1b[0m\r\n\x1b[38;2;0;255;0mCD\x1b[38;2;0;0;255mE\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_ansi_backgrounds() {
+        let cframe = CFrameData::with_background(2, 1, b"  ".to_vec(), vec![0; 6], vec![10, 20, 30, 10, 20, 30]);
+        assert_eq!(render_to_ansi(&cframe), "\x1b[H\x1b[48;2;10;20;30m  \x1b[0m");
+    }
+
+    #[test]
+    fn test_ansi_reduced_colors() {
+        let cframe = CFrameData::with_background(2, 1, b"AB".to_vec(), vec![250, 10, 5, 0, 0, 120], vec![20, 20, 20, 20, 20, 20]);
+        assert_eq!(render_to_ansi_with(&cframe, AnsiColors::Ansi256), "\x1b[H\x1b[48;5;233m\x1b[38;5;196mA\x1b[38;5;18mB\x1b[0m");
+        assert_eq!(render_to_ansi_with(&cframe, AnsiColors::Ansi16), "\x1b[H\x1b[40m\x1b[91mA\x1b[34mB\x1b[0m");
+    }
+}
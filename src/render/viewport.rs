@@ -0,0 +1,177 @@
+//! Zoom and pan viewports over large frames.
+//!
+//! A [`Viewport`] selects a window of cells and a zoom factor. When set on
+//! [`RenderConfig::viewport`], renderers only emit draw commands for the
+//! visible cells, positioned relative to the viewport's top-left corner and
+//! scaled by the zoom.
+
+use std::borrow::Cow;
+
+use super::RenderConfig;
+use crate::{CFrameData, Frame};
+
+/// A visible window of cells and a zoom factor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Viewport {
+    /// First visible column
+    pub offset_x: u32,
+    /// First visible row
+    pub offset_y: u32,
+    /// Number of visible columns (`None` = to the right edge)
+    pub cols: Option<u32>,
+    /// Number of visible rows (`None` = to the bottom edge)
+    pub rows: Option<u32>,
+    /// Scale applied to the font size; `1.0` is unzoomed
+    pub zoom: f64,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {offset_x: 0, offset_y: 0, cols: None, rows: None, zoom: 1.0}
+    }
+}
+
+impl Viewport {
+    /// A window of `cols` x `rows` cells starting at (`offset_x`, `offset_y`).
+    pub fn new(offset_x: u32, offset_y: u32, cols: u32, rows: u32) -> Self {
+        Self {offset_x, offset_y, cols: Some(cols), rows: Some(rows), zoom: 1.0}
+    }
+
+    /// Set the zoom factor.
+    pub fn with_zoom(mut self, zoom: f64) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Zoom factor, treating non-positive or non-finite values as `1.0`.
+    #[inline]
+    pub fn effective_zoom(&self) -> f64 {
+        if self.zoom.is_finite() && self.zoom > 0.0 { self.zoom } else { 1.0 }
+    }
+
+    /// Move the window by `(dx, dy)` cells, stopping at the top-left edge.
+    pub fn pan(&mut self, dx: i64, dy: i64) {
+        self.offset_x = (self.offset_x as i64 + dx).clamp(0, u32::MAX as i64) as u32;
+        self.offset_y = (self.offset_y as i64 + dy).clamp(0, u32::MAX as i64) as u32;
+    }
+
+    /// Keep the window inside a `width` x `height` frame, shifting it back
+    /// when it was panned past the right or bottom edge.
+    pub fn clamp_to(&mut self, width: u32, height: u32) {
+        let cols = self.cols.unwrap_or(width).min(width);
+        let rows = self.rows.unwrap_or(height).min(height);
+        self.offset_x = self.offset_x.min(width - cols);
+        self.offset_y = self.offset_y.min(height - rows);
+    }
+
+    /// The visible `(col, row, cols, rows)` of a `width` x `height` frame.
+    pub fn visible_region(&self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let col = self.offset_x.min(width);
+        let row = self.offset_y.min(height);
+        let cols = self.cols.unwrap_or(u32::MAX).min(width - col);
+        let rows = self.rows.unwrap_or(u32::MAX).min(height - row);
+        (col, row, cols, rows)
+    }
+
+    /// Map a visible cell back to its frame cell.
+    #[inline]
+    pub fn to_frame_cell(&self, col: u32, row: u32) -> (u32, u32) {
+        (col + self.offset_x, row + self.offset_y)
+    }
+}
+
+/// Resolve `config.viewport`: crop `cframe` to the visible region and scale
+/// the font size by the zoom. The returned config has no viewport, so
+/// applying it twice is a no-op.
+pub(crate) fn apply<'a>(cframe: &'a CFrameData, config: &'a RenderConfig) -> (Cow<'a, CFrameData>, Cow<'a, RenderConfig>) {
+    let Some(viewport) = config.viewport else {
+        return (Cow::Borrowed(cframe), Cow::Borrowed(config));
+    };
+    let mut resolved = config.clone();
+    resolved.viewport = None;
+    resolved.font_size *= viewport.effective_zoom();
+
+    let region = viewport.visible_region(cframe.width, cframe.height);
+    if region == (0, 0, cframe.width, cframe.height) {
+        return (Cow::Borrowed(cframe), Cow::Owned(resolved));
+    }
+    let (col, row, cols, rows) = region;
+    (Cow::Owned(cframe.crop(col, row, cols, rows)), Cow::Owned(resolved))
+}
+
+/// [`apply`] for a text-only frame: crop `frame.content` to the visible
+/// region (one column per char) and scale the font size by the zoom.
+pub(crate) fn apply_text<'a>(frame: &'a Frame, config: &'a RenderConfig) -> (Cow<'a, Frame>, Cow<'a, RenderConfig>) {
+    let Some(viewport) = config.viewport else {
+        return (Cow::Borrowed(frame), Cow::Borrowed(config));
+    };
+    let mut resolved = config.clone();
+    resolved.viewport = None;
+    resolved.font_size *= viewport.effective_zoom();
+
+    let (width, height) = frame.dimensions();
+    let (width, height) = (width.min(u32::MAX as usize) as u32, height.min(u32::MAX as usize) as u32);
+    let region = viewport.visible_region(width, height);
+    if region == (0, 0, width, height) {
+        return (Cow::Borrowed(frame), Cow::Owned(resolved));
+    }
+    let (col, row, cols, rows) = region;
+    let content = frame.content.lines().skip(row as usize).take(rows as usize)
+        .map(|line| line.chars().skip(col as usize).take(cols as usize).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (Cow::Owned(Frame::text_only(content)), Cow::Owned(resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{render_cframe, render_text_frame};
+    use crate::FrameColors;
+
+    #[test]
+    fn test_viewport_region_and_pan() {
+        let mut viewport = Viewport::new(8, 2, 4, 4);
+        assert_eq!(viewport.visible_region(10, 5), (8, 2, 2, 3));
+        viewport.pan(-20, 1);
+        assert_eq!((viewport.offset_x, viewport.offset_y), (0, 3));
+        viewport.clamp_to(10, 5);
+        assert_eq!((viewport.offset_x, viewport.offset_y), (0, 1));
+        assert_eq!(viewport.to_frame_cell(1, 1), (1, 2));
+        assert_eq!(Viewport::default().visible_region(3, 2), (0, 0, 3, 2));
+    }
+
+    #[test]
+    fn test_viewport_render_only_visible_cells() {
+        let chars = b"ABCDEFGHI".to_vec();
+        let rgb = (0..9u8).flat_map(|i| [i * 20, 0, 0]).collect();
+        let cframe = CFrameData::with_background(3, 3, chars, rgb, vec![9; 27]);
+        let mut config = RenderConfig::new(10.0);
+        config.viewport = Some(Viewport::new(1, 1, 2, 2).with_zoom(2.0));
+
+        let result = render_cframe(&cframe, &config);
+        let texts: Vec<&str> = result.batches.iter().map(|b| b.text.as_str()).collect();
+        assert_eq!(texts, ["E", "F", "H", "I"]);
+        assert_eq!((result.batches[0].x, result.batches[0].y), (0.0, 0.0));
+        let zoomed = RenderConfig::new(20.0);
+        assert_eq!((result.width, result.height), (2.0 * zoomed.char_width(), 2.0 * zoomed.line_height()));
+        assert_eq!(result.background_batches.len(), 2);
+        assert_ne!(config.cache_key(), RenderConfig::new(10.0).cache_key());
+    }
+
+    #[test]
+    fn test_viewport_crops_text_frames() {
+        let frame = Frame::text_only("abc\ndef\nghi".to_string());
+        let colors = FrameColors {foreground: (255, 255, 255), background: (0, 0, 0)};
+        let mut config = RenderConfig::new(10.0);
+        config.viewport = Some(Viewport::new(1, 1, 2, 2).with_zoom(2.0));
+
+        let result = render_text_frame(&frame, &colors, &config);
+        let texts: Vec<&str> = result.batches.iter().map(|b| b.text.as_str()).collect();
+        assert_eq!(texts, ["ef", "hi"]);
+        assert_eq!((result.batches[1].x, result.batches[1].y), (0.0, RenderConfig::new(20.0).line_height()));
+        let zoomed = RenderConfig::new(20.0);
+        assert_eq!((result.width, result.height), (2.0 * zoomed.char_width(), 2.0 * zoomed.line_height()));
+    }
+}
@@ -0,0 +1,382 @@
+//! WebGL2 renderer for large frames.
+//!
+//! The 2D canvas `fillText` path shapes every text run on every frame. This
+//! backend rasterizes the printable ASCII range into a glyph atlas once per
+//! font configuration, uploads it as a texture, and then draws each frame as
+//! instanced quads: one per background run and one per glyph. It consumes the
+//! same [`render_cframe`] batches and [`RenderConfig`] as the 2D path,
+//! except for [`RenderConfig::glow`], which only the 2D path draws.
+//!
+//! [`CanvasRenderer`] picks WebGL2 when available and falls back to the 2D
+//! canvas renderer automatically.
+
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CanvasRenderingContext2d, HtmlCanvasElement, WebGl2RenderingContext as Gl, WebGlBuffer, WebGlProgram, WebGlShader, WebGlTexture,
+    WebGlUniformLocation, WebGlVertexArrayObject,
+};
+
+use super::web::{apply_logical_size, device_pixel_ratio, render_to_canvas};
+use super::{render_cframe, RenderConfig, RenderResult};
+use crate::CFrameData;
+
+/// First character stored in the glyph atlas.
+const FIRST_GLYPH: u8 = b' ';
+/// Number of glyphs in the atlas (printable ASCII, `' '..='~'`).
+const GLYPH_COUNT: u32 = 95;
+/// Atlas columns; rows follow from [`GLYPH_COUNT`].
+const ATLAS_COLUMNS: u32 = 16;
+/// Floats per instance: x, y, width, height, glyph, r, g, b, alpha.
+const INSTANCE_FLOATS: usize = 9;
+/// Glyph value marking a solid (untextured) rectangle.
+const SOLID: f32 = -1.0;
+
+const VERTEX_SHADER: &str = r#"#version 300 es
+layout(location = 0) in vec2 a_corner;
+layout(location = 1) in vec4 a_rect;
+layout(location = 2) in float a_glyph;
+layout(location = 3) in vec4 a_color;
+uniform vec2 u_resolution;
+uniform vec2 u_atlas_grid;
+out vec2 v_uv;
+out vec4 v_color;
+flat out float v_glyph;
+void main() {
+    vec2 pos = a_rect.xy + a_corner * a_rect.zw;
+    vec2 clip = pos / u_resolution * 2.0 - 1.0;
+    gl_Position = vec4(clip.x, -clip.y, 0.0, 1.0);
+    v_color = vec4(a_color.rgb / 255.0, a_color.a);
+    v_glyph = a_glyph;
+    vec2 cell = vec2(mod(a_glyph, u_atlas_grid.x), floor(a_glyph / u_atlas_grid.x));
+    v_uv = (cell + a_corner) / u_atlas_grid;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"#version 300 es
+precision mediump float;
+in vec2 v_uv;
+in vec4 v_color;
+flat in float v_glyph;
+uniform sampler2D u_atlas;
+out vec4 out_color;
+void main() {
+    float alpha = (v_glyph < 0.0 ? 1.0 : texture(u_atlas, v_uv).a) * v_color.a;
+    if (alpha <= 0.0) {
+        discard;
+    }
+    out_color = vec4(v_color.rgb * alpha, alpha);
+}
+"#;
+
+/// Glyph atlas texture and the layout it was built for.
+struct GlyphAtlas {
+    texture: WebGlTexture,
+    /// [`RenderConfig::cache_key`] combined with the device pixel ratio
+    key: u64,
+    /// Measured advance of one glyph in CSS pixels
+    char_width: f64,
+}
+
+/// WebGL2 frame renderer bound to one canvas.
+pub struct WebGlRenderer {
+    canvas: HtmlCanvasElement,
+    gl: Gl,
+    program: WebGlProgram,
+    vao: WebGlVertexArrayObject,
+    instance_buffer: WebGlBuffer,
+    resolution: Option<WebGlUniformLocation>,
+    atlas_grid: Option<WebGlUniformLocation>,
+    atlas: Option<GlyphAtlas>,
+    /// Reused instance data, to avoid reallocating every frame
+    instances: Vec<f32>,
+}
+
+impl WebGlRenderer {
+    /// Create a renderer, failing when the canvas cannot provide a WebGL2
+    /// context (unsupported browser, or a 2D context was already taken).
+    pub fn new(canvas: &HtmlCanvasElement) -> Result<Self, String> {
+        let gl = canvas
+            .get_context("webgl2")
+            .map_err(|_| "Failed to get webgl2 context".to_string())?
+            .ok_or_else(|| "No webgl2 context available".to_string())?
+            .dyn_into::<Gl>()
+            .map_err(|_| "Failed to cast to WebGl2RenderingContext".to_string())?;
+
+        let program = link_program(&gl, VERTEX_SHADER, FRAGMENT_SHADER)?;
+        let vao = gl.create_vertex_array().ok_or("Failed to create vertex array")?;
+        gl.bind_vertex_array(Some(&vao));
+
+        let corner_buffer = gl.create_buffer().ok_or("Failed to create corner buffer")?;
+        gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&corner_buffer));
+        let corners = js_sys::Float32Array::from(&[0.0f32, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0][..]);
+        gl.buffer_data_with_array_buffer_view(Gl::ARRAY_BUFFER, &corners, Gl::STATIC_DRAW);
+        gl.enable_vertex_attrib_array(0);
+        gl.vertex_attrib_pointer_with_i32(0, 2, Gl::FLOAT, false, 0, 0);
+
+        let instance_buffer = gl.create_buffer().ok_or("Failed to create instance buffer")?;
+        gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&instance_buffer));
+        let stride = (INSTANCE_FLOATS * 4) as i32;
+        for (location, size, offset) in [(1u32, 4i32, 0i32), (2, 1, 16), (3, 4, 20)] {
+            gl.enable_vertex_attrib_array(location);
+            gl.vertex_attrib_pointer_with_i32(location, size, Gl::FLOAT, false, stride, offset);
+            gl.vertex_attrib_divisor(location, 1);
+        }
+        gl.bind_vertex_array(None);
+
+        let resolution = gl.get_uniform_location(&program, "u_resolution");
+        let atlas_grid = gl.get_uniform_location(&program, "u_atlas_grid");
+        Ok(Self {canvas: canvas.clone(), gl, program, vao, instance_buffer, resolution, atlas_grid, atlas: None, instances: Vec::new()})
+    }
+
+    /// Render a color frame, rebuilding the glyph atlas only when the font
+    /// configuration or device pixel ratio changed.
+    pub fn render(&mut self, cframe: &CFrameData, config: &RenderConfig) -> Result<(), String> {
+        let (cframe, config) = super::viewport::apply(cframe, config);
+        let (cframe, config) = (cframe.as_ref(), config.as_ref());
+        let dpr = device_pixel_ratio(config);
+        let key = atlas_key(config, dpr);
+        if self.atlas.as_ref().map(|atlas| atlas.key) != Some(key) {
+            self.atlas = Some(build_atlas(&self.gl, config, dpr, key)?);
+        }
+        let char_width = self.atlas.as_ref().map(|atlas| atlas.char_width).unwrap_or_else(|| config.char_width());
+
+        let mut measured_config = config.clone();
+        if config.font_size > 0.0 {
+            measured_config.sizing.char_width_ratio = char_width / config.font_size;
+        }
+        let result = render_cframe(cframe, &measured_config);
+
+        self.canvas.set_width((result.width * dpr).ceil() as u32);
+        self.canvas.set_height((result.height * dpr).ceil() as u32);
+        apply_logical_size(&self.canvas, result.width, result.height)?;
+
+        fill_instances(&mut self.instances, &result, char_width, config.line_height(), config.opacity.clamp(0.0, 1.0));
+        self.draw(result.width, result.height)
+    }
+
+    fn draw(&self, width: f64, height: f64) -> Result<(), String> {
+        let gl = &self.gl;
+        gl.viewport(0, 0, self.canvas.width() as i32, self.canvas.height() as i32);
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(Gl::COLOR_BUFFER_BIT);
+        if self.instances.is_empty() {
+            return Ok(());
+        }
+
+        gl.use_program(Some(&self.program));
+        gl.uniform2f(self.resolution.as_ref(), width as f32, height as f32);
+        gl.uniform2f(self.atlas_grid.as_ref(), ATLAS_COLUMNS as f32, GLYPH_COUNT.div_ceil(ATLAS_COLUMNS) as f32);
+        gl.active_texture(Gl::TEXTURE0);
+        gl.bind_texture(Gl::TEXTURE_2D, self.atlas.as_ref().map(|atlas| &atlas.texture));
+        gl.enable(Gl::BLEND);
+        gl.blend_func(Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+
+        gl.bind_vertex_array(Some(&self.vao));
+        gl.bind_buffer(Gl::ARRAY_BUFFER, Some(&self.instance_buffer));
+        let data = js_sys::Float32Array::from(self.instances.as_slice());
+        gl.buffer_data_with_array_buffer_view(Gl::ARRAY_BUFFER, &data, Gl::STREAM_DRAW);
+        gl.draw_arrays_instanced(Gl::TRIANGLE_STRIP, 0, 4, (self.instances.len() / INSTANCE_FLOATS) as i32);
+        gl.bind_vertex_array(None);
+
+        if gl.is_context_lost() {
+            return Err("WebGL2 context lost".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Renderer that uses WebGL2 when available and the 2D canvas otherwise.
+pub enum CanvasRenderer {
+    WebGl(WebGlRenderer),
+    Canvas2d(HtmlCanvasElement),
+}
+
+impl CanvasRenderer {
+    /// Bind to `canvas`, preferring WebGL2.
+    ///
+    /// A canvas can only ever hand out one kind of context, so the choice is
+    /// made once here: if WebGL2 is unavailable the 2D path is used for the
+    /// lifetime of the renderer.
+    pub fn new(canvas: &HtmlCanvasElement) -> Self {
+        match WebGlRenderer::new(canvas) {
+            Ok(renderer) => CanvasRenderer::WebGl(renderer),
+            Err(_) => CanvasRenderer::Canvas2d(canvas.clone()),
+        }
+    }
+
+    /// Whether the WebGL2 backend is in use.
+    pub fn is_webgl(&self) -> bool {
+        matches!(self, CanvasRenderer::WebGl(_))
+    }
+
+    /// Render a color frame with the active backend.
+    pub fn render(&mut self, cframe: &CFrameData, config: &RenderConfig) -> Result<(), String> {
+        match self {
+            CanvasRenderer::WebGl(renderer) => renderer.render(cframe, config),
+            CanvasRenderer::Canvas2d(canvas) => render_to_canvas(cframe, canvas, config),
+        }
+    }
+}
+
+fn atlas_key(config: &RenderConfig, dpr: f64) -> u64 {
+    crate::data::fnv1a(config.cache_key(), &dpr.to_bits().to_le_bytes())
+}
+
+/// Append one quad per background run and per glyph of `result`. Frame
+/// primitives are scaled by `opacity`; the overlay stays opaque.
+fn fill_instances(instances: &mut Vec<f32>, result: &RenderResult, char_width: f64, line_height: f64, opacity: f64) {
+    instances.clear();
+    let mut push = |x: f64, y: f64, w: f64, h: f64, glyph: f32, color: (u8, u8, u8), alpha: f64| {
+        instances.extend_from_slice(&[x as f32, y as f32, w as f32, h as f32, glyph, color.0 as f32, color.1 as f32, color.2 as f32, alpha as f32]);
+    };
+    for rect in result.background.iter().chain(&result.background_batches) {
+        push(rect.x, rect.y, rect.width, rect.height, SOLID, rect.color, opacity);
+    }
+    // Frame text and the post-effect rects over it, then the overlay's rects
+    // and text on top of everything.
+    let layers = [(&[][..], &result.batches[..], opacity, &result.post[..]), (&result.overlay.rects[..], &result.overlay.batches[..], 1.0, &[][..])];
+    for (rects, batches, alpha, post) in layers {
+        for rect in rects {
+            push(rect.x, rect.y, rect.width, rect.height, SOLID, rect.color, alpha);
+        }
+        for batch in batches {
+            let alpha = alpha * batch.opacity.clamp(0.0, 1.0);
+            // One cell per char; chars missing from the atlas leave theirs empty.
+            for (i, ch) in batch.text.chars().enumerate() {
+                let glyph = (ch as u32).wrapping_sub(FIRST_GLYPH as u32);
+                if ch == ' ' || glyph >= GLYPH_COUNT {
+                    continue;
+                }
+                push(batch.x + i as f64 * char_width, batch.y, char_width, line_height, glyph as f32, batch.color, alpha);
+            }
+        }
+        for rect in post {
+            push(rect.x, rect.y, rect.width, rect.height, SOLID, rect.color, rect.opacity.clamp(0.0, 1.0));
+        }
+    }
+}
+
+/// Rasterize printable ASCII into a white-on-transparent atlas and upload it.
+fn build_atlas(gl: &Gl, config: &RenderConfig, dpr: f64, key: u64) -> Result<GlyphAtlas, String> {
+    let document = web_sys::window().and_then(|window| window.document()).ok_or("No document available")?;
+    let canvas = document
+        .create_element("canvas")
+        .map_err(|_| "Failed to create atlas canvas")?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|_| "Failed to cast atlas canvas")?;
+    let ctx = canvas
+        .get_context("2d")
+        .map_err(|_| "Failed to get 2d context".to_string())?
+        .ok_or_else(|| "No 2d context available".to_string())?
+        .dyn_into::<CanvasRenderingContext2d>()
+        .map_err(|_| "Failed to cast to CanvasRenderingContext2d".to_string())?;
+
+    ctx.set_font(&config.font_string());
+    let measured = ctx.measure_text("M").map(|metrics| metrics.width()).unwrap_or(0.0);
+    let char_width = if measured > 0.0 { measured } else { config.char_width() };
+    let cell_width = (char_width * dpr).ceil().max(1.0);
+    let cell_height = (config.line_height() * dpr).ceil().max(1.0);
+    let rows = GLYPH_COUNT.div_ceil(ATLAS_COLUMNS);
+
+    canvas.set_width(cell_width as u32 * ATLAS_COLUMNS);
+    canvas.set_height(cell_height as u32 * rows);
+    ctx.set_transform(dpr, 0.0, 0.0, dpr, 0.0, 0.0).map_err(|_| "Failed to apply DPR transform")?;
+    ctx.set_font(&config.font_string());
+    ctx.set_text_baseline("top");
+    ctx.set_fill_style_str("white");
+    ctx.set_stroke_style_str("white");
+    ctx.set_line_width(config.text_stroke_width.max(0.0));
+    for glyph in 0..GLYPH_COUNT {
+        let text = ((FIRST_GLYPH + glyph as u8) as char).to_string();
+        let x = (glyph % ATLAS_COLUMNS) as f64 * cell_width / dpr;
+        let y = (glyph / ATLAS_COLUMNS) as f64 * cell_height / dpr;
+        if config.text_stroke_width > 0.0 {
+            ctx.stroke_text(&text, x, y).map_err(|_| "Failed to stroke atlas glyph")?;
+        }
+        ctx.fill_text(&text, x, y).map_err(|_| "Failed to fill atlas glyph")?;
+    }
+
+    let texture = gl.create_texture().ok_or("Failed to create atlas texture")?;
+    gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
+    gl.pixel_storei(Gl::UNPACK_PREMULTIPLY_ALPHA_WEBGL, 1);
+    gl.tex_image_2d_with_u32_and_u32_and_html_canvas_element(Gl::TEXTURE_2D, 0, Gl::RGBA as i32, Gl::RGBA, Gl::UNSIGNED_BYTE, &canvas)
+        .map_err(|_| "Failed to upload glyph atlas")?;
+    for (param, value) in [(Gl::TEXTURE_MIN_FILTER, Gl::LINEAR), (Gl::TEXTURE_MAG_FILTER, Gl::LINEAR), (Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE), (Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE)] {
+        gl.tex_parameteri(Gl::TEXTURE_2D, param, value as i32);
+    }
+    Ok(GlyphAtlas {texture, key, char_width})
+}
+
+fn compile_shader(gl: &Gl, kind: u32, source: &str) -> Result<WebGlShader, String> {
+    let shader = gl.create_shader(kind).ok_or("Failed to create shader")?;
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+    if gl.get_shader_parameter(&shader, Gl::COMPILE_STATUS).as_bool().unwrap_or(false) {
+        Ok(shader)
+    } else {
+        Err(gl.get_shader_info_log(&shader).unwrap_or_else(|| "Failed to compile shader".to_string()))
+    }
+}
+
+fn link_program(gl: &Gl, vertex: &str, fragment: &str) -> Result<WebGlProgram, String> {
+    let program = gl.create_program().ok_or("Failed to create program")?;
+    gl.attach_shader(&program, &compile_shader(gl, Gl::VERTEX_SHADER, vertex)?);
+    gl.attach_shader(&program, &compile_shader(gl, Gl::FRAGMENT_SHADER, fragment)?);
+    gl.link_program(&program);
+    if gl.get_program_parameter(&program, Gl::LINK_STATUS).as_bool().unwrap_or(false) {
+        Ok(program)
+    } else {
+        Err(gl.get_program_info_log(&program).unwrap_or_else(|| "Failed to link program".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::post::PostRect;
+    use crate::render::{CellRectBatch, TextBatch};
+
+    #[test]
+    fn test_fill_instances_quads() {
+        let cframe = CFrameData::with_background(3, 1, b"A B".to_vec(), vec![255; 9], vec![0; 9]);
+        let config = RenderConfig::new(10.0);
+        let result = render_cframe(&cframe, &config);
+        let mut instances = Vec::new();
+        fill_instances(&mut instances, &result, config.char_width(), config.line_height(), 0.5);
+
+        // one merged background run + two glyphs ("A" and "B"; the space is skipped)
+        assert_eq!(instances.len(), 3 * INSTANCE_FLOATS);
+        assert_eq!(instances[4], SOLID);
+        assert_eq!(instances[INSTANCE_FLOATS + 4], (b'A' - FIRST_GLYPH) as f32);
+        assert_eq!(instances[2 * INSTANCE_FLOATS], 2.0 * config.char_width() as f32);
+        assert_eq!(instances[INSTANCE_FLOATS - 1], 0.5);
+    }
+
+    #[test]
+    fn test_fill_instances_draws_overlay_last() {
+        let cframe = CFrameData::new(1, 1, vec![b'A'], vec![0, 255, 0]);
+        let mut result = render_cframe(&cframe, &RenderConfig::new(10.0));
+        let rect = |color| CellRectBatch {x: 0.0, y: 0.0, width: 1.0, height: 1.0, color};
+        result.background = Some(rect((1, 1, 1)));
+        result.post = vec![PostRect {x: 0.0, y: 0.0, width: 1.0, height: 1.0, color: (2, 2, 2), opacity: 0.5}];
+        result.overlay.rects.push(rect((3, 3, 3)));
+        result.overlay.batches.push(TextBatch {text: "B".into(), x: 0.0, y: 0.0, color: (4, 4, 4), opacity: 1.0});
+
+        let mut instances = Vec::new();
+        fill_instances(&mut instances, &result, 6.0, 12.0, 1.0);
+        let colors: Vec<f32> = instances.chunks(INSTANCE_FLOATS).map(|instance| instance[5]).collect();
+        assert_eq!(colors, [1.0, 0.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_fill_instances_places_glyphs_by_char() {
+        let cframe = CFrameData::new(3, 1, vec![0xE9, b'A', b'B'], vec![255; 9]);
+        let result = render_cframe(&cframe, &RenderConfig::new(10.0));
+        let mut instances = Vec::new();
+        fill_instances(&mut instances, &result, 6.0, 12.0, 1.0);
+
+        // The non-ASCII cell has no glyph but still takes its column.
+        let glyphs: Vec<(f32, f32)> = instances.chunks(INSTANCE_FLOATS).map(|instance| (instance[0], instance[4])).collect();
+        assert_eq!(glyphs, [(6.0, (b'A' - FIRST_GLYPH) as f32), (12.0, (b'B' - FIRST_GLYPH) as f32)]);
+    }
+}
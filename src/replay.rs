@@ -0,0 +1,213 @@
+//! Deterministic record / replay of controller interactions.
+//!
+//! An [`InteractionLog`] captures every command sent to an
+//! [`AnimationController`] together with a caller-supplied timestamp. Because
+//! the controller is a pure state machine, replaying the log against the same
+//! frame set reproduces the exact playback, which makes playback bug reports
+//! reproducible and lets downstream viewers script UI tests.
+
+use crate::{AnimationController, Frame, LoopMode};
+
+/// A single command sent to an [`AnimationController`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControllerCommand {
+    Play,
+    Pause,
+    Toggle,
+    Stop,
+    Tick,
+    StepForward,
+    StepBackward,
+    Reset,
+    Seek(f64),
+    SetCurrentFrame(usize),
+    SetFrameCount(usize),
+    SetFps(u32),
+    SetLoopMode(LoopMode),
+    SetRange(f64, f64),
+}
+
+impl ControllerCommand {
+    /// Apply this command to `controller`.
+    pub fn apply(&self, controller: &mut AnimationController) {
+        match *self {
+            ControllerCommand::Play => controller.play(),
+            ControllerCommand::Pause => controller.pause(),
+            ControllerCommand::Toggle => controller.toggle(),
+            ControllerCommand::Stop => controller.stop(),
+            ControllerCommand::Tick => { controller.tick(); }
+            ControllerCommand::StepForward => controller.step_forward(),
+            ControllerCommand::StepBackward => controller.step_backward(),
+            ControllerCommand::Reset => controller.reset(),
+            ControllerCommand::Seek(position) => controller.seek(position),
+            ControllerCommand::SetCurrentFrame(frame) => controller.set_current_frame(frame),
+            ControllerCommand::SetFrameCount(count) => controller.set_frame_count(count),
+            ControllerCommand::SetFps(fps) => controller.set_fps(fps),
+            ControllerCommand::SetLoopMode(mode) => controller.set_loop_mode(mode),
+            ControllerCommand::SetRange(start, end) => controller.set_range(start, end),
+        }
+    }
+}
+
+/// A command with the time it was issued.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoggedCommand {
+    /// Milliseconds since recording started
+    pub time_ms: f64,
+    pub command: ControllerCommand,
+    /// Frame shown after the command was applied
+    pub frame: usize,
+}
+
+/// Recorded controller interactions plus the state needed to replay them.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InteractionLog {
+    /// Controller FPS when recording started
+    pub fps: u32,
+    /// Frame count when recording started
+    pub frame_count: usize,
+    /// Combined checksum of the frame set, if one was recorded
+    pub frames_checksum: Option<u64>,
+    /// Commands in the order they were issued
+    pub entries: Vec<LoggedCommand>,
+}
+
+/// Combined checksum of a frame set, used to tie a log to its frames.
+pub fn frame_set_checksum(frames: &[Frame]) -> u64 {
+    frames.iter().fold(crate::data::FNV_OFFSET, |hash, frame| crate::data::fnv1a(hash, &frame.checksum().to_le_bytes()))
+}
+
+impl InteractionLog {
+    /// Start a log from the controller's current configuration.
+    ///
+    /// The controller is snapshotted at its FPS and frame count; commands
+    /// issued before recording (seek position, loop mode, range) should be
+    /// recorded explicitly so the replay starts from the same state.
+    pub fn start(controller: &AnimationController) -> Self {
+        Self {fps: controller.fps(), frame_count: controller.frame_count(), frames_checksum: None, entries: Vec::new()}
+    }
+
+    /// Start a log tied to `frames`; replays verify they run against the same set.
+    pub fn start_with_frames(controller: &AnimationController, frames: &[Frame]) -> Self {
+        Self {frames_checksum: Some(frame_set_checksum(frames)), ..Self::start(controller)}
+    }
+
+    /// Apply `command` to `controller` and record it at `time_ms`.
+    pub fn record(&mut self, controller: &mut AnimationController, time_ms: f64, command: ControllerCommand) {
+        command.apply(controller);
+        self.entries.push(LoggedCommand {time_ms, command, frame: controller.current_frame()});
+    }
+
+    /// Number of recorded commands.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when nothing has been recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Timestamp of the last recorded command.
+    pub fn duration_ms(&self) -> f64 {
+        self.entries.last().map(|entry| entry.time_ms).unwrap_or(0.0)
+    }
+
+    /// Replay every command on a fresh controller and return it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{AnimationController, ControllerCommand, InteractionLog};
+    ///
+    /// let mut controller = AnimationController::new(10);
+    /// controller.set_frame_count(5);
+    /// let mut log = InteractionLog::start(&controller);
+    /// log.record(&mut controller, 0.0, ControllerCommand::Play);
+    /// log.record(&mut controller, 100.0, ControllerCommand::Tick);
+    /// log.record(&mut controller, 200.0, ControllerCommand::Tick);
+    ///
+    /// assert_eq!(log.replay().current_frame(), controller.current_frame());
+    /// ```
+    pub fn replay(&self) -> AnimationController {
+        self.replay_until(f64::INFINITY)
+    }
+
+    /// Replay commands issued at or before `time_ms`.
+    pub fn replay_until(&self, time_ms: f64) -> AnimationController {
+        let mut controller = AnimationController::new(self.fps);
+        controller.set_frame_count(self.frame_count);
+        for entry in self.entries.iter().take_while(|entry| entry.time_ms <= time_ms) {
+            entry.command.apply(&mut controller);
+        }
+        controller
+    }
+
+    /// Replay against `frames`, failing if they differ from the recorded set.
+    pub fn replay_against(&self, frames: &[Frame]) -> Result<AnimationController, String> {
+        if let Some(expected) = self.frames_checksum {
+            let actual = frame_set_checksum(frames);
+            if actual != expected {
+                return Err(format!("frame set checksum {:016x} does not match recorded {:016x}", actual, expected));
+            }
+        }
+        Ok(self.replay())
+    }
+
+    /// Index of the first command whose replayed frame differs from the
+    /// recorded one, or `None` when the replay matches the recording.
+    pub fn first_divergence(&self) -> Option<usize> {
+        let mut controller = AnimationController::new(self.fps);
+        controller.set_frame_count(self.frame_count);
+        self.entries.iter().position(|entry| {
+            entry.command.apply(&mut controller);
+            controller.current_frame() != entry.frame
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded() -> (AnimationController, InteractionLog) {
+        let mut controller = AnimationController::new(24);
+        controller.set_frame_count(10);
+        let mut log = InteractionLog::start(&controller);
+        log.record(&mut controller, 0.0, ControllerCommand::SetLoopMode(LoopMode::Once));
+        log.record(&mut controller, 5.0, ControllerCommand::Play);
+        for i in 0..12 {
+            log.record(&mut controller, 10.0 + i as f64 * 41.0, ControllerCommand::Tick);
+        }
+        log.record(&mut controller, 600.0, ControllerCommand::Seek(0.5));
+        log.record(&mut controller, 610.0, ControllerCommand::StepBackward);
+        (controller, log)
+    }
+
+    #[test]
+    fn test_replay_is_deterministic() {
+        let (controller, log) = recorded();
+        let replayed = log.replay();
+        assert_eq!(replayed.current_frame(), controller.current_frame());
+        assert_eq!(replayed.state(), controller.state());
+        assert_eq!(log.first_divergence(), None);
+        assert_eq!(log.replay_until(100.0).current_frame(), 3);
+    }
+
+    #[test]
+    fn test_replay_against_frame_set() {
+        let frames = vec![Frame::text_only("a".into()), Frame::text_only("b".into())];
+        let mut controller = AnimationController::new(10);
+        controller.set_frame_count(frames.len());
+        let mut log = InteractionLog::start_with_frames(&controller, &frames);
+        log.record(&mut controller, 0.0, ControllerCommand::StepForward);
+
+        assert_eq!(log.replay_against(&frames).unwrap().current_frame(), 1);
+        assert!(log.replay_against(&frames[..1]).is_err());
+    }
+}
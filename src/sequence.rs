@@ -0,0 +1,462 @@
+//! Frame sequences and sequence-level editing.
+//!
+//! A [`FrameSequence`] is an ordered list of [`Frame`]s played at one frame
+//! rate. Sequences from different sources rarely agree on dimensions, color
+//! data, or frame rate; [`FrameSequence::concat`] reconciles them so the
+//! merged animation doesn't visibly jump between segments,
+//! [`FrameSequence::retime`] fits an animation to an exact duration,
+//! [`FrameSequence::coalesce_duplicates`] folds held frames into one, and
+//! [`FrameSequence::trim`] crops every frame to the sequence's content.
+
+use crate::{Anchor, CFrameData, Frame, FrameColors, FrameMeta, ProjectDetails};
+
+/// Foreground used for text-only frames when no theme is given.
+const DEFAULT_FOREGROUND: (u8, u8, u8) = (255, 255, 255);
+/// Background used for padding and missing backgrounds when no theme is given.
+const DEFAULT_BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+
+/// An ordered list of frames with a shared frame rate.
+#[derive(Clone, Debug)]
+pub struct FrameSequence {
+    /// Frames in playback order
+    pub frames: Vec<Frame>,
+    /// Playback rate in frames per second
+    pub fps: u32,
+}
+
+/// How [`FrameSequence::concat`] normalizes its inputs.
+#[derive(Clone, Debug, Default)]
+pub struct ConcatOptions {
+    /// Where smaller frames sit inside the common canvas
+    pub anchor: Anchor,
+    /// Common theme: foreground for text-only frames, background for padding
+    /// and for frames without per-cell backgrounds. Defaults to white on black.
+    pub theme: Option<FrameColors>,
+    /// Output frame rate; defaults to the first sequence's rate. Segments at
+    /// other rates are resampled so they keep their real-time duration.
+    pub fps: Option<u32>,
+}
+
+/// How [`FrameSequence::retime`] fills or drops frames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RetimeMode {
+    /// Duplicate or drop whole frames, spread evenly over the sequence
+    #[default]
+    Nearest,
+    /// Blend the colors of neighbouring frames when both carry color data of
+    /// the same size; falls back to [`RetimeMode::Nearest`] otherwise
+    Blend,
+}
+
+impl FrameSequence {
+    /// Create a sequence from frames played at `fps`.
+    pub fn new(frames: Vec<Frame>, fps: u32) -> Self {
+        Self {frames, fps: fps.max(1)}
+    }
+
+    /// Number of frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` when the sequence has no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Playback duration in milliseconds.
+    ///
+    /// Frames whose [`FrameMeta`] carries timing count for their own
+    /// duration; the rest last one frame at `fps`.
+    pub fn duration_ms(&self) -> f64 {
+        (0..self.frames.len()).map(|index| self.frame_duration_ms(index)).sum()
+    }
+
+    /// How long frame `index` stays on screen.
+    fn frame_duration_ms(&self, index: usize) -> f64 {
+        self.frames[index].duration_ms(self.frames.get(index + 1)).unwrap_or(1000.0 / self.fps as f64)
+    }
+
+    /// Largest `(columns, rows)` over all frames.
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.frames.iter().map(frame_dimensions).fold((0, 0), |(w, h), (fw, fh)| (w.max(fw), h.max(fh)))
+    }
+
+    /// Concatenate sequences into one, normalizing them to a common form.
+    ///
+    /// - Frames are padded to the largest width and height, positioned by
+    ///   `options.anchor`.
+    /// - If any frame has color data, text-only frames are colored with the
+    ///   theme foreground.
+    /// - If any frame has per-cell backgrounds, frames without them get the
+    ///   theme background, so the backdrop doesn't flash between segments.
+    /// - Segments are resampled to the output frame rate.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{ConcatOptions, Frame, FrameSequence};
+    ///
+    /// let intro = FrameSequence::new(vec![Frame::text_only("Hi\n".into())], 10);
+    /// let body = FrameSequence::new(vec![Frame::text_only("Hello\nWorld\n".into())], 10);
+    ///
+    /// let merged = FrameSequence::concat(&[intro, body], &ConcatOptions::default());
+    /// assert_eq!(merged.len(), 2);
+    /// assert_eq!(merged.frames[0].content, "Hi   \n     \n");
+    /// ```
+    pub fn concat(sequences: &[FrameSequence], options: &ConcatOptions) -> FrameSequence {
+        let fps = options.fps.or_else(|| sequences.first().map(|s| s.fps)).unwrap_or(1).max(1);
+        let (width, height) = sequences.iter().map(|s| s.dimensions()).fold((0, 0), |(w, h), (sw, sh)| (w.max(sw), h.max(sh)));
+        let frames = sequences.iter().flat_map(|s| s.frames.iter());
+        let colored = frames.clone().any(|f| f.cframe.is_some());
+        let with_background = frames.clone().any(|f| f.cframe.as_ref().map(|c| c.has_background()).unwrap_or(false));
+        let target = Normalize {width, height, anchor: options.anchor, theme: options.theme.clone(), colored, with_background};
+
+        let mut out = Vec::new();
+        for sequence in sequences {
+            let count = resampled_count(sequence.len(), sequence.fps, fps);
+            for index in resample_indices(sequence.len(), count) {
+                out.push(target.apply(&sequence.frames[index]));
+            }
+        }
+        FrameSequence::new(out, fps)
+    }
+
+    /// Stretch or compress the sequence to last `target_ms` at the same frame rate.
+    ///
+    /// The frame count becomes `round(target_ms × fps / 1000)`, so the result
+    /// is within half a frame of the target — e.g. fitting 14.2 s of frames
+    /// to a 15 s audio track. Use [`apply_to_details`](Self::apply_to_details)
+    /// afterwards to keep the exported `details.toml` in sync.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{Frame, FrameSequence, RetimeMode};
+    ///
+    /// let frames = (0..142).map(|i| Frame::text_only(format!("{}\n", i))).collect();
+    /// let sequence = FrameSequence::new(frames, 10);
+    ///
+    /// let fitted = sequence.retime(15_000.0, RetimeMode::Nearest);
+    /// assert_eq!(fitted.len(), 150);
+    /// assert_eq!(fitted.duration_ms(), 15_000.0);
+    /// ```
+    pub fn retime(&self, target_ms: f64, mode: RetimeMode) -> FrameSequence {
+        let len = self.frames.len();
+        if len == 0 {
+            return self.clone();
+        }
+        let count = ((target_ms.max(0.0) * self.fps as f64 / 1000.0).round() as usize).max(1);
+
+        let frames = match mode {
+            RetimeMode::Nearest => resample_indices(len, count).map(|i| self.frames[i].clone()).collect(),
+            RetimeMode::Blend => (0..count).map(|i| self.blended_frame(i as f64 * len as f64 / count as f64)).collect(),
+        };
+        FrameSequence::new(frames, self.fps)
+    }
+
+    /// Replace each run of identical consecutive frames with its first frame,
+    /// held for the run's total duration.
+    ///
+    /// The hold is stored in the frame's [`FrameMeta::duration_ms`], so
+    /// [`frame_durations`](crate::frame_durations) hands it to the
+    /// controller's variable-timing mode and playback time is unchanged.
+    /// Frames that are not repeated are left untouched.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use cascii_core_view::{frame_durations, Frame, FrameSequence};
+    ///
+    /// let frames = ["a\n", "a\n", "a\n", "b\n"].iter().map(|s| Frame::text_only(s.to_string())).collect();
+    /// let sequence = FrameSequence::new(frames, 10).coalesce_duplicates();
+    /// assert_eq!(sequence.len(), 2);
+    /// assert_eq!(frame_durations(&sequence.frames), [Some(300), None]);
+    /// assert_eq!(sequence.duration_ms(), 400.0);
+    /// ```
+    pub fn coalesce_duplicates(&self) -> FrameSequence {
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut run_start = 0;
+        while run_start < self.frames.len() {
+            let first = &self.frames[run_start];
+            let run_end = (run_start + 1..self.frames.len()).find(|&index| !same_frame(first, &self.frames[index])).unwrap_or(self.frames.len());
+            let mut frame = first.clone();
+            if run_end - run_start > 1 {
+                let held: f64 = (run_start..run_end).map(|index| self.frame_duration_ms(index)).sum();
+                let meta = frame.meta.get_or_insert_with(FrameMeta::default);
+                meta.duration_ms = Some(held);
+            }
+            frames.push(frame);
+            run_start = run_end;
+        }
+        FrameSequence::new(frames, self.fps)
+    }
+
+    /// Smallest `(x, y, width, height)` rectangle holding the content of
+    /// every frame, or `None` when all frames are blank.
+    pub fn content_bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        self.frames.iter().filter_map(Frame::content_bounds).reduce(|(ax, ay, aw, ah), (bx, by, bw, bh)| {
+            let (x, y) = (ax.min(bx), ay.min(by));
+            (x, y, (ax + aw).max(bx + bw) - x, (ay + ah).max(by + bh) - y)
+        })
+    }
+
+    /// Remove the empty margins shared by all frames.
+    ///
+    /// Every frame is cropped to the same [`content_bounds`](Self::content_bounds),
+    /// so content doesn't shift between frames and over-padded exports fill
+    /// more of the screen. A sequence of blank frames is returned unchanged.
+    pub fn trim(&self) -> FrameSequence {
+        let Some((x, y, width, height)) = self.content_bounds() else { return self.clone(); };
+        FrameSequence::new(self.frames.iter().map(|frame| frame.crop(x, y, width, height)).collect(), self.fps)
+    }
+
+    /// Frame at fractional source position `pos`, blending neighbours' colors.
+    fn blended_frame(&self, pos: f64) -> Frame {
+        let last = self.frames.len() - 1;
+        let i0 = (pos.floor() as usize).min(last);
+        let i1 = (i0 + 1).min(last);
+        let t = pos - i0 as f64;
+        let nearest = if t < 0.5 { &self.frames[i0] } else { &self.frames[i1] };
+
+        let (Some(a), Some(b)) = (&self.frames[i0].cframe, &self.frames[i1].cframe) else { return nearest.clone(); };
+        if i0 == i1 || t == 0.0 || a.width != b.width || a.height != b.height || a.has_background() != b.has_background() {
+            return nearest.clone();
+        }
+        let mut cframe = nearest.cframe.clone().unwrap_or_else(|| a.clone());
+        cframe.rgb = lerp_bytes(&a.rgb, &b.rgb, t);
+        if let (Some(bg_a), Some(bg_b)) = (&a.bg_rgb, &b.bg_rgb) {
+            cframe.bg_rgb = Some(lerp_bytes(bg_a, bg_b, t));
+        }
+        Frame::with_color(nearest.content.clone(), cframe)
+    }
+
+    /// Record this sequence's frame count and rate in project metadata.
+    ///
+    /// The `audio` flag and other fields are left untouched, so a retimed
+    /// export still points at the same soundtrack.
+    pub fn apply_to_details(&self, details: &mut ProjectDetails) {
+        details.frames = Some(self.frames.len());
+        details.fps = Some(self.fps);
+    }
+}
+
+/// Whether two frames show exactly the same text and colors.
+fn same_frame(a: &Frame, b: &Frame) -> bool {
+    a.content == b.content && match (&a.cframe, &b.cframe) {
+        (Some(x), Some(y)) => x.width == y.width && x.height == y.height && x.chars == y.chars && x.rgb == y.rgb && x.bg_rgb == y.bg_rgb,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn lerp_bytes(a: &[u8], b: &[u8], t: f64) -> Vec<u8> {
+    a.iter().zip(b).map(|(&x, &y)| (x as f64 + (y as f64 - x as f64) * t).round() as u8).collect()
+}
+
+/// Number of frames a segment of `len` frames at `from_fps` needs at `to_fps`
+/// to keep its duration.
+pub(crate) fn resampled_count(len: usize, from_fps: u32, to_fps: u32) -> usize {
+    if from_fps == to_fps || len == 0 {
+        return len;
+    }
+    ((len as f64 * to_fps as f64 / from_fps.max(1) as f64).round() as usize).max(1)
+}
+
+/// Source index for each of `count` output frames, spreading duplicated or
+/// dropped frames evenly over `len` input frames.
+pub(crate) fn resample_indices(len: usize, count: usize) -> impl Iterator<Item = usize> {
+    (0..count).map(move |i| (i * len / count).min(len.saturating_sub(1)))
+}
+
+fn frame_dimensions(frame: &Frame) -> (usize, usize) {
+    match &frame.cframe {
+        Some(cframe) => (cframe.width as usize, cframe.height as usize),
+        None => frame.dimensions(),
+    }
+}
+
+/// The common form every frame of a concatenation is converted to.
+struct Normalize {
+    width: usize,
+    height: usize,
+    anchor: Anchor,
+    theme: Option<FrameColors>,
+    colored: bool,
+    with_background: bool,
+}
+
+impl Normalize {
+    fn foreground(&self) -> (u8, u8, u8) {
+        self.theme.as_ref().map(|t| t.foreground).unwrap_or(DEFAULT_FOREGROUND)
+    }
+
+    fn background(&self) -> (u8, u8, u8) {
+        self.theme.as_ref().map(|t| t.background).unwrap_or(DEFAULT_BACKGROUND)
+    }
+
+    fn apply(&self, frame: &Frame) -> Frame {
+        let (fw, fh) = frame_dimensions(frame);
+        let (x, y) = self.anchor.resolve(self.width as f64, self.height as f64, fw as f64, fh as f64, (0.0, 0.0));
+        let (dx, dy) = (x.max(0.0) as usize, y.max(0.0) as usize);
+
+        let cell_count = self.width * self.height;
+        let mut chars = vec![b' '; cell_count];
+        let mut rgb = Vec::with_capacity(cell_count * 3);
+        let mut bg = Vec::with_capacity(cell_count * 3);
+        let (fg, back) = (self.foreground(), self.background());
+        for _ in 0..cell_count {
+            rgb.extend_from_slice(&[fg.0, fg.1, fg.2]);
+            bg.extend_from_slice(&[back.0, back.1, back.2]);
+        }
+
+        // One cell per char; chars past Latin-1 have no cell byte.
+        let lines: Vec<Vec<char>> = frame.content.lines().map(|line| line.chars().collect()).collect();
+        for row in 0..fh {
+            for col in 0..fw {
+                let idx = (row + dy) * self.width + col + dx;
+                match &frame.cframe {
+                    Some(cframe) => {
+                        chars[idx] = cframe.char_at(row, col).unwrap_or(b' ');
+                        if let Some((r, g, b)) = cframe.rgb_at(row, col) {
+                            rgb[idx * 3..idx * 3 + 3].copy_from_slice(&[r, g, b]);
+                        }
+                        if let Some((r, g, b)) = cframe.bg_rgb_at(row, col) {
+                            bg[idx * 3..idx * 3 + 3].copy_from_slice(&[r, g, b]);
+                        }
+                    }
+                    None => chars[idx] = lines.get(row).and_then(|line| line.get(col)).map_or(b' ', |&ch| u8::try_from(ch).unwrap_or(b'?')),
+                }
+            }
+        }
+
+        let (width, height) = (self.width as u32, self.height as u32);
+        let cframe = if self.with_background {
+            CFrameData::with_background(width, height, chars, rgb, bg)
+        } else {
+            CFrameData::new(width, height, chars, rgb)
+        };
+        let content = cframe.to_text();
+        if self.colored {
+            Frame::with_color(content, cframe)
+        } else {
+            Frame::text_only(content)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concat_pads_to_common_size() {
+        let small = FrameSequence::new(vec![Frame::text_only("ab\n".into())], 10);
+        let large = FrameSequence::new(vec![Frame::text_only("wxyz\nwxyz\nwxyz\n".into())], 10);
+        let options = ConcatOptions {anchor: Anchor::Center, ..ConcatOptions::default()};
+        let merged = FrameSequence::concat(&[small, large], &options);
+
+        assert_eq!(merged.dimensions(), (4, 3));
+        assert_eq!(merged.frames[0].content, "    \n ab \n    \n");
+        assert!(merged.frames.iter().all(|f| f.cframe.is_none()));
+    }
+
+    #[test]
+    fn test_concat_keeps_non_ascii_text_cells() {
+        let accented = FrameSequence::new(vec![Frame::text_only("é→b\n".into())], 10);
+        let wide = FrameSequence::new(vec![Frame::text_only("wxyz\n".into())], 10);
+        let merged = FrameSequence::concat(&[accented, wide], &ConcatOptions::default());
+
+        assert_eq!(merged.frames[0].content, "é?b \n");
+    }
+
+    #[test]
+    fn test_concat_colors_text_frames_with_theme() {
+        let text = FrameSequence::new(vec![Frame::text_only("A\n".into())], 10);
+        let cframe = CFrameData::with_background(1, 1, vec![b'B'], vec![255, 0, 0], vec![0, 0, 255]);
+        let color = FrameSequence::new(vec![Frame::with_color("B\n".into(), cframe)], 10);
+        let theme = FrameColors {foreground: (0, 255, 0), background: (9, 9, 9)};
+        let merged = FrameSequence::concat(&[text, color], &ConcatOptions {theme: Some(theme), ..ConcatOptions::default()});
+
+        let first = merged.frames[0].cframe.as_ref().unwrap();
+        assert_eq!(first.rgb_at(0, 0), Some((0, 255, 0)));
+        assert_eq!(first.bg_rgb_at(0, 0), Some((9, 9, 9)));
+        let second = merged.frames[1].cframe.as_ref().unwrap();
+        assert_eq!(second.rgb_at(0, 0), Some((255, 0, 0)));
+        assert_eq!(second.bg_rgb_at(0, 0), Some((0, 0, 255)));
+    }
+
+    #[test]
+    fn test_concat_resamples_frame_rates() {
+        let slow = FrameSequence::new(vec![Frame::text_only("a\n".into()), Frame::text_only("b\n".into())], 10);
+        let fast = FrameSequence::new((0..4).map(|i| Frame::text_only(format!("{}\n", i))).collect(), 20);
+        let merged = FrameSequence::concat(&[slow, fast], &ConcatOptions::default());
+
+        assert_eq!(merged.fps, 10);
+        let contents: Vec<&str> = merged.frames.iter().map(|f| f.content.as_str()).collect();
+        assert_eq!(contents, vec!["a\n", "b\n", "0\n", "2\n"]);
+        assert!((merged.duration_ms() - 400.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_retime_distributes_frames_evenly() {
+        let sequence = FrameSequence::new((0..4).map(|i| Frame::text_only(format!("{}\n", i))).collect(), 10);
+
+        let stretched = sequence.retime(800.0, RetimeMode::Nearest);
+        let contents: Vec<&str> = stretched.frames.iter().map(|f| f.content.as_str()).collect();
+        assert_eq!(contents, vec!["0\n", "0\n", "1\n", "1\n", "2\n", "2\n", "3\n", "3\n"]);
+
+        let compressed = sequence.retime(200.0, RetimeMode::Nearest);
+        let contents: Vec<&str> = compressed.frames.iter().map(|f| f.content.as_str()).collect();
+        assert_eq!(contents, vec!["0\n", "2\n"]);
+    }
+
+    #[test]
+    fn test_retime_blends_colors() {
+        let dark = CFrameData::new(1, 1, vec![b'A'], vec![0, 0, 0]);
+        let light = CFrameData::new(1, 1, vec![b'B'], vec![200, 100, 50]);
+        let sequence = FrameSequence::new(vec![Frame::with_color("A\n".into(), dark), Frame::with_color("B\n".into(), light)], 10);
+
+        let blended = sequence.retime(400.0, RetimeMode::Blend);
+        assert_eq!(blended.len(), 4);
+        assert_eq!(blended.frames[1].cframe.as_ref().unwrap().rgb, vec![100, 50, 25]);
+        assert_eq!(blended.frames[1].content, "B\n");
+
+        let mut details = ProjectDetails {audio: Some(true), ..ProjectDetails::default()};
+        blended.apply_to_details(&mut details);
+        assert_eq!((details.frames, details.fps, details.audio), (Some(4), Some(10), Some(true)));
+    }
+
+    #[test]
+    fn test_coalesce_duplicates_keeps_duration() {
+        let red = CFrameData::new(1, 1, vec![b'A'], vec![255, 0, 0]);
+        let blue = CFrameData::new(1, 1, vec![b'A'], vec![0, 0, 255]);
+        let frames = vec![
+            Frame::with_color("A\n".into(), red.clone()),
+            Frame::with_color("A\n".into(), red.clone()),
+            Frame::with_color("A\n".into(), blue),
+            Frame::with_color("A\n".into(), red),
+            Frame::text_only("A\n".into()),
+        ];
+        let sequence = FrameSequence::new(frames, 20);
+        let coalesced = sequence.coalesce_duplicates();
+
+        assert_eq!(coalesced.len(), 4);
+        assert_eq!(crate::frame_durations(&coalesced.frames), [Some(100), None, None, None]);
+        assert_eq!(coalesced.duration_ms(), sequence.duration_ms());
+        assert_eq!(coalesced.coalesce_duplicates().len(), 4);
+    }
+
+    #[test]
+    fn test_trim_uses_shared_bounds() {
+        let frames = vec![Frame::text_only("     \n a   \n     \n".into()), Frame::text_only("     \n     \n   b \n".into())];
+        let sequence = FrameSequence::new(frames, 10);
+        assert_eq!(sequence.content_bounds(), Some((1, 1, 3, 2)));
+
+        let trimmed = sequence.trim();
+        let contents: Vec<&str> = trimmed.frames.iter().map(|f| f.content.as_str()).collect();
+        assert_eq!(contents, ["a  \n   \n", "   \n  b\n"]);
+    }
+}
@@ -1,5 +1,123 @@
 //! Font sizing calculations for fitting ASCII frames to containers.
 
+use std::sync::Arc;
+
+/// How a frame is fitted into its container.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FitMode {
+    /// Largest size at which the whole frame fits (letterboxed)
+    #[default]
+    Contain,
+    /// Smallest size at which the frame covers the container (cropped),
+    /// for full-bleed backgrounds
+    Cover,
+    /// Fill the container width; the height may overflow or fall short
+    StretchWidth,
+    /// Fill the container height; the width may overflow or fall short
+    StretchHeight,
+    /// Always use this font size, ignoring the container
+    Fixed(f64),
+}
+
+impl FitMode {
+    /// Pick a font size from the sizes that exactly fill the container's
+    /// width and height.
+    #[inline]
+    fn select(self, from_width: f64, from_height: f64) -> f64 {
+        match self {
+            FitMode::Contain => from_width.min(from_height),
+            FitMode::Cover => from_width.max(from_height),
+            FitMode::StretchWidth => from_width,
+            FitMode::StretchHeight => from_height,
+            FitMode::Fixed(size) => size,
+        }
+    }
+}
+
+/// Rounding applied to calculated font sizes.
+///
+/// Frames cached at slightly different fractional sizes shimmer when
+/// swapped; snapping keeps small container changes on the same size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontSnap {
+    /// Keep fractional sizes
+    #[default]
+    Off,
+    /// Snap to whole pixels
+    Integer,
+    /// Snap to half pixels
+    Half,
+}
+
+impl FontSnap {
+    /// Snap `font_size`, rounding up when `up` is set and down otherwise so
+    /// the fit mode's guarantee (fits / covers) still holds.
+    #[inline]
+    fn apply(self, font_size: f64, up: bool) -> f64 {
+        let steps = match self {
+            FontSnap::Off => return font_size,
+            FontSnap::Integer => 1.0,
+            FontSnap::Half => 2.0,
+        };
+        if up { (font_size * steps).ceil() / steps } else { (font_size * steps).floor() / steps }
+    }
+}
+
+/// Per-character advance widths for fonts that are not truly monospace.
+///
+/// Advances are stored as ratios of the font size, like
+/// [`FontSizing::char_width_ratio`], for the 256 byte values a frame cell can
+/// hold. Characters without an entry are assumed to advance by exactly one
+/// cell. The table is shared between clones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharAdvances {
+    ratios: Arc<Vec<f64>>,
+}
+
+impl Default for CharAdvances {
+    fn default() -> Self {
+        Self {ratios: Arc::new(vec![0.0; 256])}
+    }
+}
+
+impl CharAdvances {
+    /// An empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a table from advances in pixels measured at `font_size`.
+    pub fn from_measured(advances: impl IntoIterator<Item = (char, f64)>, font_size: f64) -> Self {
+        let mut table = Self::new();
+        if font_size > 0.0 {
+            for (ch, width) in advances {
+                table.set(ch, width / font_size);
+            }
+        }
+        table
+    }
+
+    /// Set the advance of `ch` as a ratio of the font size. Characters past
+    /// U+00FF and non-positive ratios are ignored.
+    pub fn set(&mut self, ch: char, ratio: f64) {
+        if let (Ok(byte), true) = (u8::try_from(ch), ratio.is_finite() && ratio > 0.0) {
+            Arc::make_mut(&mut self.ratios)[byte as usize] = ratio;
+        }
+    }
+
+    /// The advance of `ch` as a ratio of the font size, if known.
+    #[inline]
+    pub fn ratio(&self, ch: char) -> Option<f64> {
+        let ratio = *self.ratios.get(ch as usize)?;
+        (ratio > 0.0).then_some(ratio)
+    }
+
+    /// The raw ratios, indexed by byte value (`0.0` = unknown).
+    pub fn ratios(&self) -> &[f64] {
+        &self.ratios
+    }
+}
+
 /// Font sizing configuration and calculations.
 ///
 /// Provides methods to calculate optimal font sizes for displaying
@@ -14,8 +132,38 @@ pub struct FontSizing {
     pub min_font_size: f64,
     /// Maximum allowed font size in pixels
     pub max_font_size: f64,
-    /// Padding to subtract from container dimensions
-    pub padding: f64,
+    /// Padding to subtract from the container width (both sides combined)
+    pub padding_x: f64,
+    /// Padding to subtract from the container height (both sides combined),
+    /// e.g. room for toolbars above or below the canvas
+    pub padding_y: f64,
+    /// How the frame is fitted into the container
+    pub fit: FitMode,
+    /// Rounding applied to calculated font sizes
+    pub snap: FontSnap,
+    /// Largest canvas width in pixels the computed size may produce
+    pub max_canvas_width: f64,
+    /// Largest canvas height in pixels the computed size may produce
+    pub max_canvas_height: f64,
+    /// Per-character advances of a proportional font; renderers position
+    /// deviating glyphs individually so columns stay aligned
+    pub advances: Option<CharAdvances>,
+    /// Physical pixels per logical pixel, e.g. a winit / tao window's DPI
+    /// scale (the native counterpart of the web `devicePixelRatio`)
+    pub scale_factor: f64,
+}
+
+/// Browsers fail to allocate canvases past roughly this many pixels per side.
+pub const MAX_CANVAS_DIMENSION: f64 = 16_384.0;
+
+/// A calculated font size and whether the canvas size limit reduced it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FontFit {
+    /// Font size in pixels
+    pub font_size: f64,
+    /// `true` when the container would have allowed a larger size than the
+    /// maximum canvas dimensions
+    pub canvas_clamped: bool,
 }
 
 impl Default for FontSizing {
@@ -25,7 +173,14 @@ impl Default for FontSizing {
             line_height_ratio: 1.11,
             min_font_size: 1.0,
             max_font_size: 50.0,
-            padding: 20.0,
+            padding_x: 20.0,
+            padding_y: 20.0,
+            fit: FitMode::Contain,
+            snap: FontSnap::Off,
+            max_canvas_width: MAX_CANVAS_DIMENSION,
+            max_canvas_height: MAX_CANVAS_DIMENSION,
+            advances: None,
+            scale_factor: 1.0,
         }
     }
 }
@@ -36,6 +191,12 @@ impl FontSizing {
         Self::default()
     }
 
+    /// Default sizing with the same `padding` subtracted from both container
+    /// dimensions, as the single `padding` field used to do.
+    pub fn with_padding(padding: f64) -> Self {
+        Self {padding_x: padding, padding_y: padding, ..Self::default()}
+    }
+
     /// Calculate the optimal font size to fit content in a container.
     ///
     /// This is a convenience method using default ratios.
@@ -64,7 +225,8 @@ impl FontSizing {
         Self::default().calculate_font_size(cols, rows, container_width, container_height)
     }
 
-    /// Calculate the optimal font size using this sizing configuration.
+    /// Calculate the optimal font size using this sizing configuration and
+    /// its [`FitMode`].
     ///
     /// ## Arguments
     ///
@@ -75,30 +237,43 @@ impl FontSizing {
     ///
     /// ## Returns
     ///
-    /// The optimal font size in pixels.
+    /// The optimal font size in pixels. Use [`fit_font_size`](Self::fit_font_size)
+    /// to also learn whether the canvas size limit kicked in.
     pub fn calculate_font_size(&self, cols: usize, rows: usize, container_width: f64, container_height: f64) -> f64 {
+        self.fit_font_size(cols, rows, container_width, container_height).font_size
+    }
+
+    /// Like [`calculate_font_size`](Self::calculate_font_size), but also
+    /// reports whether the size was reduced to respect
+    /// [`max_canvas_width`](Self::max_canvas_width) /
+    /// [`max_canvas_height`](Self::max_canvas_height).
+    pub fn fit_font_size(&self, cols: usize, rows: usize, container_width: f64, container_height: f64) -> FontFit {
+        let per_px = (cols as f64 * self.char_width_ratio, rows as f64 * self.line_height_ratio);
+        if let FitMode::Fixed(size) = self.fit {
+            return self.resolve(size, per_px);
+        }
         if cols == 0 || rows == 0 {
-            return self.min_font_size;
+            return self.resolve(self.min_font_size, per_px);
         }
 
-        let available_width = container_width - self.padding;
-        let available_height = container_height - self.padding;
+        let available_width = container_width - self.padding_x;
+        let available_height = container_height - self.padding_y;
 
         if available_width <= 0.0 || available_height <= 0.0 {
-            return self.min_font_size;
+            return self.resolve(self.min_font_size, per_px);
         }
 
         // Calculate max font size that fits width
-        let max_font_from_width = available_width / (cols as f64 * self.char_width_ratio);
+        let max_font_from_width = available_width / per_px.0;
 
         // Calculate max font size that fits height
-        let max_font_from_height = available_height / (rows as f64 * self.line_height_ratio);
+        let max_font_from_height = available_height / per_px.1;
 
-        // Use the smaller of the two to ensure both dimensions fit
-        let optimal_font_size = max_font_from_width.min(max_font_from_height);
+        // Contain uses the smaller of the two so both dimensions fit
+        let optimal_font_size = self.fit.select(max_font_from_width, max_font_from_height);
 
         // Clamp to valid range
-        optimal_font_size.max(self.min_font_size).min(self.max_font_size)
+        self.resolve(optimal_font_size, per_px)
     }
 
     /// Calculate a new font size from a measured rendered block size.
@@ -108,21 +283,116 @@ impl FontSizing {
     /// scales the content uniformly by font size only.
     pub fn calculate_font_size_from_measured_size(&self, measured_width: f64, measured_height: f64, measured_font_size: f64, container_width: f64, container_height: f64) -> f64 {
         if measured_width <= 0.0 || measured_height <= 0.0 || measured_font_size <= 0.0 {
-            return self.min_font_size;
+            return match self.fit {
+                FitMode::Fixed(size) => self.clamp(size),
+                _ => self.min_font_size,
+            };
+        }
+        let per_px = (measured_width / measured_font_size, measured_height / measured_font_size);
+        if let FitMode::Fixed(size) = self.fit {
+            return self.resolve(size, per_px).font_size;
         }
 
-        let available_width = container_width - self.padding;
-        let available_height = container_height - self.padding;
+        let available_width = container_width - self.padding_x;
+        let available_height = container_height - self.padding_y;
 
         if available_width <= 0.0 || available_height <= 0.0 {
-            return self.min_font_size;
+            return self.resolve(self.min_font_size, per_px).font_size;
         }
 
         let width_scale = available_width / measured_width;
         let height_scale = available_height / measured_height;
-        let optimal_font_size = measured_font_size * width_scale.min(height_scale);
+        let optimal_font_size = measured_font_size * self.fit.select(width_scale, height_scale);
+
+        self.resolve(optimal_font_size, per_px).font_size
+    }
 
-        optimal_font_size.max(self.min_font_size).min(self.max_font_size)
+    /// Largest font size at which a `cols` x `rows` frame stays within
+    /// [`max_canvas_width`](Self::max_canvas_width) and
+    /// [`max_canvas_height`](Self::max_canvas_height) physical pixels at the
+    /// sizing's [`scale_factor`](Self::scale_factor).
+    pub fn max_font_size_for_canvas(&self, cols: usize, rows: usize) -> f64 {
+        self.max_font_size_for_canvas_at(cols, rows, self.effective_scale_factor())
+    }
+
+    /// [`max_font_size_for_canvas`](Self::max_font_size_for_canvas) at an
+    /// explicit scale, e.g. a device pixel ratio override.
+    pub(crate) fn max_font_size_for_canvas_at(&self, cols: usize, rows: usize, scale: f64) -> f64 {
+        self.canvas_limit((cols as f64 * self.char_width_ratio * scale, rows as f64 * self.line_height_ratio * scale))
+    }
+
+    /// Canvas-size limit for content of `(width, height)` physical pixels per
+    /// pixel of font size.
+    fn canvas_limit(&self, (width_per_px, height_per_px): (f64, f64)) -> f64 {
+        let limit = |max: f64, per_px: f64| if per_px > 0.0 { max / per_px } else { f64::INFINITY };
+        limit(self.max_canvas_width, width_per_px).min(limit(self.max_canvas_height, height_per_px))
+    }
+
+    /// Scale factor, treating non-positive or non-finite values as `1.0`.
+    #[inline]
+    pub fn effective_scale_factor(&self) -> f64 {
+        if self.scale_factor.is_finite() && self.scale_factor > 0.0 { self.scale_factor } else { 1.0 }
+    }
+
+    /// A logical font size in physical pixels.
+    #[inline]
+    pub fn physical_font_size(&self, font_size: f64) -> f64 {
+        font_size * self.effective_scale_factor()
+    }
+
+    /// Backing-store size in physical pixels of a frame at a logical font
+    /// size, rounded up like the web canvas path.
+    pub fn physical_canvas_dimensions(&self, cols: usize, rows: usize, font_size: f64) -> (u32, u32) {
+        let (width, height) = self.canvas_dimensions(cols, rows, font_size);
+        let scale = self.effective_scale_factor();
+        ((width * scale).ceil() as u32, (height * scale).ceil() as u32)
+    }
+
+    /// Clamp and snap `font_size`, then enforce the canvas limit, which wins
+    /// over [`min_font_size`](Self::min_font_size) since oversized canvases
+    /// fail silently.
+    fn resolve(&self, font_size: f64, (width_per_px, height_per_px): (f64, f64)) -> FontFit {
+        let font_size = self.clamp(font_size);
+        let scale = self.effective_scale_factor();
+        let limit = self.canvas_limit((width_per_px * scale, height_per_px * scale));
+        if font_size > limit {
+            FontFit {font_size: self.snap.apply(limit, false), canvas_clamped: true}
+        } else {
+            FontFit {font_size, canvas_clamped: false}
+        }
+    }
+
+    /// Stable integer key for a font size, in hundredths of a pixel.
+    ///
+    /// Suitable for `FrameCanvasCache::invalidate_for_font_size_key`: sizes
+    /// that only differ by float noise share a key.
+    #[inline]
+    pub fn font_size_key(font_size: f64) -> i32 {
+        (font_size * 100.0).round() as i32
+    }
+
+    #[inline]
+    fn clamp(&self, font_size: f64) -> f64 {
+        let snapped = self.snap.apply(font_size, self.fit == FitMode::Cover);
+        snapped.max(self.min_font_size).min(self.max_font_size)
+    }
+
+    /// Replace the default ratios with ones measured from the actual font.
+    ///
+    /// `measured_char_width` and `measured_line_height` are the pixel size
+    /// of one cell rendered at `font_size` (on the web, see
+    /// `render::web::measure_font_metrics`). Non-positive or non-finite
+    /// measurements leave the corresponding ratio unchanged.
+    pub fn calibrate(&mut self, measured_char_width: f64, measured_line_height: f64, font_size: f64) {
+        if !font_size.is_finite() || font_size <= 0.0 {
+            return;
+        }
+        if measured_char_width.is_finite() && measured_char_width > 0.0 {
+            self.char_width_ratio = measured_char_width / font_size;
+        }
+        if measured_line_height.is_finite() && measured_line_height > 0.0 {
+            self.line_height_ratio = measured_line_height / font_size;
+        }
     }
 
     /// Calculate the character width in pixels for a given font size.
@@ -149,6 +419,96 @@ impl FontSizing {
     }
 }
 
+/// Filters a stream of container sizes (e.g. from a `ResizeObserver` during
+/// a window drag) down to the ones worth re-rendering for.
+///
+/// A size is reported only when the font size it produces differs from the
+/// last reported one by more than [`threshold`](Self::threshold), and, with
+/// a [`debounce_ms`](Self::debounce_ms), only once sizes stop arriving for
+/// that long. The first observation is always reported immediately.
+#[derive(Clone, Debug)]
+pub struct ResizeTracker {
+    sizing: FontSizing,
+    cols: usize,
+    rows: usize,
+    /// Minimum font-size change in pixels worth reporting
+    pub threshold: f64,
+    /// Quiet period in milliseconds before a changed size is reported
+    pub debounce_ms: f64,
+    current: Option<f64>,
+    /// Changed font size waiting out the debounce, and when it was observed
+    pending: Option<(f64, f64)>,
+}
+
+impl ResizeTracker {
+    /// Track a `cols` x `rows` frame fitted with `sizing`.
+    pub fn new(sizing: FontSizing, cols: usize, rows: usize) -> Self {
+        Self {sizing, cols, rows, threshold: 0.0, debounce_ms: 0.0, current: None, pending: None}
+    }
+
+    /// Set the minimum font-size change worth reporting.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set the quiet period before a changed size is reported.
+    pub fn with_debounce(mut self, debounce_ms: f64) -> Self {
+        self.debounce_ms = debounce_ms;
+        self
+    }
+
+    /// The last reported font size.
+    pub fn font_size(&self) -> Option<f64> {
+        self.current
+    }
+
+    /// Change the frame dimensions; the next observation is reported.
+    pub fn set_frame_size(&mut self, cols: usize, rows: usize) {
+        self.cols = cols;
+        self.rows = rows;
+        self.current = None;
+        self.pending = None;
+    }
+
+    /// Feed a container size observed at `now_ms`. Returns the new font size
+    /// when the caller should re-render now.
+    pub fn observe(&mut self, container_width: f64, container_height: f64, now_ms: f64) -> Option<f64> {
+        let font_size = self.sizing.calculate_font_size(self.cols, self.rows, container_width, container_height);
+        match self.current {
+            Some(current) if !self.changed(current, font_size) => {
+                self.pending = None;
+                None
+            }
+            Some(_) if self.debounce_ms > 0.0 => {
+                self.pending = Some((font_size, now_ms));
+                None
+            }
+            _ => {
+                self.pending = None;
+                self.current = Some(font_size);
+                self.current
+            }
+        }
+    }
+
+    /// Report a debounced size once its quiet period has passed. Call this
+    /// from a timer or the next animation frame.
+    pub fn poll(&mut self, now_ms: f64) -> Option<f64> {
+        let (font_size, since) = self.pending?;
+        if now_ms - since < self.debounce_ms {
+            return None;
+        }
+        self.pending = None;
+        self.current = Some(font_size);
+        self.current
+    }
+
+    fn changed(&self, current: f64, next: f64) -> bool {
+        (next - current).abs() > self.threshold && FontSizing::font_size_key(next) != FontSizing::font_size_key(current)
+    }
+}
+
 /// Calculate character position in pixels.
 ///
 /// ## Arguments
@@ -185,6 +545,59 @@ mod tests {
         assert!(font_size > 15.0 && font_size < 17.0);
     }
 
+    #[test]
+    fn test_fit_modes() {
+        let sizing = |fit| FontSizing {fit, ..FontSizing::with_padding(0.0)};
+        // 10 cols x 10 rows in 60x222: width fits 10px, height fits 20px
+        assert_eq!(sizing(FitMode::Contain).calculate_font_size(10, 10, 60.0, 222.0), 10.0);
+        assert!((sizing(FitMode::Cover).calculate_font_size(10, 10, 60.0, 222.0) - 20.0).abs() < 1e-9);
+        assert_eq!(sizing(FitMode::StretchWidth).calculate_font_size(10, 10, 60.0, 222.0), 10.0);
+        assert!((sizing(FitMode::StretchHeight).calculate_font_size(10, 10, 222.0, 111.0) - 10.0).abs() < 1e-9);
+        assert_eq!(sizing(FitMode::Fixed(14.0)).calculate_font_size(0, 0, 0.0, 0.0), 14.0);
+        assert_eq!(sizing(FitMode::Fixed(99.0)).calculate_font_size(10, 10, 60.0, 222.0), 50.0);
+        assert!((sizing(FitMode::Cover).calculate_font_size_from_measured_size(60.0, 111.0, 10.0, 60.0, 222.0) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_font_snap() {
+        // Unsnapped: (800-20) / (80 * 0.6) = 16.25
+        let snapped = |snap, fit| FontSizing {snap, fit, ..FontSizing::default()}.calculate_font_size(80, 24, 800.0, 600.0);
+        assert_eq!(snapped(FontSnap::Integer, FitMode::Contain), 16.0);
+        assert_eq!(snapped(FontSnap::Half, FitMode::Contain), 16.0);
+        assert_eq!(snapped(FontSnap::Half, FitMode::StretchWidth), 16.0);
+        // Cover is height-bound (≈ 21.77) and rounds up
+        assert_eq!(snapped(FontSnap::Half, FitMode::Cover), 22.0);
+        assert_eq!(snapped(FontSnap::Off, FitMode::Contain), 16.25);
+
+        assert_eq!(FontSizing::font_size_key(16.25), 1625);
+        assert_eq!(FontSizing::font_size_key(16.249_999_9), FontSizing::font_size_key(16.25));
+    }
+
+    #[test]
+    fn test_asymmetric_padding() {
+        // 10 cols x 10 rows: width would fit 10px, height 100 - 44.5 fits 5px
+        let sizing = FontSizing {padding_x: 0.0, padding_y: 44.5, ..FontSizing::default()};
+        assert!((sizing.calculate_font_size(10, 10, 60.0, 100.0) - 5.0).abs() < 1e-9);
+        let sizing = FontSizing {padding_y: 0.0, ..sizing};
+        assert_eq!(sizing.calculate_font_size(10, 10, 60.0, 1000.0), 10.0);
+        assert_eq!((FontSizing::with_padding(5.0).padding_x, FontSizing::with_padding(5.0).padding_y), (5.0, 5.0));
+    }
+
+    #[test]
+    fn test_max_canvas_dimension_clamps() {
+        // 1000 cols at 0.6: a 16384px canvas allows at most ~27.3px
+        let sizing = FontSizing {max_font_size: 100.0, ..FontSizing::default()};
+        let fit = sizing.fit_font_size(1000, 10, 100_000.0, 100_000.0);
+        assert!(fit.canvas_clamped);
+        assert!((fit.font_size - MAX_CANVAS_DIMENSION / 600.0).abs() < 1e-9);
+        assert!(sizing.canvas_dimensions(1000, 10, fit.font_size).0 <= MAX_CANVAS_DIMENSION);
+        assert!(!sizing.fit_font_size(80, 24, 800.0, 600.0).canvas_clamped);
+
+        let tight = FontSizing {max_canvas_width: 60.0, snap: FontSnap::Integer, fit: FitMode::Fixed(20.0), ..sizing};
+        assert_eq!(tight.fit_font_size(10, 10, 0.0, 0.0), FontFit {font_size: 10.0, canvas_clamped: true});
+        assert!((tight.max_font_size_for_canvas(10, 10) - 10.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_calculate_font_size_zero_dimensions() {
         let sizing = FontSizing::default();
@@ -207,6 +620,65 @@ mod tests {
         assert!(font_size > 15.0 && font_size < 17.0);
     }
 
+    #[test]
+    fn test_calibrate() {
+        let mut sizing = FontSizing::default();
+        sizing.calibrate(6.5, 12.0, 10.0);
+        assert!((sizing.char_width_ratio - 0.65).abs() < 1e-9);
+        assert!((sizing.line_height_ratio - 1.2).abs() < 1e-9);
+        assert!((sizing.canvas_dimensions(10, 2, 20.0).0 - 130.0).abs() < 1e-9);
+
+        sizing.calibrate(0.0, f64::NAN, 10.0);
+        sizing.calibrate(8.0, 8.0, 0.0);
+        assert!((sizing.char_width_ratio - 0.65).abs() < 1e-9);
+        assert!((sizing.line_height_ratio - 1.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resize_tracker() {
+        // 10 cols x 10 rows without padding: font size = width / 6 (height is ample)
+        let mut tracker = ResizeTracker::new(FontSizing::with_padding(0.0), 10, 10).with_threshold(0.5);
+        assert_eq!(tracker.observe(60.0, 1000.0, 0.0), Some(10.0));
+        assert_eq!(tracker.observe(62.0, 1000.0, 10.0), None);
+        assert_eq!(tracker.observe(66.0, 1000.0, 20.0), Some(11.0));
+
+        let mut tracker = tracker.with_debounce(100.0);
+        assert_eq!(tracker.observe(72.0, 1000.0, 30.0), None);
+        assert_eq!(tracker.observe(78.0, 1000.0, 80.0), None);
+        assert_eq!(tracker.poll(150.0), None);
+        assert_eq!(tracker.poll(180.0), Some(13.0));
+        assert_eq!(tracker.poll(300.0), None);
+
+        // Dragging back to the reported size drops the pending one.
+        tracker.observe(90.0, 1000.0, 400.0);
+        tracker.observe(78.0, 1000.0, 410.0);
+        assert_eq!(tracker.poll(1000.0), None);
+        assert_eq!(tracker.font_size(), Some(13.0));
+    }
+
+    #[test]
+    fn test_char_advances() {
+        let mut advances = CharAdvances::from_measured([('i', 3.0), ('W', 9.0), ('\u{263A}', 8.0)], 10.0);
+        assert_eq!(advances.ratio('i'), Some(0.3));
+        assert_eq!(advances.ratio('W'), Some(0.9));
+        assert_eq!(advances.ratio('M'), None);
+        assert_eq!(advances.ratio('\u{263A}'), None);
+        let shared = advances.clone();
+        advances.set('M', 0.6);
+        assert_eq!((advances.ratio('M'), shared.ratio('M')), (Some(0.6), None));
+    }
+
+    #[test]
+    fn test_scale_factor() {
+        let sizing = FontSizing {scale_factor: 2.0, ..FontSizing::default()};
+        assert_eq!(sizing.physical_font_size(12.0), 24.0);
+        assert_eq!(sizing.physical_canvas_dimensions(80, 24, 10.0), (960, 533));
+        // Logical sizing is unchanged; the canvas limit applies to physical pixels.
+        assert_eq!(sizing.calculate_font_size(80, 24, 800.0, 600.0), FontSizing::default().calculate_font_size(80, 24, 800.0, 600.0));
+        assert!((sizing.max_font_size_for_canvas(1000, 10) - MAX_CANVAS_DIMENSION / 1200.0).abs() < 1e-9);
+        assert_eq!(FontSizing {scale_factor: 0.0, ..sizing}.effective_scale_factor(), 1.0);
+    }
+
     #[test]
     fn test_char_position() {
         let (x, y) = char_position(10, 5, 12.0);
@@ -0,0 +1,217 @@
+//! Random-access frame sources.
+//!
+//! [`FrameSource`] abstracts over where frames live: a fully loaded
+//! `Vec<Frame>`, shared frames still arriving from a [`FrameLoaderState`],
+//! or packed containers ([`PackedCFrameBlob`], [`FrameAtlas`]) that decode
+//! a frame on request. Playback
+//! ([`AnimationController::tick_source`](crate::AnimationController::tick_source)),
+//! rendering ([`render_source_frame`](crate::render::render_source_frame))
+//! and [`FramePlayer::set_source`](crate::FramePlayer::set_source) accept
+//! any source, so they no longer assume every frame is in memory.
+
+use std::borrow::Cow;
+
+use crate::{CFrameData, Frame, FrameAtlas, FrameLoaderState, PackedCFrameBlob, SharedFrame};
+
+/// Indexed access to the frames of an animation.
+///
+/// ## Example
+///
+/// ```rust
+/// use cascii_core_view::{CFrameData, Frame, FrameSource, PackedCFrameBlob};
+///
+/// let frames = vec![Frame::text_only("a".into()), Frame::text_only("b".into())];
+/// assert_eq!(frames.len(), 2);
+/// assert_eq!(FrameSource::get(&frames, 1).unwrap().content, "b");
+///
+/// let packed = PackedCFrameBlob::new(1, 1, 1, vec![b'X', 255, 0, 0]);
+/// assert_eq!(packed.cframe(0).unwrap().chars, b"X");
+/// assert_eq!(FrameSource::get(&packed, 0).unwrap().content, "X\n");
+/// ```
+pub trait FrameSource {
+    /// Number of frames the source will hold, including ones not ready yet.
+    fn len(&self) -> usize;
+
+    /// Returns `true` when the source holds no frames.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Frame `index`, borrowed when stored as a [`Frame`] and decoded
+    /// otherwise. `None` when out of range or not ready.
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>>;
+
+    /// Color data of frame `index`. Sources storing color separately
+    /// override this to skip building the text content.
+    fn cframe(&self, index: usize) -> Option<Cow<'_, CFrameData>> {
+        match self.get(index)? {
+            Cow::Borrowed(frame) => frame.cframe.as_ref().map(Cow::Borrowed),
+            Cow::Owned(frame) => frame.cframe.map(Cow::Owned),
+        }
+    }
+
+    /// Whether [`get`](Self::get) can return frame `index` right now.
+    fn is_ready(&self, index: usize) -> bool {
+        index < self.len()
+    }
+
+    /// Whether frame `index` has color data. Sources that know without
+    /// decoding the frame override this.
+    fn has_color(&self, index: usize) -> bool {
+        self.cframe(index).is_some()
+    }
+
+    /// `(cols, rows)` of frame `index`, as [`Frame::dimensions`]. Sources
+    /// storing color data report its size without decoding the frame.
+    fn dimensions(&self, index: usize) -> Option<(usize, usize)> {
+        self.get(index).map(|frame| frame.dimensions())
+    }
+}
+
+impl FrameSource for [Frame] {
+    fn len(&self) -> usize {
+        <[Frame]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        <[Frame]>::get(self, index).map(Cow::Borrowed)
+    }
+}
+
+impl FrameSource for Vec<Frame> {
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        FrameSource::get(self.as_slice(), index)
+    }
+}
+
+impl FrameSource for [SharedFrame] {
+    fn len(&self) -> usize {
+        <[SharedFrame]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        <[SharedFrame]>::get(self, index).map(|frame| Cow::Borrowed(&**frame))
+    }
+}
+
+impl FrameSource for Vec<SharedFrame> {
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        FrameSource::get(self.as_slice(), index)
+    }
+}
+
+/// Frames still loading count towards `len` (from the text total) but are
+/// not ready until they arrive.
+impl FrameSource for FrameLoaderState {
+    fn len(&self) -> usize {
+        self.frames.len().max(self.progress.text_total)
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        FrameSource::get(self.frames.as_slice(), index)
+    }
+
+    fn is_ready(&self, index: usize) -> bool {
+        index < self.frames.len()
+    }
+}
+
+/// Frames are decoded on request; their text is rebuilt from the characters.
+impl FrameSource for PackedCFrameBlob {
+    fn len(&self) -> usize {
+        PackedCFrameBlob::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        let cframe = self.decode_frame(index)?;
+        Some(Cow::Owned(Frame::with_color(cframe.to_text(), cframe)))
+    }
+
+    fn cframe(&self, index: usize) -> Option<Cow<'_, CFrameData>> {
+        self.decode_frame(index).map(Cow::Owned)
+    }
+
+    fn has_color(&self, index: usize) -> bool {
+        index < PackedCFrameBlob::len(self)
+    }
+
+    fn dimensions(&self, index: usize) -> Option<(usize, usize)> {
+        (index < PackedCFrameBlob::len(self)).then_some((self.width as usize, self.height as usize))
+    }
+}
+
+/// Frames are copied out of the atlas on request; their text is rebuilt
+/// from the characters.
+impl FrameSource for FrameAtlas {
+    fn len(&self) -> usize {
+        FrameAtlas::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<Cow<'_, Frame>> {
+        let cframe = self.decode(index)?;
+        Some(Cow::Owned(Frame::with_color(cframe.to_text(), cframe)))
+    }
+
+    fn cframe(&self, index: usize) -> Option<Cow<'_, CFrameData>> {
+        self.decode(index).map(Cow::Owned)
+    }
+
+    fn has_color(&self, index: usize) -> bool {
+        index < FrameAtlas::len(self)
+    }
+
+    fn dimensions(&self, index: usize) -> Option<(usize, usize)> {
+        self.get(index).map(|frame| (frame.width as usize, frame.height as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LoadingPhase;
+
+    #[test]
+    fn test_loader_state_reports_pending_frames() {
+        let mut state = FrameLoaderState::new();
+        state.phase = LoadingPhase::LoadingText;
+        state.progress.reset(3);
+        state.frames.push(Frame::text_only("a".into()).into());
+
+        assert_eq!(FrameSource::len(&state), 3);
+        assert!(state.is_ready(0));
+        assert!(!state.is_ready(1));
+        assert!(FrameSource::get(&state, 1).is_none());
+        assert!(state.cframe(0).is_none());
+    }
+
+    #[test]
+    fn test_packed_source_decodes_frames() {
+        let packed = PackedCFrameBlob::new(2, 2, 1, vec![b'h', 1, 2, 3, b'i', 4, 5, 6, b'y', 0, 0, 0, b'o', 0, 0, 0]);
+
+        assert_eq!(FrameSource::len(&packed), 2);
+        assert_eq!((packed.has_color(1), packed.has_color(2), packed.dimensions(0)), (true, false, Some((2, 1))));
+        assert_eq!(packed.cframe(0).unwrap().rgb, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(FrameSource::get(&packed, 1).unwrap().content, "yo\n");
+        assert!(FrameSource::get(&packed, 2).is_none());
+    }
+
+    #[test]
+    fn test_atlas_source_decodes_frames() {
+        let cframe = CFrameData::new(2, 1, b"hi".to_vec(), vec![1, 2, 3, 4, 5, 6]);
+        let atlas = FrameAtlas::from_cframes([&cframe]);
+
+        assert_eq!(FrameSource::len(&atlas), 1);
+        assert_eq!((atlas.has_color(0), FrameSource::dimensions(&atlas, 0)), (true, Some((2, 1))));
+        assert_eq!(atlas.cframe(0).unwrap().rgb, cframe.rgb);
+        assert_eq!(FrameSource::get(&atlas, 0).unwrap().content, "hi\n");
+        assert!(FrameSource::get(&atlas, 1).is_none());
+    }
+}
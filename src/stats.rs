@@ -0,0 +1,89 @@
+//! Content statistics of color frames, for auto-exposure, auto-crop, and QA
+//! tooling.
+
+use std::collections::HashSet;
+
+use crate::CFrameData;
+
+/// Number of buckets in [`FrameStats::brightness_histogram`].
+pub const BRIGHTNESS_BINS: usize = 16;
+
+/// Summary of a frame's visible content. "Content" cells are the ones with a
+/// non-space character.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameStats {
+    /// Number of cells with a non-space character
+    pub content_cells: usize,
+    /// `content_cells` as a fraction of all cells (0.0 - 1.0)
+    pub density: f64,
+    /// Number of distinct foreground colors among content cells
+    pub distinct_colors: usize,
+    /// Content cells per foreground luma bucket, darkest first; bucket `i`
+    /// covers luma `i * 256 / BRIGHTNESS_BINS` up to the next bucket
+    pub brightness_histogram: [u32; BRIGHTNESS_BINS],
+    /// Mean foreground luma of content cells (0.0 - 255.0)
+    pub mean_brightness: f64,
+    /// Smallest `(x, y, width, height)` cell rectangle holding all content,
+    /// or `None` for a blank frame
+    pub bounding_box: Option<(u32, u32, u32, u32)>,
+}
+
+impl CFrameData {
+    /// Compute [`FrameStats`] for this frame.
+    pub fn stats(&self) -> FrameStats {
+        let width = self.width as usize;
+        let mut histogram = [0u32; BRIGHTNESS_BINS];
+        let mut colors = HashSet::new();
+        let mut luma_sum = 0.0;
+        let mut content_cells = 0;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+
+        for (idx, (&ch, rgb)) in self.chars.iter().zip(self.rgb.chunks_exact(3)).enumerate() {
+            if ch == b' ' || width == 0 {
+                continue;
+            }
+            let (x, y) = (idx % width, idx / width);
+            content_cells += 1;
+            (min_x, min_y, max_x, max_y) = (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y));
+            colors.insert((rgb[0], rgb[1], rgb[2]));
+            let luma = 0.2126 * rgb[0] as f64 + 0.7152 * rgb[1] as f64 + 0.0722 * rgb[2] as f64;
+            luma_sum += luma;
+            histogram[((luma as usize) * BRIGHTNESS_BINS / 256).min(BRIGHTNESS_BINS - 1)] += 1;
+        }
+
+        let cells = self.pixel_count();
+        let bounding_box = (content_cells > 0).then(|| (min_x as u32, min_y as u32, (max_x - min_x + 1) as u32, (max_y - min_y + 1) as u32));
+        FrameStats {
+            content_cells,
+            density: if cells > 0 { content_cells as f64 / cells as f64 } else { 0.0 },
+            distinct_colors: colors.len(),
+            brightness_histogram: histogram,
+            mean_brightness: if content_cells > 0 { luma_sum / content_cells as f64 } else { 0.0 },
+            bounding_box,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_stats() {
+        let rgb = [[0, 0, 0], [255, 255, 255], [0, 0, 0], [255, 255, 255], [255, 255, 255], [9, 9, 9]].concat();
+        let cframe = CFrameData::new(3, 2, b"  A BC".to_vec(), rgb);
+        let stats = cframe.stats();
+
+        assert_eq!(stats.content_cells, 3);
+        assert_eq!(stats.density, 0.5);
+        assert_eq!(stats.distinct_colors, 3);
+        assert_eq!(stats.brightness_histogram[0], 2);
+        assert_eq!(stats.brightness_histogram[BRIGHTNESS_BINS - 1], 1);
+        assert!((stats.mean_brightness - (255.0 + 9.0) / 3.0).abs() < 1e-6);
+        assert_eq!(stats.bounding_box, Some((1, 0, 2, 2)));
+
+        let blank = CFrameData::new(2, 1, b"  ".to_vec(), vec![0; 6]).stats();
+        assert_eq!((blank.content_cells, blank.bounding_box, blank.mean_brightness), (0, None, 0.0));
+    }
+}
@@ -0,0 +1,223 @@
+//! Timeline metadata: chapters, bookmarks, scene cuts, and seek-strip
+//! thumbnails.
+//!
+//! A [`Timeline`] is computed once (scene cuts are detected from frame
+//! content) and exported as a JSON sidecar with the `json` feature, so web
+//! players and static site embeds can reuse it without re-analyzing frames.
+
+use crate::{Frame, ProjectDetails};
+
+/// Current sidecar format version.
+pub const TIMELINE_VERSION: u32 = 1;
+
+/// A named frame range.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chapter {
+    pub title: String,
+    /// First frame of the chapter
+    pub start_frame: usize,
+    /// Last frame of the chapter (inclusive)
+    pub end_frame: usize,
+}
+
+/// A labelled point on the timeline.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bookmark {
+    pub frame: usize,
+    pub label: String,
+}
+
+/// A seek-strip thumbnail reference (the image itself lives elsewhere).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Thumbnail {
+    pub frame: usize,
+    /// Path or URL of the thumbnail image
+    pub src: String,
+}
+
+/// Timeline metadata for one animation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeline {
+    /// Sidecar format version
+    pub version: u32,
+    /// Playback rate the frame indices refer to
+    pub fps: u32,
+    /// Total number of frames
+    pub frame_count: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub chapters: Vec<Chapter>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bookmarks: Vec<Bookmark>,
+    /// Frames that start a new scene, ascending
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub scene_cuts: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub thumbnails: Vec<Thumbnail>,
+}
+
+impl Timeline {
+    /// Create an empty timeline for `frame_count` frames at `fps`.
+    pub fn new(frame_count: usize, fps: u32) -> Self {
+        Self {version: TIMELINE_VERSION, fps: fps.max(1), frame_count, chapters: Vec::new(), bookmarks: Vec::new(), scene_cuts: Vec::new(), thumbnails: Vec::new()}
+    }
+
+    /// Create a timeline for `frames`, detecting scene cuts where at least
+    /// `threshold` (0.0 - 1.0) of the cells change between neighbours.
+    pub fn from_frames(frames: &[Frame], fps: u32, threshold: f64) -> Self {
+        Self {scene_cuts: detect_scene_cuts(frames, threshold), ..Self::new(frames.len(), fps)}
+    }
+
+    /// Add a chapter (builder style). The range is clamped to the timeline.
+    pub fn with_chapter(mut self, title: impl Into<String>, start_frame: usize, end_frame: usize) -> Self {
+        let last = self.frame_count.saturating_sub(1);
+        let start_frame = start_frame.min(last);
+        self.chapters.push(Chapter {title: title.into(), start_frame, end_frame: end_frame.clamp(start_frame, last)});
+        self.chapters.sort_by_key(|chapter| chapter.start_frame);
+        self
+    }
+
+    /// Add a chapter for each of the details' `[sections]`, titled by the
+    /// section name (builder style).
+    pub fn with_sections(self, details: &ProjectDetails) -> Self {
+        details.sections.iter().fold(self, |timeline, (name, section)| timeline.with_chapter(name.clone(), section.start, section.end))
+    }
+
+    /// Add a bookmark (builder style).
+    pub fn with_bookmark(mut self, frame: usize, label: impl Into<String>) -> Self {
+        self.bookmarks.push(Bookmark {frame: frame.min(self.frame_count.saturating_sub(1)), label: label.into()});
+        self.bookmarks.sort_by_key(|bookmark| bookmark.frame);
+        self
+    }
+
+    /// Replace the thumbnails with `count` evenly spaced seek-strip entries,
+    /// naming each one with `src(frame)`.
+    pub fn with_seek_strip(mut self, count: usize, src: impl Fn(usize) -> String) -> Self {
+        let count = count.min(self.frame_count);
+        self.thumbnails = (0..count).map(|i| i * self.frame_count / count).map(|frame| Thumbnail {frame, src: src(frame)}).collect();
+        self
+    }
+
+    /// Chapter containing `frame`, if any.
+    pub fn chapter_at(&self, frame: usize) -> Option<&Chapter> {
+        self.chapters.iter().find(|chapter| (chapter.start_frame..=chapter.end_frame).contains(&frame))
+    }
+
+    /// Time of `frame` in milliseconds at the timeline's fps.
+    pub fn frame_time_ms(&self, frame: usize) -> f64 {
+        frame as f64 * 1000.0 / self.fps as f64
+    }
+
+    /// The part of the timeline between `start_frame` and `end_frame`
+    /// (inclusive), rebased so `start_frame` becomes frame 0. Chapters that
+    /// straddle the range are clipped to it.
+    pub fn range(&self, start_frame: usize, end_frame: usize) -> Self {
+        let end_frame = end_frame.min(self.frame_count.saturating_sub(1));
+        let start_frame = start_frame.min(end_frame);
+        let inside = |frame: usize| (start_frame..=end_frame).contains(&frame);
+        Self {
+            version: self.version,
+            fps: self.fps,
+            frame_count: if self.frame_count == 0 { 0 } else { end_frame - start_frame + 1 },
+            chapters: self.chapters.iter()
+                .filter(|c| c.start_frame <= end_frame && c.end_frame >= start_frame)
+                .map(|c| Chapter {title: c.title.clone(), start_frame: c.start_frame.max(start_frame) - start_frame, end_frame: c.end_frame.min(end_frame) - start_frame})
+                .collect(),
+            bookmarks: self.bookmarks.iter().filter(|b| inside(b.frame)).map(|b| Bookmark {frame: b.frame - start_frame, label: b.label.clone()}).collect(),
+            scene_cuts: self.scene_cuts.iter().filter(|&&f| inside(f)).map(|f| f - start_frame).collect(),
+            thumbnails: self.thumbnails.iter().filter(|t| inside(t.frame)).map(|t| Thumbnail {frame: t.frame - start_frame, src: t.src.clone()}).collect(),
+        }
+    }
+
+    /// Serialize as a JSON sidecar.
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parse a JSON sidecar written by [`to_json_string`](Self::to_json_string).
+    #[cfg(feature = "json")]
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Frames (after the first) where at least `threshold` of the cells differ
+/// from the previous frame. A change of dimensions always counts as a cut.
+pub fn detect_scene_cuts(frames: &[Frame], threshold: f64) -> Vec<usize> {
+    (1..frames.len()).filter(|&i| changed_fraction(&frames[i - 1], &frames[i]) >= threshold).collect()
+}
+
+/// Fraction of cells whose character or color differs between two frames.
+fn changed_fraction(prev: &Frame, next: &Frame) -> f64 {
+    if let (Some(a), Some(b)) = (&prev.cframe, &next.cframe) {
+        if (a.width, a.height) != (b.width, b.height) {
+            return 1.0;
+        }
+        let cells = a.chars.len().max(1);
+        let changed = (0..a.chars.len())
+            .filter(|&i| a.chars[i] != b.chars[i] || a.rgb[i * 3..i * 3 + 3].iter().zip(&b.rgb[i * 3..i * 3 + 3]).any(|(x, y)| x.abs_diff(*y) > 32))
+            .count();
+        return changed as f64 / cells as f64;
+    }
+    if prev.dimensions() != next.dimensions() {
+        return 1.0;
+    }
+    let cells = prev.content.len().max(1);
+    let changed = prev.content.bytes().zip(next.content.bytes()).filter(|(a, b)| a != b).count();
+    changed as f64 / cells as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<Frame> {
+        ["aaaa", "aaab", "zzzz", "zzzz"].iter().map(|s| Frame::text_only(s.to_string())).collect()
+    }
+
+    #[test]
+    fn test_scene_cuts_and_range() {
+        let timeline = Timeline::from_frames(&frames(), 10, 0.5)
+            .with_chapter("intro", 0, 1)
+            .with_chapter("main", 2, 99)
+            .with_bookmark(3, "end")
+            .with_seek_strip(2, |frame| format!("thumb_{}.png", frame));
+
+        assert_eq!(timeline.scene_cuts, vec![2]);
+        assert_eq!(timeline.chapters[1].end_frame, 3);
+        assert_eq!(timeline.chapter_at(1).unwrap().title, "intro");
+        assert_eq!(timeline.thumbnails.iter().map(|t| t.frame).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(timeline.frame_time_ms(3), 300.0);
+
+        let range = timeline.range(1, 2);
+        assert_eq!(range.frame_count, 2);
+        assert_eq!(range.chapters, vec![Chapter {title: "intro".into(), start_frame: 0, end_frame: 0}, Chapter {title: "main".into(), start_frame: 1, end_frame: 1}]);
+        assert_eq!(range.scene_cuts, vec![1]);
+        assert!(range.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn test_chapters_from_sections() {
+        let mut details = ProjectDetails::default();
+        details.sections.insert("outro".into(), crate::DetailsSection {start: 2, end: 9, ..Default::default()});
+        details.sections.insert("intro".into(), crate::DetailsSection {start: 0, end: 1, fps: Some(4), ..Default::default()});
+
+        let timeline = Timeline::from_frames(&frames(), 10, 0.5).with_sections(&details);
+        assert_eq!(timeline.chapters, vec![Chapter {title: "intro".into(), start_frame: 0, end_frame: 1}, Chapter {title: "outro".into(), start_frame: 2, end_frame: 3}]);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_roundtrip() {
+        let timeline = Timeline::from_frames(&frames(), 24, 0.5).with_chapter("all", 0, 3).with_bookmark(1, "here");
+        let json = timeline.to_json_string().unwrap();
+        assert_eq!(Timeline::from_json_str(&json).unwrap(), timeline);
+
+        let minimal = Timeline::from_json_str(r#"{"version": 1, "fps": 12, "frame_count": 5}"#).unwrap();
+        assert_eq!(minimal, Timeline::new(5, 12));
+    }
+}